@@ -219,13 +219,13 @@ simulate_session_stop() {
   [ "$output" = "test-session" ]
 }
 
-@test "session-stop appends to session log" {
+@test "session-stop publishes session_stop event" {
   cd "$TEST_TEMP_DIR"
 
   simulate_session_stop
 
-  [ -f ".yolo-planning/.session-log.jsonl" ]
-  run jq -r '.model' ".yolo-planning/.session-log.jsonl"
+  [ -f ".yolo-planning/.events/event-log.jsonl" ]
+  run jq -r 'select(.event == "session_stop") | .data.model' ".yolo-planning/.events/event-log.jsonl"
   [ "$output" = "test" ]
 }
 
@@ -238,8 +238,8 @@ simulate_session_stop() {
   # Cost ledger should be gone
   [ ! -f ".yolo-planning/.cost-ledger.json" ]
 
-  # Session log should contain the cost_summary entry (jq slurp handles multi-line JSON)
-  run jq -s '[.[] | select(.type == "cost_summary")] | length' ".yolo-planning/.session-log.jsonl"
+  # Event log should contain the cost_summary event (jq slurp handles multi-line JSON)
+  run jq -s '[.[] | select(.event == "cost_summary")] | length' ".yolo-planning/.events/event-log.jsonl"
   [ "$output" = "1" ]
 }
 
@@ -311,5 +311,5 @@ simulate_session_stop() {
   [ ! -f ".yolo-planning/.active-agent" ]
   [ ! -f ".yolo-planning/.agent-panes" ]
   [ -f ".yolo-planning/.yolo-session" ]
-  [ -f ".yolo-planning/.session-log.jsonl" ]
+  [ -f ".yolo-planning/.events/event-log.jsonl" ]
 }
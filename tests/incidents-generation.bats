@@ -58,3 +58,16 @@ EVENTS
   [ -f ".yolo-planning/phases/01-test/01-INCIDENTS.md" ]
   grep -q "Total: 1 incidents" ".yolo-planning/phases/01-test/01-INCIDENTS.md"
 }
+
+@test "generate-incidents: --json correlates blocker and resolution into one incident" {
+  cd "$TEST_TEMP_DIR"
+  cat > .yolo-planning/.events/event-log.jsonl << 'EVENTS'
+{"ts":"2026-01-01T00:00:00Z","event":"task_blocked","phase":1,"correlation_id":"c1","data":{"task_id":"1-1-T1","gate":"required_checks"}}
+{"ts":"2026-01-01T00:10:00Z","event":"task_completed_confirmed","phase":1,"correlation_id":"c1","data":{"task_id":"1-1-T1"}}
+EVENTS
+  run "$YOLO_BIN" incidents 1 --json
+  [ "$status" -eq 0 ]
+  echo "$output" | grep -q '"resolution_secs":600'
+  [ -f ".yolo-planning/phases/01-test/01-INCIDENTS.json" ]
+  grep -q "Root Causes (1)" ".yolo-planning/phases/01-test/01-INCIDENTS.md"
+}
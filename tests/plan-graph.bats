@@ -0,0 +1,72 @@
+#!/usr/bin/env bats
+
+load test_helper
+
+setup() {
+  setup_temp_dir
+  create_test_config
+
+  YOLO_BIN="${YOLO_BIN:-$HOME/.cargo/bin/yolo}"
+
+  PHASE="$TEST_TEMP_DIR/phases/01-test"
+  mkdir -p "$PHASE"
+  cat > "$PHASE/01-01-PLAN.md" << 'PLAN'
+---
+phase: "01"
+plan: "01"
+title: "Base"
+wave: 1
+depends_on: []
+---
+### Task 1: Base
+PLAN
+  cat > "$PHASE/01-02-PLAN.md" << 'PLAN'
+---
+phase: "01"
+plan: "02"
+title: "Top"
+wave: 2
+depends_on: ["01"]
+---
+### Task 1: Top
+PLAN
+}
+
+teardown() {
+  teardown_temp_dir
+}
+
+@test "plan-graph computes waves and critical path" {
+  cd "$TEST_TEMP_DIR"
+  run "$YOLO_BIN" plan-graph "$PHASE"
+  [ "$status" -eq 0 ]
+  echo "$output" | jq -e '.waves[1].plans == ["01-02"]'
+  echo "$output" | jq -e '.critical_path.plans == ["01-01", "01-02"]'
+}
+
+@test "plan-graph rejects dependency cycles" {
+  cd "$TEST_TEMP_DIR"
+  sed -i 's/depends_on: \[\]/depends_on: ["02"]/' "$PHASE/01-01-PLAN.md"
+  run "$YOLO_BIN" plan-graph "$PHASE"
+  [ "$status" -eq 1 ]
+  echo "$output" | jq -e '.cycles == [["01-01", "01-02"]]'
+}
+
+@test "plan-graph flags wave that contradicts dependencies" {
+  cd "$TEST_TEMP_DIR"
+  sed -i 's/^wave: 2/wave: 1/' "$PHASE/01-02-PLAN.md"
+  run "$YOLO_BIN" plan-graph "$PHASE"
+  [ "$status" -eq 1 ]
+  echo "$output" | jq -e '[.issues[].type] | index("wave_conflict") != null'
+}
+
+@test "plan-graph exports DOT and Mermaid" {
+  cd "$TEST_TEMP_DIR"
+  run "$YOLO_BIN" plan-graph "$PHASE" --format dot
+  [ "$status" -eq 0 ]
+  [[ "$output" == *'"01-01" -> "01-02"'* ]]
+
+  run "$YOLO_BIN" plan-graph "$PHASE" --format mermaid
+  [ "$status" -eq 0 ]
+  [[ "$output" == *"p01_01 --> p01_02"* ]]
+}
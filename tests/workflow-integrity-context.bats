@@ -61,31 +61,31 @@ LEAD_FILE="$BATS_TEST_DIRNAME/../agents/yolo-lead.md"
 }
 
 @test "SKILL.md tracks step_2 completion" {
-  grep -q 'steps_completed += \["step_2"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_2$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_2b completion" {
-  grep -q 'steps_completed += \["step_2b"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_2b$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_2c completion" {
-  grep -q 'steps_completed += \["step_2c"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_2c$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_3 completion" {
-  grep -q 'steps_completed += \["step_3"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_3$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_3c completion" {
-  grep -q 'steps_completed += \["step_3c"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_3c$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_3d completion" {
-  grep -q 'steps_completed += \["step_3d"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_3d$' "$SKILL_FILE"
 }
 
 @test "SKILL.md tracks step_4 conditional completion" {
-  grep -q 'steps_completed += \["step_4"\]' "$SKILL_FILE"
+  grep -q 'append .yolo-planning/.execution-state.json steps_completed step_4$' "$SKILL_FILE"
 }
 
 # Step 5 validation gate
@@ -110,8 +110,8 @@ LEAD_FILE="$BATS_TEST_DIRNAME/../agents/yolo-lead.md"
   grep -q "Step ordering verified" "$SKILL_FILE"
 }
 
-@test "SKILL.md contains jq subtraction formula for step validation" {
-  grep -q '\$req - \$done' "$SKILL_FILE"
+@test "SKILL.md subtracts completed steps from required steps" {
+  grep -q 'json minus "\$REQUIRED_STEPS" "\$COMPLETED"' "$SKILL_FILE"
 }
 
 # Anti-takeover in Lead agent
@@ -2,7 +2,7 @@ use rusqlite::Connection;
 use std::env;
 use std::io::Read;
 use std::path::PathBuf;
-use crate::commands::{state_updater, statusline, hard_gate, session_start, metrics_report, token_baseline, token_budget, token_economics_report, lock_lite, lease_lock, two_phase_complete, bootstrap_claude, bootstrap_project, bootstrap_requirements, bootstrap_roadmap, bootstrap_state, bootstrap_all, suggest_next, list_todos, phase_detect, detect_stack, infer_project_context, planning_git, resolve_model, resolve_turns, resolve_agent, log_event, collect_metrics, compress_context, prune_completed, generate_contract, contract_revision, assess_plan_risk, resolve_gate_policy, smart_route, route_monorepo, snapshot_resume, persist_state, recover_state, compile_rolling_summary, generate_gsd_index, generate_incidents, artifact_registry, infer_gsd_summary, cache_context, cache_nuke, delta_files, help_output, bump_version, doctor_cleanup, auto_repair, rollout_stage, verify, install_hooks, migrate_config, migrate_orphaned_state, tier_context, clean_stale_teams, tmux_watchdog, verify_init_todo, verify_vibe, verify_claude_bootstrap, pre_push_hook, validate_plan, review_plan, check_regression, commit_lint, diff_against_plan, qa_suite, release_suite, validate_requirements, verify_plan_completion, parse_frontmatter, resolve_plugin_root, config_read, compile_progress, git_state, extract_changelog};
+use crate::commands::{state_updater, statusline, hard_gate, session_start, metrics_report, token_baseline, token_budget, token_economics_report, lock_lite, lease_lock, two_phase_complete, bootstrap_claude, bootstrap_project, bootstrap_requirements, bootstrap_roadmap, bootstrap_state, bootstrap_all, suggest_next, list_todos, phase_detect, detect_stack, infer_project_context, planning_git, resolve_model, resolve_turns, resolve_agent, log_event, collect_metrics, compress_context, prune_completed, generate_contract, contract_revision, assess_plan_risk, resolve_gate_policy, smart_route, route_monorepo, snapshot_resume, persist_state, recover_state, compile_rolling_summary, generate_gsd_index, generate_incidents, artifact_registry, infer_gsd_summary, cache_context, cache_nuke, delta_files, help_output, bump_version, doctor_cleanup, auto_repair, rollout_stage, verify, install_hooks, migrate_config, migrate_orphaned_state, tier_context, clean_stale_teams, tmux_watchdog, verify_init_todo, verify_vibe, verify_claude_bootstrap, pre_push_hook, validate_plan, review_plan, check_regression, commit_lint, diff_against_plan, qa_suite, release_suite, validate_requirements, verify_plan_completion, parse_frontmatter, resolve_plugin_root, config_read, compile_progress, git_state, extract_changelog, plan_graph, config_edit, flags, snapshot, changelog, json_query, pricing};
 use crate::hooks;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -80,12 +80,19 @@ pub enum Command {
     ParseFrontmatter,
     ResolvePluginRoot,
     ConfigRead,
+    Config,
+    Flags,
+    Snapshot,
+    Changelog,
+    Json,
+    Pricing,
     CompileProgress,
     GitState,
     QaSuite,
     ReleaseSuite,
     BootstrapAll,
     ExtractChangelog,
+    PlanGraph,
 }
 
 impl Command {
@@ -164,12 +171,19 @@ impl Command {
             "parse-frontmatter" => Some(Command::ParseFrontmatter),
             "resolve-plugin-root" => Some(Command::ResolvePluginRoot),
             "config-read" => Some(Command::ConfigRead),
+            "config" => Some(Command::Config),
+            "flags" => Some(Command::Flags),
+            "snapshot" => Some(Command::Snapshot),
+            "changelog" => Some(Command::Changelog),
+            "json" => Some(Command::Json),
+            "pricing" => Some(Command::Pricing),
             "compile-progress" => Some(Command::CompileProgress),
             "git-state" => Some(Command::GitState),
             "qa-suite" => Some(Command::QaSuite),
             "release-suite" => Some(Command::ReleaseSuite),
             "bootstrap-all" => Some(Command::BootstrapAll),
             "extract-changelog" => Some(Command::ExtractChangelog),
+            "plan-graph" => Some(Command::PlanGraph),
             _ => None,
         }
     }
@@ -250,12 +264,19 @@ impl Command {
             Command::ParseFrontmatter => "parse-frontmatter",
             Command::ResolvePluginRoot => "resolve-plugin-root",
             Command::ConfigRead => "config-read",
+            Command::Config => "config",
+            Command::Flags => "flags",
+            Command::Snapshot => "snapshot",
+            Command::Changelog => "changelog",
+            Command::Json => "json",
+            Command::Pricing => "pricing",
             Command::CompileProgress => "compile-progress",
             Command::GitState => "git-state",
             Command::QaSuite => "qa-suite",
             Command::ReleaseSuite => "release-suite",
             Command::BootstrapAll => "bootstrap-all",
             Command::ExtractChangelog => "extract-changelog",
+            Command::PlanGraph => "plan-graph",
         }
     }
 
@@ -279,10 +300,10 @@ impl Command {
             "verify-claude-bootstrap", "pre-push", "validate-plan", "review-plan",
             "check-regression", "commit-lint", "diff-against-plan",
             "validate-requirements", "verify-plan-completion",
-            "parse-frontmatter", "resolve-plugin-root", "config-read",
-            "compile-progress", "git-state",
+            "parse-frontmatter", "resolve-plugin-root", "config-read", "config", "flags", "snapshot",
+            "changelog", "json", "pricing", "compile-progress", "git-state",
             "qa-suite", "release-suite", "bootstrap-all",
-            "extract-changelog",
+            "extract-changelog", "plan-graph",
         ]
     }
 
@@ -758,8 +779,7 @@ pub fn run_cli(args: Vec<String>, db_path: PathBuf) -> Result<(String, i32), Str
         Some(Command::CleanStaleTeams) => {
             let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
             let claude_dir = cwd.join(".claude");
-            let log_file = cwd.join(".yolo-planning").join("clean-stale-teams.log");
-            let (teams, tasks) = clean_stale_teams::clean_stale_teams(&claude_dir, &log_file);
+            let (teams, tasks) = clean_stale_teams::clean_stale_teams(&claude_dir, &cwd.join(".yolo-planning"));
             Ok((format!("Cleaned {} teams, {} task dirs", teams, tasks), 0))
         }
         Some(Command::TmuxWatchdog) => {
@@ -824,6 +844,30 @@ pub fn run_cli(args: Vec<String>, db_path: PathBuf) -> Result<(String, i32), Str
             let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
             config_read::execute(&args, &cwd)
         }
+        Some(Command::Config) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            config_edit::execute(&args, &cwd)
+        }
+        Some(Command::Flags) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            flags::execute(&args, &cwd)
+        }
+        Some(Command::Snapshot) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            snapshot::execute(&args, &cwd)
+        }
+        Some(Command::Changelog) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            changelog::execute(&args, &cwd)
+        }
+        Some(Command::Json) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            json_query::execute(&args, &cwd)
+        }
+        Some(Command::Pricing) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            pricing::execute(&args, &cwd)
+        }
         Some(Command::CompileProgress) => {
             let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
             compile_progress::execute(&args, &cwd)
@@ -848,6 +892,10 @@ pub fn run_cli(args: Vec<String>, db_path: PathBuf) -> Result<(String, i32), Str
             let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
             extract_changelog::execute(&args, &cwd)
         }
+        Some(Command::PlanGraph) => {
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            plan_graph::execute(&args, &cwd)
+        }
         None => {
             let suggestion = Command::suggest(&args[1]);
             let msg = if let Some(s) = suggestion {
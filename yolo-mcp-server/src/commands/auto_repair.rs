@@ -1,13 +1,96 @@
 use std::fs;
 use std::path::Path;
+use std::process::Command;
 
 use serde_json::{json, Value};
 
+use super::feature_flags::FeatureFlag;
+use super::layered_config::Config;
+use super::frontmatter::{self, Frontmatter};
+use super::{atomic_io, event_bus, generate_contract, hard_gate, log_event};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 2;
+const MAX_ATTEMPTS_CEILING: u32 = 5;
+
+/// SUMMARY.md frontmatter fields required by the validate-summary hook.
+const SUMMARY_REQUIRED_FIELDS: &[&str] = &[
+    "phase",
+    "plan",
+    "status",
+    "tasks_completed",
+    "tasks_total",
+    "commit_hashes",
+];
+
+/// Inputs shared by every remediation step.
+struct RepairContext<'a> {
+    cwd: &'a Path,
+    planning_dir: &'a Path,
+    phase: &'a str,
+    plan: &'a str,
+}
+
+/// A remediation step registered for a gate type. Each step is followed by a
+/// re-run of the gate; `apply` returns a short description of what it changed.
+struct RepairStrategy {
+    name: &'static str,
+    description: &'static str,
+    apply: fn(&RepairContext) -> Result<String, String>,
+}
+
+/// Registry of remediation steps per gate type, in the order they are tried.
+/// Gates with no registered steps are not repairable and escalate immediately.
+fn strategies_for(gate_type: &str) -> Vec<RepairStrategy> {
+    match gate_type {
+        "contract_compliance" => vec![RepairStrategy {
+            name: "regenerate_contract",
+            description: "regenerate the contract from the current plan",
+            apply: repair_contract_compliance,
+        }],
+        "required_checks" => vec![RepairStrategy {
+            name: "run_fix_commands",
+            description: "run the project's formatter/linter fix commands, then re-run checks",
+            apply: run_fix_commands,
+        }],
+        "commit_hygiene" => vec![RepairStrategy {
+            name: "amend_commit_message",
+            description: "amend the last unpushed commit message into conventional format",
+            apply: amend_commit_message,
+        }],
+        "artifact_persistence" => vec![RepairStrategy {
+            name: "rebuild_summary_frontmatter",
+            description: "fill in missing SUMMARY.md frontmatter fields from the plan and git log",
+            apply: rebuild_summary_frontmatter,
+        }],
+        _ => Vec::new(),
+    }
+}
+
 /// Execute auto-repair for a gate failure.
-/// Usage: yolo auto-repair <gate_type> <phase> <plan> <task> <contract_path>
-/// Attempts bounded auto-repair (max 2 retries). Non-repairable gates escalate immediately.
+/// Usage: yolo auto-repair <gate_type> <phase> <plan> <task> <contract_path> [--dry-run] [--max-attempts N]
+/// Runs each registered remediation step followed by a gate re-run, for a bounded
+/// number of attempts. Non-repairable gates escalate immediately.
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
-    if args.len() < 7 {
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let mut max_attempts_flag: Option<u32> = None;
+    let mut positional: Vec<&String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--max-attempts" {
+            max_attempts_flag = args.get(i + 1).and_then(|v| v.parse().ok());
+            i += 2;
+            continue;
+        }
+        if let Some(v) = arg.strip_prefix("--max-attempts=") {
+            max_attempts_flag = v.parse().ok();
+        } else if !arg.starts_with("--") {
+            positional.push(arg);
+        }
+        i += 1;
+    }
+
+    if positional.len() < 7 {
         let result = json!({
             "repaired": false,
             "attempts": 0,
@@ -17,17 +100,17 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         return Ok((serde_json::to_string(&result).unwrap(), 0));
     }
 
-    let gate_type = &args[2];
-    let phase = &args[3];
-    let plan = &args[4];
-    let task = &args[5];
-    let _contract_path = &args[6];
+    let gate_type = positional[2];
+    let phase = positional[3];
+    let plan = positional[4];
+    let task = positional[5];
+    let contract_path = positional[6];
 
     let planning_dir = cwd.join(".yolo-planning");
-    let config_path = planning_dir.join("config.json");
+    let config = Config::load(cwd);
 
     // Check feature flag
-    if !is_v2_hard_gates_enabled(&config_path) {
+    if !config.flag(FeatureFlag::V2HardGates) {
         let result = json!({
             "repaired": false,
             "attempts": 0,
@@ -38,8 +121,11 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Determine if gate is repairable
-    if !is_repairable(gate_type) {
-        log_blocker_event(&planning_dir, phase, plan, task, gate_type, "manual_intervention", 0);
+    let strategies = strategies_for(gate_type);
+    if strategies.is_empty() {
+        if !dry_run {
+            log_blocker_event(&planning_dir, phase, plan, task, gate_type, "manual_intervention", 0, &[]);
+        }
         let result = json!({
             "repaired": false,
             "attempts": 0,
@@ -49,29 +135,67 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         return Ok((serde_json::to_string(&result).unwrap(), 0));
     }
 
-    // Attempt repair (max 2 retries)
-    let max_retries = 2;
+    let max_attempts = max_attempts_flag
+        .or_else(|| {
+            config
+                .get("auto_repair_max_attempts")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32)
+        })
+        .unwrap_or(DEFAULT_MAX_ATTEMPTS)
+        .clamp(1, MAX_ATTEMPTS_CEILING);
+
+    if dry_run {
+        let planned: Vec<Value> = strategies
+            .iter()
+            .map(|s| json!({"strategy": s.name, "description": s.description}))
+            .collect();
+        let result = json!({
+            "repaired": false,
+            "dry_run": true,
+            "attempts": 0,
+            "max_attempts": max_attempts,
+            "gate": gate_type,
+            "planned": planned
+        });
+        return Ok((serde_json::to_string(&result).unwrap(), 0));
+    }
+
+    let ctx = RepairContext {
+        cwd,
+        planning_dir: &planning_dir,
+        phase,
+        plan,
+    };
+
+    let mut history: Vec<Value> = Vec::new();
     let mut attempt = 0;
     let mut repaired = false;
 
-    while attempt < max_retries {
+    'attempts: while attempt < max_attempts {
         attempt += 1;
 
-        // Execute repair strategy
-        match gate_type.as_str() {
-            "contract_compliance" => {
-                repair_contract_compliance(cwd, &planning_dir, phase, plan);
+        for strategy in &strategies {
+            let (outcome, detail) = match (strategy.apply)(&ctx) {
+                Ok(detail) => ("applied", detail),
+                Err(detail) => ("failed", detail),
+            };
+
+            // Re-run gate check after every remediation step
+            let (passed, evidence) = check_gate_passes(cwd, gate_type, phase, plan, task, contract_path);
+            history.push(json!({
+                "attempt": attempt,
+                "strategy": strategy.name,
+                "outcome": outcome,
+                "detail": detail,
+                "gate_result": if passed { "pass" } else { "fail" },
+                "evidence": evidence
+            }));
+
+            if passed {
+                repaired = true;
+                break 'attempts;
             }
-            "required_checks" => {
-                // Re-run is the repair — no extra action needed
-            }
-            _ => {}
-        }
-
-        // Re-run gate check
-        if check_gate_passes(cwd, gate_type, phase, plan, task, _contract_path) {
-            repaired = true;
-            break;
         }
     }
 
@@ -79,62 +203,238 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         let result = json!({
             "repaired": true,
             "attempts": attempt,
-            "gate": gate_type
+            "gate": gate_type,
+            "history": history
         });
         Ok((serde_json::to_string(&result).unwrap(), 0))
     } else {
-        log_blocker_event(&planning_dir, phase, plan, task, gate_type, "investigate_and_fix", attempt);
+        log_blocker_event(&planning_dir, phase, plan, task, gate_type, "investigate_and_fix", attempt, &history);
         let result = json!({
             "repaired": false,
             "attempts": attempt,
             "gate": gate_type,
-            "reason": "max retries exhausted, escalated to lead"
+            "reason": "max retries exhausted, escalated to lead",
+            "history": history
         });
         Ok((serde_json::to_string(&result).unwrap(), 0))
     }
 }
 
-fn is_v2_hard_gates_enabled(config_path: &Path) -> bool {
-    if let Ok(content) = fs::read_to_string(config_path)
-        && let Ok(config) = serde_json::from_str::<Value>(&content) {
-            return config.get("v2_hard_gates")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
+/// Attempt to repair contract compliance by regenerating the contract.
+fn repair_contract_compliance(ctx: &RepairContext) -> Result<String, String> {
+    let phases_dir = ctx.planning_dir.join("phases");
+    let phase_dir = find_phase_dir(&phases_dir, ctx.phase)
+        .ok_or_else(|| format!("phase {} directory not found", ctx.phase))?;
+    let plan_path = find_plan_file(&phase_dir, ctx.phase, ctx.plan)
+        .ok_or_else(|| format!("plan {} not found in {}", ctx.plan, phase_dir.display()))?;
+
+    match generate_contract::try_generate(&plan_path, ctx.cwd) {
+        Ok(Some((_contract, path))) => Ok(format!("regenerated {}", path)),
+        Ok(None) => Err("contract generation skipped (contract flags disabled or plan unreadable)".to_string()),
+        Err(e) => Err(format!("invalid YAML frontmatter at {}", e)),
+    }
+}
+
+/// Formatter/linter fix commands listed in `auto_repair_fix_commands`. Nothing
+/// is inferred from manifests: these commands rewrite the working tree.
+fn resolve_fix_commands(planning_dir: &Path) -> Vec<String> {
+    Config::load(planning_dir.parent().unwrap_or(planning_dir))
+        .get("auto_repair_fix_commands")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+/// Run the configured formatter/linter fix commands.
+fn run_fix_commands(ctx: &RepairContext) -> Result<String, String> {
+    let commands = resolve_fix_commands(ctx.planning_dir);
+    if commands.is_empty() {
+        return Ok("no fix commands configured, re-running checks".to_string());
+    }
+
+    let mut failed = Vec::new();
+    for cmd in &commands {
+        let ok = Command::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .current_dir(ctx.cwd)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !ok {
+            failed.push(cmd.as_str());
         }
-    false
+    }
+
+    if failed.is_empty() {
+        Ok(format!("ran: {}", commands.join("; ")))
+    } else {
+        Err(format!("fix commands failed: {}", failed.join("; ")))
+    }
 }
 
-fn is_repairable(gate_type: &str) -> bool {
-    matches!(gate_type, "contract_compliance" | "required_checks")
+fn git_output(cwd: &Path, args: &[&str]) -> Option<String> {
+    let out = Command::new("git").args(args).current_dir(cwd).output().ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&out.stdout).to_string())
 }
 
-/// Attempt to repair contract compliance by regenerating the contract.
-fn repair_contract_compliance(cwd: &Path, planning_dir: &Path, phase: &str, plan: &str) {
-    let phases_dir = planning_dir.join("phases");
+/// Rewrite a commit subject into `{type}({phase}-{plan}): {description}`.
+fn to_conventional_subject(subject: &str, phase: &str, plan: &str) -> String {
+    const TYPES: &[&str] = &["feat", "fix", "test", "refactor", "perf", "docs", "style", "chore"];
+    let scope = format!("{:0>2}-{:0>2}", phase, plan);
+    let subject = subject.trim();
+
+    // Already conventional: "{type}({scope}): ..."
+    if let Some((head, rest)) = subject.split_once("): ")
+        && let Some((commit_type, scope)) = head.split_once('(')
+        && TYPES.contains(&commit_type)
+        && !scope.is_empty()
+        && !rest.trim().is_empty()
+    {
+        return subject.to_string();
+    }
+
+    // Already typed but missing a scope: "fix: thing" -> "fix(01-02): thing"
+    if let Some((prefix, rest)) = subject.split_once(':') {
+        let prefix = prefix.trim();
+        if TYPES.contains(&prefix) {
+            return format!("{}({}): {}", prefix, scope, rest.trim());
+        }
+    }
+
+    let first_word = subject
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .to_lowercase();
+    let commit_type = match first_word.trim_end_matches(|c: char| !c.is_alphanumeric()) {
+        "fix" | "fixed" | "fixes" | "resolve" | "resolved" | "correct" => "fix",
+        "add" | "added" | "adds" | "implement" | "implemented" | "introduce" | "create" | "support" => "feat",
+        "test" | "tests" => "test",
+        "refactor" | "rename" | "extract" | "move" | "simplify" | "cleanup" => "refactor",
+        "doc" | "docs" | "document" | "documented" => "docs",
+        "perf" | "optimize" | "optimise" | "speed" => "perf",
+        "format" | "style" | "lint" => "style",
+        _ => "chore",
+    };
+
+    format!("{}({}): {}", commit_type, scope, subject)
+}
+
+/// Amend the last commit message into conventional format. Refuses to rewrite
+/// a commit that is already reachable from a remote branch.
+fn amend_commit_message(ctx: &RepairContext) -> Result<String, String> {
+    let message = git_output(ctx.cwd, &["log", "-1", "--pretty=%B"])
+        .ok_or_else(|| "no commit to amend".to_string())?;
+    let pushed = git_output(ctx.cwd, &["branch", "-r", "--contains", "HEAD"]).unwrap_or_default();
+    if !pushed.trim().is_empty() {
+        return Err("HEAD is already pushed, refusing to amend".to_string());
+    }
+
+    let mut lines = message.trim_end().lines();
+    let subject = lines.next().unwrap_or("").to_string();
+    let body: Vec<&str> = lines.collect();
+    let new_subject = to_conventional_subject(&subject, ctx.phase, ctx.plan);
+    if new_subject == subject {
+        return Ok("commit message already conventional".to_string());
+    }
+
+    let mut new_message = new_subject.clone();
+    if !body.is_empty() {
+        new_message.push('\n');
+        new_message.push_str(&body.join("\n"));
+    }
+
+    let out = Command::new("git")
+        .args(["commit", "--amend", "--only", "--no-verify", "-m", &new_message])
+        .current_dir(ctx.cwd)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+    if !out.status.success() {
+        return Err(format!("git commit --amend failed: {}", String::from_utf8_lossy(&out.stderr).trim()));
+    }
+    Ok(format!("amended subject to '{}'", new_subject))
+}
 
-    // Find phase directory
-    let phase_dir = find_phase_dir(&phases_dir, phase);
-    if phase_dir.is_none() {
-        return;
+/// Fill in missing required frontmatter fields on the SUMMARY.md of `ctx.plan`,
+/// deriving values from the matching PLAN.md and the git log.
+fn rebuild_summary_frontmatter(ctx: &RepairContext) -> Result<String, String> {
+    let phases_dir = ctx.planning_dir.join("phases");
+    let phase_dir = find_phase_dir(&phases_dir, ctx.phase)
+        .ok_or_else(|| format!("phase {} directory not found", ctx.phase))?;
+    let path = find_summary_file(&phase_dir, ctx.phase, ctx.plan)
+        .ok_or_else(|| format!("SUMMARY for plan {} not found", ctx.plan))?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let fm = frontmatter::parse(&content)
+        .map_err(|e| format!("{}: {}", name, e))?
+        .unwrap_or_else(|| Frontmatter { fields: Default::default(), body: content.clone() });
+    match fill_summary_frontmatter(ctx, &phase_dir, fm) {
+        Some(updated) => {
+            atomic_io::atomic_write(&path, updated.render().as_bytes()).map_err(|e| e.to_string())?;
+            Ok(format!("rebuilt frontmatter: {}", name))
+        }
+        None => Ok(format!("{} frontmatter up to date", name)),
     }
-    let phase_dir = phase_dir.unwrap();
+}
 
-    // Find plan file
-    let plan_file = find_plan_file(&phase_dir, phase, plan);
-    if plan_file.is_none() {
-        return;
+/// Returns the frontmatter with missing fields added, or None when nothing is missing.
+fn fill_summary_frontmatter(ctx: &RepairContext, phase_dir: &Path, mut fm: Frontmatter) -> Option<Frontmatter> {
+    let missing: Vec<&str> = SUMMARY_REQUIRED_FIELDS.iter().copied().filter(|f| !fm.has(f)).collect();
+    if missing.is_empty() {
+        return None;
     }
 
-    // Regenerate contract using the Rust generate-contract command
-    let plan_path = plan_file.unwrap();
-    let yolo_bin = std::env::var("HOME")
-        .map(|h| format!("{}/.cargo/bin/yolo", h))
-        .unwrap_or_else(|_| "yolo".to_string());
+    let phase_id = format!("{:0>2}", ctx.phase);
+    let plan_id = format!("{:0>2}", ctx.plan);
+    let status = fm.scalar("status").unwrap_or_else(|| "complete".to_string());
+    let tasks_total = find_plan_file(phase_dir, ctx.phase, ctx.plan)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|c| {
+            let body = frontmatter::parse(&c).ok().flatten().map(|f| f.body).unwrap_or(c);
+            generate_contract::count_tasks(&body)
+        })
+        .unwrap_or(0);
+    let tasks_completed = if status == "complete" { tasks_total } else { 0 };
+    let scope = format!("({}-{})", phase_id, plan_id);
+    let hashes: Vec<Value> = git_output(ctx.cwd, &["log", "--format=%h", "--fixed-strings", "--grep", &scope])
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| json!(l))
+        .collect();
+
+    for field in missing {
+        let value = match field {
+            "phase" => json!(phase_id),
+            "plan" => json!(plan_id),
+            "status" => json!(status),
+            "tasks_completed" => json!(tasks_completed),
+            "tasks_total" => json!(tasks_total),
+            "commit_hashes" => Value::Array(hashes.clone()),
+            _ => continue,
+        };
+        fm.fields.insert(field.to_string(), value);
+    }
+    Some(fm)
+}
 
-    let _ = std::process::Command::new(&yolo_bin)
-        .args(["generate-contract", plan_path.to_str().unwrap_or("")])
-        .current_dir(cwd)
-        .output();
+/// `{phase}-{plan}-SUMMARY.md` or `{plan}-SUMMARY.md` for the given plan.
+fn find_summary_file(phase_dir: &Path, phase: &str, plan: &str) -> Option<std::path::PathBuf> {
+    let padded_phase = format!("{:0>2}", phase);
+    let padded_plan = format!("{:0>2}", plan);
+    let names = [
+        format!("{}-{}-SUMMARY.md", padded_phase, padded_plan),
+        format!("{}-{}-SUMMARY.md", phase, plan),
+        format!("{}-SUMMARY.md", padded_plan),
+        format!("{}-SUMMARY.md", plan),
+    ];
+    names.iter().map(|n| phase_dir.join(n)).find(|p| p.is_file())
 }
 
 fn find_phase_dir(phases_dir: &Path, phase: &str) -> Option<std::path::PathBuf> {
@@ -176,36 +476,31 @@ fn find_plan_file(phase_dir: &Path, phase: &str, plan: &str) -> Option<std::path
     None
 }
 
-/// Re-run the hard gate check. Returns true if gate passes.
-fn check_gate_passes(cwd: &Path, gate_type: &str, phase: &str, plan: &str, task: &str, contract_path: &str) -> bool {
-    let yolo_bin = std::env::var("HOME")
-        .map(|h| format!("{}/.cargo/bin/yolo", h))
-        .unwrap_or_else(|_| "yolo".to_string());
-
-    let output = std::process::Command::new(&yolo_bin)
-        .args(["hard-gate", gate_type, phase, plan, task, contract_path])
-        .current_dir(cwd)
-        .output();
+/// Re-run the hard gate check in-process. Returns (passed, evidence).
+fn check_gate_passes(cwd: &Path, gate_type: &str, phase: &str, plan: &str, task: &str, contract_path: &str) -> (bool, String) {
+    let args: Vec<String> = ["yolo", "hard-gate", gate_type, phase, plan, task, contract_path]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
 
-    if let Ok(out) = output
-        && let Ok(text) = String::from_utf8(out.stdout)
+    if let Ok((text, _)) = hard_gate::execute_gate(&args, cwd)
         && let Ok(result) = serde_json::from_str::<Value>(&text) {
-            return result.get("result")
+            let passed = result.get("result")
                 .and_then(|v| v.as_str())
                 .map(|s| s == "pass")
                 .unwrap_or(false);
+            let evidence = result.get("evidence").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            return (passed, evidence);
         }
-    false
+    (false, "gate check unavailable".to_string())
 }
 
-/// Log a blocker event to the event log.
-fn log_blocker_event(planning_dir: &Path, phase: &str, plan: &str, task: &str, gate: &str, next_action: &str, attempts: u32) {
-    let events_dir = planning_dir.join(".events");
-    let _ = fs::create_dir_all(&events_dir);
-    let events_file = events_dir.join("event-log.jsonl");
-
+/// Log a blocker event to the event log, carrying the full remediation history.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn log_blocker_event(planning_dir: &Path, phase: &str, plan: &str, task: &str, gate: &str, next_action: &str, attempts: u32, history: &[Value]) {
     let event = json!({
         "event": "task_blocked",
+        "correlation_id": log_event::correlation_id(planning_dir),
         "phase": phase,
         "plan": plan,
         "data": {
@@ -213,20 +508,12 @@ fn log_blocker_event(planning_dir: &Path, phase: &str, plan: &str, task: &str, g
             "gate": gate,
             "owner": "lead",
             "next_action": next_action,
-            "attempts": attempts
+            "attempts": attempts,
+            "remediation_history": history
         }
     });
 
-    if let Ok(line) = serde_json::to_string(&event) {
-        let _ = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&events_file)
-            .and_then(|mut f| {
-                use std::io::Write;
-                writeln!(f, "{}", line)
-            });
-    }
+    event_bus::publish(planning_dir, &event);
 }
 
 #[cfg(test)]
@@ -281,13 +568,13 @@ mod tests {
     }
 
     #[test]
-    fn test_is_repairable() {
-        assert!(is_repairable("contract_compliance"));
-        assert!(is_repairable("required_checks"));
-        assert!(!is_repairable("protected_file"));
-        assert!(!is_repairable("commit_hygiene"));
-        assert!(!is_repairable("artifact_persistence"));
-        assert!(!is_repairable("verification_threshold"));
+    fn test_strategies_registered_for_repairable_gates() {
+        for gate in ["contract_compliance", "required_checks", "commit_hygiene", "artifact_persistence"] {
+            assert!(!strategies_for(gate).is_empty(), "{}", gate);
+        }
+        for gate in ["protected_file", "verification_threshold", "forbidden_commands"] {
+            assert!(strategies_for(gate).is_empty(), "{}", gate);
+        }
     }
 
     #[test]
@@ -297,7 +584,7 @@ mod tests {
         fs::create_dir_all(&planning).unwrap();
         fs::write(planning.join("config.json"), r#"{"v2_hard_gates": true}"#).unwrap();
 
-        for gate in &["commit_hygiene", "artifact_persistence", "verification_threshold"] {
+        for gate in &["protected_file", "verification_threshold", "forbidden_commands"] {
             let args: Vec<String> = vec![
                 "yolo".into(), "auto-repair".into(),
                 gate.to_string(), "1".into(), "1".into(), "1".into(), "/tmp/c".into(),
@@ -327,10 +614,12 @@ mod tests {
         let (output, code) = execute(&args, dir.path()).unwrap();
         assert_eq!(code, 0);
         let result: Value = serde_json::from_str(&output).unwrap();
-        // Gate check may pass or fail depending on binary availability;
-        // verify the structure is correct
-        assert!(result.get("repaired").is_some());
-        assert!(result.get("attempts").is_some());
+        assert_eq!(result["repaired"], false);
+        assert_eq!(result["attempts"], DEFAULT_MAX_ATTEMPTS);
+        assert_eq!(result["reason"], "max retries exhausted, escalated to lead");
+        let history = result["history"].as_array().unwrap();
+        assert_eq!(history.len(), DEFAULT_MAX_ATTEMPTS as usize);
+        assert!(history.iter().all(|h| h["gate_result"] == "fail" && h["evidence"] == "contract file not found"));
     }
 
     #[test]
@@ -339,7 +628,7 @@ mod tests {
         let planning = dir.path().join(".yolo-planning");
         fs::create_dir_all(&planning).unwrap();
 
-        log_blocker_event(&planning, "1", "1", "t1", "contract_compliance", "fix", 2);
+        log_blocker_event(&planning, "1", "1", "t1", "contract_compliance", "fix", 2, &[json!({"attempt": 1, "strategy": "regenerate_contract"})]);
 
         let events_file = planning.join(".events/event-log.jsonl");
         assert!(events_file.exists());
@@ -348,6 +637,7 @@ mod tests {
         assert_eq!(event["event"], "task_blocked");
         assert_eq!(event["data"]["gate"], "contract_compliance");
         assert_eq!(event["data"]["attempts"], 2);
+        assert_eq!(event["data"]["remediation_history"][0]["strategy"], "regenerate_contract");
     }
 
     #[test]
@@ -373,4 +663,175 @@ mod tests {
         assert!(find_plan_file(&phase_dir, "01", "01").is_some());
         assert!(find_plan_file(&phase_dir, "01", "99").is_none());
     }
+
+    #[test]
+    fn test_dry_run_lists_strategies_without_applying() {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), r#"{"v2_hard_gates": true, "auto_repair_max_attempts": 3}"#).unwrap();
+
+        let args: Vec<String> = vec![
+            "yolo".into(), "auto-repair".into(),
+            "required_checks".into(), "1".into(), "1".into(), "1".into(), "/tmp/c".into(),
+            "--dry-run".into(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let result: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["dry_run"], true);
+        assert_eq!(result["max_attempts"], 3);
+        assert_eq!(result["planned"][0]["strategy"], "run_fix_commands");
+        assert!(!planning.join(".events/event-log.jsonl").exists());
+    }
+
+    /// Config with fix commands plus a contract whose only verification check is `check`.
+    fn setup_required_checks(dir: &TempDir, fix_commands: &[&str], check: &str) -> String {
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        let config = json!({"v2_hard_gates": true, "auto_repair_fix_commands": fix_commands});
+        fs::write(planning.join("config.json"), config.to_string()).unwrap();
+        let contract = dir.path().join("contract.json");
+        fs::write(&contract, json!({"verification_checks": [check]}).to_string()).unwrap();
+        contract.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_max_attempts_bounds_history() {
+        let dir = TempDir::new().unwrap();
+        let contract = setup_required_checks(&dir, &["true"], "false");
+        let args: Vec<String> = vec![
+            "yolo".into(), "auto-repair".into(),
+            "required_checks".into(), "1".into(), "1".into(), "1".into(),
+            contract,
+            "--max-attempts".into(), "3".into(),
+        ];
+        let (output, _) = execute(&args, dir.path()).unwrap();
+        let result: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["repaired"], false);
+        assert_eq!(result["attempts"], 3);
+        let history = result["history"].as_array().unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0]["strategy"], "run_fix_commands");
+        assert_eq!(history[0]["outcome"], "applied");
+        assert_eq!(history[0]["gate_result"], "fail");
+
+        let events = event_bus::load_events(&dir.path().join(".yolo-planning"));
+        let blocker = events.iter().find(|e| e["event"] == "task_blocked").unwrap();
+        assert_eq!(blocker["data"]["remediation_history"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_fix_command_repairs_required_checks() {
+        let dir = TempDir::new().unwrap();
+        let contract = setup_required_checks(&dir, &["touch fixed.txt"], "test -f fixed.txt");
+        let args: Vec<String> = vec![
+            "yolo".into(), "auto-repair".into(),
+            "required_checks".into(), "1".into(), "1".into(), "1".into(),
+            contract,
+        ];
+        let (output, _) = execute(&args, dir.path()).unwrap();
+        let result: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(result["repaired"], true);
+        assert_eq!(result["attempts"], 1);
+        assert_eq!(result["history"][0]["gate_result"], "pass");
+    }
+
+    #[test]
+    fn test_run_fix_commands_reports_failures() {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(
+            planning.join("config.json"),
+            r#"{"auto_repair_fix_commands": ["touch fixed.txt", "exit 3"]}"#,
+        ).unwrap();
+        let ctx = RepairContext { cwd: dir.path(), planning_dir: &planning, phase: "1", plan: "1" };
+
+        let err = run_fix_commands(&ctx).unwrap_err();
+        assert!(err.contains("exit 3"));
+        assert!(dir.path().join("fixed.txt").exists());
+    }
+
+    #[test]
+    fn test_resolve_fix_commands_only_from_config() {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        fs::write(dir.path().join("pyproject.toml"), "").unwrap();
+        assert!(resolve_fix_commands(&planning).is_empty());
+
+        fs::write(planning.join("config.json"), r#"{"auto_repair_fix_commands": ["cargo fmt --all"]}"#).unwrap();
+        assert_eq!(resolve_fix_commands(&planning), vec!["cargo fmt --all".to_string()]);
+    }
+
+    #[test]
+    fn test_to_conventional_subject() {
+        assert_eq!(to_conventional_subject("Fix login redirect", "1", "2"), "fix(01-02): Fix login redirect");
+        assert_eq!(to_conventional_subject("add retry to fetch_user", "3", "1"), "feat(03-01): add retry to fetch_user");
+        assert_eq!(to_conventional_subject("fix: typo", "1", "1"), "fix(01-01): typo");
+        assert_eq!(to_conventional_subject("wip", "1", "1"), "chore(01-01): wip");
+        assert_eq!(to_conventional_subject("docs(readme): update", "1", "1"), "docs(readme): update");
+    }
+
+    #[test]
+    fn test_amend_commit_message() {
+        let dir = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(dir.path()).output().unwrap()
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "t@t"]);
+        git(&["config", "user.name", "t"]);
+        fs::write(dir.path().join("a.txt"), "a").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["commit", "-q", "-m", "implement parser\n\nDetails here"]);
+
+        let planning = dir.path().join(".yolo-planning");
+        let ctx = RepairContext { cwd: dir.path(), planning_dir: &planning, phase: "2", plan: "3" };
+        let detail = amend_commit_message(&ctx).unwrap();
+        assert!(detail.contains("feat(02-03): implement parser"));
+
+        let msg = String::from_utf8_lossy(&git(&["log", "-1", "--pretty=%B"]).stdout).to_string();
+        assert!(msg.starts_with("feat(02-03): implement parser"));
+        assert!(msg.contains("Details here"));
+        assert_eq!(amend_commit_message(&ctx).unwrap(), "commit message already conventional");
+    }
+
+    #[test]
+    fn test_rebuild_summary_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        let phase_dir = planning.join("phases/01-setup");
+        fs::create_dir_all(&phase_dir).unwrap();
+        fs::write(phase_dir.join("01-02-PLAN.md"), "---\nphase: 1\n---\n### Task 1\n### Task 2\n").unwrap();
+        fs::write(phase_dir.join("01-02-SUMMARY.md"), "---\nstatus: complete\n---\n## What Was Built\nStuff\n").unwrap();
+        fs::write(
+            phase_dir.join("01-01-SUMMARY.md"),
+            "---\nphase: \"01\"\nplan: \"01\"\nstatus: complete\ntasks_completed: 1\ntasks_total: 1\ncommit_hashes: []\n---\nbody\n",
+        ).unwrap();
+
+        let untouched = "---\nstatus: complete\n---\nother plan\n";
+        fs::write(phase_dir.join("01-03-SUMMARY.md"), untouched).unwrap();
+
+        let ctx = RepairContext { cwd: dir.path(), planning_dir: &planning, phase: "1", plan: "2" };
+        let detail = rebuild_summary_frontmatter(&ctx).unwrap();
+        assert_eq!(detail, "rebuilt frontmatter: 01-02-SUMMARY.md");
+        assert_eq!(fs::read_to_string(phase_dir.join("01-03-SUMMARY.md")).unwrap(), untouched);
+
+        let content = fs::read_to_string(phase_dir.join("01-02-SUMMARY.md")).unwrap();
+        let summary = frontmatter::parse(&content).unwrap().unwrap();
+        let typed = summary.summary();
+        assert_eq!(typed.status.as_deref(), Some("complete"));
+        assert_eq!(typed.phase.as_deref(), Some("01"));
+        assert_eq!(typed.plan.as_deref(), Some("02"));
+        assert_eq!(typed.tasks_total, Some(2));
+        assert_eq!(typed.tasks_completed, Some(2));
+        assert!(summary.has("commit_hashes"));
+        assert!(typed.commit_hashes.is_empty());
+        assert_eq!(summary.body, "## What Was Built\nStuff\n");
+
+        assert_eq!(rebuild_summary_frontmatter(&ctx).unwrap(), "01-02-SUMMARY.md frontmatter up to date");
+    }
 }
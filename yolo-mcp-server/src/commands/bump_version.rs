@@ -1,101 +1,9 @@
 use serde_json::{json, Value};
-use std::fs;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-/// Read version from VERSION file, trimmed.
-fn read_version_file(cwd: &Path) -> Result<String, String> {
-    let path = cwd.join("VERSION");
-    if !path.exists() {
-        return Err("VERSION file not found".to_string());
-    }
-    Ok(fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read VERSION: {}", e))?
-        .trim()
-        .to_string())
-}
-
-/// Extract version from a JSON file at a given JSON pointer path.
-/// For plugin.json: pointer = "/version"
-/// For marketplace.json: pointer = "/plugins/0/version"
-fn read_json_version(path: &Path, pointer: &str) -> Result<String, String> {
-    if !path.exists() {
-        return Err(format!("{} not found", path.display()));
-    }
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-    let val: Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
-    val.pointer(pointer)
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| format!("Version field not found at {} in {}", pointer, path.display()))
-}
-
-/// Write a version string into a JSON file at the given pointer path.
-fn write_json_version(path: &Path, pointer: &str, version: &str) -> Result<(), String> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-    let mut val: Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
-
-    // Navigate the pointer and set the value
-    let parts: Vec<&str> = pointer.trim_start_matches('/').split('/').collect();
-    let mut current = &mut val;
-    for (i, part) in parts.iter().enumerate() {
-        if i == parts.len() - 1 {
-            // Last part: set the value
-            if let Ok(idx) = part.parse::<usize>() {
-                current[idx] = Value::String(version.to_string());
-            } else {
-                current[*part] = Value::String(version.to_string());
-            }
-        } else {
-            // Navigate deeper
-            if let Ok(idx) = part.parse::<usize>() {
-                current = &mut current[idx];
-            } else {
-                current = &mut current[*part];
-            }
-        }
-    }
-
-    let output = serde_json::to_string_pretty(&val)
-        .map_err(|e| format!("Failed to serialize JSON: {}", e))?;
-    fs::write(path, format!("{}\n", output))
-        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
-    Ok(())
-}
-
-/// Read version from a TOML file's [package] table.
-fn read_toml_version(path: &Path) -> Result<String, String> {
-    if !path.exists() {
-        return Err(format!("{} not found", path.display()));
-    }
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-    let doc = content
-        .parse::<toml_edit::DocumentMut>()
-        .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
-    doc.get("package")
-        .and_then(|p| p.get("version"))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
-        .ok_or_else(|| format!("package.version not found in {}", path.display()))
-}
-
-/// Write version into a TOML file's [package] table, preserving formatting.
-fn write_toml_version(path: &Path, version: &str) -> Result<(), String> {
-    let content = fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
-    let mut doc = content
-        .parse::<toml_edit::DocumentMut>()
-        .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))?;
-    doc["package"]["version"] = toml_edit::value(version);
-    fs::write(path, doc.to_string())
-        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
-    Ok(())
-}
+use super::net::{Endpoint, Net};
+use super::version_files::{self, VersionFile};
 
 /// Increment the patch component of a semver string.
 fn increment_patch(version: &str) -> String {
@@ -144,50 +52,24 @@ fn max_version<'a>(a: &'a str, b: &'a str) -> &'a str {
     if parse(a) >= parse(b) { a } else { b }
 }
 
-/// All version file locations (relative to cwd).
-struct VersionFiles {
-    version_file: &'static str,
-    json_files: Vec<(&'static str, &'static str)>, // (path, json_pointer)
-    toml_files: Vec<&'static str>,
-}
-
-fn version_files() -> VersionFiles {
-    VersionFiles {
-        version_file: "VERSION",
-        json_files: vec![
-            (".claude-plugin/plugin.json", "/version"),
-            (".claude-plugin/marketplace.json", "/plugins/0/version"),
-        ],
-        toml_files: vec!["yolo-mcp-server/Cargo.toml"],
+/// Path shown for a version file, with its locator when one file holds several.
+fn display(file: &VersionFile, all: &[VersionFile]) -> String {
+    if all.iter().filter(|f| f.path == file.path).count() > 1 {
+        format!("{} ({})", file.path, file.locator.describe())
+    } else {
+        file.path.clone()
     }
 }
 
 /// Verify mode: read all version sources and report mismatches.
 fn verify_versions(cwd: &Path, start: Instant) -> Result<(String, i32), String> {
-    let vf = version_files();
+    let (files, _) = version_files::load(cwd)?;
     let mut versions: Vec<(String, String)> = Vec::new();
     let mut errors: Vec<String> = Vec::new();
 
-    // Read VERSION file
-    match read_version_file(cwd) {
-        Ok(v) => versions.push(("VERSION".to_string(), v)),
-        Err(e) => errors.push(e),
-    }
-
-    // Read JSON files
-    for (path, pointer) in &vf.json_files {
-        let full = cwd.join(path);
-        match read_json_version(&full, pointer) {
-            Ok(v) => versions.push((path.to_string(), v)),
-            Err(e) => errors.push(e),
-        }
-    }
-
-    // Read TOML files
-    for path in &vf.toml_files {
-        let full = cwd.join(path);
-        match read_toml_version(&full) {
-            Ok(v) => versions.push((path.to_string(), v)),
+    for file in &files {
+        match file.read(cwd) {
+            Ok(v) => versions.push((display(file, &files), v)),
             Err(e) => errors.push(e),
         }
     }
@@ -229,22 +111,14 @@ fn verify_versions(cwd: &Path, start: Instant) -> Result<(String, i32), String>
     Ok((response.to_string(), code))
 }
 
-/// Fetch remote VERSION from GitHub (raw content URL).
-fn fetch_remote_version(offline: bool) -> Option<String> {
+/// Fetch the published VERSION through the network policy (`--offline` skips it).
+fn fetch_remote_version(cwd: &Path, offline: bool) -> Option<String> {
     if offline {
         return None;
     }
-    let url = "https://raw.githubusercontent.com/slavpetroff/yolo/main/VERSION";
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()
-        .ok()?;
-    let resp = client.get(url).send().ok()?;
-    if resp.status().is_success() {
-        Some(resp.text().ok()?.trim().to_string())
-    } else {
-        None
-    }
+    let net = Net::load(cwd).with_timeout(Duration::from_secs(5));
+    let fetched = net.get(Endpoint::YoloVersion, Duration::ZERO, &[]);
+    fetched.body().map(|b| b.trim().to_string()).filter(|v| !v.is_empty())
 }
 
 /// Bump mode: increment version, write to all files.
@@ -255,9 +129,13 @@ fn bump_version(
     minor: bool,
     start: Instant,
 ) -> Result<(String, i32), String> {
-    let local_version = read_version_file(cwd)?;
+    let (files, configured) = version_files::load(cwd)?;
+    // The first file is the source of truth for the current version.
+    let primary = &files[0];
+    let local_version = primary.read(cwd)?;
 
-    let remote_version = fetch_remote_version(offline);
+    // Only YOLO's own layout tracks the published VERSION on GitHub.
+    let remote_version = if configured { None } else { fetch_remote_version(cwd, offline) };
     let base_version = match &remote_version {
         Some(rv) => max_version(&local_version, rv),
         None => &local_version,
@@ -278,36 +156,19 @@ fn bump_version(
         "patch"
     };
 
-    let vf = version_files();
     let mut files_updated: Vec<Value> = Vec::new();
     let mut changed: Vec<String> = Vec::new();
 
-    // Write VERSION file
-    let version_path = cwd.join(vf.version_file);
-    fs::write(&version_path, format!("{}\n", new_version))
-        .map_err(|e| format!("Failed to write VERSION: {}", e))?;
-    files_updated.push(json!({"path": vf.version_file, "old": local_version, "new": new_version}));
-    changed.push(vf.version_file.to_string());
-
-    // Write JSON files
-    for (path, pointer) in &vf.json_files {
-        let full = cwd.join(path);
-        if full.exists() {
-            let old = read_json_version(&full, pointer).unwrap_or_else(|_| "unknown".to_string());
-            write_json_version(&full, pointer, &new_version)?;
-            files_updated.push(json!({"path": path, "old": old, "new": new_version}));
-            changed.push(path.to_string());
+    for (i, file) in files.iter().enumerate() {
+        // Secondary files are optional; the primary must exist (it was just read).
+        if i > 0 && !file.exists(cwd) {
+            continue;
         }
-    }
-
-    // Write TOML files
-    for path in &vf.toml_files {
-        let full = cwd.join(path);
-        if full.exists() {
-            let old = read_toml_version(&full).unwrap_or_else(|_| "unknown".to_string());
-            write_toml_version(&full, &new_version)?;
-            files_updated.push(json!({"path": path, "old": old, "new": new_version}));
-            changed.push(path.to_string());
+        let old = file.read(cwd).unwrap_or_else(|_| "unknown".to_string());
+        file.write(cwd, &new_version)?;
+        files_updated.push(json!({"path": display(file, &files), "old": old, "new": new_version}));
+        if !changed.contains(&file.path) {
+            changed.push(file.path.clone());
         }
     }
 
@@ -350,6 +211,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::version_files::Locator;
+    use std::fs;
     use tempfile::TempDir;
 
     fn setup_test_env() -> TempDir {
@@ -494,8 +357,8 @@ mod tests {
     #[test]
     fn test_missing_version_file() {
         let dir = TempDir::new().unwrap();
-        let result = read_version_file(dir.path());
-        assert!(result.is_err());
+        let result = bump_version(dir.path(), true, false, false, Instant::now());
+        assert!(result.unwrap_err().contains("VERSION not found"));
     }
 
     #[test]
@@ -504,7 +367,8 @@ mod tests {
         let path = dir.path().join("test.json");
         fs::write(&path, r#"{"version": "1.0.0"}"#).unwrap();
 
-        write_json_version(&path, "/version", "2.0.0").unwrap();
+        let file = VersionFile { path: "test.json".into(), locator: Locator::Json("/version".into()) };
+        file.write(dir.path(), "2.0.0").unwrap();
 
         let content: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
         assert_eq!(content["version"], "2.0.0");
@@ -516,7 +380,8 @@ mod tests {
         let path = dir.path().join("test.json");
         fs::write(&path, r#"{"plugins": [{"version": "1.0.0"}]}"#).unwrap();
 
-        write_json_version(&path, "/plugins/0/version", "3.0.0").unwrap();
+        let file = VersionFile { path: "test.json".into(), locator: Locator::Json("/plugins/0/version".into()) };
+        file.write(dir.path(), "3.0.0").unwrap();
 
         let content: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
         assert_eq!(content["plugins"][0]["version"], "3.0.0");
@@ -565,9 +430,13 @@ mod tests {
             "[package]\nname = \"test\"\nversion = \"1.0.0\"\n",
         )
         .unwrap();
-        assert_eq!(read_toml_version(&path).unwrap(), "1.0.0");
-        write_toml_version(&path, "2.0.0").unwrap();
-        assert_eq!(read_toml_version(&path).unwrap(), "2.0.0");
+        let file = VersionFile {
+            path: "Cargo.toml".into(),
+            locator: Locator::Toml(vec!["package".into(), "version".into()]),
+        };
+        assert_eq!(file.read(dir.path()).unwrap(), "1.0.0");
+        file.write(dir.path(), "2.0.0").unwrap();
+        assert_eq!(file.read(dir.path()).unwrap(), "2.0.0");
         // Verify formatting preserved
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.contains("name = \"test\""));
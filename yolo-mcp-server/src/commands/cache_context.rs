@@ -1,8 +1,11 @@
+use serde_json::Value;
 use sha2::{Sha256, Digest};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use super::layered_config::Config;
+
 /// Compute a deterministic cache key from phase, role, config, and git state.
 /// Check `.yolo-planning/.cache/context/{hash}.md` for cache hit.
 /// Output: "hit {hash} {path}" or "miss {hash}". Exit 0 always.
@@ -13,10 +16,12 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
 
     let phase = &args[2];
     let role = &args[3];
-    let config_path = if args.len() > 4 {
-        cwd.join(&args[4])
+    // An explicit config path is hashed as given; otherwise the flags resolve
+    // through the layered config.
+    let config = if args.len() > 4 {
+        read_config(&cwd.join(&args[4]))
     } else {
-        cwd.join(".yolo-planning/config.json")
+        Some(Config::load(cwd).merged().clone())
     };
     let plan_path = if args.len() > 5 {
         Some(cwd.join(&args[5]))
@@ -35,8 +40,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         }
 
     // Config V3 flags
-    if config_path.is_file() {
-        let flags = read_v3_flags(&config_path);
+    if let Some(ref config) = config {
+        let flags = read_v3_flags(config);
         hash_input.push_str(&format!(":flags={}", flags));
     }
 
@@ -55,8 +60,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Rolling summary fingerprint
-    if config_path.is_file()
-        && rolling_summary_enabled(&config_path) {
+    if config.as_ref().is_some_and(rolling_summary_enabled) {
             let rolling_path = cwd.join(".yolo-planning/ROLLING-CONTEXT.md");
             if rolling_path.is_file() {
                 let rolling_sum = sha256_file(&rolling_path).unwrap_or_else(|| "norolling".to_string());
@@ -95,17 +99,17 @@ fn sha256_str(s: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-/// Read V3 config flags as comma-separated string.
-fn read_v3_flags(config_path: &Path) -> String {
-    let content = match fs::read_to_string(config_path) {
-        Ok(c) => c,
-        Err(_) => return "false,false,false,false".to_string(),
-    };
-    let json: serde_json::Value = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(_) => return "false,false,false,false".to_string(),
-    };
+/// Parse a config file; None if it is missing or not a regular file.
+fn read_config(config_path: &Path) -> Option<Value> {
+    if !config_path.is_file() {
+        return None;
+    }
+    let content = fs::read_to_string(config_path).unwrap_or_default();
+    Some(serde_json::from_str(&content).unwrap_or_default())
+}
 
+/// Read V3 config flags as comma-separated string.
+fn read_v3_flags(config: &Value) -> String {
     let flags: Vec<String> = [
         "v3_delta_context",
         "v3_context_cache",
@@ -114,7 +118,7 @@ fn read_v3_flags(config_path: &Path) -> String {
     ]
     .iter()
     .map(|key| {
-        json.get(key)
+        config.get(key)
             .and_then(|v| v.as_bool())
             .unwrap_or(false)
             .to_string()
@@ -125,10 +129,8 @@ fn read_v3_flags(config_path: &Path) -> String {
 }
 
 /// Check if rolling summary is enabled in config.
-fn rolling_summary_enabled(config_path: &Path) -> bool {
-    let content = fs::read_to_string(config_path).unwrap_or_default();
-    let json: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
-    json.get("v3_rolling_summary")
+fn rolling_summary_enabled(config: &Value) -> bool {
+    config.get("v3_rolling_summary")
         .and_then(|v| v.as_bool())
         .unwrap_or(false)
 }
@@ -230,11 +232,7 @@ mod tests {
 
     #[test]
     fn test_read_v3_flags_defaults() {
-        let dir = TempDir::new().unwrap();
-        let config = dir.path().join("config.json");
-        fs::write(&config, "{}").unwrap();
-
-        let flags = read_v3_flags(&config);
+        let flags = read_v3_flags(&serde_json::json!({}));
         assert_eq!(flags, "false,false,false,false");
     }
 
@@ -248,29 +246,20 @@ mod tests {
         )
         .unwrap();
 
-        let flags = read_v3_flags(&config);
+        let flags = read_v3_flags(&read_config(&config).unwrap());
         assert_eq!(flags, "true,false,false,true");
     }
 
     #[test]
-    fn test_read_v3_flags_missing_file() {
-        let flags = read_v3_flags(Path::new("/nonexistent/config.json"));
-        assert_eq!(flags, "false,false,false,false");
+    fn test_read_config_missing_file() {
+        assert!(read_config(Path::new("/nonexistent/config.json")).is_none());
     }
 
     #[test]
     fn test_rolling_summary_enabled() {
-        let dir = TempDir::new().unwrap();
-        let config = dir.path().join("config.json");
-
-        fs::write(&config, r#"{"v3_rolling_summary": true}"#).unwrap();
-        assert!(rolling_summary_enabled(&config));
-
-        fs::write(&config, r#"{"v3_rolling_summary": false}"#).unwrap();
-        assert!(!rolling_summary_enabled(&config));
-
-        fs::write(&config, "{}").unwrap();
-        assert!(!rolling_summary_enabled(&config));
+        assert!(rolling_summary_enabled(&serde_json::json!({"v3_rolling_summary": true})));
+        assert!(!rolling_summary_enabled(&serde_json::json!({"v3_rolling_summary": false})));
+        assert!(!rolling_summary_enabled(&serde_json::json!({})));
     }
 
     #[test]
@@ -0,0 +1,354 @@
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use super::extract_changelog;
+use super::frontmatter;
+use super::version_files;
+
+/// Section headings in output order, keyed by conventional commit type.
+const SECTIONS: &[(&str, &[&str])] = &[
+    ("Added", &["feat"]),
+    ("Fixed", &["fix"]),
+    ("Performance", &["perf"]),
+    ("Changed", &["refactor", "style"]),
+    ("Documentation", &["docs"]),
+    ("Tests", &["test"]),
+    ("Maintenance", &["build", "ci", "chore"]),
+];
+
+/// One commit since the last tag, parsed as a conventional commit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub hash: String,
+    /// Conventional type, or None for a free-form subject.
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub description: String,
+    /// `!` marker or the `BREAKING CHANGE:` footer text.
+    pub breaking: Option<String>,
+    /// Phase/plan references from trailers or SUMMARY `commit_hashes`.
+    pub refs: Vec<String>,
+}
+
+fn conventional_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^([a-z]+)(?:\(([^)]+)\))?(!)?: (.+)$").unwrap())
+}
+
+fn release_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^chore(\(release\))?: release v").unwrap())
+}
+
+fn git(cwd: &Path, args: &[&str]) -> Option<String> {
+    let out = Command::new("git").args(args).current_dir(cwd).output().ok()?;
+    out.status.success().then(|| String::from_utf8_lossy(&out.stdout).to_string())
+}
+
+/// Most recent tag reachable from HEAD, if any.
+pub fn last_tag(cwd: &Path) -> Option<String> {
+    git(cwd, &["describe", "--tags", "--abbrev=0", "HEAD"]).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+/// Parse a commit subject and body into an entry; release commits yield None.
+pub fn parse_commit(hash: &str, subject: &str, body: &str) -> Option<Entry> {
+    if release_re().is_match(subject) {
+        return None;
+    }
+    let footer = body.lines().find_map(|l| {
+        l.strip_prefix("BREAKING CHANGE:").or_else(|| l.strip_prefix("BREAKING-CHANGE:")).map(|s| s.trim().to_string())
+    });
+    let mut refs = Vec::new();
+    for line in body.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_ascii_lowercase();
+            if matches!(key.as_str(), "phase" | "plan" | "yolo-phase" | "yolo-plan") && !value.trim().is_empty() {
+                refs.push(format!("{} {}", key.trim_start_matches("yolo-"), value.trim()));
+            }
+        }
+    }
+    let entry = match conventional_re().captures(subject) {
+        Some(c) => Entry {
+            hash: hash.to_string(),
+            kind: Some(c[1].to_string()),
+            scope: c.get(2).map(|m| m.as_str().to_string()),
+            description: c[4].to_string(),
+            breaking: footer.or_else(|| c.get(3).map(|_| String::new())),
+            refs,
+        },
+        None => Entry {
+            hash: hash.to_string(),
+            kind: None,
+            scope: None,
+            description: subject.to_string(),
+            breaking: footer,
+            refs,
+        },
+    };
+    Some(entry)
+}
+
+/// Commits in `since..HEAD` (all of history when `since` is None), oldest first.
+pub fn collect(cwd: &Path, since: Option<&str>) -> Result<Vec<Entry>, String> {
+    let range = since.map(|t| format!("{}..HEAD", t)).unwrap_or_else(|| "HEAD".to_string());
+    let log = git(cwd, &["log", "--no-merges", "--reverse", "--format=%H%x1f%s%x1f%b%x1e", &range])
+        .ok_or_else(|| format!("git log {} failed", range))?;
+    let summaries = summary_refs(&cwd.join(".yolo-planning"));
+    Ok(log
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut parts = record.trim_start_matches('\n').splitn(3, '\x1f');
+            let (hash, subject, body) = (parts.next()?, parts.next()?, parts.next().unwrap_or(""));
+            if hash.is_empty() {
+                return None;
+            }
+            let mut entry = parse_commit(hash, subject, body)?;
+            for (short, link) in &summaries {
+                if hash.starts_with(short.as_str()) && !entry.refs.contains(link) {
+                    entry.refs.push(link.clone());
+                }
+            }
+            Some(entry)
+        })
+        .collect())
+}
+
+/// `(commit hash prefix, markdown link to the plan's SUMMARY)` for every
+/// SUMMARY.md that lists `commit_hashes`, including archived milestones.
+fn summary_refs(planning_dir: &Path) -> Vec<(String, String)> {
+    let mut phase_roots = vec![planning_dir.join("phases")];
+    if let Ok(milestones) = fs::read_dir(planning_dir.join("milestones")) {
+        phase_roots.extend(milestones.flatten().map(|m| m.path().join("phases")));
+    }
+    let mut refs = Vec::new();
+    for root in phase_roots {
+        for phase in fs::read_dir(&root).into_iter().flatten().flatten() {
+            for file in fs::read_dir(phase.path()).into_iter().flatten().flatten() {
+                let name = file.file_name().to_string_lossy().to_string();
+                let Some(plan_id) = name.strip_suffix("-SUMMARY.md") else { continue };
+                let Ok(Some(fm)) = frontmatter::parse_file(&file.path()) else { continue };
+                let rel = file.path().strip_prefix(planning_dir.parent().unwrap_or(planning_dir)).map(PathBuf::from).unwrap_or(file.path());
+                let link = format!("[plan {}]({})", plan_id, rel.to_string_lossy());
+                for hash in fm.list("commit_hashes").into_iter().filter(|h| h.len() >= 7) {
+                    refs.push((hash, link.clone()));
+                }
+            }
+        }
+    }
+    refs
+}
+
+fn bullet(entry: &Entry, note: Option<&str>) -> String {
+    let mut line = match &entry.scope {
+        Some(scope) => format!("- **`{}`** — {}", scope, entry.description),
+        None => format!("- {}", entry.description),
+    };
+    if let Some(note) = note.filter(|n| !n.is_empty()) {
+        line.push_str(&format!(" — {}", note));
+    }
+    if !entry.refs.is_empty() {
+        line.push_str(&format!(" ({})", entry.refs.join(", ")));
+    }
+    line
+}
+
+/// Render a `## v{version} ({date})` section in the existing CHANGELOG format:
+/// breaking changes first, then one `###` group per type, scopes together.
+pub fn render(version: &str, date: &str, entries: &[Entry]) -> String {
+    let mut out = format!("## v{} ({})\n", version, date);
+    let mut group = |title: &str, items: Vec<String>| {
+        if !items.is_empty() {
+            out.push_str(&format!("\n### {}\n{}\n", title, items.join("\n")));
+        }
+    };
+
+    group(
+        "Breaking Changes",
+        entries.iter().filter_map(|e| e.breaking.as_deref().map(|note| bullet(e, Some(note)))).collect(),
+    );
+    let by_scope = |mut items: Vec<&Entry>| -> Vec<String> {
+        // Stable sort keeps commit order within a scope; unscoped entries go last.
+        items.sort_by_key(|e| (e.scope.is_none(), e.scope.clone()));
+        items.into_iter().map(|e| bullet(e, None)).collect()
+    };
+    let rest: Vec<&Entry> = entries.iter().filter(|e| e.breaking.is_none()).collect();
+    for (title, kinds) in SECTIONS {
+        group(title, by_scope(rest.iter().copied().filter(|e| e.kind.as_deref().is_some_and(|k| kinds.contains(&k))).collect()));
+    }
+    let known: Vec<&str> = SECTIONS.iter().flat_map(|(_, k)| k.iter().copied()).collect();
+    group("Other", by_scope(rest.into_iter().filter(|e| e.kind.as_deref().is_none_or(|k| !known.contains(&k))).collect()));
+    out
+}
+
+/// Insert `section` above the newest `## ` section, keeping the preamble.
+pub fn prepend(existing: &str, section: &str) -> String {
+    if existing.trim().is_empty() {
+        return format!("# Changelog\n\n{}", section);
+    }
+    let mut offset = 0;
+    for line in existing.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return format!("{}{}\n{}", &existing[..offset], section, &existing[offset..]);
+        }
+        offset += line.len();
+    }
+    let sep = if existing.ends_with("\n\n") { "" } else if existing.ends_with('\n') { "\n" } else { "\n\n" };
+    format!("{}{}{}", existing, sep, section)
+}
+
+/// Generate the section for `version` from commits since the last tag and
+/// prepend it to CHANGELOG.md (unless `dry_run`). A changelog whose newest
+/// section is already `version` is left alone.
+pub fn generate(cwd: &Path, version: &str, since: Option<&str>, dry_run: bool) -> Result<Value, String> {
+    let path = cwd.join("CHANGELOG.md");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let since = since.map(str::to_string).or_else(|| last_tag(cwd));
+    if extract_changelog::extract_latest_section(&existing).is_some_and(|(v, _, _)| v == version) {
+        return Ok(json!({
+            "version": version,
+            "since": since,
+            "written": false,
+            "skipped": format!("CHANGELOG.md already has v{}", version),
+        }));
+    }
+
+    let entries = collect(cwd, since.as_deref())?;
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let section = render(version, &date, &entries);
+    if !dry_run {
+        fs::write(&path, prepend(&existing, &section)).map_err(|e| format!("Failed to write CHANGELOG.md: {}", e))?;
+    }
+    let mut kinds: BTreeMap<String, usize> = BTreeMap::new();
+    for e in &entries {
+        *kinds.entry(e.kind.clone().unwrap_or_else(|| "other".into())).or_default() += 1;
+    }
+    Ok(json!({
+        "version": version,
+        "since": since,
+        "commits": entries.len(),
+        "by_type": kinds,
+        "breaking": entries.iter().filter(|e| e.breaking.is_some()).count(),
+        "section": section,
+        "written": !dry_run,
+    }))
+}
+
+/// CLI entry point: `yolo changelog [--version X.Y.Z] [--since <ref>] [--dry-run]`
+///
+/// The version defaults to the current one from the release version files.
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let value_of = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned();
+
+    let version = match value_of("--version") {
+        Some(v) => v.trim_start_matches('v').to_string(),
+        None => {
+            let (files, _) = version_files::load(cwd)?;
+            files[0].read(cwd)?
+        }
+    };
+    let delta = generate(cwd, &version, value_of("--since").as_deref(), dry_run)?;
+    let response = json!({
+        "ok": true,
+        "cmd": "changelog",
+        "delta": delta,
+        "elapsed_ms": start.elapsed().as_millis() as u64
+    });
+    Ok((response.to_string(), 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(dir: &Path, msg: &str) -> String {
+        fs::write(dir.join("f.txt"), msg).unwrap();
+        for args in [vec!["add", "."], vec!["-c", "user.email=t@t", "-c", "user.name=T", "commit", "-qm", msg]] {
+            Command::new("git").args(&args).current_dir(dir).output().unwrap();
+        }
+        git(dir, &["rev-parse", "HEAD"]).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn test_parse_commit_types_and_breaking() {
+        let e = parse_commit("a", "feat(router)!: drop legacy names", "").unwrap();
+        assert_eq!(e.kind.as_deref(), Some("feat"));
+        assert_eq!(e.scope.as_deref(), Some("router"));
+        assert_eq!(e.breaking.as_deref(), Some(""));
+
+        let e = parse_commit("b", "fix: handle empty input", "Details\n\nBREAKING CHANGE: input is now required\nPlan: 03-02").unwrap();
+        assert_eq!(e.breaking.as_deref(), Some("input is now required"));
+        assert_eq!(e.refs, vec!["plan 03-02"]);
+
+        assert_eq!(parse_commit("c", "Update readme", "").unwrap().kind, None);
+        assert!(parse_commit("d", "chore: release v1.2.3", "").is_none());
+    }
+
+    #[test]
+    fn test_render_groups_by_type_and_scope() {
+        let entries = vec![
+            parse_commit("1", "fix(cli): exit code on error", "").unwrap(),
+            parse_commit("2", "feat(api): add pagination", "").unwrap(),
+            parse_commit("3", "feat: new landing page", "").unwrap(),
+            parse_commit("4", "feat(api)!: rename endpoint", "").unwrap(),
+            parse_commit("5", "wip stuff", "").unwrap(),
+        ];
+        let section = render("1.3.0", "2026-01-02", &entries);
+        assert_eq!(
+            section,
+            "## v1.3.0 (2026-01-02)\n\n\
+             ### Breaking Changes\n- **`api`** — rename endpoint\n\n\
+             ### Added\n- **`api`** — add pagination\n- new landing page\n\n\
+             ### Fixed\n- **`cli`** — exit code on error\n\n\
+             ### Other\n- wip stuff\n"
+        );
+    }
+
+    #[test]
+    fn test_prepend_keeps_preamble_and_extract_reads_it() {
+        let existing = "# Changelog\n\nAll notable changes.\n\n## v1.0.0 (2025-01-01)\n\n### Fixed\n- old\n";
+        let updated = prepend(existing, &render("1.1.0", "2026-01-02", &[parse_commit("1", "fix: new", "").unwrap()]));
+        assert!(updated.starts_with("# Changelog\n\nAll notable changes.\n\n## v1.1.0 (2026-01-02)\n"));
+        assert!(updated.contains("\n## v1.0.0 (2025-01-01)"));
+        let (version, date, body) = extract_changelog::extract_latest_section(&updated).unwrap();
+        assert_eq!((version.as_str(), date.as_deref()), ("1.1.0", Some("2026-01-02")));
+        assert!(body.contains("- new") && !body.contains("old"));
+    }
+
+    #[test]
+    fn test_generate_since_last_tag_with_summary_links() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        Command::new("git").args(["init", "-q"]).current_dir(root).output().unwrap();
+        commit(root, "feat: first");
+        Command::new("git").args(["tag", "v1.0.0"]).current_dir(root).output().unwrap();
+        let hash = commit(root, "fix(core): after tag");
+        commit(root, "chore: release v1.0.1");
+
+        let phase = root.join(".yolo-planning/phases/02-core");
+        fs::create_dir_all(&phase).unwrap();
+        fs::write(phase.join("02-01-SUMMARY.md"), format!("---\nphase: 2\nplan: 1\ncommit_hashes:\n  - {}\n---\n", &hash[..7])).unwrap();
+
+        let preview = generate(root, "1.0.1", None, true).unwrap();
+        assert_eq!(preview["since"], "v1.0.0");
+        assert_eq!(preview["commits"], 1);
+        assert_eq!(preview["written"], false);
+        assert!(preview["section"].as_str().unwrap().contains(
+            "- **`core`** — after tag ([plan 02-01](.yolo-planning/phases/02-core/02-01-SUMMARY.md))"
+        ));
+        assert!(!root.join("CHANGELOG.md").exists());
+
+        generate(root, "1.0.1", None, false).unwrap();
+        let again = generate(root, "1.0.1", None, false).unwrap();
+        assert_eq!(again["written"], false);
+        assert_eq!(fs::read_to_string(root.join("CHANGELOG.md")).unwrap().matches("## v1.0.1").count(), 1);
+    }
+}
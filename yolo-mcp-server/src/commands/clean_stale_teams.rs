@@ -1,14 +1,16 @@
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::event_bus;
+
 const STALE_THRESHOLD_SECS: u64 = 7200; // 2 hours
 
 /// Clean stale agent team directories under CLAUDE_DIR/teams/.
 /// Teams with all inbox files older than 2 hours are removed atomically.
+/// Actions are published to the event bus under `planning_dir`.
 /// Returns (teams_cleaned, tasks_cleaned).
-pub fn clean_stale_teams(claude_dir: &Path, log_file: &Path) -> (usize, usize) {
+pub fn clean_stale_teams(claude_dir: &Path, planning_dir: &Path) -> (usize, usize) {
     let teams_dir = claude_dir.join("teams");
     let tasks_dir = claude_dir.join("tasks");
 
@@ -64,7 +66,7 @@ pub fn clean_stale_teams(claude_dir: &Path, log_file: &Path) -> (usize, usize) {
         if fs::rename(&team_path, &dest).is_ok() {
             teams_cleaned += 1;
             log_msg(
-                log_file,
+                planning_dir,
                 &format!("Stale team cleanup: {team_name} (stale for {stale_hours}h)"),
             );
         }
@@ -76,7 +78,7 @@ pub fn clean_stale_teams(claude_dir: &Path, log_file: &Path) -> (usize, usize) {
             if fs::rename(&task_path, &task_dest).is_ok() {
                 tasks_cleaned += 1;
                 log_msg(
-                    log_file,
+                    planning_dir,
                     &format!("Stale tasks cleanup: {team_name} (paired with team)"),
                 );
             }
@@ -88,7 +90,7 @@ pub fn clean_stale_teams(claude_dir: &Path, log_file: &Path) -> (usize, usize) {
 
     if teams_cleaned > 0 || tasks_cleaned > 0 {
         log_msg(
-            log_file,
+            planning_dir,
             &format!("Summary: {teams_cleaned} teams cleaned, {tasks_cleaned} tasks removed"),
         );
     }
@@ -115,18 +117,8 @@ fn most_recent_mtime(dir: &Path) -> u64 {
     latest
 }
 
-fn log_msg(log_file: &Path, msg: &str) {
-    let ts = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let _ = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file)
-        .map(|mut f| {
-            let _ = writeln!(f, "[{ts}] {msg}");
-        });
+fn log_msg(planning_dir: &Path, msg: &str) {
+    event_bus::publish_message(planning_dir, "clean-stale-teams", msg);
 }
 
 #[cfg(test)]
@@ -137,8 +129,7 @@ mod tests {
     #[test]
     fn test_no_teams_dir() {
         let dir = tempdir().unwrap();
-        let log = dir.path().join("test.log");
-        let (t, ta) = clean_stale_teams(dir.path(), &log);
+        let (t, ta) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 0);
         assert_eq!(ta, 0);
     }
@@ -147,8 +138,7 @@ mod tests {
     fn test_empty_teams_dir() {
         let dir = tempdir().unwrap();
         fs::create_dir(dir.path().join("teams")).unwrap();
-        let log = dir.path().join("test.log");
-        let (t, _) = clean_stale_teams(dir.path(), &log);
+        let (t, _) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 0);
     }
 
@@ -164,8 +154,7 @@ mod tests {
         fs::create_dir_all(&inbox).unwrap();
         fs::write(inbox.join("msg.json"), "{}").unwrap();
 
-        let log = dir.path().join("test.log");
-        let (t, _) = clean_stale_teams(dir.path(), &log);
+        let (t, _) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 0);
         assert!(team.exists()); // Not cleaned
     }
@@ -177,8 +166,7 @@ mod tests {
         let team = teams.join("no-inbox-team");
         fs::create_dir_all(&team).unwrap();
 
-        let log = dir.path().join("test.log");
-        let (t, _) = clean_stale_teams(dir.path(), &log);
+        let (t, _) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 0);
         assert!(team.exists());
     }
@@ -195,13 +183,12 @@ mod tests {
         fs::create_dir_all(&inbox).unwrap();
         // No files in inbox => mtime stays 0 => definitely stale
 
-        let log = dir.path().join("test.log");
-        let (t, _) = clean_stale_teams(dir.path(), &log);
+        let (t, _) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 1);
         assert!(!team.exists()); // Cleaned
 
         // Check log
-        let log_content = fs::read_to_string(&log).unwrap();
+        let log_content = event_bus::read_all(dir.path());
         assert!(log_content.contains("Stale team cleanup: old-team"));
     }
 
@@ -222,8 +209,7 @@ mod tests {
         let task = tasks.join("paired-team");
         fs::create_dir(&task).unwrap();
 
-        let log = dir.path().join("test.log");
-        let (t, ta) = clean_stale_teams(dir.path(), &log);
+        let (t, ta) = clean_stale_teams(dir.path(), dir.path());
         assert_eq!(t, 1);
         assert_eq!(ta, 1);
         assert!(!team.exists());
@@ -29,6 +29,8 @@ fn parse_args(args: &[String]) -> (Option<String>, Vec<(String, String)>) {
 
 /// Core metrics collection function callable from other Rust code.
 /// Appends a JSON line to `.yolo-planning/.metrics/run-metrics.jsonl`.
+/// This file bypasses the event bus: the reports need its full history, which
+/// the bus would rotate away.
 /// Never fails fatally — returns Ok with CollectResult.
 pub fn collect(
     event: &str,
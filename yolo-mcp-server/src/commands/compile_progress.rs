@@ -113,7 +113,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     Ok((resp.to_string(), 0))
 }
 
-fn resolve_milestone(planning_dir: &Path, milestones_dir: &Path) -> (Option<String>, Option<std::path::PathBuf>) {
+pub(crate) fn resolve_milestone(planning_dir: &Path, milestones_dir: &Path) -> (Option<String>, Option<std::path::PathBuf>) {
     // Check ACTIVE file first
     let active_file = planning_dir.join("ACTIVE");
     if active_file.exists()
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::Path;
 
+use super::frontmatter;
+
 const LINE_CAP: usize = 200;
 
 /// CLI entry: `yolo rolling-summary [phases-dir] [output-path]`
@@ -126,61 +128,41 @@ fn discover_summaries(phases_dir: &Path) -> (Vec<std::path::PathBuf>, usize) {
     (completed, all_summaries.len())
 }
 
-/// Extract a frontmatter field from YAML frontmatter (between --- delimiters).
+/// Extract a scalar frontmatter field; empty when missing or the frontmatter
+/// is absent or unparseable.
 fn extract_frontmatter_field(content: &str, field: &str) -> String {
-    let mut in_frontmatter = false;
-    let prefix = format!("{}:", field);
-
-    for line in content.lines() {
-        if line.trim() == "---" {
-            if in_frontmatter {
-                break; // end of frontmatter
-            }
-            in_frontmatter = true;
-            continue;
-        }
-        if in_frontmatter && line.starts_with(&prefix) {
-            return line[prefix.len()..]
-                .trim()
-                .trim_matches('"')
-                .to_string();
-        }
+    match frontmatter::parse(content) {
+        Ok(Some(fm)) => fm.scalar(field).unwrap_or_default(),
+        _ => String::new(),
     }
-
-    String::new()
 }
 
 /// Extract a condensed entry from a SUMMARY.md file.
 fn extract_entry(summary_path: &Path) -> Option<String> {
     let content = fs::read_to_string(summary_path).ok()?;
 
-    let fm_phase = extract_frontmatter_field(&content, "phase");
-    let fm_plan = extract_frontmatter_field(&content, "plan");
-    let fm_title = extract_frontmatter_field(&content, "title");
-    let fm_deviations = extract_frontmatter_field(&content, "deviations");
-    let fm_commits_raw = extract_frontmatter_field(&content, "commit_hashes");
-
-    let phase = if fm_phase.is_empty() { "?" } else { &fm_phase };
-    let plan = if fm_plan.is_empty() { "?" } else { &fm_plan };
-    let title = if fm_title.is_empty() {
-        "Untitled"
-    } else {
-        &fm_title
-    };
-    let deviations = if fm_deviations.is_empty() {
-        "0"
-    } else {
-        &fm_deviations
-    };
-
-    // Parse first commit hash from comma-separated list
-    let commit = fm_commits_raw
-        .trim_matches(|c| c == '[' || c == ']')
-        .split(',')
-        .next()
-        .unwrap_or("none")
-        .trim()
-        .trim_matches('"');
+    let fm = frontmatter::parse(&content).ok().flatten();
+    let summary = fm.as_ref().map(|f| f.summary()).unwrap_or_default();
+
+    let phase = summary.phase.as_deref().filter(|s| !s.is_empty()).unwrap_or("?");
+    let plan = summary.plan.as_deref().filter(|s| !s.is_empty()).unwrap_or("?");
+    let title = summary
+        .title
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .unwrap_or("Untitled");
+    // `deviations` is either a count or a list of descriptions
+    let deviations = fm
+        .as_ref()
+        .and_then(|f| f.scalar("deviations"))
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| summary.deviations.len().to_string());
+
+    let commit = summary
+        .commit_hashes
+        .first()
+        .map(|s| s.as_str())
+        .unwrap_or("none");
     let commit = if commit.is_empty() { "none" } else { commit };
 
     // Extract "## What Was Built" — first 3 non-empty lines
@@ -0,0 +1,409 @@
+use serde_json::{json, Map, Value};
+use std::path::Path;
+use std::time::Instant;
+
+use super::atomic_io::atomic_write;
+use super::feature_flags::unmet_requirements;
+use super::layered_config::{project_config_path, Config, ConfigIssue, Layer};
+use super::role_registry::RoleRegistry;
+
+const USAGE: &str = "Usage: yolo config <validate | set <key> <value> [--force] | unset <key> [--force] | diff>";
+
+/// `yolo config {validate|set|unset|diff}`: check the layered config against the
+/// schema and flag dependencies, and edit the project layer atomically.
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+    let force = args.iter().any(|a| a == "--force");
+    let args: Vec<String> = args.iter().filter(|a| *a != "--force").cloned().collect();
+    let action = args.get(2).map(String::as_str).ok_or(USAGE)?;
+    let config = Config::load(cwd);
+
+    let (mut out, code) = match action {
+        "validate" => validate(&config, cwd),
+        "set" => {
+            let (Some(key), Some(raw)) = (args.get(3), args.get(4)) else {
+                return Err("Usage: yolo config set <key> <value> [--force]".to_string());
+            };
+            // JSON when it parses (`true`, `60000`, `{"dev": 90}`), otherwise a plain string.
+            let value = serde_json::from_str::<Value>(raw).unwrap_or_else(|_| Value::String(raw.clone()));
+            set(&config, cwd, key, Some(value), force)?
+        }
+        "unset" => {
+            let key = args.get(3).ok_or("Usage: yolo config unset <key> [--force]")?;
+            set(&config, cwd, key, None, force)?
+        }
+        "diff" => (diff(&config), 0),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    if let Some(obj) = out.as_object_mut() {
+        obj.insert("action".into(), json!(action));
+        obj.insert("elapsed_ms".into(), json!(start.elapsed().as_millis()));
+    }
+    Ok((serde_json::to_string(&out).unwrap() + "\n", code))
+}
+
+fn issue_json(i: &ConfigIssue) -> Value {
+    json!({"layer": i.layer, "origin": i.origin, "pointer": i.pointer, "message": i.message})
+}
+
+/// Unmet flag requirements in the merged config, each pointing at the flag that needs the other.
+fn dependency_errors(config: &Config) -> Vec<Value> {
+    unmet_requirements(|f| config.flag(f))
+        .into_iter()
+        .map(|u| {
+            json!({
+                "pointer": format!("/{}", u.flag.key()),
+                "flag": u.flag.key(),
+                "requires": u.requires.key(),
+                "source": config.source_of(u.flag.key()).map(|l| l.layer.name()),
+                "message": u.message,
+            })
+        })
+        .collect()
+}
+
+/// Schema and dependency errors fail validation. Role definitions that the
+/// registry had to skip (bad `roles` entries, unreadable agent files) are
+/// reported as warnings.
+fn validate(config: &Config, cwd: &Path) -> (Value, i32) {
+    let errors: Vec<Value> = config.issues().iter().map(issue_json).collect();
+    let deps = dependency_errors(config);
+    let valid = errors.is_empty() && deps.is_empty();
+    let out = json!({
+        "ok": valid,
+        "cmd": "config",
+        "valid": valid,
+        "schema_found": config.schema().is_some(),
+        "errors": errors,
+        "dependency_errors": deps,
+        "warnings": RoleRegistry::load(cwd).warnings,
+    });
+    (out, if valid { 0 } else { 1 })
+}
+
+/// Set (or, with `None`, remove) a dotted key in the project config. The edit is
+/// checked first and nothing is written if it adds a schema error, or a flag
+/// dependency error unless `force` is set (like `yolo flags enable`, which
+/// refuses the same edits).
+fn set(config: &Config, cwd: &Path, key: &str, value: Option<Value>, force: bool) -> Result<(Value, i32), String> {
+    let project = config.layer(Layer::Project).ok_or("project config layer not loaded")?;
+    if let Some(e) = &project.parse_error {
+        return Err(format!("{}: {} -- fix it before editing", project.origin, e));
+    }
+
+    let mut values = project.values.clone();
+    let previous = match &value {
+        Some(v) => set_dotted(&mut values, key, v.clone())?,
+        None => remove_dotted(&mut values, key),
+    };
+    let changed = match &value {
+        Some(v) => previous.as_ref() != Some(v),
+        None => previous.is_some(),
+    };
+
+    let candidate = config.with_layer_values(Layer::Project, values.clone());
+    let before = config.issues();
+    let new_errors: Vec<Value> = candidate
+        .issues()
+        .iter()
+        .filter(|i| i.layer == Layer::Project.name() && !before.contains(i))
+        .map(issue_json)
+        .collect();
+
+    let mut out = json!({
+        "cmd": "config",
+        "key": key,
+        "previous": previous,
+        "value": candidate.get(key).cloned().unwrap_or(Value::Null),
+        "source": candidate.source_of(key).map(|l| l.layer.name()).unwrap_or("missing"),
+    });
+    if !new_errors.is_empty() {
+        out["ok"] = json!(false);
+        out["written"] = json!(false);
+        out["errors"] = json!(new_errors);
+        return Ok((out, 1));
+    }
+
+    let deps_before = dependency_errors(config);
+    let (new_deps, old_deps): (Vec<Value>, Vec<Value>) =
+        dependency_errors(&candidate).into_iter().partition(|d| !deps_before.contains(d));
+    if !new_deps.is_empty() && !force {
+        out["ok"] = json!(false);
+        out["written"] = json!(false);
+        out["dependency_errors"] = json!(new_deps);
+        return Ok((out, 1));
+    }
+
+    if changed {
+        write_project_layer(cwd, values)?;
+    }
+    out["ok"] = json!(true);
+    out["written"] = json!(changed);
+    out["warnings"] = json!(new_deps.into_iter().chain(old_deps).collect::<Vec<_>>());
+    Ok((out, 0))
+}
+
+/// Replace `.yolo-planning/config.json` atomically with `values`.
+pub fn write_project_layer(cwd: &Path, values: Map<String, Value>) -> Result<(), String> {
+    let path = project_config_path(cwd);
+    let content = serde_json::to_string_pretty(&Value::Object(values)).unwrap() + "\n";
+    atomic_write(&path, content.as_bytes()).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+/// Insert at a dotted path, creating intermediate objects. Returns the replaced value.
+fn set_dotted(map: &mut Map<String, Value>, key: &str, value: Value) -> Result<Option<Value>, String> {
+    match key.split_once('.') {
+        None => Ok(map.insert(key.to_string(), value)),
+        Some((head, rest)) => {
+            let child = map.entry(head).or_insert_with(|| Value::Object(Map::new()));
+            match child.as_object_mut() {
+                Some(obj) => set_dotted(obj, rest, value),
+                None => Err(format!("cannot set '{}': '{}' is not an object", key, head)),
+            }
+        }
+    }
+}
+
+/// Remove a dotted path, dropping parents left empty. Returns the removed value.
+fn remove_dotted(map: &mut Map<String, Value>, key: &str) -> Option<Value> {
+    match key.split_once('.') {
+        None => map.remove(key),
+        Some((head, rest)) => {
+            let obj = map.get_mut(head)?.as_object_mut()?;
+            let removed = remove_dotted(obj, rest);
+            if obj.is_empty() {
+                map.remove(head);
+            }
+            removed
+        }
+    }
+}
+
+/// Every leaf of the merged config that differs from `config/defaults.json`.
+fn diff(config: &Config) -> Value {
+    let mut defaults = Vec::new();
+    if let Some(l) = config.layer(Layer::Defaults) {
+        flatten("", &Value::Object(l.values.clone()), &mut defaults);
+    }
+    let mut effective = Vec::new();
+    flatten("", config.merged(), &mut effective);
+
+    let defaults: Map<String, Value> = defaults.into_iter().collect();
+    let changes: Vec<Value> = effective
+        .into_iter()
+        .filter(|(k, v)| defaults.get(k) != Some(v))
+        .map(|(k, v)| {
+            json!({
+                "key": k,
+                "default": defaults.get(&k),
+                "value": v,
+                "source": config.source_of(&k).map(|l| l.layer.name()),
+            })
+        })
+        .collect();
+    json!({"ok": true, "cmd": "config", "count": changes.len(), "changes": changes})
+}
+
+/// Flatten objects into `(dotted.key, leaf)` pairs; arrays and scalars are leaves.
+fn flatten(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(obj) if prefix.is_empty() || !obj.is_empty() => {
+            for (k, v) in obj {
+                let key = if prefix.is_empty() { k.clone() } else { format!("{}.{}", prefix, k) };
+                flatten(&key, v, out);
+            }
+        }
+        _ => out.push((prefix.to_string(), value.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A plugin root with a small schema and defaults, plus a project config.
+    fn setup(project: Value) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let config_dir = dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(
+            config_dir.join("config.schema.json"),
+            json!({
+                "type": "object",
+                "properties": {
+                    "effort": {"type": "string", "enum": ["balanced", "fast"]},
+                    "agent_max_turns": {"type": "object", "additionalProperties": {"type": "integer"}},
+                    "v2_hard_gates": {"type": "boolean"},
+                    "v2_hard_contracts": {"type": "boolean"},
+                    "v3_event_log": {"type": "boolean"},
+                    "v3_metrics": {"type": "boolean"}
+                },
+                "additionalProperties": false
+            })
+            .to_string(),
+        )
+        .unwrap();
+        fs::write(
+            config_dir.join("defaults.json"),
+            json!({"effort": "balanced", "agent_max_turns": {"dev": 75}, "v3_metrics": false}).to_string(),
+        )
+        .unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), project.to_string()).unwrap();
+        dir
+    }
+
+    fn run(dir: &TempDir, args: &[&str]) -> (Value, i32) {
+        let mut full = vec!["yolo".to_string(), "config".to_string()];
+        full.extend(args.iter().map(|s| s.to_string()));
+        let (out, code) = execute(&full, dir.path()).unwrap();
+        (serde_json::from_str(&out).unwrap(), code)
+    }
+
+    fn project(dir: &TempDir) -> Value {
+        serde_json::from_str(&fs::read_to_string(dir.path().join(".yolo-planning/config.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_validate_reports_typos_and_flag_dependencies() {
+        let dir = setup(json!({"v3_metric": true, "v2_hard_gates": true, "v2_hard_contracts": true}));
+        let (out, code) = run(&dir, &["validate"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["valid"], false);
+        assert_eq!(out["errors"][0]["pointer"], "/v3_metric");
+        assert_eq!(out["errors"][0]["layer"], "project");
+        assert!(out["errors"][0]["message"].as_str().unwrap().contains("did you mean 'v3_metrics'"));
+        assert_eq!(out["dependency_errors"][0]["flag"], "v2_hard_gates");
+        assert_eq!(out["dependency_errors"][0]["requires"], "v3_event_log");
+    }
+
+    #[test]
+    fn test_validate_clean_config() {
+        let dir = setup(json!({"effort": "fast"}));
+        let (out, code) = run(&dir, &["validate"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["valid"], true);
+        assert_eq!(out["schema_found"], true);
+    }
+
+    #[test]
+    fn test_validate_reports_role_warnings() {
+        let dir = setup(json!({"roles": {"qa": {"family": "backstage"}}}));
+        let (out, _) = run(&dir, &["validate"]);
+        let warnings = out["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].as_str().unwrap().contains("unknown family 'backstage'"));
+    }
+
+    #[test]
+    fn test_shipped_defaults_with_rollout_keys_validate() {
+        let dir = TempDir::new().unwrap();
+        let shipped = Path::new(env!("CARGO_MANIFEST_DIR")).join("../config");
+        let config_dir = dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        for name in ["config.schema.json", "defaults.json"] {
+            fs::copy(shipped.join(name), config_dir.join(name)).unwrap();
+        }
+        // A project config copied from the defaults, then moved to a rollout stage.
+        let mut project: Value = serde_json::from_str(&fs::read_to_string(shipped.join("defaults.json")).unwrap()).unwrap();
+        project["rollout_stage"] = json!("partial");
+        project["max_agents"] = json!(4);
+        project["rollout_scope"] = json!("expanded");
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), project.to_string()).unwrap();
+
+        let (out, code) = run(&dir, &["validate"]);
+        assert_eq!(out["errors"], json!([]));
+        assert_eq!(out["dependency_errors"], json!([]));
+        assert_eq!(code, 0);
+    }
+
+    #[test]
+    fn test_set_rejects_schema_errors_without_writing() {
+        let dir = setup(json!({"effort": "fast"}));
+        let (out, code) = run(&dir, &["set", "v3_metric", "true"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["written"], false);
+        assert_eq!(out["errors"][0]["pointer"], "/v3_metric");
+
+        let (out, code) = run(&dir, &["set", "agent_max_turns.dev", "lots"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["errors"][0]["pointer"], "/agent_max_turns/dev");
+        assert_eq!(project(&dir), json!({"effort": "fast"}));
+    }
+
+    #[test]
+    fn test_set_and_unset_nested_key() {
+        let dir = setup(json!({"effort": "fast"}));
+        let (out, code) = run(&dir, &["set", "agent_max_turns.dev", "120"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["written"], true);
+        assert_eq!(out["source"], "project");
+        assert_eq!(project(&dir)["agent_max_turns"]["dev"], 120);
+
+        let (out, _) = run(&dir, &["unset", "agent_max_turns.dev"]);
+        assert_eq!(out["previous"], 120);
+        assert_eq!(out["value"], 75);
+        assert_eq!(out["source"], "defaults");
+        assert_eq!(project(&dir), json!({"effort": "fast"}));
+
+        let (out, _) = run(&dir, &["unset", "agent_max_turns.dev"]);
+        assert_eq!(out["written"], false);
+    }
+
+    #[test]
+    fn test_set_refuses_unmet_flag_dependency_without_force() {
+        let dir = setup(json!({}));
+        let (out, code) = run(&dir, &["set", "v2_hard_gates", "true"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["written"], false);
+        let requires: Vec<&str> =
+            out["dependency_errors"].as_array().unwrap().iter().map(|w| w["requires"].as_str().unwrap()).collect();
+        assert_eq!(requires, vec!["v2_hard_contracts", "v3_event_log"]);
+        assert_eq!(project(&dir), json!({}));
+
+        let (out, code) = run(&dir, &["set", "v2_hard_gates", "true", "--force"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["written"], true);
+        assert_eq!(out["warnings"].as_array().unwrap().len(), 2);
+        assert_eq!(project(&dir)["v2_hard_gates"], true);
+    }
+
+    #[test]
+    fn test_unset_refuses_removing_a_required_flag() {
+        let dir = setup(json!({"v2_hard_gates": true, "v2_hard_contracts": true, "v3_event_log": true}));
+        let (out, code) = run(&dir, &["unset", "v3_event_log"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["dependency_errors"][0]["flag"], "v2_hard_gates");
+        assert_eq!(project(&dir)["v3_event_log"], true);
+
+        // An edit that leaves an existing violation as it was is not refused.
+        let dir = setup(json!({"v2_hard_gates": true, "v2_hard_contracts": true}));
+        let (out, code) = run(&dir, &["set", "effort", "fast"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["warnings"][0]["requires"], "v3_event_log");
+    }
+
+    #[test]
+    fn test_diff_against_defaults() {
+        let dir = setup(json!({"effort": "fast", "agent_max_turns": {"dev": 75, "qa": 30}}));
+        let (out, _) = run(&dir, &["diff"]);
+        assert_eq!(out["count"], 2);
+        let changes = out["changes"].as_array().unwrap();
+        let find = |k: &str| changes.iter().find(|c| c["key"] == k).unwrap();
+        assert_eq!(find("effort"), &json!({"key": "effort", "default": "balanced", "value": "fast", "source": "project"}));
+        assert_eq!(find("agent_max_turns.qa")["default"], Value::Null);
+    }
+
+    #[test]
+    fn test_unknown_action_is_usage_error() {
+        let dir = setup(json!({}));
+        let args = vec!["yolo".to_string(), "config".to_string(), "edit".to_string()];
+        assert!(execute(&args, dir.path()).is_err());
+    }
+}
@@ -3,6 +3,8 @@ use std::path::Path;
 use std::time::Instant;
 use serde_json::{json, Value};
 
+use super::layered_config::Config;
+
 fn build_response(key: &str, value: Option<&Value>, default: Option<&str>, elapsed: u128) -> String {
     let (resolved, source) = match (value, default) {
         (Some(v), _) => (v.clone(), "config"),
@@ -20,11 +22,22 @@ fn build_response(key: &str, value: Option<&Value>, default: Option<&str>, elaps
 }
 
 /// Read a config key from a JSON config file with dot-notation support for nested keys.
+/// `--explain <key>` instead reports every config layer and which one won.
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let start = Instant::now();
 
     if args.len() < 3 {
-        return Err(r#"{"error":"Usage: yolo config-read <key> [default_value] [config_path]"}"#.to_string());
+        return Err(r#"{"error":"Usage: yolo config-read <key> [default_value] [config_path] | --explain <key>"}"#.to_string());
+    }
+
+    if args[2] == "--explain" {
+        let key = args.get(3).ok_or(r#"{"error":"Usage: yolo config-read --explain <key>"}"#)?;
+        let mut out = json!({"ok": true, "cmd": "config-read"});
+        if let (Some(obj), Value::Object(ex)) = (out.as_object_mut(), Config::load(cwd).explain(key)) {
+            obj.extend(ex);
+            obj.insert("elapsed_ms".into(), json!(start.elapsed().as_millis()));
+        }
+        return Ok((serde_json::to_string(&out).unwrap() + "\n", 0));
     }
 
     let key = &args[2];
@@ -177,6 +190,26 @@ mod tests {
         assert!(parsed["value"].is_null());
     }
 
+    #[test]
+    fn test_explain_reports_project_layer() {
+        let dir = tempdir().unwrap();
+        let planning_dir = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning_dir).unwrap();
+        fs::write(planning_dir.join("config.json"), r#"{"agent_max_turns":{"dev":90}}"#).unwrap();
+
+        let (out, code) = execute(
+            &["yolo".into(), "config-read".into(), "--explain".into(), "agent_max_turns.dev".into()],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["value"], 90);
+        assert_eq!(parsed["source"], "project");
+        let project = parsed["layers"].as_array().unwrap().iter()
+            .find(|l| l["layer"] == "project").unwrap();
+        assert_eq!(project["value"], 90);
+    }
+
     #[test]
     fn test_handles_missing_config_file() {
         let dir = tempdir().unwrap();
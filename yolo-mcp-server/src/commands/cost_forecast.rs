@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use super::compile_progress;
+use super::cost_history::{norm, phase_plans, round2, History, PlanFile, Pricer, Usage};
+use super::event_bus;
+use super::layered_config::Config;
+use super::log_event;
+use super::utils;
+
+/// Written next to the other planning sidecars when a projection exceeds its
+/// budget; the statusline `budget` segment reads it.
+pub const ALERT_FILE: &str = ".cost-forecast.json";
+
+/// Per-phase and per-milestone USD budgets from `cost_budget`.
+#[derive(Debug, Default)]
+pub struct Budgets {
+    phase: Option<f64>,
+    milestone: Option<f64>,
+    phases: BTreeMap<String, f64>,
+}
+
+impl Budgets {
+    pub fn from_config(config: &Config) -> Self {
+        let phases = config
+            .get("cost_budget.phases")
+            .and_then(Value::as_object)
+            .map(|m| {
+                m.iter()
+                    .filter_map(|(k, v)| Some((norm(&json!(k))?, v.as_f64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            phase: config.get("cost_budget.phase_usd").and_then(Value::as_f64),
+            milestone: config.get("cost_budget.milestone_usd").and_then(Value::as_f64),
+            phases,
+        }
+    }
+
+    fn for_phase(&self, phase: &str) -> Option<f64> {
+        self.phases.get(phase).copied().or(self.phase)
+    }
+}
+
+#[derive(Debug)]
+pub struct PhaseForecast {
+    pub phase: String,
+    pub remaining_plans: usize,
+    pub spent: Usage,
+    pub remaining: Usage,
+    pub budget_usd: Option<f64>,
+}
+
+#[derive(Debug)]
+pub struct Alert {
+    pub scope: &'static str,
+    pub phase: Option<String>,
+    pub projected_usd: f64,
+    pub budget_usd: f64,
+}
+
+#[derive(Debug)]
+pub struct Forecast {
+    pub milestone: Option<String>,
+    /// Remaining plans are priced at this profile's role-to-model mapping.
+    pub profile: String,
+    pub pricing_updated: Option<String>,
+    pub history_plans: usize,
+    pub phases: Vec<PhaseForecast>,
+    /// Expected spend of the remaining plans, by role.
+    pub by_role: BTreeMap<String, Usage>,
+    pub milestone_budget_usd: Option<f64>,
+}
+
+impl Forecast {
+    pub fn build(planning_dir: &Path, history: &History, pricer: &Pricer, budgets: &Budgets, default_effort: &str) -> Self {
+        let (milestone, phases_dir) = compile_progress::resolve_milestone(planning_dir, &planning_dir.join("milestones"));
+        let mut phases = Vec::new();
+        let mut by_role: BTreeMap<String, Usage> = BTreeMap::new();
+        for (name, dir) in phases_dir.map(|d| utils::sorted_phase_dirs(&d)).unwrap_or_default() {
+            let Some(phase) = norm(&json!(name.split('-').next().unwrap_or(""))) else { continue };
+            let open: Vec<PlanFile> = phase_plans(&dir).into_iter().filter(|p| !p.done).collect();
+            let mut remaining = Usage::default();
+            for plan in &open {
+                for (role, usage) in history.per_plan(plan.effort.as_deref().unwrap_or(default_effort)) {
+                    let usage = usage.repriced(pricer.role_rates(&role, &pricer.active_profile));
+                    remaining.add(&usage);
+                    by_role.entry(role).or_default().add(&usage);
+                }
+            }
+            phases.push(PhaseForecast {
+                budget_usd: budgets.for_phase(&phase),
+                spent: history.spent(&phase),
+                remaining_plans: open.len(),
+                remaining,
+                phase,
+            });
+        }
+        Self {
+            milestone,
+            profile: pricer.active_profile.clone(),
+            pricing_updated: pricer.pricing.updated.clone(),
+            history_plans: history.plan_count(),
+            phases,
+            by_role,
+            milestone_budget_usd: budgets.milestone,
+        }
+    }
+
+    pub fn spent_usd(&self) -> f64 {
+        self.phases.iter().fold(0.0, |sum, p| sum + p.spent.usd)
+    }
+
+    pub fn remaining_usd(&self) -> f64 {
+        self.phases.iter().fold(0.0, |sum, p| sum + p.remaining.usd)
+    }
+
+    pub fn projected_usd(&self) -> f64 {
+        self.spent_usd() + self.remaining_usd()
+    }
+
+    /// Phases and the milestone whose projected spend exceeds their budget.
+    /// Phases without remaining plans are settled and never alert.
+    pub fn alerts(&self) -> Vec<Alert> {
+        let mut alerts: Vec<Alert> = self
+            .phases
+            .iter()
+            .filter(|p| p.remaining_plans > 0)
+            .filter_map(|p| {
+                let projected = p.spent.usd + p.remaining.usd;
+                let budget = p.budget_usd?;
+                (projected > budget).then(|| Alert {
+                    scope: "phase",
+                    phase: Some(p.phase.clone()),
+                    projected_usd: projected,
+                    budget_usd: budget,
+                })
+            })
+            .collect();
+        if let Some(budget) = self.milestone_budget_usd
+            && self.projected_usd() > budget
+        {
+            alerts.push(Alert { scope: "milestone", phase: None, projected_usd: self.projected_usd(), budget_usd: budget });
+        }
+        alerts
+    }
+
+    pub fn to_json(&self) -> Value {
+        let phases: Vec<Value> = self
+            .phases
+            .iter()
+            .map(|p| {
+                let projected = p.spent.usd + p.remaining.usd;
+                json!({
+                    "phase": p.phase,
+                    "remaining_plans": p.remaining_plans,
+                    "spent_usd": round2(p.spent.usd),
+                    "forecast_tokens": p.remaining.tokens(),
+                    "forecast_usd": round2(p.remaining.usd),
+                    "projected_usd": round2(projected),
+                    "budget_usd": p.budget_usd,
+                    "over_budget": p.remaining_plans > 0 && p.budget_usd.is_some_and(|b| projected > b),
+                })
+            })
+            .collect();
+        let by_role: serde_json::Map<String, Value> = self
+            .by_role
+            .iter()
+            .map(|(role, u)| (role.clone(), json!({"tokens": u.tokens(), "usd": round2(u.usd)})))
+            .collect();
+        let alerts: Vec<Value> = self.alerts().iter().map(alert_json).collect();
+        json!({
+            "milestone": self.milestone,
+            "history_plans": self.history_plans,
+            "profile": self.profile,
+            "pricing_updated": self.pricing_updated,
+            "phases": phases,
+            "by_role": by_role,
+            "milestone_total": {
+                "spent_usd": round2(self.spent_usd()),
+                "forecast_usd": round2(self.remaining_usd()),
+                "projected_usd": round2(self.projected_usd()),
+                "budget_usd": self.milestone_budget_usd,
+            },
+            "alerts": alerts,
+        })
+    }
+}
+
+fn alert_json(a: &Alert) -> Value {
+    json!({
+        "scope": a.scope,
+        "phase": a.phase,
+        "projected_usd": round2(a.projected_usd),
+        "budget_usd": a.budget_usd,
+    })
+}
+
+/// Builds the forecast for the project at `cwd`, emits `cost_budget_exceeded`
+/// once per (scope, phase, budget) and refreshes the statusline alert file.
+pub fn run(cwd: &Path) -> Forecast {
+    let planning_dir = cwd.join(".yolo-planning");
+    let config = Config::load(cwd);
+    let effort = config.get("effort").and_then(Value::as_str).unwrap_or("balanced").to_string();
+    let pricer = Pricer::load(cwd, &config);
+    let history = History::load(&planning_dir, &effort, &pricer);
+    let forecast = Forecast::build(&planning_dir, &history, &pricer, &Budgets::from_config(&config), &effort);
+    let alerts = forecast.alerts();
+
+    let logged = event_bus::load_events(&planning_dir);
+    for alert in &alerts {
+        let phase = alert.phase.clone().unwrap_or_else(|| "0".to_string());
+        let budget = alert.budget_usd.to_string();
+        let already = logged.iter().any(|e| {
+            e["event"].as_str() == Some("cost_budget_exceeded")
+                && e["data"]["scope"].as_str() == Some(alert.scope)
+                && norm(&e["phase"]).as_deref() == Some(phase.as_str())
+                && e["data"]["budget_usd"].as_str() == Some(budget.as_str())
+        });
+        if !already {
+            let data = vec![
+                ("scope".to_string(), alert.scope.to_string()),
+                ("projected_usd".to_string(), format!("{:.2}", alert.projected_usd)),
+                ("budget_usd".to_string(), budget),
+            ];
+            let _ = log_event::log("cost_budget_exceeded", &phase, None, &data, cwd);
+        }
+    }
+
+    write_alert_file(&planning_dir, &alerts);
+    forecast
+}
+
+/// The worst overrun (by ratio) for the statusline, or no file when within budget.
+fn write_alert_file(planning_dir: &Path, alerts: &[Alert]) {
+    let path: PathBuf = planning_dir.join(ALERT_FILE);
+    let worst = alerts.iter().max_by(|a, b| {
+        (a.projected_usd / a.budget_usd.max(f64::EPSILON))
+            .partial_cmp(&(b.projected_usd / b.budget_usd.max(f64::EPSILON)))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    match worst {
+        Some(alert) if planning_dir.is_dir() => {
+            let _ = fs::write(&path, alert_json(alert).to_string());
+        }
+        _ => {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(config: Value) -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(planning.join(".metrics")).unwrap();
+        fs::write(planning.join("config.json"), config.to_string()).unwrap();
+        let p1 = planning.join("phases/01-core");
+        let p2 = planning.join("phases/02-ui");
+        fs::create_dir_all(&p1).unwrap();
+        fs::create_dir_all(&p2).unwrap();
+        fs::write(p1.join("01-PLAN.md"), "---\nphase: 1\nplan: 1\n---\n").unwrap();
+        fs::write(p1.join("01-01-SUMMARY.md"), "done").unwrap();
+        fs::write(p1.join("02-PLAN.md"), "---\nphase: 1\nplan: 2\neffort_override: thorough\n---\n").unwrap();
+        fs::write(p1.join("01-02-SUMMARY.md"), "done").unwrap();
+        fs::write(p2.join("01-PLAN.md"), "---\nphase: 2\nplan: 1\n---\n").unwrap();
+        fs::write(p2.join("02-PLAN.md"), "---\nphase: 2\nplan: 2\neffort_override: thorough\n---\n").unwrap();
+        // Plan 1.1 (balanced) cost 1M input tokens; plan 1.2 (thorough) 1M output tokens.
+        fs::write(
+            planning.join(".metrics/run-metrics.jsonl"),
+            concat!(
+                r#"{"ts":"t1","event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000,"output_tokens":0}}"#,
+                "\n",
+                r#"{"ts":"t2","event":"agent_token_usage","phase":1,"plan":2,"data":{"role":"dev","input_tokens":"0","output_tokens":"1000000"}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_forecast_uses_effort_history_for_remaining_plans() {
+        let dir = project(json!({"effort": "balanced"}));
+        let forecast = run(dir.path());
+
+        assert_eq!(forecast.history_plans, 2);
+        let p2 = forecast.phases.iter().find(|p| p.phase == "2").unwrap();
+        assert_eq!(p2.remaining_plans, 2);
+        // dev runs on opus under the default quality profile: one balanced
+        // plan ($5 of input) plus one thorough plan ($25 of output).
+        assert!((p2.remaining.usd - 30.0).abs() < 1e-9);
+        assert!((forecast.spent_usd() - 30.0).abs() < 1e-9);
+        assert!((forecast.projected_usd() - 60.0).abs() < 1e-9);
+        assert_eq!(forecast.to_json()["by_role"]["dev"]["tokens"], 2_000_000);
+        assert!(forecast.alerts().is_empty());
+        assert!(!dir.path().join(".yolo-planning").join(ALERT_FILE).exists());
+    }
+
+    #[test]
+    fn test_budget_overrun_alerts_once_and_writes_statusline_file() {
+        let dir = project(json!({
+            "v3_event_log": true,
+            "cost_budget": {"phase_usd": 100, "milestone_usd": 30, "phases": {"2": 10}}
+        }));
+        let planning = dir.path().join(".yolo-planning");
+
+        let alerts = run(dir.path()).alerts();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!((alerts[0].scope, alerts[0].phase.as_deref()), ("phase", Some("2")));
+        assert_eq!(alerts[1].scope, "milestone");
+
+        // Phase 2 is $30 against $10, the worse overrun.
+        let file: Value = serde_json::from_str(&fs::read_to_string(planning.join(ALERT_FILE)).unwrap()).unwrap();
+        assert_eq!(file["scope"], "phase");
+        assert_eq!(file["budget_usd"], 10.0);
+
+        run(dir.path());
+        let emitted = event_bus::load_events(&planning)
+            .iter()
+            .filter(|e| e["event"] == "cost_budget_exceeded")
+            .count();
+        assert_eq!(emitted, 2, "a repeat run must not re-emit the same alerts");
+    }
+}
@@ -0,0 +1,424 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use super::compile_progress;
+use super::event_bus;
+use super::frontmatter;
+use super::layered_config::Config;
+use super::pricing::{Pricing, Rates};
+use super::resolve_model;
+use super::utils;
+
+/// Token counts and their dollar cost.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Usage {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+    pub usd: f64,
+}
+
+impl Usage {
+    fn from_row(data: &Value, rates: Option<Rates>) -> Self {
+        let mut usage = Self {
+            input: num(&data["input_tokens"]),
+            output: num(&data["output_tokens"]),
+            cache_read: num(&data["cache_read_tokens"]),
+            cache_write: num(&data["cache_write_tokens"]),
+            usd: 0.0,
+        };
+        usage.usd = usage.cost_at(rates).unwrap_or(0.0);
+        usage
+    }
+
+    pub fn add(&mut self, other: &Usage) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_write += other.cache_write;
+        self.usd += other.usd;
+    }
+
+    pub fn scaled(&self, factor: f64) -> Usage {
+        Usage {
+            input: self.input * factor,
+            output: self.output * factor,
+            cache_read: self.cache_read * factor,
+            cache_write: self.cache_write * factor,
+            usd: self.usd * factor,
+        }
+    }
+
+    fn cost_at(&self, rates: Option<Rates>) -> Option<f64> {
+        rates.map(|r| r.cost(self.input, self.output, self.cache_read, self.cache_write))
+    }
+
+    /// The same tokens priced at `rates`, keeping the recorded cost when
+    /// there are none.
+    pub fn repriced(&self, rates: Option<Rates>) -> Usage {
+        Usage { usd: self.cost_at(rates).unwrap_or(self.usd), ..self.clone() }
+    }
+
+    pub fn tokens(&self) -> i64 {
+        (self.input + self.output + self.cache_read + self.cache_write).round() as i64
+    }
+}
+
+/// Metrics rows carry numbers; `log-event` stores every data value as a string.
+fn num(v: &Value) -> f64 {
+    v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())).unwrap_or(0.0)
+}
+
+/// "01", 1 and "1" all name phase 1.
+pub(crate) fn norm(v: &Value) -> Option<String> {
+    let s = v.as_i64().map(|n| n.to_string()).or_else(|| v.as_str().map(str::to_string))?;
+    let trimmed = s.trim_start_matches('0');
+    Some(if trimmed.is_empty() && !s.is_empty() { "0".to_string() } else { trimmed.to_string() })
+}
+
+pub fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+/// Prices usage rows. A row is charged at the model it names (`data.model`),
+/// else at the model its role resolves to under the row's `model_profile`,
+/// else under the active profile.
+pub struct Pricer {
+    pub pricing: Pricing,
+    pub active_profile: String,
+    pub profiles: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Pricer {
+    pub fn load(cwd: &Path, config: &Config) -> Self {
+        Self {
+            pricing: Pricing::load(cwd),
+            active_profile: config.get("model_profile").and_then(Value::as_str).unwrap_or("quality").to_string(),
+            profiles: resolve_model::profile_models(cwd),
+        }
+    }
+
+    pub fn model_for(&self, role: &str, profile: &str) -> Option<&str> {
+        self.profiles.get(profile)?.get(role).map(String::as_str)
+    }
+
+    /// Rates for `role` under `profile`.
+    pub fn role_rates(&self, role: &str, profile: &str) -> Option<Rates> {
+        self.model_for(role, profile).and_then(|m| self.pricing.rates(m))
+    }
+}
+
+/// One `agent_token_usage` row, attributed.
+#[derive(Debug)]
+pub struct UsageRow {
+    pub phase: String,
+    pub plan: String,
+    pub role: String,
+    pub profile: String,
+    /// Pricing-table ID, or the raw model name when the table has no entry.
+    pub model: String,
+    pub priced: bool,
+    pub usage: Usage,
+}
+
+/// Token usage of one finished (or in-flight) plan, split by role.
+#[derive(Debug, Default)]
+struct PlanUsage {
+    effort: String,
+    by_role: BTreeMap<String, Usage>,
+}
+
+/// `agent_token_usage` rows from run metrics and the event log. Rows logged
+/// to both stores are counted once.
+#[derive(Debug, Default)]
+pub struct History {
+    pub rows: Vec<UsageRow>,
+    plans: BTreeMap<(String, String), PlanUsage>,
+}
+
+impl History {
+    pub fn load(planning_dir: &Path, default_effort: &str, pricer: &Pricer) -> Self {
+        let metrics = fs::read_to_string(planning_dir.join(".metrics").join("run-metrics.jsonl")).unwrap_or_default();
+        let rows = metrics
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l.trim()).ok())
+            .chain(event_bus::load_events(planning_dir));
+        let mut history = History::default();
+        let mut seen = BTreeSet::new();
+        for row in rows {
+            if row["event"].as_str() != Some("agent_token_usage") {
+                continue;
+            }
+            let data = &row["data"];
+            let key = format!(
+                "{}|{}|{}|{}|{}|{}",
+                row["ts"], row["phase"], row["plan"], data["role"], num(&data["input_tokens"]), num(&data["output_tokens"])
+            );
+            if !seen.insert(key) {
+                continue;
+            }
+            history.add(&row, planning_dir, default_effort, pricer);
+        }
+        history
+    }
+
+    fn add(&mut self, row: &Value, planning_dir: &Path, default_effort: &str, pricer: &Pricer) {
+        let data = &row["data"];
+        let phase = norm(&row["phase"]).unwrap_or_else(|| "?".to_string());
+        let plan = norm(&row["plan"]).or_else(|| norm(&data["plan"])).unwrap_or_else(|| "?".to_string());
+        let role = data["role"].as_str().or_else(|| row["role"].as_str()).unwrap_or("unknown").to_string();
+        let profile = data["model_profile"].as_str().unwrap_or(&pricer.active_profile).to_string();
+        let named = data["model"].as_str().or_else(|| pricer.model_for(&role, &profile)).unwrap_or("unknown");
+        let (model, rates) = match pricer.pricing.resolve(named) {
+            Some((id, rates)) => (id.to_string(), Some(rates)),
+            None => (named.to_string(), None),
+        };
+        let usage = Usage::from_row(data, rates);
+
+        let entry = self.plans.entry((phase.clone(), plan.clone())).or_insert_with(|| PlanUsage {
+            effort: data["effort"]
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| plan_effort(planning_dir, &phase, &plan))
+                .unwrap_or_else(|| default_effort.to_string()),
+            by_role: BTreeMap::new(),
+        });
+        entry.by_role.entry(role.clone()).or_default().add(&usage);
+        self.rows.push(UsageRow { phase, plan, role, profile, model, priced: rates.is_some(), usage });
+    }
+
+    pub fn plan_count(&self) -> usize {
+        self.plans.len()
+    }
+
+    /// Mean usage per plan by role, over plans at `effort` when there are
+    /// any, otherwise over all plans.
+    pub fn per_plan(&self, effort: &str) -> BTreeMap<String, Usage> {
+        let at_effort: Vec<&PlanUsage> = self.plans.values().filter(|p| p.effort == effort).collect();
+        let sample: Vec<&PlanUsage> = if at_effort.is_empty() { self.plans.values().collect() } else { at_effort };
+        let mut sum: BTreeMap<String, Usage> = BTreeMap::new();
+        for plan in &sample {
+            for (role, usage) in &plan.by_role {
+                sum.entry(role.clone()).or_default().add(usage);
+            }
+        }
+        let n = sample.len().max(1) as f64;
+        sum.into_iter().map(|(role, usage)| (role, usage.scaled(1.0 / n))).collect()
+    }
+
+    pub fn spent(&self, phase: &str) -> Usage {
+        let mut total = Usage::default();
+        for ((p, _), plan) in &self.plans {
+            if p == phase {
+                plan.by_role.values().for_each(|u| total.add(u));
+            }
+        }
+        total
+    }
+}
+
+/// A plan file in a phase directory and whether a summary closes it.
+#[derive(Debug)]
+pub(crate) struct PlanFile {
+    pub plan: String,
+    pub effort: Option<String>,
+    pub done: bool,
+}
+
+/// Plans are `NN-PLAN.md` or `PP-NN-PLAN.md`; summaries follow either form,
+/// so a plan counts as done when any summary ends in its plan number.
+pub(crate) fn phase_plans(dir: &Path) -> Vec<PlanFile> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    let summarised: BTreeSet<String> = names
+        .iter()
+        .filter_map(|n| n.strip_suffix("-SUMMARY.md"))
+        .filter_map(|prefix| norm(&json!(prefix.rsplit('-').next().unwrap_or(""))))
+        .collect();
+    names
+        .iter()
+        .filter_map(|name| {
+            let prefix = name.strip_suffix("-PLAN.md")?;
+            let fm = frontmatter::parse_file(&dir.join(name)).ok().flatten().map(|f| f.plan());
+            let plan = fm
+                .as_ref()
+                .and_then(|f| f.plan.clone())
+                .or_else(|| prefix.rsplit('-').next().map(str::to_string))
+                .and_then(|p| norm(&json!(p)))?;
+            Some(PlanFile {
+                done: summarised.contains(&plan),
+                effort: fm.and_then(|f| f.effort_override),
+                plan,
+            })
+        })
+        .collect()
+}
+
+/// The `effort_override` of a plan in the live phases directory, if it sets one.
+fn plan_effort(planning_dir: &Path, phase: &str, plan: &str) -> Option<String> {
+    let (_, phases_dir) = compile_progress::resolve_milestone(planning_dir, &planning_dir.join("milestones"));
+    let (_, dir) = utils::sorted_phase_dirs(&phases_dir?)
+        .into_iter()
+        .find(|(name, _)| norm(&json!(name.split('-').next().unwrap_or(""))).as_deref() == Some(phase))?;
+    phase_plans(&dir).into_iter().find(|p| p.plan == plan)?.effort
+}
+
+/// Recorded spend broken down every way `report-tokens --cost` shows it, and
+/// the same history repriced under each model profile.
+#[derive(Debug)]
+pub struct Attribution {
+    pub by_role: BTreeMap<String, Usage>,
+    pub by_phase: BTreeMap<String, Usage>,
+    pub by_plan: BTreeMap<String, Usage>,
+    pub by_model: BTreeMap<String, Usage>,
+    pub by_profile: BTreeMap<String, Usage>,
+    pub total: Usage,
+    pub unpriced_tokens: i64,
+    pub active_profile: String,
+    /// Total cost had every row run under each profile.
+    pub profile_totals: BTreeMap<String, f64>,
+    pub pricing_updated: Option<String>,
+}
+
+impl Attribution {
+    pub fn build(history: &History, pricer: &Pricer) -> Self {
+        let mut a = Attribution {
+            by_role: BTreeMap::new(),
+            by_phase: BTreeMap::new(),
+            by_plan: BTreeMap::new(),
+            by_model: BTreeMap::new(),
+            by_profile: BTreeMap::new(),
+            total: Usage::default(),
+            unpriced_tokens: 0,
+            active_profile: pricer.active_profile.clone(),
+            profile_totals: pricer.profiles.keys().map(|p| (p.clone(), 0.0)).collect(),
+            pricing_updated: pricer.pricing.updated.clone(),
+        };
+        for row in &history.rows {
+            let u = &row.usage;
+            a.by_role.entry(row.role.clone()).or_default().add(u);
+            a.by_phase.entry(row.phase.clone()).or_default().add(u);
+            a.by_plan.entry(format!("{}.{}", row.phase, row.plan)).or_default().add(u);
+            a.by_model.entry(row.model.clone()).or_default().add(u);
+            a.by_profile.entry(row.profile.clone()).or_default().add(u);
+            a.total.add(u);
+            if !row.priced {
+                a.unpriced_tokens += u.tokens();
+            }
+            for (profile, total) in a.profile_totals.iter_mut() {
+                *total += u.repriced(pricer.role_rates(&row.role, profile)).usd;
+            }
+        }
+        a
+    }
+
+    pub fn to_json(&self) -> Value {
+        let group = |m: &BTreeMap<String, Usage>| -> Value {
+            let obj: Map<String, Value> =
+                m.iter().map(|(k, u)| (k.clone(), json!({"tokens": u.tokens(), "usd": round2(u.usd)}))).collect();
+            Value::Object(obj)
+        };
+        let totals: Map<String, Value> = self.profile_totals.iter().map(|(k, v)| (k.clone(), json!(round2(*v)))).collect();
+        json!({
+            "total_usd": round2(self.total.usd),
+            "total_tokens": self.total.tokens(),
+            "unpriced_tokens": self.unpriced_tokens,
+            "pricing_updated": self.pricing_updated,
+            "by_role": group(&self.by_role),
+            "by_phase": group(&self.by_phase),
+            "by_plan": group(&self.by_plan),
+            "by_model": group(&self.by_model),
+            "by_profile": group(&self.by_profile),
+            "profile_comparison": {
+                "active": self.active_profile,
+                "totals_usd": totals,
+            },
+        })
+    }
+}
+
+/// Cost attribution for the project at `cwd`.
+pub fn attribute(cwd: &Path) -> Attribution {
+    let config = Config::load(cwd);
+    let effort = config.get("effort").and_then(Value::as_str).unwrap_or("balanced").to_string();
+    let pricer = Pricer::load(cwd, &config);
+    let history = History::load(&cwd.join(".yolo-planning"), &effort, &pricer);
+    Attribution::build(&history, &pricer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(config: Value, metrics: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(planning.join(".metrics")).unwrap();
+        fs::write(planning.join("config.json"), config.to_string()).unwrap();
+        fs::write(planning.join(".metrics/run-metrics.jsonl"), metrics).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_rows_priced_by_named_model_or_role_profile() {
+        let dir = project(
+            json!({"model_profile": "quality"}),
+            concat!(
+                // dev under quality resolves to opus: 1M input at $5.
+                r#"{"ts":"t1","event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000}}"#,
+                "\n",
+                // An explicit model wins over the role mapping: 1M output on haiku at $5.
+                r#"{"ts":"t2","event":"agent_token_usage","phase":1,"plan":2,"data":{"role":"dev","model":"claude-haiku-4-5-20251001","output_tokens":"1000000"}}"#,
+                "\n",
+                r#"{"ts":"t3","event":"agent_token_usage","phase":2,"plan":1,"data":{"role":"scout","model":"gpt-x","input_tokens":500}}"#,
+                "\n",
+            ),
+        );
+        let a = attribute(dir.path());
+
+        assert!((a.by_model["claude-opus-4-6"].usd - 5.0).abs() < 1e-9);
+        assert!((a.by_model["claude-haiku-4-5"].usd - 5.0).abs() < 1e-9);
+        assert_eq!(a.by_model["gpt-x"].usd, 0.0);
+        assert_eq!(a.unpriced_tokens, 500);
+        assert!((a.by_phase["1"].usd - 10.0).abs() < 1e-9);
+        assert!((a.by_plan["1.2"].usd - 5.0).abs() < 1e-9);
+        assert!((a.by_profile["quality"].usd - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profile_comparison_reprices_history() {
+        let dir = project(
+            json!({}),
+            r#"{"ts":"t1","event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000,"output_tokens":1000000}}"#,
+        );
+        let a = attribute(dir.path());
+        // dev: opus ($5 + $25) under quality, sonnet ($3 + $15) under balanced and budget.
+        assert!((a.profile_totals["quality"] - 30.0).abs() < 1e-9);
+        assert!((a.profile_totals["balanced"] - 18.0).abs() < 1e-9);
+        assert!((a.profile_totals["budget"] - 18.0).abs() < 1e-9);
+        assert_eq!(a.to_json()["profile_comparison"]["active"], "quality");
+    }
+
+    #[test]
+    fn test_history_deduplicates_rows_in_both_stores() {
+        let row = r#"{"ts":"t1","event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000,"output_tokens":0}}"#;
+        let dir = project(json!({}), row);
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(planning.join(".events")).unwrap();
+        fs::write(event_bus::log_path(&planning), format!("{}\n", row)).unwrap();
+
+        let pricer = Pricer::load(dir.path(), &Config::load(dir.path()));
+        let history = History::load(&planning, "balanced", &pricer);
+        assert_eq!(history.rows.len(), 1);
+        assert!((history.spent("1").input - 1_000_000.0).abs() < 1e-9);
+    }
+}
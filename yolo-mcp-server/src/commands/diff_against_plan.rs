@@ -1,14 +1,20 @@
 use regex::Regex;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::OnceLock;
 
+use super::atomic_io;
+use super::file_overlap::patterns_overlap;
+use super::frontmatter;
+use super::symbols::{self, Language};
+
 /// Cross-references declared files in SUMMARY against actual git diffs.
 ///
 /// Usage: yolo diff-against-plan <summary_path> [--commits hash1,hash2]
+///        [--semantic] [--plan <plan_path>] [--write-verification [path]]
 ///
 /// Checks:
 /// 1. Read files_modified from SUMMARY frontmatter or ## Files Modified section
@@ -19,11 +25,22 @@ use std::sync::OnceLock;
 /// The `--commits` flag fully overrides frontmatter `commit_hashes` when present
 /// with a non-empty value. Empty value is treated as flag-not-passed.
 ///
-/// Exit codes: 0=match, 1=mismatch
+/// `--semantic` maps each commit's hunks to the enclosing symbols (Rust, Python,
+/// JS/TS, Go, Java/C#, Kotlin, shell, Ruby) and adds a `semantic` object: which
+/// PLAN must_haves have a matching change, which commits touched files declared
+/// in neither SUMMARY nor PLAN `files_modified`, and a per-task coverage matrix.
+/// The plan defaults to the sibling `{NN-MM}-PLAN.md` of the SUMMARY.
+///
+/// `--write-verification` (implies `--semantic`) writes the matrix as a
+/// per-plan section of VERIFICATION.md. Without a path, the phase directory's
+/// VERIFICATION.md is only updated if it already exists, so the verification
+/// gate never sees a file QA has not written.
+///
+/// Exit codes: 0=match, 1=mismatch (file-level only; semantic results are advisory)
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     if args.len() < 3 {
         return Err(
-            "Usage: yolo diff-against-plan <summary_path> [--commits hash1,hash2]".to_string(),
+            "Usage: yolo diff-against-plan <summary_path> [--commits hash1,hash2] [--semantic] [--plan <plan_path>] [--write-verification [path]]".to_string(),
         );
     }
 
@@ -49,10 +66,21 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let declared_files = extract_declared_files(&summary_content);
 
     // Extract commit hashes from frontmatter
-    let frontmatter = extract_frontmatter(&summary_content);
-    let mut commit_hashes = match &frontmatter {
-        Some(fm) => parse_list_field(fm, "commit_hashes"),
-        None => Vec::new(),
+    let mut commit_hashes = match frontmatter::parse(&summary_content) {
+        Ok(Some(fm)) => fm.summary().commit_hashes,
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            let resp = json!({
+                "ok": false,
+                "cmd": "diff-against-plan",
+                "declared": declared_files.len(),
+                "actual": 0,
+                "undeclared": [],
+                "missing": [format!("Invalid SUMMARY frontmatter at {}", e)],
+                "fixable_by": "dev",
+            });
+            return Ok((resp.to_string(), 1));
+        }
     };
 
     // --commits flag overrides frontmatter commit_hashes
@@ -82,7 +110,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let ok = undeclared.is_empty() && missing.is_empty();
 
     let fixable_by = if ok { "none" } else { "dev" };
-    let resp = json!({
+    let mut resp = json!({
         "ok": ok,
         "cmd": "diff-against-plan",
         "declared": declared_files.len(),
@@ -92,74 +120,31 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         "fixable_by": fixable_by,
     });
 
-    Ok((resp.to_string(), if ok { 0 } else { 1 }))
-}
-
-/// Extract frontmatter content between first `---` and second `---`.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-    let after_first = &content[3..];
-    let rest = after_first.trim_start_matches(|c: char| c != '\n');
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(rest[..end_idx].to_string())
-    } else if rest.ends_with("---") {
-        let trimmed = rest.trim_end_matches("---");
-        Some(trimmed.to_string())
-    } else {
-        None
-    }
-}
-
-/// Parse a YAML list field from frontmatter.
-fn parse_list_field(frontmatter: &str, field_name: &str) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut in_field = false;
-    let prefix = format!("{}:", field_name);
+    let write_target = write_verification_flag(args);
+    if args.iter().any(|a| a == "--semantic") || write_target.is_some() {
+        let plan_path = parse_flag(args, "--plan")
+            .filter(|p| !p.is_empty())
+            .map(|p| cwd.join(p))
+            .or_else(|| find_plan_for_summary(summary_path));
+        let report = SemanticReport::build(plan_path.as_deref(), &commit_hashes, &declared_files, cwd);
+        resp["semantic"] = report.to_json();
 
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim();
-
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                if inner.trim().is_empty() {
-                    return result;
-                }
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !cleaned.is_empty() {
-                        result.push(cleaned.to_string());
-                    }
-                }
-                return result;
-            }
-
-            if value == "[]" {
-                return result;
-            }
-
-            in_field = true;
-            continue;
-        }
-
-        if in_field {
-            if let Some(stripped) = trimmed.strip_prefix("- ") {
-                let item = stripped.trim().trim_matches('"').trim_matches('\'');
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() {
-                break;
-            }
+        if let Some(explicit) = write_target {
+            let (target, create) = match explicit {
+                Some(p) => (cwd.join(p), true),
+                None => (
+                    summary_path.parent().unwrap_or(Path::new(".")).join("VERIFICATION.md"),
+                    false,
+                ),
+            };
+            resp["verification"] = match write_verification(&target, &report, create) {
+                Ok(written) => json!({"path": target.to_string_lossy(), "written": written}),
+                Err(e) => json!({"path": target.to_string_lossy(), "written": false, "error": e}),
+            };
         }
     }
 
-    result
+    Ok((resp.to_string(), if ok { 0 } else { 1 }))
 }
 
 /// Extract file paths from the ## Files Modified section.
@@ -226,6 +211,459 @@ fn get_git_files(hashes: &[String], cwd: &Path) -> Vec<String> {
     files.into_iter().collect()
 }
 
+/// `--write-verification` with an optional path: `Some(None)` for the default target.
+fn write_verification_flag(args: &[String]) -> Option<Option<String>> {
+    let pos = args.iter().position(|a| a == "--write-verification")?;
+    Some(args.get(pos + 1).filter(|v| !v.starts_with("--")).cloned())
+}
+
+/// `03-01-SUMMARY.md` -> sibling `03-01-PLAN.md`, else `01-PLAN.md`.
+fn find_plan_for_summary(summary_path: &Path) -> Option<PathBuf> {
+    let name = summary_path.file_name()?.to_string_lossy().to_string();
+    let stem = name.strip_suffix("-SUMMARY.md")?;
+    let dir = summary_path.parent()?;
+    let mut candidates = vec![dir.join(format!("{}-PLAN.md", stem))];
+    if let Some((_, plan)) = stem.rsplit_once('-') {
+        candidates.push(dir.join(format!("{}-PLAN.md", plan)));
+    }
+    candidates.into_iter().find(|p| p.exists())
+}
+
+/// One file in a commit, with the symbols its hunks touched and its added lines.
+#[derive(Debug, Default)]
+struct FileChange {
+    path: String,
+    deleted: bool,
+    ranges: Vec<(usize, usize)>,
+    symbols: Vec<String>,
+    added: Vec<String>,
+}
+
+#[derive(Debug)]
+struct CommitChange {
+    hash: String,
+    files: Vec<FileChange>,
+}
+
+/// Parse `git show -U0` output into per-file hunk ranges (new-side) and added lines.
+fn parse_unified_diff(diff: &str) -> Vec<FileChange> {
+    fn hunk_re() -> &'static Regex {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        RE.get_or_init(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@").unwrap())
+    }
+
+    let mut files: Vec<FileChange> = Vec::new();
+    let mut old_path = String::new();
+    // `---`/`+++` are only headers before a file's first hunk; later they are content.
+    let mut in_header = false;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            old_path.clear();
+            in_header = true;
+        } else if in_header && let Some(p) = line.strip_prefix("--- ") {
+            old_path = p.strip_prefix("a/").unwrap_or(p).to_string();
+        } else if in_header && let Some(p) = line.strip_prefix("+++ ") {
+            let deleted = p == "/dev/null";
+            let path = if deleted { old_path.clone() } else { p.strip_prefix("b/").unwrap_or(p).to_string() };
+            files.push(FileChange { path, deleted, ..Default::default() });
+        } else if let Some(caps) = hunk_re().captures(line) {
+            in_header = false;
+            if let Some(file) = files.last_mut() {
+                let start: usize = caps[1].parse().unwrap_or(1);
+                let len: usize = caps.get(2).and_then(|m| m.as_str().parse().ok()).unwrap_or(1);
+                // Pure deletions (len 0) point at the line before the removed block.
+                file.ranges.push((start.max(1), len.max(1)));
+            }
+        } else if !in_header
+            && let Some(added) = line.strip_prefix('+')
+            && let Some(file) = files.last_mut()
+        {
+            file.added.push(added.to_string());
+        }
+    }
+    files
+}
+
+/// Files, hunks and touched symbols for one commit. None when git can't show it.
+fn commit_changes(hash: &str, cwd: &Path) -> Option<CommitChange> {
+    let output = Command::new("git")
+        .args(["show", "--format=", "-U0", "--no-color", "--no-ext-diff", "--no-renames", hash])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut files = parse_unified_diff(&String::from_utf8_lossy(&output.stdout));
+
+    for file in files.iter_mut().filter(|f| !f.deleted) {
+        let Some(lang) = Language::from_path(Path::new(&file.path)) else { continue };
+        let Ok(content) = Command::new("git")
+            .args(["show", &format!("{}:{}", hash, file.path)])
+            .current_dir(cwd)
+            .output()
+        else {
+            continue;
+        };
+        let defs = symbols::extract(lang, &String::from_utf8_lossy(&content.stdout));
+        for &(start, len) in &file.ranges {
+            for sym in symbols::touched_by(&defs, start, len) {
+                if !file.symbols.contains(&sym.name) {
+                    file.symbols.push(sym.name.clone());
+                }
+            }
+        }
+    }
+
+    Some(CommitChange { hash: hash.to_string(), files })
+}
+
+/// A plan task and the files it declares.
+#[derive(Debug, Clone, PartialEq)]
+struct PlanTask {
+    number: usize,
+    name: String,
+    files: Vec<String>,
+}
+
+/// Tasks from `### Task N: Name` sections (`**Files:**` lines) or `<task>`
+/// blocks (`<name>`, `<files>`).
+fn plan_tasks(body: &str) -> Vec<PlanTask> {
+    fn heading_re() -> &'static Regex {
+        static RE: OnceLock<Regex> = OnceLock::new();
+        RE.get_or_init(|| Regex::new(r"^###\s+Task\s+(\d+)\s*:\s*(.*)$").unwrap())
+    }
+    fn clean(entry: &str) -> Option<String> {
+        let entry = entry.split(" (").next().unwrap_or(entry);
+        let entry = entry.trim().trim_matches('`').trim();
+        (!entry.is_empty()).then(|| entry.to_string())
+    }
+
+    let mut tasks: Vec<PlanTask> = Vec::new();
+    let mut in_files = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if let Some(caps) = heading_re().captures(trimmed) {
+            tasks.push(PlanTask {
+                number: caps[1].parse().unwrap_or(tasks.len() + 1),
+                name: caps[2].trim().to_string(),
+                files: Vec::new(),
+            });
+        } else if trimmed.starts_with("<task") && !trimmed.starts_with("<tasks") {
+            tasks.push(PlanTask { number: tasks.len() + 1, name: String::new(), files: Vec::new() });
+        } else if let Some(task) = tasks.last_mut() {
+            if let Some(rest) = trimmed.strip_prefix("**Files:**") {
+                task.files.extend(rest.split(',').filter_map(clean));
+            } else if let Some(name) = trimmed.strip_prefix("<name>") {
+                task.name = name.trim_end_matches("</name>").trim().to_string();
+            } else if trimmed == "<files>" {
+                in_files = true;
+            } else if trimmed == "</files>" {
+                in_files = false;
+            } else if in_files {
+                task.files.extend(clean(trimmed));
+            }
+        }
+    }
+    tasks
+}
+
+/// Words too generic to count as evidence on their own.
+const STOPWORDS: &[&str] = &[
+    "with", "from", "that", "this", "have", "should", "must", "when", "then", "into", "each",
+    "work", "works", "pass", "passes", "test", "tests", "code", "file", "files", "exist",
+    "exists", "added", "adds", "support", "supports", "handle", "handles", "using", "also",
+    "only", "more", "than", "does", "done", "make", "makes", "correctly", "properly", "new",
+];
+
+#[derive(Debug)]
+struct MustHaveMatch {
+    text: String,
+    /// "matched", "unmatched" or "unverifiable" (no identifiers or keywords).
+    status: &'static str,
+    evidence: Vec<String>,
+}
+
+#[derive(Debug)]
+struct TaskCoverage {
+    task: PlanTask,
+    commits: Vec<String>,
+    symbols: Vec<String>,
+    /// "covered", "partial", "missing" or "unknown" (task declares no files).
+    status: &'static str,
+}
+
+#[derive(Debug, Default)]
+struct SemanticReport {
+    plan_id: String,
+    plan_path: Option<PathBuf>,
+    commits: Vec<CommitChange>,
+    unreadable_commits: Vec<String>,
+    undeclared: Vec<(String, Vec<String>)>,
+    must_haves: Vec<MustHaveMatch>,
+    tasks: Vec<TaskCoverage>,
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
+
+impl SemanticReport {
+    fn build(plan_path: Option<&Path>, hashes: &[String], declared: &[String], cwd: &Path) -> Self {
+        let mut report = SemanticReport { plan_path: plan_path.map(Path::to_path_buf), ..Default::default() };
+        for hash in hashes {
+            match commit_changes(hash, cwd) {
+                Some(c) => report.commits.push(c),
+                None => report.unreadable_commits.push(hash.clone()),
+            }
+        }
+
+        let fm = plan_path
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|c| frontmatter::parse(&c).ok().flatten());
+        let plan = fm.as_ref().map(|f| f.plan()).unwrap_or_default();
+        report.plan_id = match (&plan.phase, &plan.plan) {
+            (Some(ph), Some(pl)) if !pl.contains('-') => format!("{}-{}", ph, pl),
+            (_, Some(pl)) => pl.clone(),
+            _ => plan_path
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().trim_end_matches("-PLAN.md").to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+        };
+
+        let allowed: Vec<&String> = declared.iter().chain(plan.files_modified.iter()).collect();
+        for commit in &report.commits {
+            let stray: Vec<String> = commit
+                .files
+                .iter()
+                .filter(|f| !allowed.iter().any(|a| patterns_overlap(a, &f.path)))
+                .map(|f| f.path.clone())
+                .collect();
+            if !stray.is_empty() {
+                report.undeclared.push((commit.hash.clone(), stray));
+            }
+        }
+
+        report.must_haves = plan.must_haves.iter().map(|m| report.match_must_have(m)).collect();
+
+        if let Some(body) = fm.as_ref().map(|f| f.body.as_str()) {
+            report.tasks = plan_tasks(body).into_iter().map(|t| report.task_coverage(t)).collect();
+        }
+        report
+    }
+
+    fn changes(&self) -> impl Iterator<Item = (&CommitChange, &FileChange)> {
+        self.commits.iter().flat_map(|c| c.files.iter().map(move |f| (c, f)))
+    }
+
+    fn match_must_have(&self, text: &str) -> MustHaveMatch {
+        let mut evidence: Vec<String> = Vec::new();
+        let ids = symbols::identifiers(text);
+        for id in &ids {
+            let leaf = symbols::leaf(id);
+            let is_path = id.contains('/') || (id.contains('.') && !id.contains("::"));
+            for (commit, file) in self.changes() {
+                let found = if is_path && (file.path == *id || file.path.ends_with(&format!("/{}", id))) {
+                    Some(format!("{} changed ({})", file.path, short(&commit.hash)))
+                } else if file.symbols.iter().any(|s| s == leaf) {
+                    Some(format!("`{}` in {} ({})", leaf, file.path, short(&commit.hash)))
+                } else if file.added.iter().any(|l| contains_word(l, leaf)) {
+                    Some(format!("`{}` added in {} ({})", leaf, file.path, short(&commit.hash)))
+                } else {
+                    None
+                };
+                if let Some(e) = found
+                    && !evidence.contains(&e)
+                {
+                    evidence.push(e);
+                }
+            }
+        }
+
+        let keywords: Vec<String> = if ids.is_empty() {
+            text.split(|c: char| !c.is_alphanumeric())
+                .map(|w| w.to_lowercase())
+                .filter(|w| w.len() >= 4 && !STOPWORDS.contains(&w.as_str()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        for (commit, file) in self.changes() {
+            for sym in &file.symbols {
+                let words = symbols::name_words(sym);
+                if let Some(k) = keywords.iter().find(|k| words.contains(k)) {
+                    let e = format!("keyword '{}' ~ `{}` in {} ({})", k, sym, file.path, short(&commit.hash));
+                    if !evidence.contains(&e) {
+                        evidence.push(e);
+                    }
+                }
+            }
+        }
+
+        let status = if !evidence.is_empty() {
+            "matched"
+        } else if ids.is_empty() && keywords.is_empty() {
+            "unverifiable"
+        } else {
+            "unmatched"
+        };
+        MustHaveMatch { text: text.to_string(), status, evidence }
+    }
+
+    fn task_coverage(&self, task: PlanTask) -> TaskCoverage {
+        let mut commits: Vec<String> = Vec::new();
+        let mut syms: Vec<String> = Vec::new();
+        let mut touched = 0;
+        for declared in &task.files {
+            let mut hit = false;
+            for (commit, file) in self.changes().filter(|(_, f)| patterns_overlap(declared, &f.path)) {
+                hit = true;
+                let h = short(&commit.hash).to_string();
+                if !commits.contains(&h) {
+                    commits.push(h);
+                }
+                for s in &file.symbols {
+                    if !syms.contains(s) {
+                        syms.push(s.clone());
+                    }
+                }
+            }
+            if hit {
+                touched += 1;
+            }
+        }
+        let status = match (task.files.len(), touched) {
+            (0, _) => "unknown",
+            (n, t) if t == n => "covered",
+            (_, 0) => "missing",
+            _ => "partial",
+        };
+        TaskCoverage { task, commits, symbols: syms, status }
+    }
+
+    fn to_json(&self) -> Value {
+        let matched = self.must_haves.iter().filter(|m| m.status == "matched").count();
+        json!({
+            "plan": self.plan_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            "commits": self.commits.iter().map(|c| json!({
+                "hash": c.hash,
+                "files": c.files.iter().map(|f| json!({
+                    "path": f.path,
+                    "deleted": f.deleted,
+                    "symbols": f.symbols,
+                })).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
+            "unreadable_commits": self.unreadable_commits,
+            "undeclared": self.undeclared.iter().map(|(h, files)| json!({"commit": h, "files": files})).collect::<Vec<_>>(),
+            "must_haves": self.must_haves.iter().map(|m| json!({
+                "must_have": m.text,
+                "status": m.status,
+                "evidence": m.evidence,
+            })).collect::<Vec<_>>(),
+            "must_haves_matched": matched,
+            "must_haves_total": self.must_haves.len(),
+            "tasks": self.tasks.iter().map(|t| json!({
+                "task": t.task.number,
+                "name": t.task.name,
+                "files": t.task.files,
+                "commits": t.commits,
+                "symbols": t.symbols,
+                "status": t.status,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// The coverage section for VERIFICATION.md, wrapped in per-plan markers.
+    fn to_markdown(&self) -> String {
+        let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+        let code_list = |items: &[String]| {
+            if items.is_empty() {
+                "-".to_string()
+            } else {
+                items.iter().map(|i| format!("`{}`", cell(i))).collect::<Vec<_>>().join(", ")
+            }
+        };
+
+        let mut out = format!("<!-- coverage:{} -->\n## Coverage Matrix: Plan {}\n\n", self.plan_id, self.plan_id);
+        out.push_str("| Task | Files | Commits | Symbols changed | Coverage |\n");
+        out.push_str("|------|-------|---------|-----------------|----------|\n");
+        for t in &self.tasks {
+            out.push_str(&format!(
+                "| {}. {} | {} | {} | {} | {} |\n",
+                t.task.number,
+                cell(&t.task.name),
+                code_list(&t.task.files),
+                if t.commits.is_empty() { "-".to_string() } else { t.commits.join(", ") },
+                code_list(&t.symbols),
+                t.status
+            ));
+        }
+
+        if !self.must_haves.is_empty() {
+            out.push_str("\n| Must-have | Change | Evidence |\n");
+            out.push_str("|-----------|--------|----------|\n");
+            for m in &self.must_haves {
+                let evidence = if m.evidence.is_empty() { "-".to_string() } else { cell(&m.evidence.join("; ")) };
+                out.push_str(&format!("| {} | {} | {} |\n", cell(&m.text), m.status, evidence));
+            }
+        }
+
+        if !self.undeclared.is_empty() {
+            out.push_str("\n**Undeclared files:**\n");
+            for (hash, files) in &self.undeclared {
+                out.push_str(&format!("- {}: {}\n", short(hash), code_list(files)));
+            }
+        }
+        out.push_str(&format!("<!-- /coverage:{} -->\n", self.plan_id));
+        out
+    }
+}
+
+fn contains_word(line: &str, word: &str) -> bool {
+    !word.is_empty()
+        && line.match_indices(word).any(|(i, _)| {
+            let before = line[..i].chars().next_back();
+            let after = line[i + word.len()..].chars().next();
+            let boundary = |c: Option<char>| c.is_none_or(|c| !(c.is_alphanumeric() || c == '_'));
+            boundary(before) && boundary(after)
+        })
+}
+
+/// Replace this plan's coverage section in VERIFICATION.md, or append it.
+/// Returns false when the file is absent and `create` is off.
+fn write_verification(path: &Path, report: &SemanticReport, create: bool) -> Result<bool, String> {
+    let section = report.to_markdown();
+    let existing = match fs::read_to_string(path) {
+        Ok(c) => Some(c),
+        Err(_) if create => None,
+        Err(_) => return Ok(false),
+    };
+
+    let start_marker = format!("<!-- coverage:{} -->", report.plan_id);
+    let end_marker = format!("<!-- /coverage:{} -->", report.plan_id);
+    let content = match existing {
+        None => section,
+        Some(c) => match (c.find(&start_marker), c.find(&end_marker)) {
+            (Some(start), Some(end)) if end > start => {
+                let after = &c[end + end_marker.len()..];
+                let after = after.strip_prefix('\n').unwrap_or(after);
+                format!("{}{}{}", &c[..start], section, after)
+            }
+            _ => {
+                let sep = if c.ends_with("\n\n") || c.is_empty() { "" } else if c.ends_with('\n') { "\n" } else { "\n\n" };
+                format!("{}{}{}", c, sep, section)
+            }
+        },
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    atomic_io::atomic_write(path, content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
 /// Parse a --flag value pair from args.
 fn parse_flag(args: &[String], flag: &str) -> Option<String> {
     let mut iter = args.iter();
@@ -418,4 +856,169 @@ commit_hashes: []
         ];
         assert_eq!(parse_flag(&args, "--commits"), None);
     }
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1..2 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn a() {
++    retry();
++++ not a header
+@@ -10 +12 @@
+-old
++new
+diff --git a/gone.rs b/gone.rs
+deleted file mode 100644
+--- a/gone.rs
++++ /dev/null
+@@ -1,2 +0,0 @@
+-fn x() {}
+";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].ranges, vec![(4, 2), (12, 1)]);
+        assert_eq!(files[0].added, vec!["    retry();", "++ not a header", "new"]);
+        assert_eq!(files[1].path, "gone.rs");
+        assert!(files[1].deleted);
+    }
+
+    #[test]
+    fn test_plan_tasks_markdown_and_xml() {
+        let md = "### Task 1: Add retry\n**Files:** `src/a.rs`, `src/b.rs` (new)\n\n### Task 2: Docs\nText.\n";
+        let tasks = plan_tasks(md);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "Add retry");
+        assert_eq!(tasks[0].files, vec!["src/a.rs", "src/b.rs"]);
+        assert!(tasks[1].files.is_empty());
+
+        let xml = "<tasks>\n<task type=\"auto\">\n  <name>Wire it</name>\n  <files>\n    src/c.rs\n  </files>\n</task>\n</tasks>\n";
+        let tasks = plan_tasks(xml);
+        assert_eq!(tasks, vec![PlanTask { number: 1, name: "Wire it".into(), files: vec!["src/c.rs".into()] }]);
+    }
+
+    #[test]
+    fn test_contains_word() {
+        assert!(contains_word("let x = fetch_user(id);", "fetch_user"));
+        assert!(!contains_word("let x = fetch_user_by_id(id);", "fetch_user"));
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let out = Command::new("git")
+            .args(["-c", "user.email=test@test.com", "-c", "user.name=Test"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    }
+
+    fn semantic_fixture() -> (tempfile::TempDir, PathBuf, String) {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/client.rs"), "pub struct Client;\n\nimpl Client {\n    pub fn fetch_user(&self) {\n        get();\n    }\n}\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-qm", "initial"]);
+
+        fs::write(root.join("src/client.rs"), "pub struct Client;\n\nimpl Client {\n    pub fn fetch_user(&self) {\n        with_retry(get);\n    }\n}\n").unwrap();
+        fs::write(root.join("notes.txt"), "scratch\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "-qm", "feat(03-01): retry fetch_user"]);
+        let hash = git(root, &["rev-parse", "HEAD"]);
+
+        let phase = root.join(".yolo-planning/phases/03-net");
+        fs::create_dir_all(&phase).unwrap();
+        fs::write(
+            phase.join("03-01-PLAN.md"),
+            "---\nphase: \"03\"\nplan: \"01\"\ntitle: Retry\nwave: 1\ndepends_on: []\nfiles_modified: [src/client.rs]\nmust_haves:\n  - \"add retry to fetch_user\"\n  - \"`Cache::evict` drops stale entries\"\n  - \"Everything is documented\"\n---\n\n### Task 1: Retry fetch\n**Files:** `src/client.rs`\n\n### Task 2: Cache\n**Files:** `src/cache.rs`\n",
+        )
+        .unwrap();
+        let summary = phase.join("03-01-SUMMARY.md");
+        fs::write(
+            &summary,
+            format!("---\nphase: \"03\"\nplan: \"01\"\ncommit_hashes: [\"{}\"]\n---\n\n## Files Modified\n\n- `src/client.rs`\n", hash),
+        )
+        .unwrap();
+        (dir, summary, hash)
+    }
+
+    #[test]
+    fn test_semantic_mode_matches_must_haves_and_tasks() {
+        let (dir, summary, hash) = semantic_fixture();
+        let args: Vec<String> = vec![
+            "yolo".into(),
+            "diff-against-plan".into(),
+            summary.to_string_lossy().to_string(),
+            "--semantic".into(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        let v: Value = serde_json::from_str(&output).unwrap();
+        // notes.txt is undeclared at the file level too
+        assert_eq!(code, 1);
+        let sem = &v["semantic"];
+        assert_eq!(sem["commits"][0]["files"][0]["path"], "notes.txt");
+        assert_eq!(sem["commits"][0]["files"][1]["symbols"], json!(["fetch_user"]));
+        assert_eq!(sem["undeclared"], json!([{"commit": hash, "files": ["notes.txt"]}]));
+
+        let mh = sem["must_haves"].as_array().unwrap();
+        assert_eq!(mh[0]["status"], "matched");
+        assert!(mh[0]["evidence"][0].as_str().unwrap().contains("`fetch_user` in src/client.rs"));
+        assert_eq!(mh[1]["status"], "unmatched");
+        assert_eq!(mh[2]["status"], "unmatched");
+        assert_eq!(sem["must_haves_matched"], 1);
+
+        assert_eq!(sem["tasks"][0]["status"], "covered");
+        assert_eq!(sem["tasks"][0]["symbols"], json!(["fetch_user"]));
+        assert_eq!(sem["tasks"][1]["status"], "missing");
+        assert!(v.get("verification").is_none());
+    }
+
+    #[test]
+    fn test_write_verification_only_updates_existing_by_default() {
+        let (dir, summary, _) = semantic_fixture();
+        let verification = summary.parent().unwrap().join("VERIFICATION.md");
+        let args: Vec<String> = vec![
+            "yolo".into(),
+            "diff-against-plan".into(),
+            summary.to_string_lossy().to_string(),
+            "--write-verification".into(),
+        ];
+
+        let (output, _) = execute(&args, dir.path()).unwrap();
+        let v: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(v["verification"]["written"], false);
+        assert!(!verification.exists());
+
+        fs::write(&verification, "# Verification\n\n## Result\n\n**Status:** PASS\n").unwrap();
+        execute(&args, dir.path()).unwrap();
+        execute(&args, dir.path()).unwrap();
+        let content = fs::read_to_string(&verification).unwrap();
+        assert!(content.starts_with("# Verification\n"));
+        assert_eq!(content.matches("<!-- coverage:03-01 -->").count(), 1, "section is replaced, not duplicated");
+        assert!(content.contains("| 1. Retry fetch | `src/client.rs` |"));
+        assert!(content.contains("| add retry to fetch_user | matched |"));
+        assert!(content.contains("**Undeclared files:**"));
+    }
+
+    #[test]
+    fn test_write_verification_explicit_path_creates_file() {
+        let (dir, summary, _) = semantic_fixture();
+        let args: Vec<String> = vec![
+            "yolo".into(),
+            "diff-against-plan".into(),
+            summary.to_string_lossy().to_string(),
+            "--write-verification".into(),
+            "out/COVERAGE.md".into(),
+        ];
+        let (output, _) = execute(&args, dir.path()).unwrap();
+        let v: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(v["verification"]["written"], true);
+        let content = fs::read_to_string(dir.path().join("out/COVERAGE.md")).unwrap();
+        assert!(content.starts_with("<!-- coverage:03-01 -->\n## Coverage Matrix: Plan 03-01"));
+    }
 }
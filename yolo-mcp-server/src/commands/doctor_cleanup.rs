@@ -1,14 +1,15 @@
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::event_bus;
+
 const STALE_THRESHOLD_SECS: u64 = 7200; // 2 hours
 const COMPACTION_MARKER_MAX_AGE_SECS: u64 = 60;
 
 /// Execute the doctor command.
-/// action: "scan" or "cleanup"
+/// action: "scan", "cleanup" or "integrity"
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let action = if args.len() > 2 { args[2].as_str() } else { "scan" };
 
@@ -24,11 +25,11 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
             Ok((findings.join("\n"), 0))
         }
         "cleanup" => {
-            let log_file = planning_dir.join(".hook-errors.log");
-            let output = run_cleanup(&planning_dir, &claude_dir, &log_file);
+            let output = run_cleanup(&planning_dir, &claude_dir);
             Ok((output, 0))
         }
-        _ => Err("Usage: yolo doctor {scan|cleanup}".to_string()),
+        "integrity" => super::doctor_integrity::execute(args, &planning_dir, cwd),
+        _ => Err("Usage: yolo doctor {scan|cleanup|integrity [--fix]}".to_string()),
     }
 }
 
@@ -55,8 +56,8 @@ fn run_scan(planning_dir: &Path, claude_dir: &Path) -> Vec<String> {
 }
 
 /// Run cleanup and return summary.
-fn run_cleanup(planning_dir: &Path, claude_dir: &Path, log_file: &Path) -> String {
-    log_action(log_file, "cleanup started");
+fn run_cleanup(planning_dir: &Path, claude_dir: &Path) -> String {
+    log_action(planning_dir, "cleanup started");
 
     // Count issues before cleanup
     let teams_count = scan_stale_teams(claude_dir).len();
@@ -65,21 +66,21 @@ fn run_cleanup(planning_dir: &Path, claude_dir: &Path, log_file: &Path) -> Strin
     let marker_count = scan_stale_markers(planning_dir).len();
 
     // Cleanup stale teams (delegate to existing module)
-    super::clean_stale_teams::clean_stale_teams(claude_dir, log_file);
+    super::clean_stale_teams::clean_stale_teams(claude_dir, planning_dir);
 
     // Cleanup orphaned processes
-    cleanup_orphaned_processes(log_file);
+    cleanup_orphaned_processes(planning_dir);
 
     // Cleanup dangling PIDs
-    cleanup_dangling_pids(planning_dir, log_file);
+    cleanup_dangling_pids(planning_dir);
 
     // Cleanup stale markers
-    cleanup_stale_markers(planning_dir, log_file);
+    cleanup_stale_markers(planning_dir);
 
     let summary = format!(
         "cleanup complete: teams={teams_count}, orphans={orphan_count}, pids={pid_count}, markers={marker_count}"
     );
-    log_action(log_file, &summary);
+    log_action(planning_dir, &summary);
     summary
 }
 
@@ -227,7 +228,7 @@ fn scan_stale_markers(planning_dir: &Path) -> Vec<String> {
 
 // --- Cleanup functions ---
 
-fn cleanup_orphaned_processes(log_file: &Path) {
+fn cleanup_orphaned_processes(planning_dir: &Path) {
     let orphans = scan_orphaned_processes();
     if orphans.is_empty() {
         return;
@@ -248,7 +249,7 @@ fn cleanup_orphaned_processes(log_file: &Path) {
 
     for pid in &pids {
         if process_alive(*pid) {
-            log_action(log_file, &format!("sent SIGTERM to orphan process {pid}"));
+            log_action(planning_dir, &format!("sent SIGTERM to orphan process {pid}"));
             unsafe {
                 libc::kill(*pid as i32, libc::SIGTERM);
             }
@@ -261,17 +262,17 @@ fn cleanup_orphaned_processes(log_file: &Path) {
     // SIGKILL survivors
     for pid in &pids {
         if process_alive(*pid) {
-            log_action(log_file, &format!("sent SIGKILL to survivor process {pid}"));
+            log_action(planning_dir, &format!("sent SIGKILL to survivor process {pid}"));
             unsafe {
                 libc::kill(*pid as i32, libc::SIGKILL);
             }
         }
     }
 
-    log_action(log_file, "orphaned processes cleanup completed");
+    log_action(planning_dir, "orphaned processes cleanup completed");
 }
 
-fn cleanup_dangling_pids(planning_dir: &Path, log_file: &Path) {
+fn cleanup_dangling_pids(planning_dir: &Path) {
     let pid_file = planning_dir.join(".agent-pids");
     if !pid_file.exists() {
         return;
@@ -312,10 +313,10 @@ fn cleanup_dangling_pids(planning_dir: &Path, log_file: &Path) {
         let _ = fs::write(&pid_file, contents + "\n");
     }
 
-    log_action(log_file, &format!("pruned {pruned} dead PIDs from .agent-pids"));
+    log_action(planning_dir, &format!("pruned {pruned} dead PIDs from .agent-pids"));
 }
 
-fn cleanup_stale_markers(planning_dir: &Path, log_file: &Path) {
+fn cleanup_stale_markers(planning_dir: &Path) {
     let markers = [".watchdog-pid", ".compaction-marker", ".active-agent"];
     let mut removed = 0u32;
 
@@ -352,13 +353,13 @@ fn cleanup_stale_markers(planning_dir: &Path, log_file: &Path) {
 
         if is_stale
             && fs::remove_file(&marker_path).is_ok() {
-                log_action(log_file, &format!("removed stale marker: {marker_name}"));
+                log_action(planning_dir, &format!("removed stale marker: {marker_name}"));
                 removed += 1;
             }
     }
 
     log_action(
-        log_file,
+        planning_dir,
         &format!("stale markers cleanup completed: {removed} removed"),
     );
 }
@@ -388,18 +389,8 @@ fn process_alive(pid: u32) -> bool {
     unsafe { libc::kill(pid as i32, 0) == 0 }
 }
 
-fn log_action(log_file: &Path, msg: &str) {
-    let ts = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let _ = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_file)
-        .map(|mut f| {
-            let _ = writeln!(f, "[{ts}] Doctor cleanup: {msg}");
-        });
+fn log_action(planning_dir: &Path, msg: &str) {
+    event_bus::publish_message(planning_dir, "doctor", &format!("Doctor cleanup: {msg}"));
 }
 
 #[cfg(test)]
@@ -496,18 +487,17 @@ mod tests {
     #[test]
     fn test_cleanup_stale_markers() {
         let dir = tempdir().unwrap();
-        let log = dir.path().join("test.log");
 
         // Create markers
         fs::write(dir.path().join(".watchdog-pid"), "4000001").unwrap();
         fs::write(dir.path().join(".active-agent"), "test").unwrap();
 
-        cleanup_stale_markers(dir.path(), &log);
+        cleanup_stale_markers(dir.path());
 
         assert!(!dir.path().join(".watchdog-pid").exists());
         assert!(!dir.path().join(".active-agent").exists());
 
-        let log_content = fs::read_to_string(&log).unwrap();
+        let log_content = event_bus::read_all(dir.path());
         assert!(log_content.contains("removed stale marker: .watchdog-pid"));
         assert!(log_content.contains("removed stale marker: .active-agent"));
         assert!(log_content.contains("2 removed"));
@@ -516,7 +506,6 @@ mod tests {
     #[test]
     fn test_cleanup_dangling_pids() {
         let dir = tempdir().unwrap();
-        let log = dir.path().join("test.log");
 
         // Write mix of dead PIDs and our own (alive) PID
         let our_pid = std::process::id();
@@ -526,7 +515,7 @@ mod tests {
         )
         .unwrap();
 
-        cleanup_dangling_pids(dir.path(), &log);
+        cleanup_dangling_pids(dir.path());
 
         // File should still exist with our PID
         let content = fs::read_to_string(dir.path().join(".agent-pids")).unwrap();
@@ -534,18 +523,17 @@ mod tests {
         assert!(!content.contains("4000001"));
         assert!(!content.contains("4000002"));
 
-        let log_content = fs::read_to_string(&log).unwrap();
+        let log_content = event_bus::read_all(dir.path());
         assert!(log_content.contains("pruned 2 dead PIDs"));
     }
 
     #[test]
     fn test_cleanup_dangling_pids_all_dead() {
         let dir = tempdir().unwrap();
-        let log = dir.path().join("test.log");
 
         fs::write(dir.path().join(".agent-pids"), "4000001\n4000002\n").unwrap();
 
-        cleanup_dangling_pids(dir.path(), &log);
+        cleanup_dangling_pids(dir.path());
 
         // File should be removed entirely
         assert!(!dir.path().join(".agent-pids").exists());
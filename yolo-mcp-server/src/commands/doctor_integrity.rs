@@ -0,0 +1,581 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::atomic_io;
+use super::feature_flags::FeatureFlag;
+use super::frontmatter;
+use super::generate_contract;
+use super::journal::Transaction;
+use super::layered_config::Config;
+use super::state_updater::{count_plans_and_summaries, update_roadmap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A safe, data-preserving repair for an issue.
+#[derive(Debug, Clone, PartialEq)]
+enum Fix {
+    /// Generate the missing contract from its PLAN.md.
+    GenerateContract(PathBuf),
+    /// Drop a `.sha256` sidecar whose file no longer exists.
+    RemoveSidecar(PathBuf),
+    /// Corrupt file with a usable `.backup`: restore the previous version.
+    RestoreBackup(PathBuf),
+    /// File edited outside `atomic_io`: seal its current content.
+    Reseal(PathBuf),
+    /// Recompute the ROADMAP row and checklist entry for a phase directory.
+    SyncRoadmap(PathBuf),
+    /// Rewrite the `of M` phase total in STATE.md.
+    StateTotal(usize),
+}
+
+impl Fix {
+    fn describe(&self) -> &'static str {
+        match self {
+            Fix::GenerateContract(_) => "generated contract",
+            Fix::RemoveSidecar(_) => "removed orphaned sidecar",
+            Fix::RestoreBackup(_) => "restored from backup",
+            Fix::Reseal(_) => "resealed checksum",
+            Fix::SyncRoadmap(_) => "synced roadmap row",
+            Fix::StateTotal(_) => "corrected phase total",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Issue {
+    check: &'static str,
+    severity: Severity,
+    path: String,
+    message: String,
+    fix: Option<Fix>,
+}
+
+impl Issue {
+    fn new(check: &'static str, severity: Severity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Issue { check, severity, path: path.into(), message: message.into(), fix: None }
+    }
+
+    fn fixable(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "check": self.check,
+            "severity": self.severity.as_str(),
+            "path": self.path,
+            "message": self.message,
+            "fixable": self.fix.is_some(),
+        })
+    }
+}
+
+/// `yolo doctor integrity [--fix]`: validate the planning artifacts as a whole
+/// and optionally apply safe repairs. Exits 1 while errors remain.
+pub fn execute(args: &[String], planning_dir: &Path, cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+    let apply = args.iter().skip(3).any(|a| a == "--fix");
+
+    let issues = scan(planning_dir, cwd);
+    let fixed = if apply { apply_fixes(&issues, planning_dir, cwd)? } else { Vec::new() };
+    let remaining = if apply { scan(planning_dir, cwd) } else { issues };
+
+    let mut counts = BTreeMap::new();
+    for s in [Severity::Error, Severity::Warning, Severity::Info] {
+        counts.insert(s.as_str(), remaining.iter().filter(|i| i.severity == s).count());
+    }
+    let errors = counts["error"];
+    let out = json!({
+        "ok": errors == 0,
+        "cmd": "doctor",
+        "action": "integrity",
+        "issues": remaining.iter().map(Issue::to_json).collect::<Vec<_>>(),
+        "summary": counts,
+        "fixed": fixed,
+        "elapsed_ms": start.elapsed().as_millis(),
+    });
+    Ok((serde_json::to_string(&out).unwrap() + "\n", if errors > 0 { 1 } else { 0 }))
+}
+
+fn scan(planning_dir: &Path, cwd: &Path) -> Vec<Issue> {
+    let phases = phase_dirs(planning_dir);
+    let mut issues = Vec::new();
+    issues.extend(check_phase_numbering(&phases));
+    issues.extend(check_plans_and_summaries(&phases, planning_dir, cwd));
+    issues.extend(check_sidecars(planning_dir));
+    issues.extend(check_roadmap(&phases, planning_dir));
+    issues.extend(check_state(&phases, planning_dir));
+    issues.sort_by_key(|i| std::cmp::Reverse(i.severity));
+    issues
+}
+
+/// Phase directories under `phases/` with their leading number, sorted by name.
+fn phase_dirs(planning_dir: &Path) -> Vec<(Option<u32>, PathBuf)> {
+    let Ok(entries) = fs::read_dir(planning_dir.join("phases")) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+    dirs.sort();
+    dirs.into_iter().map(|d| (phase_number(&d), d)).collect()
+}
+
+fn phase_number(dir: &Path) -> Option<u32> {
+    let name = dir.file_name()?.to_string_lossy().to_string();
+    name.split('-').next()?.parse().ok()
+}
+
+fn rel(path: &Path, planning_dir: &Path) -> String {
+    path.strip_prefix(planning_dir).unwrap_or(path).to_string_lossy().to_string()
+}
+
+fn check_phase_numbering(phases: &[(Option<u32>, PathBuf)]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut by_number: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+    for (num, dir) in phases {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        match num {
+            Some(n) => by_number.entry(*n).or_default().push(name),
+            None => issues.push(Issue::new(
+                "phase_numbering",
+                Severity::Warning,
+                format!("phases/{}", name),
+                "phase directory has no numeric prefix",
+            )),
+        }
+    }
+    let mut prev: Option<u32> = None;
+    for (n, names) in &by_number {
+        if names.len() > 1 {
+            issues.push(Issue::new(
+                "phase_numbering",
+                Severity::Error,
+                format!("phases/{}", names.join(", phases/")),
+                format!("{} phase directories share number {}", names.len(), n),
+            ));
+        }
+        let message = match prev {
+            None if *n != 1 => Some(format!("phases start at {} instead of 1", n)),
+            Some(p) if *n != p + 1 => Some(format!("phase {} follows phase {} -- numbering is not sequential", n, p)),
+            _ => None,
+        };
+        if let Some(message) = message {
+            issues.push(Issue::new("phase_numbering", Severity::Warning, format!("phases/{}", names[0]), message));
+        }
+        prev = Some(*n);
+    }
+    issues
+}
+
+/// Numeric (phase, plan) of a PLAN.md from its frontmatter, falling back to the file name.
+fn plan_ids(path: &Path, prefix: &str) -> Option<(u64, u64)> {
+    let fm = frontmatter::parse_file(path).ok().flatten().map(|f| f.plan());
+    let mut parts = prefix.split('-');
+    let phase = fm.as_ref().and_then(|f| f.phase.clone()).or_else(|| parts.next().map(str::to_string))?;
+    let plan = fm.as_ref().and_then(|f| f.plan.clone()).or_else(|| parts.next_back().map(str::to_string))?;
+    Some((phase.parse().ok()?, plan.parse().ok()?))
+}
+
+fn check_plans_and_summaries(phases: &[(Option<u32>, PathBuf)], planning_dir: &Path, cwd: &Path) -> Vec<Issue> {
+    let config = Config::load(cwd);
+    let contracts_on = config.flag(FeatureFlag::V3ContractLite) || config.flag(FeatureFlag::V2HardContracts);
+    let contracts_dir = planning_dir.join(".contracts");
+    let mut issues = Vec::new();
+
+    for (_, dir) in phases {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+
+        for name in &names {
+            let path = dir.join(name);
+            if let Some(prefix) = name.strip_suffix("-SUMMARY.md") {
+                if !names.contains(&format!("{}-PLAN.md", prefix)) {
+                    issues.push(Issue::new(
+                        "summary_without_plan",
+                        Severity::Error,
+                        rel(&path, planning_dir),
+                        format!("no {}-PLAN.md for this summary", prefix),
+                    ));
+                }
+            } else if let Some(prefix) = name.strip_suffix("-PLAN.md")
+                && contracts_on
+            {
+                let Some((phase, plan)) = plan_ids(&path, prefix) else {
+                    continue;
+                };
+                if !contracts_dir.join(format!("{}-{}.json", phase, plan)).exists() {
+                    issues.push(
+                        Issue::new(
+                            "plan_without_contract",
+                            Severity::Warning,
+                            rel(&path, planning_dir),
+                            format!("contract enforcement is on but .contracts/{}-{}.json is missing", phase, plan),
+                        )
+                        .fixable(Fix::GenerateContract(path.clone())),
+                    );
+                }
+            }
+        }
+    }
+    issues
+}
+
+fn sidecars(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            sidecars(&path, out);
+        } else if path.extension().is_some_and(|e| e == "sha256") {
+            out.push(path);
+        }
+    }
+}
+
+fn backup_path(target: &Path) -> PathBuf {
+    let mut backup = target.as_os_str().to_os_string();
+    backup.push(".backup");
+    PathBuf::from(backup)
+}
+
+/// Content no reader could use: empty, or a `.json` file that does not parse.
+fn looks_corrupt(path: &Path, content: &[u8]) -> bool {
+    content.is_empty()
+        || (path.extension().is_some_and(|e| e == "json") && serde_json::from_slice::<Value>(content).is_err())
+}
+
+fn check_sidecars(planning_dir: &Path) -> Vec<Issue> {
+    let mut found = Vec::new();
+    sidecars(planning_dir, &mut found);
+    found.sort();
+
+    let mut issues = Vec::new();
+    for sidecar in found {
+        let target = sidecar.with_extension("");
+        if !target.exists() {
+            issues.push(
+                Issue::new("orphan_sidecar", Severity::Info, rel(&sidecar, planning_dir), "checksum sidecar for a missing file")
+                    .fixable(Fix::RemoveSidecar(sidecar.clone())),
+            );
+            continue;
+        }
+        if atomic_io::verify_checksum(&target).unwrap_or(true) {
+            continue;
+        }
+        let content = fs::read(&target).unwrap_or_default();
+        let path = rel(&target, planning_dir);
+        if looks_corrupt(&target, &content) {
+            let backup_ok = fs::read(backup_path(&target)).is_ok_and(|b| !looks_corrupt(&target, &b));
+            let issue = Issue::new("checksum_mismatch", Severity::Error, path, "content is corrupt and does not match its .sha256 sidecar");
+            issues.push(if backup_ok { issue.fixable(Fix::RestoreBackup(target)) } else { issue });
+        } else {
+            issues.push(
+                Issue::new("checksum_mismatch", Severity::Warning, path, "content changed outside atomic writes; .sha256 sidecar is stale")
+                    .fixable(Fix::Reseal(target)),
+            );
+        }
+    }
+    issues
+}
+
+/// `(phase, done, total)` for each `| N - name | d/t | ... |` ROADMAP progress row.
+fn roadmap_rows(content: &str) -> Vec<(u32, Option<(usize, usize)>)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            if cells.len() < 3 || !line.trim_start().starts_with('|') {
+                return None;
+            }
+            let phase = cells[1].split(" - ").next()?.trim().parse().ok()?;
+            let counts = cells[2].split_once('/').and_then(|(d, t)| Some((d.trim().parse().ok()?, t.trim().parse().ok()?)));
+            Some((phase, counts))
+        })
+        .collect()
+}
+
+fn check_roadmap(phases: &[(Option<u32>, PathBuf)], planning_dir: &Path) -> Vec<Issue> {
+    let roadmap = planning_dir.join("ROADMAP.md");
+    let Ok(content) = fs::read_to_string(&roadmap) else {
+        return Vec::new();
+    };
+    let rows = roadmap_rows(&content);
+    let mut issues = Vec::new();
+
+    for (phase, counts) in &rows {
+        let Some((_, dir)) = phases.iter().find(|(n, _)| *n == Some(*phase)) else {
+            issues.push(Issue::new(
+                "roadmap_mismatch",
+                Severity::Error,
+                "ROADMAP.md",
+                format!("progress row for phase {} has no phase directory", phase),
+            ));
+            continue;
+        };
+        let (plans, summaries) = count_plans_and_summaries(dir);
+        if plans > 0 && *counts != Some((summaries, plans)) {
+            let shown = counts.map(|(d, t)| format!("{}/{}", d, t)).unwrap_or_else(|| "?".to_string());
+            issues.push(
+                Issue::new(
+                    "roadmap_mismatch",
+                    Severity::Warning,
+                    "ROADMAP.md",
+                    format!("phase {} shows {} but its directory has {}/{}", phase, shown, summaries, plans),
+                )
+                .fixable(Fix::SyncRoadmap(dir.clone())),
+            );
+        }
+        let complete = plans > 0 && summaries >= plans;
+        if complete && content.lines().any(|l| l.starts_with(&format!("- [ ] Phase {}:", phase))) {
+            issues.push(
+                Issue::new("roadmap_mismatch", Severity::Warning, "ROADMAP.md", format!("phase {} is complete but unchecked", phase))
+                    .fixable(Fix::SyncRoadmap(dir.clone())),
+            );
+        }
+    }
+    for (num, dir) in phases {
+        if let Some(n) = num
+            && !rows.is_empty()
+            && !rows.iter().any(|(p, _)| p == n)
+        {
+            issues.push(Issue::new(
+                "roadmap_mismatch",
+                Severity::Warning,
+                rel(dir, planning_dir),
+                format!("phase {} has no ROADMAP progress row", n),
+            ));
+        }
+    }
+    issues
+}
+
+/// `(current, total)` from the STATE.md `Phase: N of M (Name)` line.
+fn state_phase(content: &str) -> Option<(u32, Option<usize>)> {
+    let line = content.lines().find(|l| l.starts_with("Phase:"))?;
+    let rest = line.trim_start_matches("Phase:").trim();
+    let (current, tail) = rest.split_once(" of ").unwrap_or((rest, ""));
+    let total = tail.split_whitespace().next().and_then(|t| t.parse().ok());
+    Some((current.trim().parse().ok()?, total))
+}
+
+fn check_state(phases: &[(Option<u32>, PathBuf)], planning_dir: &Path) -> Vec<Issue> {
+    let Ok(content) = fs::read_to_string(planning_dir.join("STATE.md")) else {
+        return Vec::new();
+    };
+    let Some((current, total)) = state_phase(&content) else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+    if !phases.is_empty() && !phases.iter().any(|(n, _)| *n == Some(current)) {
+        issues.push(Issue::new(
+            "state_phase",
+            Severity::Error,
+            "STATE.md",
+            format!("current phase {} has no phase directory", current),
+        ));
+    }
+    if let Some(total) = total
+        && !phases.is_empty()
+        && total != phases.len()
+    {
+        issues.push(
+            Issue::new(
+                "state_phase",
+                Severity::Warning,
+                "STATE.md",
+                format!("phase total is {} but there are {} phase directories", total, phases.len()),
+            )
+            .fixable(Fix::StateTotal(phases.len())),
+        );
+    }
+    issues
+}
+
+/// Apply every fixable issue. ROADMAP and STATE.md edits commit as one journaled
+/// transaction; sidecar and contract repairs are single-file writes.
+fn apply_fixes(issues: &[Issue], planning_dir: &Path, cwd: &Path) -> Result<Vec<Value>, String> {
+    let mut tx = Transaction::begin(planning_dir);
+    let mut fixed = Vec::new();
+    let mut seen: Vec<&Fix> = Vec::new();
+
+    for issue in issues {
+        let Some(fix) = &issue.fix else { continue };
+        if seen.contains(&fix) {
+            continue;
+        }
+        seen.push(fix);
+        let done = match fix {
+            Fix::GenerateContract(plan) => generate_contract::generate(plan, cwd).is_some(),
+            Fix::RemoveSidecar(sidecar) => fs::remove_file(sidecar).is_ok(),
+            Fix::RestoreBackup(target) => fs::read(backup_path(target))
+                .and_then(|b| atomic_io::atomic_write(target, &b).and_then(|_| atomic_io::write_checksum(target, &b)))
+                .is_ok(),
+            Fix::Reseal(target) => fs::read(target).and_then(|c| atomic_io::write_checksum(target, &c)).is_ok(),
+            Fix::SyncRoadmap(dir) => update_roadmap(&mut tx, dir, planning_dir).is_ok(),
+            Fix::StateTotal(count) => {
+                let state_md = planning_dir.join("STATE.md");
+                let content = tx.read_to_string(&state_md).map_err(|e| e.to_string())?;
+                let mut lines: Vec<String> = Vec::new();
+                for line in content.lines() {
+                    match (line.starts_with("Phase:"), line.split_once(" of ")) {
+                        (true, Some((head, tail))) => {
+                            let rest = tail.split_once(' ').map(|(_, r)| format!(" {}", r)).unwrap_or_default();
+                            lines.push(format!("{} of {}{}", head, count, rest));
+                        }
+                        _ => lines.push(line.to_string()),
+                    }
+                }
+                tx.stage(&state_md, lines.join("\n") + "\n");
+                true
+            }
+        };
+        if done {
+            fixed.push(json!({"check": issue.check, "path": issue.path, "action": fix.describe()}));
+        }
+    }
+    tx.commit().map_err(|e| format!("Failed to commit repairs: {}", e))?;
+    Ok(fixed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(planning.join("phases/01-setup")).unwrap();
+        fs::create_dir_all(planning.join("phases/02-build")).unwrap();
+        fs::write(planning.join("config.json"), "{}").unwrap();
+        (dir, planning)
+    }
+
+    fn run(dir: &TempDir, planning: &Path, fix: bool) -> (Value, i32) {
+        let mut args: Vec<String> = ["yolo", "doctor", "integrity"].iter().map(|s| s.to_string()).collect();
+        if fix {
+            args.push("--fix".to_string());
+        }
+        let (out, code) = execute(&args, planning, dir.path()).unwrap();
+        (serde_json::from_str(&out).unwrap(), code)
+    }
+
+    fn checks(out: &Value) -> Vec<(String, String)> {
+        out["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| (i["check"].as_str().unwrap().to_string(), i["severity"].as_str().unwrap().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_tree_has_no_issues() {
+        let (dir, planning) = setup();
+        fs::write(planning.join("phases/01-setup/01-01-PLAN.md"), "plan").unwrap();
+        fs::write(planning.join("phases/01-setup/01-01-SUMMARY.md"), "summary").unwrap();
+        fs::write(planning.join("STATE.md"), "Phase: 2 of 2 (Build)\n").unwrap();
+        let (out, code) = run(&dir, &planning, false);
+        assert_eq!(code, 0);
+        assert_eq!(out["issues"], json!([]));
+    }
+
+    #[test]
+    fn test_detects_numbering_orphan_summary_and_bad_state() {
+        let (dir, planning) = setup();
+        fs::create_dir_all(planning.join("phases/04-ship")).unwrap();
+        fs::create_dir_all(planning.join("phases/04-docs")).unwrap();
+        fs::write(planning.join("phases/02-build/02-03-SUMMARY.md"), "summary").unwrap();
+        fs::write(planning.join("STATE.md"), "Phase: 7 of 3 (Gone)\n").unwrap();
+
+        let (out, code) = run(&dir, &planning, false);
+        assert_eq!(code, 1);
+        let found = checks(&out);
+        assert!(found.contains(&("phase_numbering".into(), "error".into())));
+        assert!(found.contains(&("phase_numbering".into(), "warning".into())));
+        assert!(found.contains(&("summary_without_plan".into(), "error".into())));
+        assert!(found.contains(&("state_phase".into(), "error".into())));
+        assert!(found.contains(&("state_phase".into(), "warning".into())));
+        assert_eq!(out["issues"][0]["severity"], "error");
+    }
+
+    #[test]
+    fn test_fix_repairs_sidecars_roadmap_and_state_total() {
+        let (dir, planning) = setup();
+        fs::write(planning.join("phases/01-setup/01-01-PLAN.md"), "plan").unwrap();
+        fs::write(planning.join("phases/01-setup/01-01-SUMMARY.md"), "summary").unwrap();
+        fs::write(
+            planning.join("ROADMAP.md"),
+            "- [ ] Phase 1: Setup\n| 1 - Setup | 0/1 | planned | - |\n| 2 - Build | 0/0 | planned | - |\n",
+        )
+        .unwrap();
+        fs::write(planning.join("STATE.md"), "Phase: 2 of 5 (Build)\nStatus: ready\n").unwrap();
+
+        let state = planning.join(".execution-state.json");
+        atomic_io::atomic_write_with_checksum(&state, br#"{"status":"running"}"#).unwrap();
+        atomic_io::atomic_write_with_checksum(&state, b"{\"status\":\"complete\"}").unwrap();
+        fs::write(&state, "{\"status\":").unwrap(); // torn write
+        let notes = planning.join("notes.md");
+        atomic_io::atomic_write_with_checksum(&notes, b"v1").unwrap();
+        fs::write(&notes, "hand edited").unwrap();
+        fs::write(planning.join("gone.json.sha256"), "abc").unwrap();
+
+        let (before, _) = run(&dir, &planning, false);
+        let found = checks(&before);
+        assert!(found.contains(&("checksum_mismatch".into(), "error".into())));
+        assert!(found.contains(&("checksum_mismatch".into(), "warning".into())));
+        assert!(found.contains(&("orphan_sidecar".into(), "info".into())));
+        assert!(found.contains(&("roadmap_mismatch".into(), "warning".into())));
+
+        let (out, code) = run(&dir, &planning, true);
+        assert_eq!(code, 0, "{}", out);
+        assert_eq!(out["issues"], json!([]));
+        assert_eq!(out["fixed"].as_array().unwrap().len(), 5);
+
+        assert_eq!(fs::read_to_string(&state).unwrap(), r#"{"status":"running"}"#);
+        assert!(atomic_io::verify_checksum(&notes).unwrap());
+        assert!(!planning.join("gone.json.sha256").exists());
+        let roadmap = fs::read_to_string(planning.join("ROADMAP.md")).unwrap();
+        assert!(roadmap.contains("- [x] Phase 1: Setup"));
+        assert!(roadmap.contains("| 1 - Setup | 1/1 | complete |"));
+        assert_eq!(fs::read_to_string(planning.join("STATE.md")).unwrap(), "Phase: 2 of 2 (Build)\nStatus: ready\n");
+    }
+
+    #[test]
+    fn test_plan_without_contract_only_when_contracts_enabled() {
+        let (dir, planning) = setup();
+        fs::write(planning.join("phases/01-setup/01-01-PLAN.md"), "---\nphase: 1\nplan: 1\ntitle: Setup\n---\n## Tasks\n").unwrap();
+        let (out, _) = run(&dir, &planning, false);
+        assert!(!checks(&out).iter().any(|(c, _)| c == "plan_without_contract"));
+
+        fs::write(planning.join("config.json"), r#"{"v3_contract_lite": true}"#).unwrap();
+        let (out, _) = run(&dir, &planning, false);
+        assert!(checks(&out).contains(&("plan_without_contract".into(), "warning".into())));
+
+        let (out, _) = run(&dir, &planning, true);
+        assert_eq!(out["fixed"][0]["action"], "generated contract");
+        assert!(planning.join(".contracts/1-1.json").exists());
+    }
+}
@@ -0,0 +1,349 @@
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::layered_config::Config;
+use crate::telemetry::db::TelemetryDb;
+
+/// Rotate the canonical log once it grows past this many bytes.
+const DEFAULT_MAX_BYTES: u64 = 5 * 1024 * 1024;
+/// Number of rotated segments (`event-log.jsonl.1` .. `.N`) to retain.
+const DEFAULT_KEEP: usize = 3;
+/// Upper bound for socket/webhook delivery so a dead collector never stalls a hook.
+const SINK_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A secondary destination that receives a copy of every published event.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Sink {
+    /// Mirror lines into another JSONL file (relative paths resolve against the project root).
+    Jsonl { path: String },
+    /// Insert into the `events` table of the telemetry database.
+    Sqlite {
+        #[serde(default)]
+        path: Option<String>,
+    },
+    /// Write one line per event to a Unix domain socket.
+    Socket { path: String },
+    /// POST each event to a collector on a loopback address.
+    Webhook { url: String },
+}
+
+/// `event_bus` section of `.yolo-planning/config.json`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BusConfig {
+    pub max_bytes: u64,
+    pub keep: usize,
+    pub sinks: Vec<Sink>,
+}
+
+impl Default for BusConfig {
+    fn default() -> Self {
+        Self { max_bytes: DEFAULT_MAX_BYTES, keep: DEFAULT_KEEP, sinks: Vec::new() }
+    }
+}
+
+impl BusConfig {
+    /// Read the `event_bus` key from the layered config; defaults on any error.
+    pub fn load(planning_dir: &Path) -> Self {
+        Config::load(planning_dir.parent().unwrap_or(planning_dir))
+            .get("event_bus")
+            .cloned()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Canonical event store: `.yolo-planning/.events/event-log.jsonl`.
+pub fn log_path(planning_dir: &Path) -> PathBuf {
+    planning_dir.join(".events").join("event-log.jsonl")
+}
+
+/// Rotated segments, oldest first, followed by the live log (only existing files).
+pub fn log_segments(planning_dir: &Path) -> Vec<PathBuf> {
+    let live = log_path(planning_dir);
+    let mut rotated: Vec<(usize, PathBuf)> = fs::read_dir(live.parent().unwrap_or(planning_dir))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let n = name.strip_prefix("event-log.jsonl.")?.parse::<usize>().ok()?;
+            Some((n, e.path()))
+        })
+        .collect();
+    rotated.sort_by_key(|(n, _)| std::cmp::Reverse(*n));
+    let mut segments: Vec<PathBuf> = rotated.into_iter().map(|(_, p)| p).collect();
+    if live.is_file() {
+        segments.push(live);
+    }
+    segments
+}
+
+/// Full event history across rotated segments, oldest first.
+pub fn read_all(planning_dir: &Path) -> String {
+    let mut out = String::new();
+    for seg in log_segments(planning_dir) {
+        if let Ok(content) = fs::read_to_string(&seg) {
+            out.push_str(&content);
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Parsed events across rotated segments, oldest first; malformed lines are skipped.
+pub fn load_events(planning_dir: &Path) -> Vec<Value> {
+    read_all(planning_dir)
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+        .collect()
+}
+
+/// Publish one event: append to the canonical log (rotating by size), then fan
+/// out to configured sinks. Sink failures are swallowed; the canonical write wins.
+/// Adds a `ts` field when the event object has none.
+pub fn publish(planning_dir: &Path, event: &Value) {
+    let mut event = event.clone();
+    if let Some(obj) = event.as_object_mut()
+        && !obj.contains_key("ts")
+    {
+        obj.insert("ts".into(), json!(Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string()));
+    }
+    let line = match serde_json::to_string(&event) {
+        Ok(l) => l,
+        Err(_) => return,
+    };
+
+    let config = BusConfig::load(planning_dir);
+    let live = log_path(planning_dir);
+    if let Some(parent) = live.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    rotate_if_needed(&live, line.len() as u64 + 1, &config);
+    append_line(&live, &line);
+
+    let root = planning_dir.parent().unwrap_or(planning_dir);
+    for sink in &config.sinks {
+        deliver(sink, root, &event, &line);
+    }
+}
+
+/// Publish a free-form diagnostic message (replaces the old `.hook-errors.log`).
+pub fn publish_message(planning_dir: &Path, source: &str, message: &str) {
+    publish(planning_dir, &json!({
+        "event": "hook_message",
+        "data": {"source": source, "message": message},
+    }));
+}
+
+fn append_line(path: &Path, line: &str) {
+    if let Ok(mut f) = fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(f, "{}", line);
+    }
+}
+
+/// Shift `log.N-1 -> log.N ... log -> log.1` when the next write would exceed `max_bytes`.
+fn rotate_if_needed(live: &Path, incoming: u64, config: &BusConfig) {
+    if config.max_bytes == 0 {
+        return;
+    }
+    let size = fs::metadata(live).map(|m| m.len()).unwrap_or(0);
+    if size == 0 || size + incoming <= config.max_bytes {
+        return;
+    }
+    let segment = |n: usize| PathBuf::from(format!("{}.{}", live.display(), n));
+    if config.keep == 0 {
+        let _ = fs::remove_file(live);
+        return;
+    }
+    let _ = fs::remove_file(segment(config.keep));
+    for n in (1..config.keep).rev() {
+        let from = segment(n);
+        if from.exists() {
+            let _ = fs::rename(&from, segment(n + 1));
+        }
+    }
+    let _ = fs::rename(live, segment(1));
+}
+
+fn resolve(root: &Path, path: &str) -> PathBuf {
+    let p = Path::new(path);
+    if p.is_absolute() { p.to_path_buf() } else { root.join(p) }
+}
+
+fn deliver(sink: &Sink, root: &Path, event: &Value, line: &str) {
+    match sink {
+        Sink::Jsonl { path } => {
+            let target = resolve(root, path);
+            if let Some(parent) = target.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            append_line(&target, line);
+        }
+        Sink::Sqlite { path } => {
+            let db_path = resolve(root, path.as_deref().unwrap_or(".yolo-telemetry.db"));
+            if let Ok(db) = TelemetryDb::new(db_path) {
+                let kind = event.get("event").and_then(|v| v.as_str()).unwrap_or("unknown");
+                let _ = db.record_event(kind, line);
+            }
+        }
+        Sink::Socket { path } => send_socket(&resolve(root, path), line),
+        Sink::Webhook { url } => {
+            if is_loopback_url(url) {
+                send_webhook(url.clone(), event.clone());
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send_socket(path: &Path, line: &str) {
+    use std::os::unix::net::UnixStream;
+    if let Ok(mut stream) = UnixStream::connect(path) {
+        let _ = stream.set_write_timeout(Some(SINK_TIMEOUT));
+        let _ = writeln!(stream, "{}", line);
+    }
+}
+
+#[cfg(not(unix))]
+fn send_socket(_path: &Path, _line: &str) {}
+
+/// Webhooks may only target a local collector; anything else is dropped.
+pub fn is_loopback_url(url: &str) -> bool {
+    let rest = match url.strip_prefix("http://").or_else(|| url.strip_prefix("https://")) {
+        Some(r) => r,
+        None => return false,
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(""),
+        None => authority.split(':').next().unwrap_or(""),
+    };
+    host == "localhost" || host == "::1" || host.starts_with("127.")
+}
+
+fn send_webhook(url: String, event: Value) {
+    // Blocking reqwest must not run on a tokio worker; a dedicated thread keeps it isolated.
+    let _ = std::thread::spawn(move || {
+        if let Ok(client) = reqwest::blocking::Client::builder().timeout(SINK_TIMEOUT).build() {
+            let _ = client.post(&url).json(&event).send();
+        }
+    })
+    .join();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn planning(config: Value) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), config.to_string()).unwrap();
+        (dir, planning)
+    }
+
+    #[test]
+    fn test_publish_appends_to_canonical_log_with_ts() {
+        let (_dir, planning) = planning(json!({}));
+        publish(&planning, &json!({"event": "phase_start", "phase": 1}));
+        let content = fs::read_to_string(log_path(&planning)).unwrap();
+        let v: Value = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(v["event"], "phase_start");
+        assert!(v["ts"].is_string());
+    }
+
+    #[test]
+    fn test_rotation_by_size_keeps_configured_segments() {
+        let (_dir, planning) = planning(json!({"event_bus": {"max_bytes": 120, "keep": 2}}));
+        for i in 0..20 {
+            publish(&planning, &json!({"event": "checkpoint", "data": {"i": i}}));
+        }
+        let events = planning.join(".events");
+        assert!(events.join("event-log.jsonl.1").exists());
+        assert!(events.join("event-log.jsonl.2").exists());
+        assert!(!events.join("event-log.jsonl.3").exists());
+        assert!(fs::metadata(log_path(&planning)).unwrap().len() <= 120);
+
+        // read_all is ordered oldest-first across segments
+        let all = read_all(&planning);
+        let seq: Vec<i64> = all
+            .lines()
+            .filter_map(|l| serde_json::from_str::<Value>(l).ok())
+            .filter_map(|v| v["data"]["i"].as_i64())
+            .collect();
+        assert!(seq.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*seq.last().unwrap(), 19);
+    }
+
+    #[test]
+    fn test_jsonl_and_sqlite_sinks() {
+        let (dir, planning) = planning(json!({"event_bus": {"sinks": [
+            {"type": "jsonl", "path": "mirror/events.jsonl"},
+            {"type": "sqlite", "path": "telemetry.db"}
+        ]}}));
+        publish(&planning, &json!({"event": "gate_passed"}));
+        let mirror = fs::read_to_string(dir.path().join("mirror/events.jsonl")).unwrap();
+        assert!(mirror.contains("gate_passed"));
+        let conn = rusqlite::Connection::open(dir.path().join("telemetry.db")).unwrap();
+        let kind: String = conn
+            .query_row("SELECT event_type FROM events", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(kind, "gate_passed");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_socket_sink_receives_line() {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+        let (dir, planning) = planning(json!({"event_bus": {"sinks": [
+            {"type": "socket", "path": "bus.sock"}
+        ]}}));
+        let listener = UnixListener::bind(dir.path().join("bus.sock")).unwrap();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(stream).read_line(&mut line).unwrap();
+            line
+        });
+        publish(&planning, &json!({"event": "task_started"}));
+        assert!(handle.join().unwrap().contains("task_started"));
+    }
+
+    #[test]
+    fn test_unreachable_sinks_do_not_block_canonical_write() {
+        let (_dir, planning) = planning(json!({"event_bus": {"sinks": [
+            {"type": "socket", "path": "missing.sock"},
+            {"type": "webhook", "url": "http://example.com/collect"}
+        ]}}));
+        publish(&planning, &json!({"event": "error"}));
+        assert!(log_path(&planning).exists());
+    }
+
+    #[test]
+    fn test_is_loopback_url() {
+        assert!(is_loopback_url("http://127.0.0.1:9000/events"));
+        assert!(is_loopback_url("http://localhost/events"));
+        assert!(is_loopback_url("http://[::1]:8080"));
+        assert!(!is_loopback_url("http://example.com/events"));
+        assert!(!is_loopback_url("http://localhost.evil.com/"));
+        assert!(!is_loopback_url("ftp://127.0.0.1"));
+    }
+
+    #[test]
+    fn test_invalid_bus_config_falls_back_to_defaults() {
+        let (_dir, planning) = planning(json!({"event_bus": {"sinks": [{"type": "carrier-pigeon"}]}}));
+        assert_eq!(BusConfig::load(&planning), BusConfig::default());
+    }
+}
@@ -62,7 +62,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
 
 /// Parse the first `## v{VERSION}` or `## [{VERSION}]` section from changelog content.
 /// Returns (version, optional_date, body).
-fn extract_latest_section(content: &str) -> Option<(String, Option<String>, String)> {
+pub(crate) fn extract_latest_section(content: &str) -> Option<(String, Option<String>, String)> {
     let mut version: Option<String> = None;
     let mut date: Option<String> = None;
     let mut body_lines: Vec<&str> = Vec::new();
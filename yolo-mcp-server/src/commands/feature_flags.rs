@@ -2,6 +2,8 @@ use serde_json::Value;
 use std::fs;
 use std::path::Path;
 
+use super::layered_config::Config;
+
 /// All feature flags in the system. Adding a new flag here forces
 /// exhaustive handling at compile time.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,11 +88,148 @@ impl FeatureFlag {
     ];
 }
 
-/// Read a feature flag from the project config.
-/// Returns false if config is missing, unreadable, or flag is absent.
+/// Static metadata for a flag: what it needs, what it cannot run alongside,
+/// which rollout stage turns it on, and a one-line description.
+#[derive(Debug, Clone, Copy)]
+pub struct FlagSpec {
+    /// Flags that must also be on, each with what breaks without it.
+    pub requires: &'static [(FeatureFlag, &'static str)],
+    /// Flags that must not be on at the same time.
+    pub conflicts: &'static [FeatureFlag],
+    /// Stage in `config/rollout-stages.json` that enables the flag; `None` for opt-in flags.
+    pub default_stage: Option<u8>,
+    pub description: &'static str,
+}
+
+const fn flag_spec(
+    requires: &'static [(FeatureFlag, &'static str)],
+    conflicts: &'static [FeatureFlag],
+    default_stage: Option<u8>,
+    description: &'static str,
+) -> FlagSpec {
+    FlagSpec { requires, conflicts, default_stage, description }
+}
+
+impl FeatureFlag {
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.key() == key)
+    }
+
+    /// Dependency metadata. `default_stage` must match `config/rollout-stages.json`.
+    pub fn spec(&self) -> FlagSpec {
+        use FeatureFlag::*;
+        match self {
+            V2HardContracts => flag_spec(&[], &[], None, "Strict 11-field contracts with hard stop on violation"),
+            V2HardGates => flag_spec(
+                &[
+                    (V2HardContracts, "contract_compliance gate will fail"),
+                    (V3EventLog, "forbidden_commands gate will see no blocked commands"),
+                ],
+                &[],
+                None,
+                "Strict validation gates (hard stop)",
+            ),
+            V2TypedProtocol => flag_spec(&[], &[], None, "Strict typing on inter-agent messages"),
+            V2RoleIsolation => flag_spec(&[], &[], None, "File-write boundaries by agent role"),
+            V2TwoPhaseCompletion => flag_spec(
+                &[(V3EventLog, "completion events will be lost")],
+                &[],
+                None,
+                "Tasks require artifact registration before completion",
+            ),
+            V2TokenBudgets => flag_spec(&[], &[], None, "Per-agent token budget tracking and context truncation"),
+            V3DeltaContext => flag_spec(&[], &[], Some(2), "Include changed-file slices in compiled context"),
+            V3ContextCache => flag_spec(&[], &[], Some(2), "Cache compiled context index between runs"),
+            V3PlanResearchPersist => flag_spec(&[], &[], Some(3), "Write Scout findings to RESEARCH.md during planning"),
+            V3Metrics => flag_spec(&[], &[], Some(1), "Instrument timing, token usage, agent counts"),
+            V3ContractLite => flag_spec(
+                &[],
+                &[],
+                Some(3),
+                "Lightweight 5-field task contracts (advisory warnings, fail-open)",
+            ),
+            V3LockLite => flag_spec(&[], &[], Some(3), "File locks for parallel Dev teammates"),
+            V3ValidationGates => flag_spec(&[], &[], Some(3), "Pre-task and post-task validation checks"),
+            V3SmartRouting => flag_spec(&[], &[], Some(3), "Route tasks to agents by complexity"),
+            V3EventLog => flag_spec(&[], &[], Some(1), "Structured event entries at lifecycle points"),
+            V3SchemaValidation => flag_spec(&[], &[], Some(3), "Validate PLAN.md/SUMMARY.md structure against schemas"),
+            V3SnapshotResume => flag_spec(&[], &[], Some(3), "Per-plan checkpoints for interrupted work"),
+            V3LeaseLocks => flag_spec(
+                &[(V3LockLite, "lease-lock commands are skipped")],
+                &[],
+                Some(3),
+                "Time-limited file locks with renewal and reassignment",
+            ),
+            V3EventRecovery => flag_spec(
+                &[(V3EventLog, "event recovery will find no events")],
+                &[],
+                Some(3),
+                "Replay event log to recover from failures",
+            ),
+            V3MonorepoRouting => flag_spec(&[], &[], Some(3), "Package-aware agent context in monorepos"),
+            V4PostEditTestCheck => flag_spec(&[], &[], None, "Remind to run tests after source edits"),
+            V4SessionCacheWarm => flag_spec(&[], &[], None, "Pre-warm the context cache at session start"),
+        }
+    }
+
+    /// Flags that list this one in `requires`.
+    pub fn dependents(&self) -> Vec<FeatureFlag> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|f| f.spec().requires.iter().any(|(dep, _)| dep == self))
+            .collect()
+    }
+
+    /// Flags this one conflicts with, in either direction.
+    pub fn conflicts_with(&self) -> Vec<FeatureFlag> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|f| self.spec().conflicts.contains(f) || f.spec().conflicts.contains(self))
+            .collect()
+    }
+}
+
+/// A flag that is on while a flag it requires is off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmetRequirement {
+    pub flag: FeatureFlag,
+    pub requires: FeatureFlag,
+    /// `"<flag> requires <dep> -- enable <dep> first or <consequence>."`
+    pub message: String,
+}
+
+/// Requirements that the flag states given by `enabled` leave unmet.
+pub fn unmet_requirements(enabled: impl Fn(FeatureFlag) -> bool) -> Vec<UnmetRequirement> {
+    let mut unmet = Vec::new();
+    for flag in FeatureFlag::ALL.iter().copied().filter(|f| enabled(*f)) {
+        for (dep, why) in flag.spec().requires.iter().filter(|(d, _)| !enabled(*d)) {
+            unmet.push(UnmetRequirement {
+                flag,
+                requires: *dep,
+                message: format!("{} requires {} -- enable {} first or {}.", flag.key(), dep.key(), dep.key(), why),
+            });
+        }
+    }
+    unmet
+}
+
+/// Pairs of enabled flags that conflict, each pair reported once.
+pub fn active_conflicts(enabled: impl Fn(FeatureFlag) -> bool) -> Vec<(FeatureFlag, FeatureFlag)> {
+    let mut pairs = Vec::new();
+    for flag in FeatureFlag::ALL.iter().copied().filter(|f| enabled(*f)) {
+        for other in flag.spec().conflicts.iter().copied().filter(|o| enabled(*o)) {
+            pairs.push((flag, other));
+        }
+    }
+    pairs
+}
+
+/// Read a feature flag from the layered config (defaults, user, project, `YOLO_*` env).
+/// Returns false if no layer sets the flag to a boolean.
 pub fn is_enabled(flag: FeatureFlag, cwd: &Path) -> bool {
-    let config_path = cwd.join(".yolo-planning").join("config.json");
-    read_flag_from_path(flag, &config_path)
+    Config::load(cwd).flag(flag)
 }
 
 /// Read a feature flag from a specific config file path.
@@ -157,20 +296,24 @@ mod tests {
         let config: Value = serde_json::from_str(&content).expect("valid JSON");
 
         assert_eq!(
-            config.get("v3_event_recovery").and_then(|v| v.as_bool()),
+            config.get("v3_snapshot_resume").and_then(|v| v.as_bool()),
             Some(true),
-            "v3_event_recovery must default to true"
+            "v3_snapshot_resume must default to true"
         );
         assert_eq!(
-            config.get("v3_snapshot_resume").and_then(|v| v.as_bool()),
+            config.get("v3_event_recovery").and_then(|v| v.as_bool()),
             Some(true),
-            "v3_snapshot_resume must default to true"
+            "v3_event_recovery must default to true"
         );
         assert_eq!(
             config.get("v3_lease_locks").and_then(|v| v.as_bool()),
             Some(true),
             "v3_lease_locks must default to true"
         );
+        // Their dependencies ship on too, so the defaults validate cleanly.
+        for dep in ["v3_event_log", "v3_lock_lite"] {
+            assert_eq!(config.get(dep).and_then(|v| v.as_bool()), Some(true), "{} must default to true", dep);
+        }
     }
 
     #[test]
@@ -192,13 +335,13 @@ mod tests {
         let config = serde_json::json!({"v3_lock_lite": false});
         fs::write(planning_dir.join("config.json"), config.to_string()).unwrap();
 
-        assert!(!is_enabled(FeatureFlag::V3LockLite, dir.path()));
+        assert!(!is_enabled(FeatureFlag::V3ContractLite, dir.path()));
     }
 
     #[test]
     fn test_is_enabled_missing_config() {
         let dir = tempfile::tempdir().unwrap();
-        assert!(!is_enabled(FeatureFlag::V3LockLite, dir.path()));
+        assert!(!is_enabled(FeatureFlag::V3ContractLite, dir.path()));
     }
 
     #[test]
@@ -209,6 +352,54 @@ mod tests {
         let config = serde_json::json!({"v3_event_log": true});
         fs::write(planning_dir.join("config.json"), config.to_string()).unwrap();
 
-        assert!(!is_enabled(FeatureFlag::V3LockLite, dir.path()));
+        assert!(!is_enabled(FeatureFlag::V3ContractLite, dir.path()));
+    }
+
+    #[test]
+    fn test_unmet_requirements() {
+        let on = [FeatureFlag::V2HardGates, FeatureFlag::V2HardContracts];
+        let unmet = unmet_requirements(|f| on.contains(&f));
+        assert_eq!(unmet.len(), 1);
+        assert_eq!(unmet[0].requires, FeatureFlag::V3EventLog);
+        assert!(unmet[0].message.starts_with("v2_hard_gates requires v3_event_log"));
+        assert!(unmet_requirements(|_| true).is_empty());
+        assert!(unmet_requirements(|_| false).is_empty());
+    }
+
+    #[test]
+    fn test_dependency_graph_is_acyclic_and_consistent() {
+        fn visit(flag: FeatureFlag, path: &mut Vec<FeatureFlag>) {
+            assert!(!path.contains(&flag), "dependency cycle through {}", flag.key());
+            path.push(flag);
+            for (dep, _) in flag.spec().requires {
+                visit(*dep, path);
+            }
+            path.pop();
+        }
+        for flag in FeatureFlag::ALL {
+            visit(*flag, &mut Vec::new());
+            let spec = flag.spec();
+            for (dep, _) in spec.requires {
+                assert!(!flag.conflicts_with().contains(dep), "{} requires and conflicts with {}", flag.key(), dep.key());
+                // A staged flag's dependencies must be staged no later than it.
+                if let Some(stage) = spec.default_stage {
+                    let dep_stage = dep.spec().default_stage;
+                    assert!(dep_stage.is_some_and(|d| d <= stage), "{} is staged before {}", flag.key(), dep.key());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dependents_and_conflicts() {
+        let dependents = FeatureFlag::V3EventLog.dependents();
+        assert!(dependents.contains(&FeatureFlag::V3EventRecovery));
+        assert!(dependents.contains(&FeatureFlag::V2HardGates));
+        // Lite and hard contracts can both be on; the contract hook lets hard win.
+        assert!(FeatureFlag::V2HardContracts.conflicts_with().is_empty());
+        let on = [FeatureFlag::V2HardContracts, FeatureFlag::V3ContractLite];
+        assert!(active_conflicts(|f| on.contains(&f)).is_empty());
+        assert_eq!(FeatureFlag::from_key("v3_metrics"), Some(FeatureFlag::V3Metrics));
+        assert_eq!(FeatureFlag::from_key("v3_metric"), None);
     }
 }
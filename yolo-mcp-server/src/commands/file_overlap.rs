@@ -0,0 +1,359 @@
+use globset::{GlobBuilder, GlobMatcher};
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::plan_graph::{GraphAnalysis, PlanGraph};
+
+/// Two plans scheduled in the same wave whose `files_modified` sets intersect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileOverlap {
+    pub wave: u32,
+    pub plan_a: String,
+    pub plan_b: String,
+    /// Overlapping `(plan_a pattern, plan_b pattern)` pairs.
+    pub pairs: Vec<(String, String)>,
+    /// True when at least one pair is the same literal path; glob-only
+    /// overlaps are a likely (not certain) collision.
+    pub exact: bool,
+}
+
+impl FileOverlap {
+    pub fn involves(&self, plan: &str) -> bool {
+        self.plan_a == plan || self.plan_b == plan
+    }
+
+    pub fn other(&self, plan: &str) -> &str {
+        if self.plan_a == plan { &self.plan_b } else { &self.plan_a }
+    }
+
+    pub fn describe(&self) -> String {
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|(a, b)| if a == b { a.clone() } else { format!("{} ~ {}", a, b) })
+            .collect();
+        format!(
+            "Plans {} and {} (wave {}) both modify: {}",
+            self.plan_a,
+            self.plan_b,
+            self.wave,
+            pairs.join(", ")
+        )
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "wave": self.wave,
+            "plans": [self.plan_a, self.plan_b],
+            "files": self.pairs.iter().map(|(a, b)| json!([a, b])).collect::<Vec<_>>(),
+            "exact": self.exact,
+        })
+    }
+}
+
+/// A proposed wave change that removes file overlaps without breaking dependencies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveMove {
+    pub plan: String,
+    pub from: u32,
+    pub to: u32,
+}
+
+impl WaveMove {
+    pub fn to_json(&self) -> Value {
+        json!({"plan": self.plan, "from": self.from, "to": self.to})
+    }
+}
+
+/// Normalise a `files_modified` entry: drop `./`, and treat `dir/` as `dir/**`.
+fn normalize(pattern: &str) -> String {
+    let p = pattern.trim().trim_matches('`');
+    let p = p.strip_prefix("./").unwrap_or(p);
+    match p.strip_suffix('/') {
+        Some(dir) if !dir.is_empty() => format!("{}/**", dir),
+        _ => p.to_string(),
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+fn matcher(pattern: &str) -> Option<GlobMatcher> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .ok()
+        .map(|g| g.compile_matcher())
+}
+
+/// A concrete path the glob would match: wildcards become `x`, classes their
+/// first member and alternations their first branch.
+fn sample_path(pattern: &str) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                while chars.peek() == Some(&'*') {
+                    chars.next();
+                }
+                out.push('x');
+            }
+            '?' => out.push('x'),
+            '[' => {
+                let mut first = None;
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if first.is_none() && c != '!' && c != '^' {
+                        first = Some(c);
+                    }
+                }
+                out.push(first.unwrap_or('x'));
+            }
+            '{' => {
+                let mut depth = 1;
+                let mut taking = true;
+                for c in chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        ',' if depth == 1 => taking = false,
+                        _ if taking => out.push(c),
+                        _ => {}
+                    }
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Whether two `files_modified` entries can name the same file. Literal paths
+/// must be equal; a glob must match the literal; two globs overlap when either
+/// matches a sample path of the other (a heuristic that catches the nesting
+/// cases plans actually use, like `src/**` vs `src/cli/*.rs`).
+pub fn patterns_overlap(a: &str, b: &str) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    if a == b {
+        return true;
+    }
+    let matches = |glob: &str, path: &str| matcher(glob).map(|m| m.is_match(path)).unwrap_or(false);
+    match (is_glob(&a), is_glob(&b)) {
+        (false, false) => false,
+        (true, false) => matches(&a, &b),
+        (false, true) => matches(&b, &a),
+        (true, true) => matches(&a, &sample_path(&b)) || matches(&b, &sample_path(&a)),
+    }
+}
+
+/// The wave each plan will run in: its declared `wave:`, else the computed one.
+pub fn effective_waves(graph: &PlanGraph, analysis: &GraphAnalysis) -> Vec<u32> {
+    graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(n, node)| {
+            node.declared_wave
+                .or_else(|| analysis.waves.get(n).copied())
+                .unwrap_or(1)
+        })
+        .collect()
+}
+
+fn overlap_between(graph: &PlanGraph, a: usize, b: usize, wave: u32) -> Option<FileOverlap> {
+    let files_a = &graph.nodes[a].frontmatter.files_modified;
+    let files_b = &graph.nodes[b].frontmatter.files_modified;
+    let mut pairs = Vec::new();
+    let mut exact = false;
+    for fa in files_a {
+        for fb in files_b {
+            if patterns_overlap(fa, fb) {
+                let (na, nb) = (normalize(fa), normalize(fb));
+                exact |= na == nb && !is_glob(&na);
+                pairs.push((fa.clone(), fb.clone()));
+            }
+        }
+    }
+    if pairs.is_empty() {
+        return None;
+    }
+    Some(FileOverlap {
+        wave,
+        plan_a: graph.nodes[a].id.clone(),
+        plan_b: graph.nodes[b].id.clone(),
+        pairs,
+        exact,
+    })
+}
+
+/// Every pair of same-phase, same-wave plans whose file sets intersect.
+pub fn find_overlaps(graph: &PlanGraph, waves: &[u32]) -> Vec<FileOverlap> {
+    let mut overlaps = Vec::new();
+    for a in 0..graph.nodes.len() {
+        for b in (a + 1)..graph.nodes.len() {
+            if graph.nodes[a].phase != graph.nodes[b].phase || waves[a] != waves[b] {
+                continue;
+            }
+            if let Some(o) = overlap_between(graph, a, b, waves[a]) {
+                overlaps.push(o);
+            }
+        }
+    }
+    overlaps
+}
+
+/// Reassign waves so no two overlapping plans share one. Plans are placed in
+/// dependency order, each in the earliest wave that is after its same-phase
+/// dependencies, not before its current wave, and free of overlaps. Returns
+/// None when the graph has cycles.
+pub fn suggest_waves(graph: &PlanGraph, analysis: &GraphAnalysis) -> Option<Vec<WaveMove>> {
+    if !analysis.cycles.is_empty() {
+        return None;
+    }
+    let current = effective_waves(graph, analysis);
+
+    // Computed waves already order dependencies; ties broken by id.
+    let mut order: Vec<usize> = (0..graph.nodes.len()).collect();
+    order.sort_by_key(|&n| (analysis.waves.get(n).copied().unwrap_or(1), n));
+
+    let mut assigned: Vec<Option<u32>> = vec![None; graph.nodes.len()];
+    for &n in &order {
+        let node = &graph.nodes[n];
+        let after_deps = graph
+            .edges
+            .iter()
+            .filter(|e| e.to == n && !e.cross_phase)
+            .filter_map(|e| assigned[e.from])
+            .max()
+            .map(|w| w + 1)
+            .unwrap_or(1);
+        let mut wave = after_deps.max(current[n]);
+        loop {
+            let clash = (0..graph.nodes.len()).any(|m| {
+                m != n
+                    && assigned[m] == Some(wave)
+                    && graph.nodes[m].phase == node.phase
+                    && overlap_between(graph, n, m, wave).is_some()
+            });
+            if !clash {
+                break;
+            }
+            wave += 1;
+        }
+        assigned[n] = Some(wave);
+    }
+
+    Some(
+        graph
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(n, node)| {
+                let to = assigned[n].unwrap_or(current[n]);
+                (to != current[n]).then(|| WaveMove { plan: node.id.clone(), from: current[n], to })
+            })
+            .collect(),
+    )
+}
+
+/// Find the graph node for a plan file (by canonical path, then file name).
+pub fn node_for_path(graph: &PlanGraph, plan_path: &Path) -> Option<usize> {
+    let canonical = plan_path.canonicalize().ok();
+    graph
+        .nodes
+        .iter()
+        .position(|n| canonical.is_some() && n.path.canonicalize().ok() == canonical)
+        .or_else(|| {
+            let name = plan_path.file_name()?;
+            graph.nodes.iter().position(|n| n.path.file_name() == Some(name))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_literal_paths() {
+        assert!(patterns_overlap("src/main.rs", "./src/main.rs"));
+        assert!(!patterns_overlap("src/main.rs", "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_glob_against_literal() {
+        assert!(patterns_overlap("src/*.rs", "src/main.rs"));
+        assert!(!patterns_overlap("src/*.rs", "src/cli/router.rs"));
+        assert!(patterns_overlap("src/**", "src/cli/router.rs"));
+        assert!(patterns_overlap("src/", "src/cli/router.rs"));
+    }
+
+    #[test]
+    fn test_glob_against_glob() {
+        assert!(patterns_overlap("src/**", "src/cli/*.rs"));
+        assert!(patterns_overlap("src/cli/*.rs", "src/**/*.rs"));
+        assert!(patterns_overlap("tests/*.{rs,bats}", "tests/*.rs"));
+        assert!(!patterns_overlap("src/**/*.rs", "docs/**/*.md"));
+        assert!(!patterns_overlap("src/*.rs", "src/*.md"));
+    }
+
+    #[test]
+    fn test_sample_path() {
+        assert_eq!(sample_path("src/**/*.rs"), "src/x/x.rs");
+        assert_eq!(sample_path("a/[bc]?.{md,txt}"), "a/bx.md");
+    }
+
+    fn write_plan(dir: &Path, name: &str, fm: &str) {
+        fs::write(dir.join(name), format!("---\n{}\n---\n### Task 1: Work\n", fm)).unwrap();
+    }
+
+    #[test]
+    fn test_find_overlaps_and_suggest() {
+        let dir = tempdir().unwrap();
+        let phase = dir.path().join("01-core");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "01-01-PLAN.md", "plan: 1\nwave: 1\nfiles_modified: [src/main.rs]");
+        write_plan(&phase, "01-02-PLAN.md", "plan: 2\nwave: 1\nfiles_modified: [\"src/*.rs\"]");
+        write_plan(&phase, "01-03-PLAN.md", "plan: 3\nwave: 1\nfiles_modified: [docs/a.md]");
+        write_plan(&phase, "01-04-PLAN.md", "plan: 4\nwave: 2\ndepends_on: [2]\nfiles_modified: [docs/a.md]");
+
+        let graph = PlanGraph::load(&phase);
+        let analysis = graph.analyze();
+        let overlaps = find_overlaps(&graph, &effective_waves(&graph, &analysis));
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!((overlaps[0].plan_a.as_str(), overlaps[0].plan_b.as_str()), ("01-01", "01-02"));
+        assert!(!overlaps[0].exact);
+
+        let moves = suggest_waves(&graph, &analysis).unwrap();
+        // 01-02 moves to wave 2, which pushes its dependent 01-04 to wave 3.
+        assert_eq!(
+            moves,
+            vec![
+                WaveMove { plan: "01-02".into(), from: 1, to: 2 },
+                WaveMove { plan: "01-04".into(), from: 2, to: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_node_for_path() {
+        let dir = tempdir().unwrap();
+        write_plan(dir.path(), "01-01-PLAN.md", "plan: 1\nwave: 1");
+        let graph = PlanGraph::load(dir.path());
+        assert_eq!(node_for_path(&graph, &dir.path().join("01-01-PLAN.md")), Some(0));
+        assert_eq!(node_for_path(&graph, &dir.path().join("09-09-PLAN.md")), None);
+    }
+}
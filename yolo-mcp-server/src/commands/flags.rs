@@ -0,0 +1,278 @@
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::Instant;
+
+use super::config_edit::write_project_layer;
+use super::feature_flags::{unmet_requirements, FeatureFlag};
+use super::layered_config::{closest_key, Config, Layer};
+
+const USAGE: &str = "Usage: yolo flags <list | why <flag> | enable <flag> [--with-deps] | disable <flag> [--with-dependents]>";
+
+/// `yolo flags {list|why|enable|disable}`: show flag state against the dependency
+/// graph and toggle flags in the project config, refusing unsafe combinations.
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+    let action = args.get(2).map(String::as_str).ok_or(USAGE)?;
+    let config = Config::load(cwd);
+    let cascade = args.iter().skip(3).any(|a| a == "--with-deps" || a == "--with-dependents");
+
+    let (mut out, code) = match action {
+        "list" => list(&config),
+        "why" => (why(&config, parse_flag(args)?), 0),
+        "enable" => toggle(&config, cwd, parse_flag(args)?, true, cascade)?,
+        "disable" => toggle(&config, cwd, parse_flag(args)?, false, cascade)?,
+        _ => return Err(USAGE.to_string()),
+    };
+
+    if let Some(obj) = out.as_object_mut() {
+        obj.insert("cmd".into(), json!("flags"));
+        obj.insert("action".into(), json!(action));
+        obj.insert("elapsed_ms".into(), json!(start.elapsed().as_millis()));
+    }
+    Ok((serde_json::to_string(&out).unwrap() + "\n", code))
+}
+
+fn parse_flag(args: &[String]) -> Result<FeatureFlag, String> {
+    let key = args.iter().skip(3).find(|a| !a.starts_with("--")).ok_or(USAGE)?;
+    FeatureFlag::from_key(key).ok_or_else(|| {
+        let mut msg = format!("unknown flag '{}'", key);
+        if let Some(hint) = closest_key(key, FeatureFlag::ALL.iter().map(|f| f.key())) {
+            msg.push_str(&format!(" (did you mean '{}'?)", hint));
+        }
+        msg
+    })
+}
+
+/// Requirement and conflict violations for `flag` given the effective config.
+fn problems(config: &Config, flag: FeatureFlag) -> Vec<String> {
+    if !config.flag(flag) {
+        return Vec::new();
+    }
+    let mut out: Vec<String> = unmet_requirements(|f| config.flag(f))
+        .into_iter()
+        .filter(|u| u.flag == flag)
+        .map(|u| u.message)
+        .collect();
+    for other in flag.conflicts_with().into_iter().filter(|o| config.flag(*o)) {
+        out.push(format!("{} conflicts with {} -- disable one of them.", flag.key(), other.key()));
+    }
+    out
+}
+
+fn flag_state(config: &Config, flag: FeatureFlag) -> Value {
+    let spec = flag.spec();
+    json!({
+        "flag": flag.key(),
+        "enabled": config.flag(flag),
+        "source": config.source_of(flag.key()).map(|l| l.layer.name()).unwrap_or("missing"),
+        "description": spec.description,
+        "default_stage": spec.default_stage,
+        "requires": spec.requires.iter().map(|(d, why)| json!({"flag": d.key(), "enabled": config.flag(*d), "why": why})).collect::<Vec<_>>(),
+        "required_by": flag.dependents().iter().map(|d| json!({"flag": d.key(), "enabled": config.flag(*d)})).collect::<Vec<_>>(),
+        "conflicts": flag.conflicts_with().iter().map(|c| json!({"flag": c.key(), "enabled": config.flag(*c)})).collect::<Vec<_>>(),
+        "problems": problems(config, flag),
+    })
+}
+
+fn list(config: &Config) -> (Value, i32) {
+    let flags: Vec<Value> = FeatureFlag::ALL.iter().map(|f| flag_state(config, *f)).collect();
+    let problems: usize = FeatureFlag::ALL.iter().map(|f| problems(config, *f).len()).sum();
+    (json!({"ok": true, "flags": flags, "problems": problems}), 0)
+}
+
+fn why(config: &Config, flag: FeatureFlag) -> Value {
+    let mut out = flag_state(config, flag);
+    let reason = match config.source_of(flag.key()) {
+        Some(l) => format!(
+            "{} by the {} layer ({})",
+            if config.flag(flag) { "enabled" } else { "disabled" },
+            l.layer.name(),
+            l.origin
+        ),
+        None => "disabled: no config layer sets it".to_string(),
+    };
+    out["ok"] = json!(true);
+    out["reason"] = json!(reason);
+    out["layers"] = config.explain(flag.key())["layers"].clone();
+    out
+}
+
+/// Flags that must change along with `flag`: its transitive requirements when
+/// enabling, its enabled transitive dependents when disabling.
+fn cascade_set(config: &Config, flag: FeatureFlag, enable: bool) -> Vec<FeatureFlag> {
+    let mut set = vec![flag];
+    let mut i = 0;
+    while i < set.len() {
+        let next: Vec<FeatureFlag> = if enable {
+            set[i].spec().requires.iter().map(|(d, _)| *d).collect()
+        } else {
+            set[i].dependents()
+        };
+        for f in next {
+            if config.flag(f) != enable && !set.contains(&f) {
+                set.push(f);
+            }
+        }
+        i += 1;
+    }
+    set
+}
+
+fn toggle(config: &Config, cwd: &Path, flag: FeatureFlag, enable: bool, cascade: bool) -> Result<(Value, i32), String> {
+    let project = config.layer(Layer::Project).ok_or("project config layer not loaded")?;
+    if let Some(e) = &project.parse_error {
+        return Err(format!("{}: {} -- fix it before editing", project.origin, e));
+    }
+
+    let changing = if cascade { cascade_set(config, flag, enable) } else { vec![flag] };
+    let will_be = |f: FeatureFlag| if changing.contains(&f) { enable } else { config.flag(f) };
+
+    let mut refused = Vec::new();
+    for f in &changing {
+        if enable {
+            for (dep, why) in f.spec().requires.iter().filter(|(d, _)| !will_be(*d)) {
+                refused.push(format!(
+                    "{} requires {} ({} otherwise) -- enable it first or pass --with-deps",
+                    f.key(), dep.key(), why
+                ));
+            }
+            for other in f.conflicts_with().into_iter().filter(|o| will_be(*o)) {
+                refused.push(format!("{} conflicts with {} -- disable {} first", f.key(), other.key(), other.key()));
+            }
+        } else {
+            for dependent in f.dependents().into_iter().filter(|d| will_be(*d)) {
+                refused.push(format!(
+                    "{} is required by {} -- disable it first or pass --with-dependents",
+                    f.key(), dependent.key()
+                ));
+            }
+        }
+    }
+    if !refused.is_empty() {
+        return Ok((json!({"ok": false, "flag": flag.key(), "changed": [], "refused": refused}), 1));
+    }
+
+    let mut values = project.values.clone();
+    let mut changed = Vec::new();
+    for f in &changing {
+        if values.get(f.key()) != Some(&json!(enable)) {
+            values.insert(f.key().to_string(), json!(enable));
+            changed.push(f.key());
+        }
+    }
+    let candidate = config.with_layer_values(Layer::Project, values.clone());
+    if !changed.is_empty() {
+        write_project_layer(cwd, values)?;
+    }
+
+    // A higher layer (YOLO_* env) can still override the project value.
+    let overridden: Vec<Value> = changing
+        .iter()
+        .filter(|f| candidate.flag(**f) != enable)
+        .map(|f| json!({"flag": f.key(), "source": candidate.source_of(f.key()).map(|l| l.layer.name())}))
+        .collect();
+    Ok((
+        json!({
+            "ok": true,
+            "flag": flag.key(),
+            "enabled": candidate.flag(flag),
+            "changed": changed,
+            "overridden": overridden,
+        }),
+        0,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup(project: Value) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), project.to_string()).unwrap();
+        dir
+    }
+
+    fn run(dir: &TempDir, args: &[&str]) -> (Value, i32) {
+        let mut full = vec!["yolo".to_string(), "flags".to_string()];
+        full.extend(args.iter().map(|s| s.to_string()));
+        let (out, code) = execute(&full, dir.path()).unwrap();
+        (serde_json::from_str(&out).unwrap(), code)
+    }
+
+    fn project(dir: &TempDir) -> Value {
+        serde_json::from_str(&fs::read_to_string(dir.path().join(".yolo-planning/config.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_enable_refuses_missing_dependency() {
+        let dir = setup(json!({"v3_event_log": false}));
+        let (out, code) = run(&dir, &["enable", "v3_event_recovery"]);
+        assert_eq!(code, 1);
+        assert_eq!(out["ok"], false);
+        assert!(out["refused"][0].as_str().unwrap().contains("requires v3_event_log"));
+        assert_eq!(project(&dir), json!({"v3_event_log": false}));
+    }
+
+    #[test]
+    fn test_enable_with_deps_enables_transitively() {
+        let dir = setup(json!({"v3_event_log": false}));
+        let (out, code) = run(&dir, &["enable", "v2_hard_gates", "--with-deps"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["enabled"], true);
+        let p = project(&dir);
+        assert_eq!(p["v2_hard_gates"], true);
+        assert_eq!(p["v2_hard_contracts"], true);
+        assert_eq!(p["v3_event_log"], true);
+    }
+
+    #[test]
+    fn test_enable_hard_gates_alongside_contract_lite() {
+        let dir = setup(json!({"v3_contract_lite": true}));
+        let (out, code) = run(&dir, &["enable", "v2_hard_gates", "--with-deps"]);
+        assert_eq!(code, 0, "{}", out);
+        let p = project(&dir);
+        assert_eq!(p["v2_hard_contracts"], true);
+        assert_eq!(p["v3_contract_lite"], true);
+    }
+
+    #[test]
+    fn test_disable_refuses_enabled_dependents() {
+        let dir = setup(json!({"v3_event_log": true, "v3_event_recovery": true}));
+        let (out, code) = run(&dir, &["disable", "v3_event_log"]);
+        assert_eq!(code, 1);
+        assert!(out["refused"][0].as_str().unwrap().contains("required by v3_event_recovery"));
+
+        let (out, code) = run(&dir, &["disable", "v3_event_log", "--with-dependents"]);
+        assert_eq!(code, 0);
+        assert_eq!(out["changed"], json!(["v3_event_log", "v3_event_recovery"]));
+        assert_eq!(project(&dir), json!({"v3_event_log": false, "v3_event_recovery": false}));
+    }
+
+    #[test]
+    fn test_why_and_list_report_problems() {
+        let dir = setup(json!({"v3_lease_locks": true, "v3_lock_lite": false}));
+        let (out, _) = run(&dir, &["why", "v3_lease_locks"]);
+        assert_eq!(out["enabled"], true);
+        assert_eq!(out["source"], "project");
+        assert!(out["reason"].as_str().unwrap().starts_with("enabled by the project layer"));
+        assert_eq!(out["requires"][0]["flag"], "v3_lock_lite");
+        assert!(out["problems"][0].as_str().unwrap().contains("requires v3_lock_lite"));
+
+        let (out, _) = run(&dir, &["list"]);
+        assert_eq!(out["flags"].as_array().unwrap().len(), FeatureFlag::ALL.len());
+        assert!(out["problems"].as_u64().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_unknown_flag_suggests_closest() {
+        let dir = setup(json!({}));
+        let args: Vec<String> = ["yolo", "flags", "enable", "v3_metric"].iter().map(|s| s.to_string()).collect();
+        let err = execute(&args, dir.path()).unwrap_err();
+        assert_eq!(err, "unknown flag 'v3_metric' (did you mean 'v3_metrics'?)");
+    }
+}
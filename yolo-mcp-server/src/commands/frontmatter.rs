@@ -0,0 +1,666 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// What went wrong while reading a frontmatter block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterErrorKind {
+    /// Opening `---` without a closing `---`.
+    Unterminated,
+    /// The block is not valid YAML.
+    Yaml,
+    /// The block is valid YAML but not a mapping.
+    NotMapping,
+}
+
+/// A frontmatter parse error. `line` and `column` are 1-based positions in the
+/// whole file (not the frontmatter block), so they can be reported as-is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontmatterError {
+    pub kind: FrontmatterErrorKind,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for FrontmatterError {}
+
+/// A parsed frontmatter block and the markdown body that follows it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frontmatter {
+    pub fields: Map<String, Value>,
+    pub body: String,
+}
+
+/// Parse the YAML frontmatter at the top of a markdown document.
+///
+/// Returns `Ok(None)` when the document does not start with `---`, and an error
+/// with the file position when the block is unterminated or not a YAML mapping.
+/// An empty block (`---\n---`) parses to an empty field map.
+pub fn parse(content: &str) -> Result<Option<Frontmatter>, FrontmatterError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.split_inclusive('\n');
+    match lines.next() {
+        Some(first) if first.trim() == "---" => {}
+        _ => return Ok(None),
+    }
+
+    let mut yaml = String::new();
+    let mut body_start = None;
+    let mut offset = content.split_inclusive('\n').next().map(str::len).unwrap_or(0);
+    let mut line_count = 1;
+    for line in lines {
+        line_count += 1;
+        if line.trim() == "---" {
+            body_start = Some(offset + line.len());
+            break;
+        }
+        yaml.push_str(line);
+        offset += line.len();
+    }
+
+    let body_start = body_start.ok_or_else(|| FrontmatterError {
+        kind: FrontmatterErrorKind::Unterminated,
+        line: line_count,
+        column: 1,
+        message: "frontmatter not closed (missing second '---')".to_string(),
+    })?;
+
+    let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).map_err(|e| {
+        // +1 accounts for the opening `---` line
+        let (line, column) = e
+            .location()
+            .map(|loc| (loc.line() + 1, loc.column()))
+            .unwrap_or((2, 1));
+        let message = e.to_string();
+        let message = message.split(" at line ").next().unwrap_or(&message).to_string();
+        FrontmatterError { kind: FrontmatterErrorKind::Yaml, line, column, message }
+    })?;
+
+    let fields = match yaml_to_json(parsed) {
+        Value::Object(map) => map,
+        Value::Null => Map::new(),
+        other => {
+            return Err(FrontmatterError {
+                kind: FrontmatterErrorKind::NotMapping,
+                line: 2,
+                column: 1,
+                message: format!("frontmatter must be a mapping, found {}", json_type_name(&other)),
+            });
+        }
+    };
+
+    Ok(Some(Frontmatter {
+        fields,
+        body: content[body_start..].to_string(),
+    }))
+}
+
+/// Read and parse the frontmatter of a file. Read errors are reported at line 1.
+pub fn parse_file(path: &Path) -> Result<Option<Frontmatter>, FrontmatterError> {
+    let content = fs::read_to_string(path).map_err(|e| FrontmatterError {
+        kind: FrontmatterErrorKind::Yaml,
+        line: 1,
+        column: 1,
+        message: format!("failed to read {}: {}", path.display(), e),
+    })?;
+    parse(&content)
+}
+
+fn json_type_name(v: &Value) -> &'static str {
+    match v {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a mapping",
+    }
+}
+
+fn yaml_to_json(v: serde_yaml::Value) -> Value {
+    match v {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::from(u)
+            } else {
+                n.as_f64().map(Value::from).unwrap_or(Value::Null)
+            }
+        }
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(seq) => Value::Array(seq.into_iter().map(yaml_to_json).collect()),
+        serde_yaml::Value::Mapping(map) => {
+            let mut out = Map::new();
+            for (k, v) in map {
+                let key = match yaml_to_json(k) {
+                    Value::String(s) => s,
+                    other => scalar_to_string(&other).unwrap_or_else(|| other.to_string()),
+                };
+                out.insert(key, yaml_to_json(v));
+            }
+            Value::Object(out)
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+/// Render a scalar as text (`01` stays `"01"`, `2` becomes `"2"`). Null, lists
+/// and mappings return None.
+pub fn scalar_to_string(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Render a list item as text. An unquoted item such as `- fetch_user: retries 3 times`
+/// parses as a mapping and renders back to `key: value`; nested lists join with ", ".
+fn item_to_string(v: &Value) -> Option<String> {
+    match v {
+        Value::Object(map) => Some(
+            map.iter()
+                .map(|(k, v)| match item_to_string(v) {
+                    Some(v) if !v.is_empty() => format!("{}: {}", k, v),
+                    _ => k.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        Value::Array(items) => Some(items.iter().filter_map(item_to_string).collect::<Vec<_>>().join(", ")),
+        other => scalar_to_string(other),
+    }
+}
+
+/// Render a value as a list of strings: lists yield one item per entry (mappings
+/// rendered as `key: value`), a non-empty scalar yields a single item, everything
+/// else yields nothing.
+pub fn value_to_list(v: &Value) -> Vec<String> {
+    match v {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(item_to_string)
+            .filter(|s| !s.trim().is_empty())
+            .collect(),
+        other => scalar_to_string(other)
+            .filter(|s| !s.trim().is_empty())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Convert every scalar in a value to its string form, keeping lists and mappings.
+pub fn stringify_scalars(v: &Value) -> Value {
+    match v {
+        Value::Array(items) => Value::Array(items.iter().map(stringify_scalars).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter().map(|(k, v)| (k.clone(), stringify_scalars(v))).collect(),
+        ),
+        Value::Null => Value::String(String::new()),
+        other => Value::String(scalar_to_string(other).unwrap_or_default()),
+    }
+}
+
+impl Frontmatter {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields.get(key)
+    }
+
+    /// True when the key is present, even with an empty value.
+    pub fn has(&self, key: &str) -> bool {
+        self.fields.contains_key(key)
+    }
+
+    /// Scalar value as text; None when missing, null or not a scalar.
+    pub fn scalar(&self, key: &str) -> Option<String> {
+        self.get(key).and_then(scalar_to_string)
+    }
+
+    /// List value as strings. Inline (`[a, b]`) and block (`- a`) lists behave the same.
+    pub fn list(&self, key: &str) -> Vec<String> {
+        self.get(key).map(value_to_list).unwrap_or_default()
+    }
+
+    /// The document as markdown again: the fields as a YAML block, then the body.
+    pub fn render(&self) -> String {
+        let yaml = if self.fields.is_empty() {
+            String::new()
+        } else {
+            serde_yaml::to_string(&self.fields).unwrap_or_default()
+        };
+        format!("---\n{}---\n{}", yaml, self.body)
+    }
+
+    pub fn plan(&self) -> PlanFrontmatter {
+        PlanFrontmatter::from_frontmatter(self)
+    }
+
+    pub fn summary(&self) -> SummaryFrontmatter {
+        SummaryFrontmatter::from_frontmatter(self)
+    }
+}
+
+/// A `cross_phase_deps` entry: `"03-01"`, `"phase:03:plan:01"` or
+/// `{phase: 3, plan: "03-01", artifact: ..., reason: ...}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossPhaseDep {
+    pub phase: String,
+    pub plan: String,
+    pub artifact: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl CrossPhaseDep {
+    /// Parse a cross-phase dep reference like "phase:03:plan:01" or "03-01".
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.starts_with("phase:") {
+            let parts: Vec<&str> = s.split(':').collect();
+            if parts.len() >= 4 && parts[2] == "plan" {
+                return Some(CrossPhaseDep {
+                    phase: parts[1].to_string(),
+                    plan: parts[3].to_string(),
+                    artifact: None,
+                    reason: None,
+                });
+            }
+        }
+
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts.len() == 2 {
+            return Some(CrossPhaseDep {
+                phase: parts[0].to_string(),
+                plan: parts[1].to_string(),
+                artifact: None,
+                reason: None,
+            });
+        }
+
+        None
+    }
+
+    fn from_value(v: &Value) -> Option<Self> {
+        match v {
+            Value::Object(map) => {
+                let get = |k: &str| map.get(k).and_then(scalar_to_string);
+                let plan_raw = get("plan")?;
+                // `plan: "03-01"` carries the phase too; `phase:` wins when both are given.
+                let (phase_from_plan, plan) = match plan_raw.split_once('-') {
+                    Some((p, n)) => (Some(p.to_string()), n.to_string()),
+                    None => (None, plan_raw),
+                };
+                let phase = get("phase").or(phase_from_plan)?;
+                let phase = if phase.len() == 1 { format!("{:0>2}", phase) } else { phase };
+                Some(CrossPhaseDep { phase, plan, artifact: get("artifact"), reason: get("reason") })
+            }
+            other => scalar_to_string(other).and_then(|s| Self::parse(&s)),
+        }
+    }
+}
+
+/// Typed PLAN.md frontmatter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlanFrontmatter {
+    pub phase: Option<String>,
+    pub plan: Option<String>,
+    pub title: Option<String>,
+    pub plan_type: Option<String>,
+    /// Raw `wave:` value; see [`PlanFrontmatter::wave_number`].
+    pub wave: Option<String>,
+    pub depends_on: Vec<String>,
+    pub cross_phase_deps: Vec<CrossPhaseDep>,
+    pub autonomous: Option<bool>,
+    pub effort_override: Option<String>,
+    pub agent: Option<String>,
+    pub skills_used: Vec<String>,
+    pub files_modified: Vec<String>,
+    pub forbidden_paths: Vec<String>,
+    pub forbidden_commands: Vec<String>,
+    pub verification_checks: Vec<String>,
+    /// Flattened must-haves: a plain list, or the `truths`/`artifacts`/`key_links` mapping.
+    pub must_haves: Vec<String>,
+}
+
+impl PlanFrontmatter {
+    pub fn from_frontmatter(fm: &Frontmatter) -> Self {
+        PlanFrontmatter {
+            phase: fm.scalar("phase"),
+            plan: fm.scalar("plan"),
+            title: fm.scalar("title"),
+            plan_type: fm.scalar("type"),
+            wave: fm.scalar("wave"),
+            depends_on: fm.list("depends_on"),
+            cross_phase_deps: match fm.get("cross_phase_deps") {
+                Some(Value::Array(items)) => items.iter().filter_map(CrossPhaseDep::from_value).collect(),
+                Some(other) => CrossPhaseDep::from_value(other).into_iter().collect(),
+                None => Vec::new(),
+            },
+            autonomous: fm.get("autonomous").and_then(|v| v.as_bool()),
+            effort_override: fm.scalar("effort_override"),
+            agent: fm.scalar("agent"),
+            skills_used: fm.list("skills_used"),
+            files_modified: fm.list("files_modified"),
+            forbidden_paths: fm.list("forbidden_paths"),
+            forbidden_commands: fm.list("forbidden_commands"),
+            verification_checks: fm.list("verification_checks"),
+            must_haves: fm.get("must_haves").map(flatten_must_haves).unwrap_or_default(),
+        }
+    }
+
+    /// `wave:` as a number, when it parses as one.
+    pub fn wave_number(&self) -> Option<u32> {
+        self.wave.as_deref().and_then(|w| w.trim().parse().ok())
+    }
+}
+
+fn flatten_must_haves(v: &Value) -> Vec<String> {
+    let Value::Object(map) = v else {
+        return value_to_list(v);
+    };
+
+    let mut out = Vec::new();
+    if let Some(truths) = map.get("truths") {
+        out.extend(value_to_list(truths));
+    }
+    if let Some(Value::Array(artifacts)) = map.get("artifacts") {
+        for a in artifacts {
+            match a {
+                Value::Object(art) => {
+                    let get = |k: &str| art.get(k).and_then(scalar_to_string);
+                    match (get("path"), get("provides")) {
+                        (Some(path), Some(p)) => out.push(format!("{} provides {}", path, p)),
+                        (Some(path), None) => out.push(path),
+                        _ => out.extend(item_to_string(a)),
+                    }
+                }
+                other => out.extend(item_to_string(other)),
+            }
+        }
+    }
+    if let Some(Value::Array(links)) = map.get("key_links") {
+        for l in links {
+            match l {
+                Value::Object(link) => {
+                    let get = |k: &str| link.get(k).and_then(scalar_to_string);
+                    match (get("from"), get("to"), get("via")) {
+                        (Some(from), Some(to), Some(via)) => out.push(format!("{} -> {} via {}", from, to, via)),
+                        (Some(from), Some(to), None) => out.push(format!("{} -> {}", from, to)),
+                        _ => out.extend(item_to_string(l)),
+                    }
+                }
+                other => out.extend(item_to_string(other)),
+            }
+        }
+    }
+    out.retain(|s| !s.trim().is_empty());
+    out
+}
+
+/// Typed SUMMARY.md frontmatter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SummaryFrontmatter {
+    pub phase: Option<String>,
+    pub plan: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub completed: Option<String>,
+    pub tasks_completed: Option<u32>,
+    pub tasks_total: Option<u32>,
+    pub commit_hashes: Vec<String>,
+    pub deviations: Vec<String>,
+    pub files_modified: Vec<String>,
+}
+
+impl SummaryFrontmatter {
+    pub fn from_frontmatter(fm: &Frontmatter) -> Self {
+        let number = |k: &str| fm.scalar(k).and_then(|s| s.trim().parse().ok());
+        SummaryFrontmatter {
+            phase: fm.scalar("phase"),
+            plan: fm.scalar("plan"),
+            title: fm.scalar("title"),
+            status: fm.scalar("status"),
+            completed: fm.scalar("completed"),
+            tasks_completed: number("tasks_completed"),
+            tasks_total: number("tasks_total"),
+            commit_hashes: fm.list("commit_hashes"),
+            deviations: fm.list("deviations"),
+            files_modified: fm.list("files_modified"),
+        }
+    }
+}
+
+/// Typed `.contracts/{phase}-{plan}.json`. The V3 lite contract only carries
+/// `phase`, `plan`, `task_count`, `must_haves` and `allowed_paths`; the V2 full
+/// fields are `None` there and are omitted when serialized.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContractSpec {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_id: Option<String>,
+    pub phase: u64,
+    pub plan: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub objective: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_ids: Option<Vec<String>>,
+    pub task_count: usize,
+    pub allowed_paths: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forbidden_paths: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<i64>>,
+    pub must_haves: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_checks: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_token_budget: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_hash: Option<String>,
+}
+
+impl ContractSpec {
+    /// Load a contract file; errors carry the JSON line and column.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| {
+            format!("{}: line {}, column {}: {}", path.display(), e.line(), e.column(), e)
+        })
+    }
+
+    /// Contract as a JSON value with sorted keys, the form that is hashed and written.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_frontmatter() {
+        assert_eq!(parse("# Title\nbody").unwrap(), None);
+        assert_eq!(parse("").unwrap(), None);
+    }
+
+    #[test]
+    fn test_empty_frontmatter() {
+        let fm = parse("---\n---\n# Body\n").unwrap().unwrap();
+        assert!(fm.fields.is_empty());
+        assert_eq!(fm.body, "# Body\n");
+    }
+
+    #[test]
+    fn test_render_round_trips() {
+        let fm = parse("---\nplan: \"02\"\ncommit_hashes:\n  - abc1234\n---\n# Body\n").unwrap().unwrap();
+        let rendered = fm.render();
+        assert!(rendered.ends_with("---\n# Body\n"));
+        assert_eq!(parse(&rendered).unwrap().unwrap(), fm);
+        assert_eq!(parse("---\n---\nbody").unwrap().unwrap().render(), "---\n---\nbody");
+    }
+
+    #[test]
+    fn test_scalars_keep_leading_zeros() {
+        let fm = parse("---\nphase: 01\nplan: \"02\"\nwave: 1\ntitle: 'Quoted: title'\n---\n").unwrap().unwrap();
+        assert_eq!(fm.scalar("phase").as_deref(), Some("01"));
+        assert_eq!(fm.scalar("plan").as_deref(), Some("02"));
+        assert_eq!(fm.scalar("wave").as_deref(), Some("1"));
+        assert_eq!(fm.scalar("title").as_deref(), Some("Quoted: title"));
+        assert_eq!(fm.scalar("missing"), None);
+    }
+
+    #[test]
+    fn test_inline_and_block_lists_match() {
+        let inline = parse("---\ndepends_on: [01, \"02\", '03']\n---\n").unwrap().unwrap();
+        let block = parse("---\ndepends_on:\n  - 01\n  - \"02\"\n  - '03'\n---\n").unwrap().unwrap();
+        assert_eq!(inline.list("depends_on"), vec!["01", "02", "03"]);
+        assert_eq!(inline.list("depends_on"), block.list("depends_on"));
+        let empty = parse("---\ndepends_on: []\n---\n").unwrap().unwrap();
+        assert!(empty.has("depends_on"));
+        assert!(empty.list("depends_on").is_empty());
+    }
+
+    #[test]
+    fn test_list_items_with_colons() {
+        let fm = parse("---\nmust_haves:\n  - \"retry: 3 attempts\"\n  - plain item\n---\n").unwrap().unwrap();
+        assert_eq!(fm.list("must_haves"), vec!["retry: 3 attempts", "plain item"]);
+
+        // Unquoted, the first item parses as a mapping; it must not be dropped.
+        let fm = parse("---\nmust_haves:\n  - fetch_user: retries 3 times\n  - plain\n---\n").unwrap().unwrap();
+        assert_eq!(fm.list("must_haves"), vec!["fetch_user: retries 3 times", "plain"]);
+        assert_eq!(PlanFrontmatter::from_frontmatter(&fm).must_haves, vec!["fetch_user: retries 3 times", "plain"]);
+    }
+
+    #[test]
+    fn test_yaml_error_has_file_position() {
+        let err = parse("---\nphase: 1\ndepends_on: [01, 02\ntitle: x\n---\n").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::Yaml);
+        assert_eq!(err.line, 4);
+        assert!(err.column >= 1);
+        assert!(err.to_string().starts_with("line 4, column"));
+    }
+
+    #[test]
+    fn test_unterminated_frontmatter() {
+        let err = parse("---\nphase: 1\n").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::Unterminated);
+    }
+
+    #[test]
+    fn test_not_mapping() {
+        let err = parse("---\n- a\n- b\n---\n").unwrap_err();
+        assert_eq!(err.kind, FrontmatterErrorKind::NotMapping);
+    }
+
+    #[test]
+    fn test_plan_frontmatter_template_shape() {
+        let content = r#"---
+phase: 03
+plan: 02
+title: Add retries
+wave: 2
+depends_on: [01]
+cross_phase_deps: [{phase: 2, plan: "02-01", artifact: "src/api.rs", reason: "client"}, "phase:01:plan:03"]
+autonomous: true
+files_modified: [src/fetch.rs]
+must_haves:
+  truths: ["fetch_user retries 3 times"]
+  artifacts: [{path: "src/fetch.rs", provides: "retry loop"}]
+  key_links: [{from: "fetch_user", to: "retry", via: "call"}]
+---
+body
+"#;
+        let plan = parse(content).unwrap().unwrap().plan();
+        assert_eq!(plan.phase.as_deref(), Some("03"));
+        assert_eq!(plan.wave_number(), Some(2));
+        assert_eq!(plan.depends_on, vec!["01"]);
+        assert_eq!(plan.autonomous, Some(true));
+        assert_eq!(plan.files_modified, vec!["src/fetch.rs"]);
+        assert_eq!(plan.cross_phase_deps.len(), 2);
+        assert_eq!(plan.cross_phase_deps[0].phase, "02");
+        assert_eq!(plan.cross_phase_deps[0].plan, "01");
+        assert_eq!(plan.cross_phase_deps[0].artifact.as_deref(), Some("src/api.rs"));
+        assert_eq!(plan.cross_phase_deps[1].phase, "01");
+        assert_eq!(plan.cross_phase_deps[1].plan, "03");
+        assert_eq!(
+            plan.must_haves,
+            vec![
+                "fetch_user retries 3 times",
+                "src/fetch.rs provides retry loop",
+                "fetch_user -> retry via call",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cross_phase_dep_parse() {
+        let dep = CrossPhaseDep::parse("phase:03:plan:01").unwrap();
+        assert_eq!(dep.phase, "03");
+        assert_eq!(dep.plan, "01");
+        let dep2 = CrossPhaseDep::parse("04-02").unwrap();
+        assert_eq!(dep2.phase, "04");
+        assert_eq!(dep2.plan, "02");
+        assert!(CrossPhaseDep::parse("invalid").is_none());
+    }
+
+    #[test]
+    fn test_summary_frontmatter() {
+        let content = "---\nphase: \"01\"\nplan: \"02\"\nstatus: complete\ntasks_completed: 3\ntasks_total: 3\ncommit_hashes:\n  - abc123\n  - def456\n---\n";
+        let summary = parse(content).unwrap().unwrap().summary();
+        assert_eq!(summary.status.as_deref(), Some("complete"));
+        assert_eq!(summary.tasks_completed, Some(3));
+        assert_eq!(summary.commit_hashes, vec!["abc123", "def456"]);
+    }
+
+    #[test]
+    fn test_contract_spec_roundtrip_omits_full_fields() {
+        let lite = ContractSpec {
+            phase: 1,
+            plan: 2,
+            task_count: 3,
+            must_haves: vec!["a".into()],
+            allowed_paths: vec!["src/a.rs".into()],
+            ..Default::default()
+        };
+        let v = lite.to_value();
+        let keys: Vec<&String> = v.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["allowed_paths", "must_haves", "phase", "plan", "task_count"]);
+        let back: ContractSpec = serde_json::from_value(v).unwrap();
+        assert_eq!(back, lite);
+    }
+
+    #[test]
+    fn test_contract_spec_load_reports_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1-1.json");
+        fs::write(&path, "{\n  \"phase\": 1,\n  \"plan\": \n}").unwrap();
+        let err = ContractSpec::load(&path).unwrap_err();
+        assert!(err.contains("line 4"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_stringify_scalars() {
+        let fm = parse("---\nwave: 1\nautonomous: true\ndeps: [1, 2]\n---\n").unwrap().unwrap();
+        let v = stringify_scalars(&Value::Object(fm.fields));
+        assert_eq!(v["wave"], "1");
+        assert_eq!(v["autonomous"], "true");
+        assert_eq!(v["deps"][1], "2");
+    }
+}
@@ -1,4 +1,6 @@
-use crate::commands::utils::split_frontmatter;
+use crate::commands::feature_flags::FeatureFlag;
+use crate::commands::frontmatter::{self, ContractSpec, Frontmatter, FrontmatterError};
+use crate::commands::layered_config::Config;
 use regex::Regex;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
@@ -7,66 +9,6 @@ use std::fs;
 use std::path::Path;
 use std::sync::OnceLock;
 
-/// Extract a simple scalar value from frontmatter: `key: value`
-fn fm_scalar(fm: &str, key: &str) -> Option<String> {
-    let prefix = format!("{}:", key);
-    for line in fm.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let val = trimmed[prefix.len()..].trim();
-            // Strip surrounding quotes
-            let val = val.trim_matches('"').trim_matches('\'');
-            if !val.is_empty() {
-                return Some(val.to_string());
-            }
-        }
-    }
-    None
-}
-
-/// Extract a YAML list from frontmatter. Handles both:
-///   key:
-///     - item1
-///     - item2
-/// and inline: key: [item1, item2]
-fn fm_list(fm: &str, key: &str) -> Vec<String> {
-    let prefix = format!("{}:", key);
-    let mut result = Vec::new();
-    let mut in_list = false;
-
-    for line in fm.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let rest = trimmed[prefix.len()..].trim();
-            // Inline array: [item1, item2]
-            if rest.starts_with('[') && rest.ends_with(']') {
-                let inner = &rest[1..rest.len() - 1];
-                for item in inner.split(',') {
-                    let item = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !item.is_empty() {
-                        result.push(item.to_string());
-                    }
-                }
-                return result;
-            }
-            in_list = true;
-            continue;
-        }
-        if in_list {
-            if let Some(stripped) = trimmed.strip_prefix("- ") {
-                let item = stripped.trim().trim_matches('"').trim_matches('\'').trim();
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() && !trimmed.starts_with('#') {
-                // Non-indented, non-empty line means end of list
-                break;
-            }
-        }
-    }
-    result
-}
-
 fn files_pattern_re() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| Regex::new(r"\*\*Files:\*\*\s+(.+)").unwrap())
@@ -103,7 +45,7 @@ fn extract_allowed_paths(body: &str) -> Vec<String> {
 }
 
 /// Count task headings: `## Task N` or `### Task N`
-fn count_tasks(body: &str) -> usize {
+pub fn count_tasks(body: &str) -> usize {
     task_heading_re().find_iter(body).count()
 }
 
@@ -114,122 +56,86 @@ fn task_ids(phase: u64, plan: u64, count: usize) -> Vec<String> {
         .collect()
 }
 
-/// Read config.json and return (v3_contract_lite, v2_hard_contracts) flags.
-fn read_config_flags(cwd: &Path) -> (bool, bool) {
-    let config_path = cwd.join(".yolo-planning").join("config.json");
-    if !config_path.exists() {
-        return (false, false);
-    }
-    let Ok(config_str) = fs::read_to_string(&config_path) else {
-        return (false, false);
-    };
-    let Ok(config) = serde_json::from_str::<Value>(&config_str) else {
-        return (false, false);
-    };
-    let v3 = config
-        .get("v3_contract_lite")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    let v2 = config
-        .get("v2_hard_contracts")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-    (v3, v2)
-}
-
-/// Read a numeric config value with default.
-fn config_number(cwd: &Path, key: &str, default: u64) -> u64 {
-    let config_path = cwd.join(".yolo-planning").join("config.json");
-    if !config_path.exists() {
-        return default;
-    }
-    let Ok(config_str) = fs::read_to_string(&config_path) else {
-        return default;
-    };
-    let Ok(config) = serde_json::from_str::<Value>(&config_str) else {
-        return default;
-    };
-    config
-        .get(key)
-        .and_then(|v| v.as_u64())
-        .unwrap_or(default)
+/// Read (v3_contract_lite, v2_hard_contracts) from the layered config.
+fn read_config_flags(config: &Config) -> (bool, bool) {
+    (config.flag(FeatureFlag::V3ContractLite), config.flag(FeatureFlag::V2HardContracts))
 }
 
 /// Core contract generation. Returns (contract_json, output_path) or None on skip.
 pub fn generate(plan_path: &Path, cwd: &Path) -> Option<(Value, String)> {
+    try_generate(plan_path, cwd).ok().flatten()
+}
+
+/// Like `generate`, but a plan whose frontmatter is not valid YAML is an error
+/// carrying its line and column instead of a silent skip.
+pub fn try_generate(plan_path: &Path, cwd: &Path) -> Result<Option<(Value, String)>, FrontmatterError> {
     if !plan_path.exists() {
-        return None;
+        return Ok(None);
     }
 
-    let (v3_lite, v2_hard) = read_config_flags(cwd);
+    let config = Config::load(cwd);
+    let (v3_lite, v2_hard) = read_config_flags(&config);
     if !v3_lite && !v2_hard {
-        return None;
+        return Ok(None);
     }
 
-    let content = fs::read_to_string(plan_path).ok()?;
-    let (fm, body) = split_frontmatter(&content);
+    let Ok(content) = fs::read_to_string(plan_path) else {
+        return Ok(None);
+    };
+    let Some(fm) = frontmatter::parse(&content)? else {
+        return Ok(None);
+    };
+    Ok(build(&fm, &config, v2_hard, cwd))
+}
+
+fn build(fm: &Frontmatter, config: &Config, v2_hard: bool, cwd: &Path) -> Option<(Value, String)> {
+    let plan_fm = fm.plan();
 
-    let phase: u64 = fm_scalar(&fm, "phase")?.parse().ok()?;
-    let plan: u64 = fm_scalar(&fm, "plan")?.parse().ok()?;
-    let title = fm_scalar(&fm, "title").unwrap_or_default();
-    let must_haves = fm_list(&fm, "must_haves");
-    let allowed_paths = extract_allowed_paths(&body);
-    let task_count = count_tasks(&body);
+    let phase: u64 = plan_fm.phase.as_deref()?.parse().ok()?;
+    let plan: u64 = plan_fm.plan.as_deref()?.parse().ok()?;
+    let title = plan_fm.title.clone().unwrap_or_default();
+    let allowed_paths = extract_allowed_paths(&fm.body);
+    let task_count = count_tasks(&fm.body);
 
     let contract_dir = cwd.join(".yolo-planning").join(".contracts");
     fs::create_dir_all(&contract_dir).ok()?;
     let contract_file = contract_dir.join(format!("{}-{}.json", phase, plan));
 
-    let contract = if v2_hard {
+    let mut spec = ContractSpec {
+        phase,
+        plan,
+        task_count,
+        must_haves: plan_fm.must_haves.clone(),
+        allowed_paths,
+        ..Default::default()
+    };
+
+    if v2_hard {
         // V2 Full: 11 fields + contract_hash
-        let forbidden_paths = fm_list(&fm, "forbidden_paths");
-        let depends_on: Vec<Value> = fm_list(&fm, "depends_on")
-            .iter()
-            .filter_map(|s| s.parse::<i64>().ok().map(Value::from))
-            .collect();
-        let verification_checks = fm_list(&fm, "verification_checks");
-        let tids = task_ids(phase, plan, task_count);
-
-        let token_budget = config_number(cwd, "max_token_budget", 50000);
-        let timeout = config_number(cwd, "task_timeout_seconds", 600);
-
-        let body_json = json!({
-            "phase_id": format!("phase-{}", phase),
-            "plan_id": format!("phase-{}-plan-{}", phase, plan),
-            "phase": phase,
-            "plan": plan,
-            "objective": title,
-            "task_ids": tids,
-            "task_count": task_count,
-            "allowed_paths": allowed_paths,
-            "forbidden_paths": forbidden_paths,
-            "depends_on": depends_on,
-            "must_haves": must_haves,
-            "verification_checks": verification_checks,
-            "max_token_budget": token_budget,
-            "timeout_seconds": timeout,
-        });
+        spec.phase_id = Some(format!("phase-{}", phase));
+        spec.plan_id = Some(format!("phase-{}-plan-{}", phase, plan));
+        spec.objective = Some(title);
+        spec.task_ids = Some(task_ids(phase, plan, task_count));
+        spec.forbidden_paths = Some(plan_fm.forbidden_paths.clone());
+        spec.depends_on = Some(
+            plan_fm
+                .depends_on
+                .iter()
+                .filter_map(|s| s.parse::<i64>().ok())
+                .collect(),
+        );
+        spec.verification_checks = Some(plan_fm.verification_checks.clone());
+        spec.max_token_budget = Some(config.settings.max_token_budget);
+        spec.timeout_seconds = Some(config.settings.task_timeout_seconds);
 
         // SHA-256 of serialized body (matching bash: echo "$BODY" | shasum -a 256)
-        let body_str = serde_json::to_string_pretty(&body_json).ok()?;
+        let body_str = serde_json::to_string_pretty(&spec.to_value()).ok()?;
         let hash_input = format!("{}\n", body_str);
         let mut hasher = Sha256::new();
         hasher.update(hash_input.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-
-        let mut contract = body_json;
-        contract["contract_hash"] = json!(hash);
-        contract
-    } else {
-        // V3 Lite: 5 fields
-        json!({
-            "phase": phase,
-            "plan": plan,
-            "task_count": task_count,
-            "must_haves": must_haves,
-            "allowed_paths": allowed_paths,
-        })
-    };
+        spec.contract_hash = Some(format!("{:x}", hasher.finalize()));
+    }
+    let contract = spec.to_value();
 
     let contract_str = serde_json::to_string_pretty(&contract).ok()?;
     fs::write(&contract_file, &contract_str).ok()?;
@@ -245,15 +151,26 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     }
 
     let plan_path = cwd.join(&args[2]);
-    match generate(&plan_path, cwd) {
-        Some((_contract, path)) => Ok((path, 0)),
-        None => Ok(("".to_string(), 0)),
+    match try_generate(&plan_path, cwd) {
+        Ok(Some((_contract, path))) => Ok((path, 0)),
+        Ok(None) => Ok(("".to_string(), 0)),
+        Err(e) => {
+            let resp = json!({
+                "ok": false,
+                "cmd": "generate-contract",
+                "errors": [format!("Invalid YAML frontmatter at {}", e)],
+                "line": e.line,
+                "column": e.column
+            });
+            Ok((resp.to_string(), 1))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
     use tempfile::TempDir;
 
     fn setup_config(dir: &Path, v3: bool, v2: bool) {
@@ -297,39 +214,40 @@ Final task.
         .to_string()
     }
 
+    fn sample_fm() -> frontmatter::Frontmatter {
+        frontmatter::parse(&sample_plan()).unwrap().unwrap()
+    }
+
     #[test]
-    fn test_split_frontmatter() {
-        let (fm, body) = split_frontmatter(&sample_plan());
-        assert!(fm.contains("phase: 2"));
-        assert!(fm.contains("plan: 11"));
-        assert!(body.contains("## Task 1"));
+    fn test_parse_sample_frontmatter() {
+        let fm = sample_fm();
+        assert_eq!(fm.scalar("phase"), Some("2".to_string()));
+        assert_eq!(fm.scalar("plan"), Some("11".to_string()));
+        assert!(fm.body.contains("## Task 1"));
     }
 
     #[test]
-    fn test_fm_scalar() {
-        let (fm, _) = split_frontmatter(&sample_plan());
-        assert_eq!(fm_scalar(&fm, "phase"), Some("2".to_string()));
-        assert_eq!(fm_scalar(&fm, "plan"), Some("11".to_string()));
+    fn test_plan_scalars() {
+        let plan = sample_fm().plan();
+        assert_eq!(plan.phase, Some("2".to_string()));
+        assert_eq!(plan.plan, Some("11".to_string()));
         assert_eq!(
-            fm_scalar(&fm, "title"),
+            plan.title,
             Some("Migrate contract scripts to native Rust".to_string())
         );
     }
 
     #[test]
-    fn test_fm_list() {
-        let (fm, _) = split_frontmatter(&sample_plan());
-        let mh = fm_list(&fm, "must_haves");
-        assert_eq!(mh.len(), 2);
-        assert_eq!(mh[0], "SHA-256 contract hash");
-
-        let deps = fm_list(&fm, "depends_on");
-        assert_eq!(deps, vec!["9", "10"]);
+    fn test_plan_lists() {
+        let plan = sample_fm().plan();
+        assert_eq!(plan.must_haves.len(), 2);
+        assert_eq!(plan.must_haves[0], "SHA-256 contract hash");
+        assert_eq!(plan.depends_on, vec!["9", "10"]);
     }
 
     #[test]
     fn test_extract_allowed_paths() {
-        let (_, body) = split_frontmatter(&sample_plan());
+        let body = sample_fm().body;
         let paths = extract_allowed_paths(&body);
         assert_eq!(paths.len(), 4);
         assert!(paths.contains(&"yolo-mcp-server/src/commands/generate_contract.rs".to_string()));
@@ -340,7 +258,7 @@ Final task.
 
     #[test]
     fn test_count_tasks() {
-        let (_, body) = split_frontmatter(&sample_plan());
+        let body = sample_fm().body;
         assert_eq!(count_tasks(&body), 3);
     }
 
@@ -467,4 +385,19 @@ Final task.
             c2["contract_hash"].as_str().unwrap()
         );
     }
+
+    #[test]
+    fn test_execute_reports_yaml_position() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path();
+        setup_config(cwd, true, false);
+        fs::write(cwd.join("PLAN.md"), "---\nphase: 1\nplan: 1\ntitle: Auth: add login\n---\n").unwrap();
+
+        let (out, code) = execute(&["yolo".to_string(), "generate-contract".to_string(), "PLAN.md".to_string()], cwd).unwrap();
+        assert_eq!(code, 1);
+        let resp: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(resp["line"], 4);
+        assert!(resp["errors"][0].as_str().unwrap().starts_with("Invalid YAML frontmatter at line 4"));
+        assert!(generate(&cwd.join("PLAN.md"), cwd).is_none());
+    }
 }
@@ -1,126 +1,577 @@
-use serde_json::Value;
+use chrono::DateTime;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
 
-/// Read event-log.jsonl, filter blockers/rejections for a phase, build markdown report.
-pub fn generate_incidents(phase: u64, cwd: &Path) -> Result<(String, i32), String> {
-    let planning_dir = cwd.join(".yolo-planning");
-    let events_file = planning_dir.join(".events/event-log.jsonl");
+use super::event_bus;
+
+/// Events that can appear on an incident timeline.
+const TIMELINE_EVENTS: &[&str] = &[
+    "task_blocked",
+    "task_completion_rejected",
+    "gate_failed",
+    "gate_passed",
+    "task_reassigned",
+    "task_completed_confirmed",
+];
+
+/// What an incident traces back to: a hard gate, a verification check command or a file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cause {
+    pub kind: &'static str,
+    pub value: String,
+}
 
-    if !events_file.exists() {
-        return Ok(("".to_string(), 0));
+impl Cause {
+    fn new(kind: &'static str, value: &str) -> Self {
+        Self { kind, value: value.trim().to_string() }
     }
 
-    // Find phase directory
-    let phases_dir = planning_dir.join("phases");
-    let padded = format!("{:02}", phase);
+    fn label(&self) -> String {
+        format!("{} `{}`", self.kind, self.value)
+    }
 
-    let phase_dir = find_phase_dir(&phases_dir, &padded, phase);
-    let phase_dir = match phase_dir {
-        Some(d) => d,
-        None => return Ok(("".to_string(), 0)),
+    /// The contract or config change that would stop this cause recurring.
+    fn suggestion(&self) -> String {
+        match self.kind {
+            "gate" => format!(
+                "Add a fixer for the `{}` gate to `auto_repair_fix_commands`, or cover it with a plan `verification_checks` entry so it fails before the gate does.",
+                self.value
+            ),
+            "check" => format!(
+                "Have Dev run `{}` before requesting completion, or add a fixer for it to `auto_repair_fix_commands`.",
+                self.value
+            ),
+            _ => format!(
+                "Declare `{}` on the task's `**Files:**` line when it is meant to change, or keep it out of commits if it is in `forbidden_paths`.",
+                self.value
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub ts: String,
+    pub kind: &'static str,
+    pub detail: String,
+}
+
+/// One task going wrong and (maybe) recovering: its blocked/rejected events,
+/// repair attempts and gate results, correlated by `correlation_id`, plan and task.
+#[derive(Debug, Clone)]
+pub struct Incident {
+    pub phase: u64,
+    pub plan: String,
+    pub task: String,
+    pub correlation_id: String,
+    pub opened: String,
+    pub resolved: Option<String>,
+    pub timeline: Vec<Step>,
+    pub causes: BTreeSet<Cause>,
+}
+
+impl Incident {
+    /// Seconds from the first failure to the resolving event.
+    pub fn resolution_secs(&self) -> Option<i64> {
+        let resolved = self.resolved.as_deref()?;
+        let (Ok(start), Ok(end)) = (DateTime::parse_from_rfc3339(&self.opened), DateTime::parse_from_rfc3339(resolved))
+        else {
+            return None;
+        };
+        Some((end - start).num_seconds()).filter(|s| *s >= 0)
+    }
+
+    fn status(&self) -> &'static str {
+        if self.resolved.is_some() { "resolved" } else { "open" }
+    }
+
+    fn has_gate(&self, gate: &str) -> bool {
+        self.causes.iter().any(|c| c.kind == "gate" && c.value == gate)
+    }
+}
+
+/// Phase number of an event; `log-event` writes a number, `auto-repair` a string.
+fn event_phase(entry: &Value) -> u64 {
+    match entry.get("phase") {
+        Some(Value::String(s)) => s.trim().parse().unwrap_or(0),
+        Some(v) => v.as_u64().unwrap_or(0),
+        None => 0,
+    }
+}
+
+/// Plan number of an event; `log-event` writes `3`, `auto-repair` may write `"03"`.
+fn event_plan(entry: &Value) -> String {
+    let plan = field_str(entry, "plan");
+    match plan.trim().parse::<u64>() {
+        Ok(n) => n.to_string(),
+        Err(_) => plan,
+    }
+}
+
+fn data_str<'a>(entry: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    let data = entry.get("data")?;
+    keys.iter().find_map(|k| data.get(*k).and_then(|v| v.as_str())).filter(|s| !s.is_empty())
+}
+
+fn task_of(entry: &Value) -> &str {
+    data_str(entry, &["task_id", "task"]).unwrap_or("")
+}
+
+/// Auto-repair blockers name the gate instead of giving a reason.
+fn reason_of(entry: &Value) -> String {
+    match (data_str(entry, &["reason", "evidence", "errors"]), data_str(entry, &["gate"])) {
+        (Some(reason), _) => reason.to_string(),
+        (None, Some(gate)) => format!("{} gate failed", gate),
+        (None, None) => "unspecified".to_string(),
+    }
+}
+
+fn field_str(entry: &Value, key: &str) -> String {
+    match entry.get(key) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Gates, check commands and files named by an event's data.
+fn causes_of(entry: &Value) -> Vec<Cause> {
+    let mut causes = Vec::new();
+    if let Some(gate) = data_str(entry, &["gate"]) {
+        causes.push(Cause::new("gate", gate));
+    }
+    if let Some(command) = data_str(entry, &["command", "check"]) {
+        causes.push(Cause::new("check", command));
+    }
+    if let Some(files) = entry.get("data").and_then(|d| d.get("files").or_else(|| d.get("file"))) {
+        match files {
+            Value::String(f) => causes.extend(f.split(',').filter(|f| !f.trim().is_empty()).map(|f| Cause::new("file", f))),
+            Value::Array(a) => causes.extend(a.iter().filter_map(|f| f.as_str()).map(|f| Cause::new("file", f))),
+            _ => {}
+        }
+    }
+    // Rejection errors from two-phase completion, joined with "; "
+    for error in data_str(entry, &["errors"]).unwrap_or("").split("; ") {
+        if let Some(check) = error.strip_prefix("verification check failed: ") {
+            causes.push(Cause::new("check", check));
+        } else if let Some((_, check)) = error.strip_prefix("verification check ").and_then(|r| r.split_once(": ")) {
+            causes.push(Cause::new("check", check));
+        } else if let Some(file) = error.strip_suffix(" outside allowed_paths") {
+            causes.push(Cause::new("file", file));
+        }
+    }
+    // Evidence from the protected_file gate
+    if let Some(files) = data_str(entry, &["evidence"]).and_then(|e| e.strip_prefix("forbidden files staged: ")) {
+        causes.extend(files.split_whitespace().map(|f| Cause::new("file", f)));
+    }
+    causes
+}
+
+/// Timeline steps for one event; a blocker expands into its remediation history.
+fn steps_of(entry: &Value) -> Vec<Step> {
+    let ts = field_str(entry, "ts");
+    let kind = entry.get("event").and_then(|v| v.as_str()).unwrap_or("");
+    let detail = match kind {
+        "task_blocked" | "task_completion_rejected" => reason_of(entry),
+        "gate_failed" | "gate_passed" => {
+            let gate = data_str(entry, &["gate"]).unwrap_or("gate");
+            match data_str(entry, &["evidence"]) {
+                Some(evidence) => format!("{}: {}", gate, evidence),
+                None => gate.to_string(),
+            }
+        }
+        "task_reassigned" => data_str(entry, &["to", "owner", "reason"]).unwrap_or("").to_string(),
+        _ => data_str(entry, &["evidence"]).unwrap_or("").to_string(),
+    };
+    let label = match kind {
+        "task_blocked" => "blocked",
+        "task_completion_rejected" => "rejected",
+        "task_reassigned" => "reassigned",
+        "task_completed_confirmed" => "resolved",
+        "gate_failed" => "gate_failed",
+        _ => "gate_passed",
     };
 
-    // Read and parse event log
-    let content = fs::read_to_string(&events_file)
-        .map_err(|e| format!("Failed to read event log: {}", e))?;
+    let mut steps = Vec::new();
+    let history = entry.get("data").and_then(|d| d.get("remediation_history")).and_then(|h| h.as_array());
+    for attempt in history.into_iter().flatten() {
+        let text = |k: &str| attempt.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        steps.push(Step {
+            ts: ts.clone(),
+            kind: "repair",
+            detail: format!(
+                "attempt {}: {} {} (gate {})",
+                attempt.get("attempt").and_then(|v| v.as_u64()).unwrap_or(0),
+                text("strategy"),
+                text("outcome"),
+                text("gate_result"),
+            ),
+        });
+    }
+    steps.push(Step { ts, kind: label, detail });
+    steps
+}
 
-    let mut blocked: Vec<Value> = Vec::new();
-    let mut rejected: Vec<Value> = Vec::new();
+type TrackKey = (u64, String, String, String);
+
+/// The track an event joins: same phase, plan, correlation ID and task. Gate
+/// and auto-repair events carry bare task numbers, so the plan keeps task 1 of
+/// one plan apart from task 1 of another. Some producers log without a
+/// correlation ID or plan, so an empty value on either side matches any track
+/// for the phase and task, preferring the one with the latest open incident.
+fn track_key(
+    tracks: &HashMap<TrackKey, TaskTrack>,
+    phase: u64,
+    plan: &str,
+    correlation_id: &str,
+    task: &str,
+) -> TrackKey {
+    let exact = (phase, plan.to_string(), correlation_id.to_string(), task.to_string());
+    if tracks.contains_key(&exact) {
+        return exact;
+    }
+    let compatible = |a: &str, b: &str| a == b || a.is_empty() || b.is_empty();
+    tracks
+        .iter()
+        .filter(|((ph, pl, c, t), _)| {
+            *ph == phase && t == task && compatible(pl, plan) && compatible(c, correlation_id)
+        })
+        .max_by_key(|((_, pl, c, _), track)| (track.open, pl.clone(), c.clone()))
+        .map(|(key, _)| key.clone())
+        .unwrap_or(exact)
+}
 
-    for line in content.lines() {
-        let line = line.trim();
-        if line.is_empty() {
+#[derive(Default)]
+struct TaskTrack {
+    open: Option<usize>,
+    /// Gate failures seen before the task was blocked or rejected.
+    pending: Vec<Value>,
+}
+
+/// Correlate timeline events across all phases into incidents, oldest first.
+/// An incident opens on `task_blocked` or `task_completion_rejected` and is
+/// resolved by a confirmed completion or by its failing gate passing.
+pub fn build_incidents(events: &[Value]) -> Vec<Incident> {
+    let mut incidents: Vec<Incident> = Vec::new();
+    let mut tracks: HashMap<TrackKey, TaskTrack> = HashMap::new();
+
+    for entry in events {
+        let Some(kind) = entry.get("event").and_then(|v| v.as_str()) else { continue };
+        if !TIMELINE_EVENTS.contains(&kind) {
+            continue;
+        }
+        let phase = event_phase(entry);
+        let task = task_of(entry).to_string();
+        let opens = kind == "task_blocked" || kind == "task_completion_rejected";
+
+        // Blockers without a task cannot be correlated; each stands alone.
+        if task.is_empty() {
+            if opens {
+                incidents.push(open_incident(entry, phase, "unknown", &[]));
+            }
             continue;
         }
-        if let Ok(entry) = serde_json::from_str::<Value>(line) {
-            let event_phase = entry.get("phase").and_then(|v| v.as_u64()).unwrap_or(0);
-            if event_phase != phase {
-                continue;
+
+        let correlation_id = field_str(entry, "correlation_id");
+        let key = track_key(&tracks, phase, &event_plan(entry), &correlation_id, &task);
+        let track = tracks.entry(key).or_default();
+        match (kind, track.open) {
+            (_, None) if opens => {
+                let pending = std::mem::take(&mut track.pending);
+                track.open = Some(incidents.len());
+                incidents.push(open_incident(entry, phase, &task, &pending));
             }
-            match entry.get("event").and_then(|v| v.as_str()) {
-                Some("task_blocked") => blocked.push(entry),
-                Some("task_completion_rejected") => rejected.push(entry),
-                _ => {}
+            ("gate_failed", None) => track.pending.push(entry.clone()),
+            ("gate_passed" | "task_completed_confirmed" | "task_reassigned", None) => track.pending.clear(),
+            (_, Some(i)) => {
+                let incident = &mut incidents[i];
+                if incident.correlation_id.is_empty() {
+                    incident.correlation_id = correlation_id;
+                }
+                incident.timeline.extend(steps_of(entry));
+                incident.causes.extend(causes_of(entry));
+                let resolves = kind == "task_completed_confirmed"
+                    || (kind == "gate_passed" && data_str(entry, &["gate"]).is_some_and(|g| incident.has_gate(g)));
+                if resolves {
+                    incident.resolved = Some(field_str(entry, "ts"));
+                    track.open = None;
+                }
             }
+            _ => {}
         }
     }
+    incidents
+}
 
-    let total = blocked.len() + rejected.len();
-    if total == 0 {
-        return Ok(("".to_string(), 0));
+fn open_incident(entry: &Value, phase: u64, task: &str, pending: &[Value]) -> Incident {
+    let first = pending.first().unwrap_or(entry);
+    let mut incident = Incident {
+        phase,
+        plan: field_str(entry, "plan"),
+        task: task.to_string(),
+        correlation_id: field_str(entry, "correlation_id"),
+        opened: field_str(first, "ts"),
+        resolved: None,
+        timeline: Vec::new(),
+        causes: BTreeSet::new(),
+    };
+    for e in pending.iter().chain(std::iter::once(entry)) {
+        incident.timeline.extend(steps_of(e));
+        incident.causes.extend(causes_of(e));
     }
+    incident
+}
 
-    // Build markdown report
-    let mut md = String::new();
-    md.push_str(&format!("# Phase {} Incidents\n\n", phase));
-    md.push_str(&format!(
-        "Auto-generated from event log. Total: {} incidents.\n\n",
-        total
-    ));
-
-    // Blockers section
-    md.push_str(&format!("## Blockers ({})\n\n", blocked.len()));
-    if blocked.is_empty() {
-        md.push_str("No blockers recorded.\n");
-    } else {
-        md.push_str("| Time | Task | Reason | Next Action |\n");
-        md.push_str("|------|------|--------|-------------|\n");
-        for entry in &blocked {
-            let ts = entry.get("ts").and_then(|v| v.as_str()).unwrap_or("");
-            let data = entry.get("data");
-            let task_id = data
-                .and_then(|d| d.get("task_id"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown");
-            let reason = data
-                .and_then(|d| {
-                    d.get("reason")
-                        .or_else(|| d.get("evidence"))
-                })
-                .and_then(|v| v.as_str())
-                .unwrap_or("unspecified");
-            let next_action = data
-                .and_then(|d| d.get("next_action"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("none");
-            md.push_str(&format!(
-                "| {} | {} | {} | {} |\n",
-                ts, task_id, reason, next_action
-            ));
+/// `45s`, `12m`, `1h 05m`, `2d 03h`.
+fn fmt_duration(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h {:02}m", s / 3600, (s % 3600) / 60),
+        s => format!("{}d {:02}h", s / 86400, (s % 86400) / 3600),
+    }
+}
+
+fn median(mut values: Vec<i64>) -> Option<i64> {
+    values.sort_unstable();
+    let n = values.len();
+    match n {
+        0 => None,
+        _ if n % 2 == 1 => Some(values[n / 2]),
+        _ => Some((values[n / 2 - 1] + values[n / 2]) / 2),
+    }
+}
+
+/// A phase's incidents, their causes, and which of those causes also hit other phases.
+struct Report<'a> {
+    phase: u64,
+    incidents: Vec<&'a Incident>,
+    causes: BTreeMap<&'a Cause, Vec<usize>>,
+    recurring: Vec<(&'a Cause, BTreeSet<u64>)>,
+}
+
+impl<'a> Report<'a> {
+    fn build(phase: u64, all: &'a [Incident]) -> Self {
+        let incidents: Vec<&Incident> = all.iter().filter(|i| i.phase == phase).collect();
+        let mut causes: BTreeMap<&Cause, Vec<usize>> = BTreeMap::new();
+        for (n, incident) in incidents.iter().enumerate() {
+            for cause in &incident.causes {
+                causes.entry(cause).or_default().push(n);
+            }
+        }
+        let mut phases_by_cause: BTreeMap<&Cause, BTreeSet<u64>> = BTreeMap::new();
+        for incident in all {
+            for cause in &incident.causes {
+                phases_by_cause.entry(cause).or_default().insert(incident.phase);
+            }
         }
+        let recurring = causes
+            .keys()
+            .filter_map(|c| phases_by_cause.remove(c).filter(|p| p.len() > 1).map(|p| (*c, p)))
+            .collect();
+        Self { phase, incidents, causes, recurring }
+    }
+
+    fn resolved(&self) -> usize {
+        self.incidents.iter().filter(|i| i.resolved.is_some()).count()
+    }
+
+    fn median_resolution(&self, members: Option<&[usize]>) -> Option<i64> {
+        let secs = match members {
+            Some(m) => m.iter().filter_map(|n| self.incidents[*n].resolution_secs()).collect(),
+            None => self.incidents.iter().filter_map(|i| i.resolution_secs()).collect(),
+        };
+        median(secs)
     }
 
-    // Rejections section
-    md.push_str(&format!("\n## Rejections ({})\n\n", rejected.len()));
-    if rejected.is_empty() {
-        md.push_str("No rejections recorded.\n");
-    } else {
-        md.push_str("| Time | Task | Reason |\n");
-        md.push_str("|------|------|--------|\n");
-        for entry in &rejected {
-            let ts = entry.get("ts").and_then(|v| v.as_str()).unwrap_or("");
-            let data = entry.get("data");
-            let task_id = data
-                .and_then(|d| d.get("task_id"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown");
-            let reason = data
-                .and_then(|d| {
-                    d.get("reason")
-                        .or_else(|| d.get("evidence"))
+    fn to_json(&self) -> Value {
+        let incidents: Vec<Value> = self
+            .incidents
+            .iter()
+            .enumerate()
+            .map(|(n, i)| {
+                json!({
+                    "id": format!("I{}", n + 1),
+                    "task": i.task,
+                    "plan": i.plan,
+                    "correlation_id": i.correlation_id,
+                    "status": i.status(),
+                    "opened": i.opened,
+                    "resolved": i.resolved,
+                    "resolution_secs": i.resolution_secs(),
+                    "causes": i.causes.iter().map(|c| json!({"kind": c.kind, "value": c.value})).collect::<Vec<_>>(),
+                    "timeline": i.timeline.iter().map(|s| json!({"ts": s.ts, "step": s.kind, "detail": s.detail})).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let causes: Vec<Value> = self
+            .causes
+            .iter()
+            .map(|(c, members)| {
+                json!({
+                    "kind": c.kind,
+                    "value": c.value,
+                    "incidents": members.iter().map(|n| format!("I{}", n + 1)).collect::<Vec<_>>(),
+                    "median_resolution_secs": self.median_resolution(Some(members)),
                 })
-                .and_then(|v| v.as_str())
-                .unwrap_or("unspecified");
-            md.push_str(&format!("| {} | {} | {} |\n", ts, task_id, reason));
+            })
+            .collect();
+        let recurring: Vec<Value> = self
+            .recurring
+            .iter()
+            .map(|(c, phases)| json!({"kind": c.kind, "value": c.value, "phases": phases, "suggestion": c.suggestion()}))
+            .collect();
+        json!({
+            "phase": self.phase,
+            "total": self.incidents.len(),
+            "resolved": self.resolved(),
+            "open": self.incidents.len() - self.resolved(),
+            "median_resolution_secs": self.median_resolution(None),
+            "incidents": incidents,
+            "causes": causes,
+            "recurring": recurring,
+        })
+    }
+
+    fn to_markdown(&self, events: &[&Value]) -> String {
+        let mut md = String::new();
+        md.push_str(&format!("# Phase {} Incidents\n\n", self.phase));
+        md.push_str(&format!(
+            "Auto-generated from event log. Total: {} incidents ({} resolved, {} open).",
+            self.incidents.len(),
+            self.resolved(),
+            self.incidents.len() - self.resolved()
+        ));
+        if let Some(secs) = self.median_resolution(None) {
+            md.push_str(&format!(" Median time to resolution: {}.", fmt_duration(secs)));
+        }
+        md.push_str("\n\n");
+
+        let blocked: Vec<&Value> = events.iter().copied().filter(|e| e["event"] == "task_blocked").collect();
+        let rejected: Vec<&Value> = events.iter().copied().filter(|e| e["event"] == "task_completion_rejected").collect();
+
+        // Blockers section
+        md.push_str(&format!("## Blockers ({})\n\n", blocked.len()));
+        if blocked.is_empty() {
+            md.push_str("No blockers recorded.\n");
+        } else {
+            md.push_str("| Time | Task | Reason | Next Action |\n");
+            md.push_str("|------|------|--------|-------------|\n");
+            for entry in &blocked {
+                let task = Some(task_of(entry)).filter(|t| !t.is_empty()).unwrap_or("unknown");
+                let next_action = data_str(entry, &["next_action"]).unwrap_or("none");
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    field_str(entry, "ts"), task, reason_of(entry), next_action
+                ));
+            }
         }
+
+        // Rejections section
+        md.push_str(&format!("\n## Rejections ({})\n\n", rejected.len()));
+        if rejected.is_empty() {
+            md.push_str("No rejections recorded.\n");
+        } else {
+            md.push_str("| Time | Task | Reason |\n");
+            md.push_str("|------|------|--------|\n");
+            for entry in &rejected {
+                let task = Some(task_of(entry)).filter(|t| !t.is_empty()).unwrap_or("unknown");
+                md.push_str(&format!("| {} | {} | {} |\n", field_str(entry, "ts"), task, reason_of(entry)));
+            }
+        }
+
+        // Timelines section
+        md.push_str("\n## Timelines\n");
+        for (n, incident) in self.incidents.iter().enumerate() {
+            let outcome = match incident.resolution_secs() {
+                Some(secs) => format!("resolved in {}", fmt_duration(secs)),
+                None => incident.status().to_string(),
+            };
+            md.push_str(&format!("\n### I{} — {} ({})\n\n", n + 1, incident.task, outcome));
+            md.push_str("| Time | Step | Detail |\n");
+            md.push_str("|------|------|--------|\n");
+            for step in &incident.timeline {
+                md.push_str(&format!("| {} | {} | {} |\n", step.ts, step.kind, step.detail));
+            }
+        }
+
+        // Root causes section
+        md.push_str(&format!("\n## Root Causes ({})\n\n", self.causes.len()));
+        if self.causes.is_empty() {
+            md.push_str("No gate, check or file recorded on these incidents.\n");
+        } else {
+            md.push_str("| Cause | Incidents | Median Resolution |\n");
+            md.push_str("|-------|-----------|-------------------|\n");
+            for (cause, members) in &self.causes {
+                let ids: Vec<String> = members.iter().map(|n| format!("I{}", n + 1)).collect();
+                let ttr = self.median_resolution(Some(members)).map(fmt_duration).unwrap_or_else(|| "-".into());
+                md.push_str(&format!("| {} | {} | {} |\n", cause.label(), ids.join(", "), ttr));
+            }
+        }
+
+        if !self.recurring.is_empty() {
+            md.push_str(&format!("\n## Recurring Causes ({})\n\n", self.recurring.len()));
+            md.push_str("| Cause | Phases | Suggested Change |\n");
+            md.push_str("|-------|--------|------------------|\n");
+            for (cause, phases) in &self.recurring {
+                let phases: Vec<String> = phases.iter().map(u64::to_string).collect();
+                md.push_str(&format!("| {} | {} | {} |\n", cause.label(), phases.join(", "), cause.suggestion()));
+            }
+        }
+        md
+    }
+}
+
+/// Read the event log, correlate the phase's blockers and rejections into
+/// incidents, and write `{NN}-INCIDENTS.md` plus its `{NN}-INCIDENTS.json` twin.
+/// Returns the markdown path (or the JSON report with `as_json`), empty when
+/// there is nothing to report.
+pub fn generate_incidents(phase: u64, cwd: &Path) -> Result<(String, i32), String> {
+    generate(phase, false, cwd)
+}
+
+fn generate(phase: u64, as_json: bool, cwd: &Path) -> Result<(String, i32), String> {
+    let planning_dir = cwd.join(".yolo-planning");
+    let events_file = event_bus::log_path(&planning_dir);
+
+    if !events_file.exists() {
+        return Ok(("".to_string(), 0));
+    }
+
+    // Find phase directory
+    let phases_dir = planning_dir.join("phases");
+    let padded = format!("{:02}", phase);
+
+    let phase_dir = find_phase_dir(&phases_dir, &padded, phase);
+    let phase_dir = match phase_dir {
+        Some(d) => d,
+        None => return Ok(("".to_string(), 0)),
+    };
+
+    let events = event_bus::load_events(&planning_dir);
+    let incidents = build_incidents(&events);
+    let report = Report::build(phase, &incidents);
+    if report.incidents.is_empty() {
+        return Ok(("".to_string(), 0));
     }
 
-    // Write incidents file
+    let phase_events: Vec<&Value> = events.iter().filter(|e| event_phase(e) == phase).collect();
+    let md = report.to_markdown(&phase_events);
+    let report_json = report.to_json();
+
+    // Write incidents files
     let incidents_file = phase_dir.join(format!("{}-INCIDENTS.md", padded));
     fs::write(&incidents_file, &md)
         .map_err(|e| format!("Failed to write incidents file: {}", e))?;
+    let json_file = phase_dir.join(format!("{}-INCIDENTS.json", padded));
+    fs::write(&json_file, serde_json::to_string_pretty(&report_json).unwrap_or_default() + "\n")
+        .map_err(|e| format!("Failed to write incidents file: {}", e))?;
 
+    if as_json {
+        return Ok((report_json.to_string(), 0));
+    }
     Ok((incidents_file.to_string_lossy().to_string(), 0))
 }
 
@@ -143,22 +594,24 @@ fn find_phase_dir(phases_dir: &Path, padded: &str, phase: u64) -> Option<std::pa
     None
 }
 
-/// CLI entry point: `yolo incidents <phase>`
+/// CLI entry point: `yolo incidents <phase> [--json]`
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     if args.len() < 3 {
-        return Err("Usage: yolo incidents <phase-number>".to_string());
+        return Err("Usage: yolo incidents <phase-number> [--json]".to_string());
     }
 
     let phase: u64 = args[2]
         .parse()
         .map_err(|_| format!("Invalid phase number: {}", args[2]))?;
+    let as_json = args[3..].iter().any(|a| a == "--json");
 
-    generate_incidents(phase, cwd)
+    generate(phase, as_json, cwd)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::{auto_repair, log_event};
     use tempfile::TempDir;
 
     fn setup_test_env(phase: u64) -> TempDir {
@@ -348,4 +801,130 @@ mod tests {
         let args: Vec<String> = vec!["yolo".into(), "incidents".into(), "abc".into()];
         assert!(execute(&args, dir.path()).is_err());
     }
+
+    #[test]
+    fn test_timeline_resolution_and_causes() {
+        let events = vec![
+            serde_json::json!({"event": "gate_failed", "phase": 1, "plan": 1, "correlation_id": "c1", "ts": "2026-02-20T10:00:00Z",
+                "data": {"gate": "protected_file", "task": "1-1-T1", "evidence": "forbidden files staged: .env"}}),
+            serde_json::json!({"event": "task_blocked", "phase": "1", "plan": "1", "correlation_id": "c1", "ts": "2026-02-20T10:02:00Z",
+                "data": {"task": "1-1-T1", "gate": "protected_file", "next_action": "escalate",
+                    "remediation_history": [{"attempt": 1, "strategy": "unstage", "outcome": "applied", "gate_result": "fail"}]}}),
+            serde_json::json!({"event": "gate_passed", "phase": 1, "correlation_id": "c1", "ts": "2026-02-20T10:12:00Z",
+                "data": {"gate": "protected_file", "task": "1-1-T1"}}),
+            serde_json::json!({"event": "task_completion_rejected", "phase": 1, "correlation_id": "c1", "ts": "2026-02-20T11:00:00Z",
+                "data": {"task_id": "1-1-T2", "errors": "verification check failed: cargo test; src/x.rs outside allowed_paths"}}),
+            serde_json::json!({"event": "task_completion_rejected", "phase": 3, "correlation_id": "c3", "ts": "2026-02-22T09:00:00Z",
+                "data": {"task_id": "3-1-T1", "errors": "verification check failed: cargo test"}}),
+        ];
+        let incidents = build_incidents(&events);
+        assert_eq!(incidents.len(), 3);
+
+        let first = &incidents[0];
+        let steps: Vec<&str> = first.timeline.iter().map(|s| s.kind).collect();
+        assert_eq!(steps, vec!["gate_failed", "repair", "blocked", "gate_passed"]);
+        assert_eq!(first.opened, "2026-02-20T10:00:00Z", "the incident opens at the first gate failure");
+        assert_eq!(first.resolution_secs(), Some(720));
+        assert!(first.causes.contains(&Cause::new("file", ".env")));
+
+        let second = &incidents[1];
+        assert!(second.resolved.is_none());
+        assert!(second.causes.contains(&Cause::new("check", "cargo test")));
+        assert!(second.causes.contains(&Cause::new("file", "src/x.rs")));
+
+        let report = Report::build(1, &incidents);
+        assert_eq!(report.incidents.len(), 2);
+        assert_eq!(report.recurring.len(), 1);
+        assert_eq!(report.recurring[0].0, &Cause::new("check", "cargo test"));
+        assert_eq!(report.recurring[0].1.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_same_task_number_in_two_plans_stays_apart() {
+        let blocked = |plan: &str, ts: &str| {
+            serde_json::json!({"event": "task_blocked", "phase": "1", "plan": plan, "correlation_id": "c1", "ts": ts,
+                "data": {"task": "1", "gate": "required_checks", "next_action": "escalate"}})
+        };
+        let events = vec![
+            blocked("01", "2026-02-20T10:00:00Z"),
+            blocked("02", "2026-02-20T10:05:00Z"),
+            serde_json::json!({"event": "gate_passed", "phase": 1, "plan": 2, "correlation_id": "c1", "ts": "2026-02-20T10:20:00Z",
+                "data": {"gate": "required_checks", "task": "1"}}),
+        ];
+        let incidents = build_incidents(&events);
+        assert_eq!(incidents.len(), 2);
+        assert_eq!(incidents[0].plan, "01");
+        assert!(incidents[0].resolved.is_none(), "plan 02's gate pass does not resolve plan 01's incident");
+        assert_eq!(incidents[1].plan, "02");
+        assert_eq!(incidents[1].resolution_secs(), Some(900));
+    }
+
+    #[test]
+    fn test_report_sections_and_json_twin() {
+        let dir = setup_test_env(1);
+        write_events(
+            dir.path(),
+            &[
+                serde_json::json!({"event": "task_blocked", "phase": 1, "ts": "2026-02-20T10:00:00Z",
+                    "data": {"task_id": "t1", "reason": "gate failed", "gate": "required_checks"}}),
+                serde_json::json!({"event": "task_completed_confirmed", "phase": 1, "ts": "2026-02-20T11:05:00Z",
+                    "data": {"task_id": "t1"}}),
+            ],
+        );
+
+        let args: Vec<String> = vec!["yolo".into(), "incidents".into(), "1".into(), "--json".into()];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let report: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["resolved"], 1);
+        assert_eq!(report["incidents"][0]["resolution_secs"], 3900);
+        assert_eq!(report["causes"][0]["value"], "required_checks");
+
+        let phase_dir = dir.path().join(".yolo-planning/phases/01-test");
+        let content = fs::read_to_string(phase_dir.join("01-INCIDENTS.md")).unwrap();
+        assert!(content.contains("Total: 1 incidents (1 resolved, 0 open). Median time to resolution: 1h 05m."));
+        assert!(content.contains("### I1 — t1 (resolved in 1h 05m)"));
+        assert!(content.contains("| gate `required_checks` | I1 | 1h 05m |"));
+        assert!(!content.contains("## Recurring Causes"));
+        assert!(phase_dir.join("01-INCIDENTS.json").exists());
+    }
+
+    #[test]
+    fn test_correlates_real_producer_events() {
+        let dir = setup_test_env(1);
+        let planning = dir.path().join(".yolo-planning");
+        fs::write(planning.join("config.json"), r#"{"v3_event_log": true}"#).unwrap();
+        fs::write(planning.join(".execution-state.json"), r#"{"correlation_id": "run-7"}"#).unwrap();
+        let gate = |event: &str, evidence: &str| {
+            let data = vec![
+                ("gate".to_string(), "protected_file".to_string()),
+                ("task".to_string(), "1-1-T1".to_string()),
+                ("evidence".to_string(), evidence.to_string()),
+            ];
+            log_event::log(event, "1", Some("1"), &data, dir.path()).unwrap();
+        };
+
+        // hard-gate fails, auto-repair gives up and blocks, the gate later passes.
+        gate("gate_failed", "forbidden files staged: .env");
+        let history = [serde_json::json!({"attempt": 1, "strategy": "unstage", "outcome": "applied", "gate_result": "fail"})];
+        auto_repair::log_blocker_event(&planning, "1", "1", "1-1-T1", "protected_file", "escalate", 1, &history);
+        gate("gate_passed", "no forbidden files staged");
+
+        let events = event_bus::load_events(&planning);
+        assert!(!events[1]["correlation_id"].as_str().unwrap().is_empty(), "the blocker carries the run's correlation ID");
+        let incidents = build_incidents(&events);
+        assert_eq!(incidents.len(), 1);
+        let steps: Vec<&str> = incidents[0].timeline.iter().map(|s| s.kind).collect();
+        assert_eq!(steps, vec!["gate_failed", "repair", "blocked", "gate_passed"]);
+        assert!(incidents[0].resolved.is_some());
+
+        // A blocker logged before it carried a correlation ID still joins the run's gate events.
+        let mut legacy = events.clone();
+        legacy[1].as_object_mut().unwrap().remove("correlation_id");
+        let incidents = build_incidents(&legacy);
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].timeline.len(), 4);
+        assert_eq!(incidents[0].correlation_id, events[2]["correlation_id"].as_str().unwrap());
+        assert!(incidents[0].resolved.is_some());
+    }
 }
@@ -6,7 +6,7 @@ use sha2::{Sha256, Digest};
 use std::process::Command;
 use regex::Regex;
 use std::sync::OnceLock;
-use super::{log_event, collect_metrics};
+use super::{event_bus, log_event, collect_metrics};
 
 #[derive(serde::Serialize)]
 struct GateResult {
@@ -418,28 +418,25 @@ pub fn execute_gate(args: &[String], cwd: &Path) -> Result<(String, i32), String
                 return Ok(emit_res("pass", "no forbidden commands defined"));
             }
 
-            let event_log = planning_dir.join(".event-log.jsonl");
+            let event_log = event_bus::log_path(&planning_dir);
             if !event_log.exists() {
                 return Ok(emit_res("pass", "no event log, fail-open"));
             }
 
             let log_content = fs::read_to_string(&event_log).unwrap_or_default();
-            let mut violations = Vec::new();
-
-            for line in log_content.lines().rev().take(5) {
-                if line.contains("\"bash_guard_block\"") {
-                    violations.push(line.to_string());
-                }
-            }
-
-            if !violations.is_empty() {
-                let first_violation = &violations[0];
-                let mut preview = "unknown".to_string();
-                if let Ok(v) = serde_json::from_str::<Value>(first_violation) {
-                    if let Some(p) = v.get("command_preview").and_then(|v| v.as_str()) {
-                        preview = p.to_string();
-                    }
-                }
+            let violation = log_content
+                .lines()
+                .rev()
+                .take(5)
+                .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+                .find(|v| v.get("event").and_then(|e| e.as_str()) == Some("bash_guard_block"));
+
+            if let Some(v) = violation {
+                let preview = v
+                    .pointer("/data/command_preview")
+                    .or_else(|| v.get("command_preview"))
+                    .and_then(|p| p.as_str())
+                    .unwrap_or("unknown");
                 Ok(emit_res("fail", &format!("destructive command attempted: {}", preview)))
             } else {
                 Ok(emit_res("pass", "no forbidden command violations"))
@@ -550,6 +547,28 @@ mod tests {
         assert_eq!(res["result"], "pass");
     }
 
+    #[test]
+    fn test_forbidden_commands_reads_canonical_event_log() {
+        let (dir, planning_dir) = setup_test_env();
+        let contract_path = dir.path().join("contract.json");
+        fs::write(&contract_path, json!({"forbidden_commands": ["rm -rf"]}).to_string()).unwrap();
+        event_bus::publish(&planning_dir, &json!({
+            "event": "bash_guard_block",
+            "data": {"command_preview": "rm -rf /"}
+        }));
+
+        let args = vec![
+            "yolo".into(), "hard-gate".into(), "forbidden_commands".into(),
+            "1".into(), "1".into(), "1".into(), contract_path.to_string_lossy().to_string()
+        ];
+        let (out, code) = execute_gate(&args, dir.path()).unwrap();
+
+        assert_eq!(code, 2);
+        let res: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(res["result"], "fail");
+        assert_eq!(res["evidence"], "destructive command attempted: rm -rf /");
+    }
+
     #[test]
     fn test_artifact_persistence_missing_summary() {
         let (dir, planning_dir) = setup_test_env();
@@ -0,0 +1,341 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::atomic_io;
+
+/// Directory (under `.yolo-planning/`) holding committed, not yet applied journals.
+pub const JOURNAL_DIR: &str = ".journal";
+
+/// A group of file writes applied all-or-nothing across crashes.
+///
+/// Writes are staged in memory. `commit` first persists every staged write to a
+/// journal file (temp-file + fsync + rename, so the journal either exists whole
+/// or not at all), then applies each write with `atomic_write_with_checksum` and
+/// finally removes the journal. A crash after the journal lands is rolled
+/// forward by `recover` on the next startup; a crash before it leaves the
+/// files untouched. Each entry records the hash of the file it replaces, so a
+/// replay never overwrites a file that changed after the crash.
+pub struct Transaction {
+    planning_dir: PathBuf,
+    writes: BTreeMap<PathBuf, String>,
+}
+
+/// Outcome of replaying leftover journals.
+#[derive(Debug, Default)]
+pub struct Recovery {
+    /// Journals whose writes were re-applied.
+    pub rolled_forward: Vec<String>,
+    /// Unreadable or checksum-failing journals that were removed unapplied.
+    pub discarded: Vec<String>,
+    /// Files rewritten during roll-forward.
+    pub files: Vec<String>,
+    /// Files left alone because they changed after the journal was written.
+    pub skipped: Vec<String>,
+}
+
+impl Recovery {
+    pub fn is_empty(&self) -> bool {
+        self.rolled_forward.is_empty() && self.discarded.is_empty()
+    }
+}
+
+impl Transaction {
+    pub fn begin(planning_dir: &Path) -> Self {
+        Transaction { planning_dir: planning_dir.to_path_buf(), writes: BTreeMap::new() }
+    }
+
+    /// Stage `content` for `path`, replacing any earlier staged write to it.
+    pub fn stage(&mut self, path: &Path, content: impl Into<String>) {
+        self.writes.insert(path.to_path_buf(), content.into());
+    }
+
+    /// Current content of `path` as this transaction would leave it: the staged
+    /// write if there is one, otherwise what is on disk.
+    pub fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        match self.writes.get(path) {
+            Some(content) => Ok(content.clone()),
+            None => fs::read_to_string(path),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Journal, apply and clear the staged writes. Returns the written paths.
+    pub fn commit(self) -> io::Result<Vec<PathBuf>> {
+        if self.writes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let journal_path = self.write_journal()?;
+        self.apply(&journal_path)
+    }
+
+    /// Persist every staged write to a new journal and return its path.
+    fn write_journal(&self) -> io::Result<PathBuf> {
+        let journal_dir = self.planning_dir.join(JOURNAL_DIR);
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+        let id = format!("{:020}-{}", nanos, std::process::id());
+        let journal_path = journal_dir.join(format!("{}.json", id));
+
+        let entries: Vec<Value> = self
+            .writes
+            .iter()
+            .map(|(path, content)| {
+                json!({
+                    "path": relative_to(path, &self.planning_dir),
+                    "sha256": atomic_io::sha256_hex(content.as_bytes()),
+                    "before": file_hash(path),
+                    "content": content,
+                })
+            })
+            .collect();
+        let journal = json!({"id": id, "entries": entries});
+        atomic_io::atomic_write(&journal_path, journal.to_string().as_bytes())?;
+        Ok(journal_path)
+    }
+
+    /// Apply the staged writes and remove their journal. A concurrent `recover`
+    /// may already have replayed and removed it, which still counts as done.
+    fn apply(self, journal_path: &Path) -> io::Result<Vec<PathBuf>> {
+        for (path, content) in &self.writes {
+            atomic_io::atomic_write_with_checksum(path, content.as_bytes())?;
+        }
+        match fs::remove_file(journal_path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        Ok(self.writes.into_keys().collect())
+    }
+}
+
+/// Store paths inside the planning dir relative to it so a moved project still
+/// replays into the right place.
+fn relative_to(path: &Path, planning_dir: &Path) -> String {
+    path.strip_prefix(planning_dir).unwrap_or(path).to_string_lossy().to_string()
+}
+
+/// SHA-256 of the file at `path`, or None if it does not exist.
+fn file_hash(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|bytes| atomic_io::sha256_hex(&bytes))
+}
+
+/// One journaled write: the target, its new content and the hash of the
+/// content it replaces (None when the file did not exist).
+struct Entry {
+    target: PathBuf,
+    content: String,
+    sha256: String,
+    before: Option<String>,
+}
+
+/// Validated writes of a journal, or None if it is torn or any entry fails
+/// its checksum.
+fn parse_journal(content: &str, planning_dir: &Path) -> Option<Vec<Entry>> {
+    let journal: Value = serde_json::from_str(content).ok()?;
+    journal["entries"]
+        .as_array()?
+        .iter()
+        .map(|e| {
+            let path = e["path"].as_str()?;
+            let body = e["content"].as_str()?;
+            let sha256 = e["sha256"].as_str()?;
+            if atomic_io::sha256_hex(body.as_bytes()) != sha256 {
+                return None;
+            }
+            let before = match &e["before"] {
+                Value::Null => None,
+                v => Some(v.as_str()?.to_string()),
+            };
+            Some(Entry {
+                target: planning_dir.join(path),
+                content: body.to_string(),
+                sha256: sha256.to_string(),
+                before,
+            })
+        })
+        .collect()
+}
+
+/// Roll forward every committed journal left by an interrupted `commit`, oldest
+/// first, and drop stray temp files. An entry is only applied while its target
+/// still holds the content the journal replaced; targets written since are
+/// reported as skipped. Safe to call when nothing is pending.
+pub fn recover(planning_dir: &Path) -> io::Result<Recovery> {
+    let mut recovery = Recovery::default();
+    let journal_dir = planning_dir.join(JOURNAL_DIR);
+    let Ok(entries) = fs::read_dir(&journal_dir) else {
+        return Ok(recovery);
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        if !name.ends_with(".json") {
+            // Temp file from a journal write that never reached its rename.
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        let id = name.trim_end_matches(".json").to_string();
+        match fs::read_to_string(&path).ok().and_then(|c| parse_journal(&c, planning_dir)) {
+            Some(writes) => {
+                for entry in &writes {
+                    let current = file_hash(&entry.target);
+                    if current.as_deref() == Some(entry.sha256.as_str()) {
+                        continue;
+                    }
+                    if current != entry.before {
+                        recovery.skipped.push(relative_to(&entry.target, planning_dir));
+                        continue;
+                    }
+                    atomic_io::atomic_write_with_checksum(&entry.target, entry.content.as_bytes())?;
+                    recovery.files.push(relative_to(&entry.target, planning_dir));
+                }
+                recovery.rolled_forward.push(id);
+            }
+            None => recovery.discarded.push(id),
+        }
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(recovery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commit_writes_all_files_and_clears_journal() {
+        let dir = TempDir::new().unwrap();
+        let (a, b) = (dir.path().join("STATE.md"), dir.path().join("ROADMAP.md"));
+        fs::write(&a, "old").unwrap();
+
+        let mut tx = Transaction::begin(dir.path());
+        tx.stage(&a, "state v1");
+        tx.stage(&a, "state v2");
+        assert_eq!(tx.read_to_string(&a).unwrap(), "state v2");
+        tx.stage(&b, "roadmap");
+        assert_eq!(tx.commit().unwrap().len(), 2);
+
+        assert_eq!(fs::read_to_string(&a).unwrap(), "state v2");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "roadmap");
+        assert!(atomic_io::verify_checksum(&a).unwrap());
+        assert_eq!(fs::read_dir(dir.path().join(JOURNAL_DIR)).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_recover_rolls_forward_committed_journal() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("STATE.md"), "Plans: 1/2\n").unwrap();
+        fs::write(dir.path().join(".execution-state.json"), "{}").unwrap();
+        // Simulate a crash after the journal landed but before the writes applied.
+        let journal = json!({"id": "1", "entries": [
+            {
+                "path": "STATE.md",
+                "sha256": atomic_io::sha256_hex(b"Plans: 2/2\n"),
+                "before": atomic_io::sha256_hex(b"Plans: 1/2\n"),
+                "content": "Plans: 2/2\n",
+            },
+            {
+                "path": ".execution-state.json",
+                "sha256": atomic_io::sha256_hex(b"{\"status\":\"complete\"}"),
+                "before": atomic_io::sha256_hex(b"{}"),
+                "content": "{\"status\":\"complete\"}",
+            },
+        ]});
+        let journal_dir = dir.path().join(JOURNAL_DIR);
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("00000000000000000001-1.json"), journal.to_string()).unwrap();
+        fs::write(journal_dir.join("00000000000000000002-1.json.tmp.1"), "{\"id\":").unwrap();
+
+        let recovery = recover(dir.path()).unwrap();
+        assert_eq!(recovery.rolled_forward, vec!["00000000000000000001-1"]);
+        assert_eq!(recovery.files, vec!["STATE.md", ".execution-state.json"]);
+        assert_eq!(fs::read_to_string(dir.path().join("STATE.md")).unwrap(), "Plans: 2/2\n");
+        assert_eq!(fs::read_to_string(dir.path().join(".execution-state.json")).unwrap(), "{\"status\":\"complete\"}");
+        assert_eq!(fs::read_dir(&journal_dir).unwrap().count(), 0);
+        assert!(recover(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recover_skips_files_changed_since_journal() {
+        let dir = TempDir::new().unwrap();
+        let (state, roadmap) = (dir.path().join("STATE.md"), dir.path().join("ROADMAP.md"));
+        fs::write(&state, "Plans: 1/2\n").unwrap();
+
+        let mut tx = Transaction::begin(dir.path());
+        tx.stage(&state, "Plans: 2/2\n");
+        tx.stage(&roadmap, "roadmap v1");
+        // Capture the journal as a crash right after it landed would leave it.
+        let journal_dir = dir.path().join(JOURNAL_DIR);
+        let entries: Vec<Value> = tx
+            .writes
+            .iter()
+            .map(|(path, content)| {
+                json!({
+                    "path": relative_to(path, dir.path()),
+                    "sha256": atomic_io::sha256_hex(content.as_bytes()),
+                    "before": file_hash(path),
+                    "content": content,
+                })
+            })
+            .collect();
+
+        // A later session rewrote STATE.md before the journal was replayed.
+        fs::write(&state, "Plans: 3/3\n").unwrap();
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("1.json"), json!({"id": "1", "entries": entries}).to_string()).unwrap();
+
+        let recovery = recover(dir.path()).unwrap();
+        assert_eq!(recovery.rolled_forward, vec!["1"]);
+        assert_eq!(recovery.skipped, vec!["STATE.md"]);
+        assert_eq!(recovery.files, vec!["ROADMAP.md"]);
+        assert_eq!(fs::read_to_string(&state).unwrap(), "Plans: 3/3\n");
+        assert_eq!(fs::read_to_string(&roadmap).unwrap(), "roadmap v1");
+    }
+
+    #[test]
+    fn test_commit_succeeds_when_recover_replays_its_journal() {
+        let dir = TempDir::new().unwrap();
+        let state = dir.path().join("STATE.md");
+        fs::write(&state, "Plans: 1/2\n").unwrap();
+
+        let mut tx = Transaction::begin(dir.path());
+        tx.stage(&state, "Plans: 2/2\n");
+        let journal_path = tx.write_journal().unwrap();
+        // A concurrent session-start replays and removes the journal before
+        // this commit applies its own writes.
+        let recovery = recover(dir.path()).unwrap();
+        assert_eq!(recovery.files, vec!["STATE.md"]);
+        assert!(!journal_path.exists());
+
+        assert_eq!(tx.apply(&journal_path).unwrap(), vec![state.clone()]);
+        assert_eq!(fs::read_to_string(&state).unwrap(), "Plans: 2/2\n");
+    }
+
+    #[test]
+    fn test_recover_discards_corrupt_journal() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("STATE.md"), "original").unwrap();
+        let journal = json!({"id": "1", "entries": [
+            {"path": "STATE.md", "sha256": "deadbeef", "content": "tampered"},
+        ]});
+        let journal_dir = dir.path().join(JOURNAL_DIR);
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("1.json"), journal.to_string()).unwrap();
+
+        let recovery = recover(dir.path()).unwrap();
+        assert_eq!(recovery.discarded, vec!["1"]);
+        assert!(recovery.rolled_forward.is_empty());
+        assert_eq!(fs::read_to_string(dir.path().join("STATE.md")).unwrap(), "original");
+    }
+}
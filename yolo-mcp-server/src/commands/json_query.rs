@@ -0,0 +1,427 @@
+use serde_json::{Map, Value};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::atomic_io;
+
+const USAGE: &str = "Usage: yolo json <get|set|append|filter|entries|minus|build|slurp|validate> ...\n\
+  get <src> <path> [--default <value>] [--raw]\n\
+  set <src> <path> <value> [<path> <value>...]\n\
+  append <src> <path> <value> [--all] [--unique]\n\
+  filter <src> [path] [--where k=v1,v2] [--unique-by k] [--field k] [--count] [--format <tpl>] [--raw]\n\
+  entries <src> [--prefix p1,p2]   (object -> `key<TAB>value` lines)\n\
+  minus <a> <b> [--by k1,k2]\n\
+  build key=value...\n\
+  slurp            (JSON values on stdin -> array)\n\
+  validate <file>...\n\
+<src> is a file or `-` for stdin. Paths are dotted (`review_loops.03-01.status`, `checks.0.name`, `.` for the root).\n\
+Values parse as JSON when they can (`3`, `true`, `[\"a\"]`) and are strings otherwise.";
+
+/// A value given on the command line: JSON when it parses, otherwise a plain string.
+fn arg_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.trim_start_matches('.').split('.').filter(|s| !s.is_empty()).collect()
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    segments(path).into_iter().try_fold(value, |cur, seg| match cur {
+        Value::Object(m) => m.get(seg),
+        Value::Array(a) => seg.parse::<usize>().ok().and_then(|i| a.get(i)),
+        _ => None,
+    })
+}
+
+/// Mutable slot at `path`, creating intermediate objects (and replacing
+/// scalars in the way) like jq's path assignment does.
+fn slot<'a>(value: &'a mut Value, path: &str) -> Result<&'a mut Value, String> {
+    let mut cur = value;
+    for seg in segments(path) {
+        if let (Value::Array(a), Ok(i)) = (&*cur, seg.parse::<usize>())
+            && i < a.len()
+        {
+            cur = &mut cur.as_array_mut().unwrap()[i];
+            continue;
+        }
+        if !cur.is_object() {
+            if cur.is_array() {
+                return Err(format!("`{}`: array index out of range or not a number", seg));
+            }
+            *cur = Value::Object(Map::new());
+        }
+        cur = cur.as_object_mut().unwrap().entry(seg.to_string()).or_insert(Value::Null);
+    }
+    Ok(cur)
+}
+
+fn read_src(src: &str, cwd: &Path) -> Result<(Value, Option<PathBuf>), String> {
+    if src == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf).map_err(|e| format!("Failed to read stdin: {}", e))?;
+        return parse(&buf, "stdin").map(|v| (v, None));
+    }
+    let path = if Path::new(src).is_absolute() { PathBuf::from(src) } else { cwd.join(src) };
+    match fs::read_to_string(&path) {
+        Ok(content) => parse(&content, src).map(|v| (v, Some(path))),
+        // Writes create the file; reads see an empty document.
+        Err(_) => Ok((Value::Null, Some(path))),
+    }
+}
+
+fn parse(content: &str, origin: &str) -> Result<Value, String> {
+    if content.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(content).map_err(|e| format!("{}: invalid JSON: {}", origin, e))
+}
+
+/// Write back to the file atomically, or print the result for stdin input.
+fn emit(value: &Value, path: Option<PathBuf>) -> Result<(String, i32), String> {
+    match path {
+        Some(path) => {
+            let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())? + "\n";
+            atomic_io::atomic_write(&path, content.as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok((String::new(), 0))
+        }
+        None => Ok((format!("{}\n", value), 0)),
+    }
+}
+
+/// `--raw` prints strings unquoted and arrays one element per line, like `jq -r '.[]'`.
+fn render(value: &Value, raw: bool) -> String {
+    match (value, raw) {
+        (Value::String(s), true) => format!("{}\n", s),
+        (Value::Array(items), true) => items.iter().map(|v| render(v, true)).collect(),
+        (Value::Null, true) => String::new(),
+        (v, _) => format!("{}\n", v),
+    }
+}
+
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).map(String::as_str)
+}
+
+/// Positional arguments, skipping flags and the values of flags that take one.
+fn positionals(args: &[String]) -> Vec<&str> {
+    const WITH_VALUE: &[&str] = &["--default", "--where", "--unique-by", "--field", "--format", "--by", "--prefix"];
+    let mut out = Vec::new();
+    let mut iter = args.iter();
+    while let Some(a) = iter.next() {
+        if WITH_VALUE.contains(&a.as_str()) {
+            iter.next();
+        } else if !a.starts_with("--") {
+            out.push(a.as_str());
+        }
+    }
+    out
+}
+
+fn as_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// `k=v1,v2`: keep items whose `k` (compared as text) is any of the values.
+fn matches_where(item: &Value, cond: &str) -> bool {
+    let Some((key, wanted)) = cond.split_once('=') else {
+        return false;
+    };
+    let actual = lookup(item, key).map(as_text).unwrap_or_default();
+    wanted.split(',').any(|w| w == actual)
+}
+
+/// Replace `{key}` placeholders with the item's fields.
+fn format_item(template: &str, item: &Value) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        match rest[start..].find('}') {
+            Some(end) => {
+                let key = &rest[start + 1..start + end];
+                out.push_str(&lookup(item, key).map(as_text).unwrap_or_default());
+                rest = &rest[start + end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn filter(value: &Value, args: &[String]) -> Result<String, String> {
+    let items = match value {
+        Value::Array(a) => a.clone(),
+        Value::Null => Vec::new(),
+        other => return Err(format!("filter needs an array, got {}", other)),
+    };
+    let mut items: Vec<Value> = match flag_value(args, "--where") {
+        Some(cond) => items.into_iter().filter(|i| matches_where(i, cond)).collect(),
+        None => items,
+    };
+    if let Some(key) = flag_value(args, "--unique-by") {
+        // Later duplicates win, keeping the position of the first one.
+        let mut seen: Vec<String> = Vec::new();
+        let mut deduped: Vec<Value> = Vec::new();
+        for item in items {
+            let id = lookup(&item, key).map(as_text).unwrap_or_default();
+            match seen.iter().position(|s| *s == id) {
+                Some(i) => deduped[i] = item,
+                None => {
+                    seen.push(id);
+                    deduped.push(item);
+                }
+            }
+        }
+        items = deduped;
+    }
+    if args.iter().any(|a| a == "--count") {
+        return Ok(format!("{}\n", items.len()));
+    }
+    if let Some(template) = flag_value(args, "--format") {
+        return Ok(items.iter().map(|i| format_item(template, i) + "\n").collect());
+    }
+    let out = match flag_value(args, "--field") {
+        Some(key) => Value::Array(items.iter().map(|i| lookup(i, key).cloned().unwrap_or(Value::Null)).collect()),
+        None => Value::Array(items),
+    };
+    Ok(render(&out, args.iter().any(|a| a == "--raw")))
+}
+
+/// Items of `a` with no counterpart in `b`, compared on the `by` keys (whole values when empty).
+fn minus(a: &Value, b: &Value, by: &[&str]) -> Value {
+    let same = |x: &Value, y: &Value| {
+        if by.is_empty() {
+            x == y
+        } else {
+            by.iter().all(|k| lookup(x, k) == lookup(y, k))
+        }
+    };
+    let empty = Vec::new();
+    let others = b.as_array().unwrap_or(&empty);
+    let kept = a.as_array().unwrap_or(&empty).iter().filter(|x| !others.iter().any(|y| same(x, y))).cloned().collect();
+    Value::Array(kept)
+}
+
+/// A JSON value given inline, or read from a file or stdin when it is `-`/a path.
+fn operand(raw: &str, cwd: &Path) -> Result<Value, String> {
+    if raw == "-" {
+        return read_src(raw, cwd).map(|(v, _)| v);
+    }
+    Ok(arg_value(raw))
+}
+
+/// `yolo json ...`: a native stand-in for the jq one-liners the commands and
+/// skills used, so YOLO no longer needs jq installed.
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let sub = args.get(2).map(String::as_str).ok_or_else(|| USAGE.to_string())?;
+    let rest = &args[3.min(args.len())..];
+    let pos = positionals(rest);
+    let need = |n: usize| if pos.len() < n { Err(USAGE.to_string()) } else { Ok(()) };
+
+    match sub {
+        "get" => {
+            need(2)?;
+            let (doc, _) = read_src(pos[0], cwd)?;
+            let found = lookup(&doc, pos[1]).filter(|v| !v.is_null()).cloned();
+            match found.or_else(|| flag_value(rest, "--default").map(arg_value)) {
+                Some(v) => Ok((render(&v, rest.iter().any(|a| a == "--raw")), 0)),
+                None => Ok((String::new(), 1)),
+            }
+        }
+        "set" => {
+            need(3)?;
+            if pos.len().is_multiple_of(2) {
+                return Err("set takes <src> followed by <path> <value> pairs".to_string());
+            }
+            let (mut doc, path) = read_src(pos[0], cwd)?;
+            for pair in pos[1..].chunks(2) {
+                *slot(&mut doc, pair[0])? = arg_value(pair[1]);
+            }
+            emit(&doc, path)
+        }
+        "append" => {
+            need(3)?;
+            let (mut doc, path) = read_src(pos[0], cwd)?;
+            let value = arg_value(pos[2]);
+            let target = slot(&mut doc, pos[1])?;
+            if target.is_null() {
+                *target = Value::Array(Vec::new());
+            }
+            let arr = target.as_array_mut().ok_or_else(|| format!("`{}` is not an array", pos[1]))?;
+            let new_items = match (value, rest.iter().any(|a| a == "--all")) {
+                (Value::Array(items), true) => items,
+                (v, _) => vec![v],
+            };
+            let unique = rest.iter().any(|a| a == "--unique");
+            for item in new_items {
+                if !unique || !arr.contains(&item) {
+                    arr.push(item);
+                }
+            }
+            emit(&doc, path)
+        }
+        "filter" => {
+            need(1)?;
+            let (doc, _) = read_src(pos[0], cwd)?;
+            let target = pos.get(1).map(|p| lookup(&doc, p).cloned().unwrap_or(Value::Null)).unwrap_or(doc);
+            Ok((filter(&target, rest)?, 0))
+        }
+        "entries" => {
+            need(1)?;
+            let (doc, _) = read_src(pos[0], cwd)?;
+            let prefixes: Vec<&str> = flag_value(rest, "--prefix").map(|p| p.split(',').collect()).unwrap_or_default();
+            let obj = doc.as_object().ok_or_else(|| "entries needs an object".to_string())?;
+            Ok((
+                obj.iter()
+                    .filter(|(k, _)| prefixes.is_empty() || prefixes.iter().any(|p| k.starts_with(p)))
+                    .map(|(k, v)| format!("{}\t{}\n", k, as_text(v)))
+                    .collect(),
+                0,
+            ))
+        }
+        "minus" => {
+            need(2)?;
+            let by: Vec<&str> = flag_value(rest, "--by").map(|b| b.split(',').collect()).unwrap_or_default();
+            let out = minus(&operand(pos[0], cwd)?, &operand(pos[1], cwd)?, &by);
+            Ok((format!("{}\n", out), 0))
+        }
+        "build" => {
+            let mut obj = Map::new();
+            for kv in &pos {
+                let (k, v) = kv.split_once('=').ok_or_else(|| format!("build expects key=value, got `{}`", kv))?;
+                obj.insert(k.to_string(), arg_value(v));
+            }
+            Ok((format!("{}\n", Value::Object(obj)), 0))
+        }
+        "slurp" => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf).map_err(|e| format!("Failed to read stdin: {}", e))?;
+            let items: Vec<Value> = serde_json::Deserializer::from_str(&buf)
+                .into_iter::<Value>()
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("stdin: invalid JSON: {}", e))?;
+            Ok((format!("{}\n", Value::Array(items)), 0))
+        }
+        "validate" => {
+            need(1)?;
+            let errors: Vec<String> = pos
+                .iter()
+                .filter_map(|f| match fs::read_to_string(cwd.join(f)) {
+                    Ok(content) => serde_json::from_str::<Value>(&content).err().map(|e| format!("{}: invalid JSON: {}", f, e)),
+                    Err(e) => Some(format!("{}: {}", f, e)),
+                })
+                .collect();
+            let code = if errors.is_empty() { 0 } else { 1 };
+            Ok((errors.iter().map(|e| e.clone() + "\n").collect(), code))
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) -> (String, i32) {
+        let mut full = vec!["yolo".to_string(), "json".to_string()];
+        full.extend(args.iter().map(|a| a.to_string()));
+        execute(&full, dir).unwrap()
+    }
+
+    #[test]
+    fn test_get_with_default_and_raw() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("c.json"), r#"{"review_gate":"always","qa_skip_agents":["docs","scout"],"n":3}"#).unwrap();
+        assert_eq!(run(dir.path(), &["get", "c.json", ".review_gate", "--raw"]), ("always\n".into(), 0));
+        assert_eq!(run(dir.path(), &["get", "c.json", "n"]), ("3\n".into(), 0));
+        assert_eq!(run(dir.path(), &["get", "c.json", "qa_skip_agents", "--raw"]).0, "docs\nscout\n");
+        assert_eq!(run(dir.path(), &["get", "c.json", "qa_max_cycles", "--default", "3"]).0, "3\n");
+        assert_eq!(run(dir.path(), &["get", "missing.json", "x"]), (String::new(), 1));
+    }
+
+    #[test]
+    fn test_set_and_append_write_atomically() {
+        let dir = TempDir::new().unwrap();
+        let state = dir.path().join("state.json");
+        fs::write(&state, r#"{"steps_completed":["step_2"]}"#).unwrap();
+        run(dir.path(), &["append", "state.json", "steps_completed", "step_2b"]);
+        run(dir.path(), &["append", "state.json", "steps_completed", "step_2b", "--unique"]);
+        run(dir.path(), &["set", "state.json", "review_loops.03-01.status", "running", "review_loops.03-01.cycle", "1"]);
+        run(dir.path(), &["append", "state.json", "review_loops.03-01.findings_per_cycle", r#"{"high":0}"#]);
+
+        let doc: Value = serde_json::from_str(&fs::read_to_string(&state).unwrap()).unwrap();
+        assert_eq!(doc["steps_completed"], json!(["step_2", "step_2b"]));
+        assert_eq!(doc["review_loops"]["03-01"], json!({"status": "running", "cycle": 1, "findings_per_cycle": [{"high": 0}]}));
+    }
+
+    #[test]
+    fn test_filter_where_field_count_format() {
+        let checks = json!([
+            {"name": "lint", "status": "fail", "fixable_by": "dev", "evidence": "2 errors"},
+            {"name": "tests", "status": "pass", "fixable_by": "dev"},
+            {"name": "docs", "status": "fail", "fixable_by": "manual", "evidence": "missing"},
+        ]);
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(filter(&checks, &args(&["--where", "status=fail", "--count"])).unwrap(), "2\n");
+        assert_eq!(filter(&checks, &args(&["--where", "fixable_by=architect,manual", "--field", "name"])).unwrap(), "[\"docs\"]\n");
+        assert_eq!(filter(&checks, &args(&["--where", "status=fail", "--field", "name", "--raw"])).unwrap(), "lint\ndocs\n");
+        assert_eq!(
+            filter(&checks, &args(&["--where", "status=fail", "--format", "  ✗ [{name}] {evidence}"])).unwrap(),
+            "  ✗ [lint] 2 errors\n  ✗ [docs] missing\n"
+        );
+        let findings = json!([{"id": "a", "v": 1}, {"id": "b", "v": 1}, {"id": "a", "v": 2}]);
+        assert_eq!(filter(&findings, &args(&["--unique-by", "id"])).unwrap(), "[{\"id\":\"a\",\"v\":2},{\"id\":\"b\",\"v\":1}]\n");
+    }
+
+    #[test]
+    fn test_minus_finds_new_and_changed_findings() {
+        let prev = json!([{"id": "F1", "severity": "high"}, {"id": "F2", "severity": "medium"}]);
+        let curr = json!([
+            {"id": "F1", "severity": "high"},
+            {"id": "F2", "severity": "high"},
+            {"id": "F3", "severity": "medium"},
+        ]);
+        assert_eq!(
+            minus(&curr, &prev, &["id", "severity"]),
+            json!([{"id": "F2", "severity": "high"}, {"id": "F3", "severity": "medium"}])
+        );
+        assert_eq!(minus(&json!(["step_2", "step_3"]), &json!(["step_2"]), &[]), json!(["step_3"]));
+    }
+
+    #[test]
+    fn test_build_and_validate() {
+        let dir = TempDir::new().unwrap();
+        let (out, _) = run(dir.path(), &["build", "cycle=2", "verdict=reject", r#"checks=["lint"]"#]);
+        assert_eq!(serde_json::from_str::<Value>(&out).unwrap(), json!({"cycle": 2, "verdict": "reject", "checks": ["lint"]}));
+
+        fs::write(dir.path().join("ok.json"), "{}").unwrap();
+        fs::write(dir.path().join("bad.json"), "{").unwrap();
+        assert_eq!(run(dir.path(), &["validate", "ok.json"]).1, 0);
+        let (out, code) = run(dir.path(), &["validate", "ok.json", "bad.json"]);
+        assert_eq!(code, 1);
+        assert!(out.starts_with("bad.json: invalid JSON"));
+        assert_eq!(run(dir.path(), &["validate", "missing.json"]).1, 1);
+    }
+
+    #[test]
+    fn test_entries_with_prefix() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("c.json"), r#"{"effort":"fast","v3_metrics":true,"v2_hard_gates":false}"#).unwrap();
+        let (out, code) = run(dir.path(), &["entries", "c.json", "--prefix", "v3_,v2_"]);
+        assert_eq!(code, 0);
+        assert_eq!(out, "v2_hard_gates\tfalse\nv3_metrics\ttrue\n");
+    }
+}
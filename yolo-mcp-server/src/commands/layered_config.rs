@@ -0,0 +1,563 @@
+use jsonschema::error::ValidationErrorKind;
+use serde_json::{json, Map, Value};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::feature_flags::FeatureFlag;
+use super::utils::{plugin_root, YoloConfig};
+
+const BUILTIN_DEFAULTS: &str = include_str!("../../../config/defaults.json");
+
+/// Config sources, lowest precedence first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// `config/defaults.json` in the plugin root, else the copy built into the binary.
+    Defaults,
+    /// `$XDG_CONFIG_HOME/yolo/config.json` (or `~/.config/yolo/config.json`).
+    User,
+    /// `.yolo-planning/config.json` in the project.
+    Project,
+    /// `YOLO_<KEY>` environment variables.
+    Env,
+}
+
+impl Layer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Defaults => "defaults",
+            Self::User => "user",
+            Self::Project => "project",
+            Self::Env => "env",
+        }
+    }
+}
+
+/// One loaded layer: where it came from and the keys it sets.
+#[derive(Debug, Clone)]
+pub struct LayerSource {
+    pub layer: Layer,
+    /// File path, or the env var names for the env layer.
+    pub origin: String,
+    pub values: Map<String, Value>,
+    /// Set when the file exists but is not a JSON object.
+    pub parse_error: Option<String>,
+}
+
+/// A schema violation attributed to the layer that introduced it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub layer: &'static str,
+    pub origin: String,
+    pub pointer: String,
+    pub message: String,
+}
+
+/// Effective configuration for a project: all layers merged, with a typed view.
+pub struct Config {
+    /// Typed view of the merged config; keys with the wrong type fall back to defaults.
+    pub settings: YoloConfig,
+    merged: Value,
+    layers: Vec<LayerSource>,
+    schema_path: Option<PathBuf>,
+}
+
+impl Config {
+    /// Load defaults, user-global, project and environment layers for `cwd`.
+    pub fn load(cwd: &Path) -> Self {
+        let root = plugin_root(cwd);
+        let defaults_path = root.as_ref().map(|r| r.join("config").join("defaults.json"));
+        let schema_path = root
+            .as_ref()
+            .map(|r| r.join("config").join("config.schema.json"))
+            .filter(|p| p.is_file());
+
+        let mut layers = vec![match defaults_path {
+            Some(p) if p.is_file() => read_layer(Layer::Defaults, &p),
+            _ => builtin_defaults(),
+        }];
+        if let Some(p) = user_config_path() {
+            layers.push(read_layer(Layer::User, &p));
+        }
+        layers.push(read_layer(Layer::Project, &project_config_path(cwd)));
+
+        let known = known_keys(schema_path.as_deref(), &layers);
+        layers.push(env_layer(&known));
+
+        Self::from_layers(layers, schema_path)
+    }
+
+    fn from_layers(layers: Vec<LayerSource>, schema_path: Option<PathBuf>) -> Self {
+        let mut merged = Value::Object(Map::new());
+        for l in &layers {
+            deep_merge(&mut merged, &Value::Object(l.values.clone()));
+        }
+        let settings = typed_settings(&merged);
+        Self { settings, merged, layers, schema_path }
+    }
+
+    /// The fully merged config as JSON.
+    pub fn merged(&self) -> &Value {
+        &self.merged
+    }
+
+    pub fn layers(&self) -> &[LayerSource] {
+        &self.layers
+    }
+
+    pub fn layer(&self, layer: Layer) -> Option<&LayerSource> {
+        self.layers.iter().find(|l| l.layer == layer)
+    }
+
+    /// The same config with `layer`'s values replaced, for checking an edit before writing it.
+    pub fn with_layer_values(&self, layer: Layer, values: Map<String, Value>) -> Self {
+        let mut layers = self.layers.clone();
+        match layers.iter_mut().find(|l| l.layer == layer) {
+            Some(l) => {
+                l.values = values;
+                l.parse_error = None;
+            }
+            None => {
+                let at = layers.iter().position(|l| l.layer as u8 > layer as u8).unwrap_or(layers.len());
+                layers.insert(at, LayerSource { layer, origin: layer.name().to_string(), values, parse_error: None });
+            }
+        }
+        Self::from_layers(layers, self.schema_path.clone())
+    }
+
+    /// The parsed `config.schema.json`, if the plugin root has one.
+    pub fn schema(&self) -> Option<Value> {
+        self.schema_path
+            .as_ref()
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+    }
+
+    /// Look up a dot-notation key (e.g. `agent_max_turns.dev`).
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        lookup(&self.merged, key)
+    }
+
+    /// Boolean key; absent or non-boolean is `false`.
+    pub fn bool(&self, key: &str) -> bool {
+        self.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+    }
+
+    /// Non-negative integer key with a fallback.
+    pub fn u64_or(&self, key: &str, default: u64) -> u64 {
+        self.get(key).and_then(|v| v.as_u64()).unwrap_or(default)
+    }
+
+    pub fn flag(&self, flag: FeatureFlag) -> bool {
+        self.bool(flag.key())
+    }
+
+    /// `key` from the highest-precedence of `layers` that sets it, ignoring the
+    /// other layers. For settings a checked-out repository must not control.
+    pub fn get_from(&self, key: &str, layers: &[Layer]) -> Option<&Value> {
+        self.layers
+            .iter()
+            .rev()
+            .filter(|l| layers.contains(&l.layer))
+            .find_map(|l| lookup_map(&l.values, key))
+    }
+
+    /// The highest-precedence layer that sets `key`.
+    pub fn source_of(&self, key: &str) -> Option<&LayerSource> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|l| lookup_map(&l.values, key).is_some())
+    }
+
+    /// Per-layer values for `key` and the winning layer, for `config-read --explain`.
+    pub fn explain(&self, key: &str) -> Value {
+        let layers: Vec<Value> = self
+            .layers
+            .iter()
+            .map(|l| {
+                let mut entry = json!({"layer": l.layer.name(), "origin": l.origin});
+                match lookup_map(&l.values, key) {
+                    Some(v) => entry["value"] = v.clone(),
+                    None => entry["set"] = json!(false),
+                }
+                entry
+            })
+            .collect();
+        let issues: Vec<Value> = self
+            .issues()
+            .into_iter()
+            .filter(|i| issue_matches(i, key))
+            .map(|i| json!({"layer": i.layer, "origin": i.origin, "pointer": i.pointer, "message": i.message}))
+            .collect();
+        json!({
+            "key": key,
+            "value": self.get(key).cloned().unwrap_or(Value::Null),
+            "source": self.source_of(key).map(|l| l.layer.name()).unwrap_or("missing"),
+            "layers": layers,
+            "issues": issues,
+        })
+    }
+
+    /// Unknown keys and type errors in each layer, checked against `config.schema.json`.
+    /// Unknown keys get one issue each, pointing at the key, with a did-you-mean hint.
+    /// Empty when the schema cannot be found.
+    pub fn issues(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        for l in &self.layers {
+            if let Some(e) = &l.parse_error {
+                issues.push(ConfigIssue {
+                    layer: l.layer.name(),
+                    origin: l.origin.clone(),
+                    pointer: String::new(),
+                    message: e.clone(),
+                });
+            }
+        }
+        let Some(schema) = self.schema() else {
+            return issues;
+        };
+        let Ok(validator) = jsonschema::validator_for(&schema) else {
+            return issues;
+        };
+        for l in &self.layers {
+            let instance = Value::Object(l.values.clone());
+            for e in validator.iter_errors(&instance) {
+                if let ValidationErrorKind::AdditionalProperties { unexpected } = &e.kind {
+                    let parent = e.schema_path.to_string();
+                    let parent = parent.strip_suffix("/additionalProperties").unwrap_or("");
+                    let known = schema
+                        .pointer(parent)
+                        .and_then(|s| s.get("properties"))
+                        .and_then(|p| p.as_object());
+                    for key in unexpected {
+                        let mut message = format!("unknown key '{}'", key);
+                        if let Some(hint) = known.and_then(|k| closest_key(key, k.keys().map(String::as_str))) {
+                            message.push_str(&format!(" (did you mean '{}'?)", hint));
+                        }
+                        issues.push(ConfigIssue {
+                            layer: l.layer.name(),
+                            origin: l.origin.clone(),
+                            pointer: format!("{}/{}", e.instance_path, key.replace('~', "~0").replace('/', "~1")),
+                            message,
+                        });
+                    }
+                    continue;
+                }
+                issues.push(ConfigIssue {
+                    layer: l.layer.name(),
+                    origin: l.origin.clone(),
+                    pointer: e.instance_path.to_string(),
+                    message: e.to_string(),
+                });
+            }
+        }
+        issues
+    }
+}
+
+/// The project layer's file, `.yolo-planning/config.json`.
+pub fn project_config_path(cwd: &Path) -> PathBuf {
+    cwd.join(".yolo-planning").join("config.json")
+}
+
+/// `$XDG_CONFIG_HOME/yolo/config.json`, else `$HOME/.config/yolo/config.json`.
+fn user_config_path() -> Option<PathBuf> {
+    if let Ok(xdg) = env::var("XDG_CONFIG_HOME")
+        && !xdg.is_empty()
+    {
+        return Some(PathBuf::from(xdg).join("yolo").join("config.json"));
+    }
+    env::var("HOME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .map(|h| PathBuf::from(h).join(".config").join("yolo").join("config.json"))
+}
+
+/// The defaults shipped with this binary, for when no plugin root is found
+/// (hooks and shell calls run without `CLAUDE_PLUGIN_ROOT`), so flags resolve
+/// the same either way.
+fn builtin_defaults() -> LayerSource {
+    let values = match serde_json::from_str::<Value>(BUILTIN_DEFAULTS) {
+        Ok(Value::Object(m)) => m,
+        _ => Map::new(),
+    };
+    LayerSource { layer: Layer::Defaults, origin: "builtin config/defaults.json".to_string(), values, parse_error: None }
+}
+
+fn read_layer(layer: Layer, path: &Path) -> LayerSource {
+    let origin = path.to_string_lossy().to_string();
+    let mut source = LayerSource { layer, origin, values: Map::new(), parse_error: None };
+    let Ok(content) = fs::read_to_string(path) else {
+        return source;
+    };
+    match serde_json::from_str::<Value>(&content) {
+        Ok(Value::Object(m)) => source.values = m,
+        Ok(_) => source.parse_error = Some("config is not a JSON object".to_string()),
+        Err(e) => source.parse_error = Some(format!("invalid JSON: {}", e)),
+    }
+    source
+}
+
+/// Keys eligible for `YOLO_<KEY>` overrides: schema properties when the schema
+/// is available, otherwise every key some layer sets plus the feature flags.
+fn known_keys(schema_path: Option<&Path>, layers: &[LayerSource]) -> Vec<String> {
+    let from_schema = schema_path
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|s| s.get("properties").and_then(|p| p.as_object()).cloned())
+        .map(|props| props.keys().cloned().collect::<Vec<_>>());
+    if let Some(keys) = from_schema {
+        return keys;
+    }
+    let mut keys: Vec<String> = FeatureFlag::ALL.iter().map(|f| f.key().to_string()).collect();
+    for l in layers {
+        keys.extend(l.values.keys().cloned());
+    }
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// `YOLO_<KEY>` values parse as JSON when possible (`true`, `60000`, `["docs"]`),
+/// otherwise they are taken as strings.
+fn env_layer(known: &[String]) -> LayerSource {
+    let mut values = Map::new();
+    let mut names = Vec::new();
+    for key in known {
+        let var = format!("YOLO_{}", key.to_uppercase());
+        if let Ok(raw) = env::var(&var) {
+            let v = serde_json::from_str::<Value>(&raw).unwrap_or(Value::String(raw));
+            values.insert(key.clone(), v);
+            names.push(var);
+        }
+    }
+    let origin = if names.is_empty() { "YOLO_*".to_string() } else { names.join(",") };
+    LayerSource { layer: Layer::Env, origin, values, parse_error: None }
+}
+
+/// Objects merge key-by-key; anything else replaces.
+fn deep_merge(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(b), Value::Object(o)) => {
+            for (k, v) in o {
+                match b.get_mut(k) {
+                    Some(existing) if existing.is_object() && v.is_object() => deep_merge(existing, v),
+                    _ => {
+                        b.insert(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+        (b, o) => *b = o.clone(),
+    }
+}
+
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(value, |cur, part| cur.get(part))
+}
+
+fn lookup_map<'a>(map: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    let (head, rest) = match key.split_once('.') {
+        Some((h, r)) => (h, Some(r)),
+        None => (key, None),
+    };
+    let v = map.get(head)?;
+    match rest {
+        Some(r) => lookup(v, r),
+        None => Some(v),
+    }
+}
+
+/// Does an issue's JSON pointer (`/agent_max_turns/dev`) refer to dotted `key` or
+/// something under it? Root-level issues (unknown keys) match when the message names the key.
+fn issue_matches(issue: &ConfigIssue, key: &str) -> bool {
+    let dotted = issue.pointer.trim_start_matches('/').replace('/', ".");
+    if dotted.is_empty() {
+        let top = key.split('.').next().unwrap_or(key);
+        return issue.message.contains(&format!("'{}'", top));
+    }
+    dotted == key || dotted.starts_with(&format!("{}.", key))
+}
+
+/// The known key nearest to a typo, within an edit distance of 3.
+pub fn closest_key<'a>(input: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    known
+        .map(|k| (k, edit_distance(input, k)))
+        .filter(|(_, d)| *d <= 3)
+        .min_by_key(|(_, d)| *d)
+        .map(|(k, _)| k)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb { prev } else { 1 + prev.min(cur).min(row[j]) };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Deserialize the typed view, dropping top-level keys whose type does not fit.
+fn typed_settings(merged: &Value) -> YoloConfig {
+    if let Ok(s) = serde_json::from_value::<YoloConfig>(merged.clone()) {
+        return s;
+    }
+    let mut clean = Map::new();
+    if let Some(obj) = merged.as_object() {
+        for (k, v) in obj {
+            let single = Value::Object(Map::from_iter([(k.clone(), v.clone())]));
+            if serde_json::from_value::<YoloConfig>(single).is_ok() {
+                clean.insert(k.clone(), v.clone());
+            }
+        }
+    }
+    serde_json::from_value(Value::Object(clean)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn layer(layer: Layer, values: Value) -> LayerSource {
+        LayerSource {
+            layer,
+            origin: layer.name().to_string(),
+            values: values.as_object().cloned().unwrap_or_default(),
+            parse_error: None,
+        }
+    }
+
+    fn schema_file(dir: &TempDir) -> PathBuf {
+        let path = dir.path().join("config.schema.json");
+        fs::write(
+            &path,
+            json!({
+                "type": "object",
+                "properties": {
+                    "effort": {"type": "string"},
+                    "command_timeout_ms": {"type": "integer", "minimum": 1000},
+                    "agent_max_turns": {"type": "object", "additionalProperties": {"type": "integer"}},
+                    "v3_metrics": {"type": "boolean"}
+                },
+                "additionalProperties": false
+            })
+            .to_string(),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_precedence_and_deep_merge() {
+        let config = Config::from_layers(
+            vec![
+                layer(Layer::Defaults, json!({"effort": "balanced", "agent_max_turns": {"dev": 75, "qa": 25}})),
+                layer(Layer::User, json!({"effort": "fast"})),
+                layer(Layer::Project, json!({"agent_max_turns": {"dev": 100}})),
+                layer(Layer::Env, json!({"effort": "turbo"})),
+            ],
+            None,
+        );
+        assert_eq!(config.settings.effort, "turbo");
+        assert_eq!(config.get("agent_max_turns.dev"), Some(&json!(100)));
+        assert_eq!(config.get("agent_max_turns.qa"), Some(&json!(25)));
+        assert_eq!(config.source_of("effort").unwrap().layer, Layer::Env);
+        assert_eq!(config.source_of("agent_max_turns.qa").unwrap().layer, Layer::Defaults);
+        assert!(config.source_of("missing").is_none());
+    }
+
+    #[test]
+    fn test_explain_lists_every_layer() {
+        let config = Config::from_layers(
+            vec![
+                layer(Layer::Defaults, json!({"command_timeout_ms": 30000})),
+                layer(Layer::Project, json!({"command_timeout_ms": 60000})),
+            ],
+            None,
+        );
+        let ex = config.explain("command_timeout_ms");
+        assert_eq!(ex["value"], 60000);
+        assert_eq!(ex["source"], "project");
+        assert_eq!(ex["layers"][0]["value"], 30000);
+        assert_eq!(ex["layers"][1]["value"], 60000);
+    }
+
+    #[test]
+    fn test_issues_report_unknown_keys_and_types_per_layer() {
+        let dir = TempDir::new().unwrap();
+        let config = Config::from_layers(
+            vec![
+                layer(Layer::Defaults, json!({"command_timeout_ms": 30000})),
+                layer(Layer::Project, json!({"v3_metric": true, "command_timeout_ms": "slow"})),
+            ],
+            Some(schema_file(&dir)),
+        );
+        let issues = config.issues();
+        assert!(issues.iter().all(|i| i.layer == "project"));
+        assert!(issues.iter().any(|i| i.message.contains("v3_metric")));
+        assert!(issues.iter().any(|i| i.pointer == "/command_timeout_ms"));
+        let unknown = issues.iter().find(|i| i.pointer == "/v3_metric").unwrap();
+        assert_eq!(unknown.message, "unknown key 'v3_metric' (did you mean 'v3_metrics'?)");
+        // A mistyped value reads as the fallback.
+        assert_eq!(config.u64_or("command_timeout_ms", 30000), 30000);
+        assert_eq!(config.settings.command_timeout_ms, 30000);
+
+        let ex = config.explain("v3_metric");
+        assert_eq!(ex["source"], "project");
+        assert_eq!(ex["issues"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_typed_settings_skip_mistyped_keys() {
+        let config = Config::from_layers(
+            vec![layer(Layer::Project, json!({"effort": "fast", "max_tasks_per_plan": "many"}))],
+            None,
+        );
+        assert_eq!(config.settings.effort, "fast");
+        assert_eq!(config.settings.max_tasks_per_plan, YoloConfig::default().max_tasks_per_plan);
+    }
+
+    #[test]
+    fn test_env_layer_parses_json_scalars() {
+        let key = "yolo_layered_config_test_key";
+        unsafe { env::set_var("YOLO_YOLO_LAYERED_CONFIG_TEST_KEY", "60000"); }
+        let l = env_layer(&[key.to_string(), "effort_unset_for_test".to_string()]);
+        unsafe { env::remove_var("YOLO_YOLO_LAYERED_CONFIG_TEST_KEY"); }
+        assert_eq!(l.values.get(key), Some(&json!(60000)));
+        assert_eq!(l.values.len(), 1);
+        assert_eq!(l.origin, "YOLO_YOLO_LAYERED_CONFIG_TEST_KEY");
+    }
+
+    #[test]
+    fn test_load_reads_project_layer() {
+        let dir = TempDir::new().unwrap();
+        let planning = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning).unwrap();
+        fs::write(planning.join("config.json"), r#"{"v3_metrics": true, "task_lease_ttl_secs": 120}"#).unwrap();
+        let config = Config::load(dir.path());
+        assert!(config.flag(FeatureFlag::V3Metrics));
+        assert_eq!(config.u64_or("task_lease_ttl_secs", 300), 120);
+        assert_eq!(config.source_of("v3_metrics").unwrap().layer, Layer::Project);
+    }
+
+    #[test]
+    fn test_defaults_layer_without_plugin_root() {
+        // A bare temp dir has no config/defaults.json above it; the built-in copy stands in.
+        let dir = TempDir::new().unwrap();
+        let config = Config::load(dir.path());
+        let defaults = config.layer(Layer::Defaults).expect("defaults layer always loads");
+        let shipped: Value = serde_json::from_str(BUILTIN_DEFAULTS).unwrap();
+        assert_eq!(Value::Object(defaults.values.clone()), shipped);
+        assert!(config.flag(FeatureFlag::V2TypedProtocol));
+        assert!(config.flag(FeatureFlag::V3LeaseLocks));
+        assert!(!config.flag(FeatureFlag::V2HardGates));
+    }
+}
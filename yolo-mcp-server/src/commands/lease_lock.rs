@@ -1,22 +1,20 @@
 use super::domain_types::ResourceId;
 use super::feature_flags::{self, FeatureFlag};
+use super::layered_config::Config;
 use super::log_event;
 use chrono::Utc;
 use serde_json::{json, Value};
 use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
 const DEFAULT_TTL_SECS: u64 = 300;
 
-/// Read task_lease_ttl_secs from `.yolo-planning/config.json`, defaulting to 300.
+/// Read task_lease_ttl_secs from the layered config, defaulting to 300.
 fn read_task_lease_ttl(cwd: &Path) -> u64 {
-    let config_path = cwd.join(".yolo-planning").join("config.json");
-    if let Ok(content) = fs::read_to_string(&config_path)
-        && let Ok(config) = serde_json::from_str::<Value>(&content)
-        && let Some(ttl) = config.get("task_lease_ttl_secs").and_then(|v| v.as_u64()) {
-            return ttl;
-        }
-    DEFAULT_TTL_SECS
+    Config::load(cwd).settings.task_lease_ttl_secs
 }
 
 /// Get the locks directory path.
@@ -124,8 +122,30 @@ pub fn acquire(resource: &ResourceId, owner: &str, ttl_secs: u64, cwd: &Path) ->
         "type": "lease",
     });
 
-    fs::write(&lock_path, serde_json::to_string_pretty(&lock_data).unwrap())
-        .map_err(|e| json!({"action": "acquire", "result": "error", "error": e.to_string()}))?;
+    // create_new makes the check-then-write above safe against a concurrent
+    // acquirer: only one of them can create the lease file.
+    let created = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&lock_path)
+        .and_then(|mut f| f.write_all(serde_json::to_string_pretty(&lock_data).unwrap().as_bytes()));
+    match created {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let held_by = read_lease(&lock_path)
+                .and_then(|l| l.get("owner").and_then(|v| v.as_str()).map(String::from))
+                .unwrap_or_default();
+            return Err(json!({
+                "action": "acquire",
+                "result": "conflict",
+                "resource": resource_str,
+                "held_by": held_by,
+                "requested_by": owner,
+                "hard_enforcement": feature_flags::is_enabled(FeatureFlag::V2HardGates, cwd),
+            }));
+        }
+        Err(e) => return Err(json!({"action": "acquire", "result": "error", "error": e.to_string()})),
+    }
 
     Ok(json!({
         "action": "acquire",
@@ -137,6 +157,32 @@ pub fn acquire(resource: &ResourceId, owner: &str, ttl_secs: u64, cwd: &Path) ->
     }))
 }
 
+/// Run `f` while holding the lease on `resource`, waiting up to `wait` for
+/// another owner to release it. The TTL bounds how long a crashed holder can
+/// block later callers.
+pub fn with_lease<T>(
+    resource: &ResourceId,
+    owner: &str,
+    ttl_secs: u64,
+    wait: Duration,
+    cwd: &Path,
+    f: impl FnOnce() -> T,
+) -> Result<T, String> {
+    let start = Instant::now();
+    loop {
+        match acquire(resource, owner, ttl_secs, cwd) {
+            Ok(_) => break,
+            Err(v) if v["result"] == "conflict" && start.elapsed() < wait => {
+                thread::sleep(Duration::from_millis(25));
+            }
+            Err(v) => return Err(format!("Failed to lease {}: {}", resource, v)),
+        }
+    }
+    let result = f();
+    let _ = release(resource, owner, cwd);
+    Ok(result)
+}
+
 /// Renew an existing lease lock, resetting its TTL.
 pub fn renew(resource: &ResourceId, owner: &str, ttl_secs: u64, cwd: &Path) -> Result<Value, Value> {
     let resource_str = resource.as_str();
@@ -1,4 +1,6 @@
+use super::event_bus;
 use super::feature_flags::{self, FeatureFlag};
+use super::rollout_stage;
 use chrono::Utc;
 use serde_json::{json, Value};
 use std::env;
@@ -27,6 +29,7 @@ const ALLOWED_EVENT_TYPES: &[&str] = &[
     "token_overage", "token_cap_escalated", "file_conflict", "smart_route",
     "contract_revision", "cache_hit", "task_completion_rejected",
     "snapshot_restored", "state_recovered", "message_rejected",
+    "bash_guard_block", "cost_budget_exceeded",
     // Token tracking
     "agent_token_usage",
     // Feedback loop events
@@ -52,8 +55,22 @@ fn parse_args(args: &[String]) -> (Option<String>, Vec<(String, String)>) {
     (plan, data_pairs)
 }
 
+/// The current run's correlation ID: `YOLO_CORRELATION_ID`, else the one in
+/// `.execution-state.json`, else "".
+pub fn correlation_id(planning_dir: &Path) -> String {
+    let from_env = env::var("YOLO_CORRELATION_ID").unwrap_or_default();
+    if !from_env.is_empty() {
+        return from_env;
+    }
+    fs::read_to_string(planning_dir.join(".execution-state.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|state| state.get("correlation_id").and_then(|v| v.as_str()).map(str::to_string))
+        .unwrap_or_default()
+}
+
 /// Core logging function callable from other Rust code.
-/// Appends a JSON line to `.yolo-planning/.events/event-log.jsonl`.
+/// Publishes to the event bus (canonical store `.yolo-planning/.events/event-log.jsonl`).
 /// Never fails fatally — returns Ok with LogResult on any path.
 pub fn log(
     event_type: &str,
@@ -88,22 +105,7 @@ pub fn log(
         });
     }
 
-    // Resolve correlation_id: env var → execution-state.json → ""
-    let mut correlation_id = env::var("YOLO_CORRELATION_ID").unwrap_or_default();
-
-    if correlation_id.is_empty() {
-        let exec_state_path = planning_dir.join(".execution-state.json");
-        if exec_state_path.exists()
-            && let Ok(state_str) = fs::read_to_string(&exec_state_path)
-            && let Ok(state) = serde_json::from_str::<Value>(&state_str) {
-                correlation_id = state
-                    .get("correlation_id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-            }
-    }
-
+    let correlation_id = correlation_id(&planning_dir);
     let ts = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
     let event_id = Uuid::new_v4().to_string();
 
@@ -129,21 +131,11 @@ pub fn log(
         obj["data"] = Value::Object(data_obj);
     }
 
-    // Ensure .events/ directory exists
-    let events_dir = planning_dir.join(".events");
-    let _ = fs::create_dir_all(&events_dir);
-
-    // Append atomically
-    let events_file = events_dir.join("event-log.jsonl");
-    let line = format!("{}\n", serde_json::to_string(&obj).unwrap_or_default());
+    event_bus::publish(&planning_dir, &obj);
 
-    use std::io::Write;
-    if let Ok(mut file) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&events_file)
-    {
-        let _ = file.write_all(line.as_bytes());
+    // A finished phase is when rollout health is re-evaluated.
+    if event_type == "phase_end" {
+        rollout_stage::auto_evaluate(cwd);
     }
 
     Ok(LogResult {
@@ -3,10 +3,12 @@ use std::fs;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 
+use super::event_bus;
+
 pub fn generate_metrics_report(cwd: &Path, phase_filter: Option<&str>) -> Result<(String, i32), String> {
     let planning_dir = cwd.join(".yolo-planning");
     let metrics_file = planning_dir.join(".metrics").join("run-metrics.jsonl");
-    let events_file = planning_dir.join(".events").join("event-log.jsonl");
+    let events_file = event_bus::log_path(&planning_dir);
     let config_file = planning_dir.join("config.json");
 
     if !metrics_file.exists() && !events_file.exists() {
@@ -25,7 +27,7 @@ pub fn generate_metrics_report(cwd: &Path, phase_filter: Option<&str>) -> Result
     out.push('\n');
 
     // Load data
-    let events: Vec<Value> = load_jsonl(&events_file);
+    let events: Vec<Value> = event_bus::load_events(&planning_dir);
     let metrics: Vec<Value> = load_jsonl(&metrics_file);
 
     // Filter by phase if needed
@@ -1,6 +1,7 @@
 pub mod atomic_io;
 pub mod domain_types;
 pub mod feature_flags;
+pub mod frontmatter;
 pub mod utils;
 pub mod bootstrap_all;
 pub mod bootstrap_claude;
@@ -81,3 +82,23 @@ pub mod compile_progress;
 pub mod git_state;
 pub mod release_suite;
 pub mod extract_changelog;
+pub mod plan_graph;
+pub mod file_overlap;
+pub mod symbols;
+pub mod must_have_evidence;
+pub mod role_registry;
+pub mod event_bus;
+pub mod layered_config;
+pub mod config_edit;
+pub mod flags;
+pub mod snapshot;
+pub mod journal;
+pub mod doctor_integrity;
+pub mod version_files;
+pub mod changelog;
+pub mod net;
+pub mod json_query;
+pub mod statusline_layout;
+pub mod cost_forecast;
+pub mod pricing;
+pub mod cost_history;
@@ -0,0 +1,382 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use super::file_overlap::patterns_overlap;
+use super::symbols::{self, Language};
+use super::utils::run_with_timeout;
+
+/// Default per-command timeout when `command_timeout_ms` is not configured.
+pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound on files scanned when searching a directory scope for a symbol.
+const MAX_SCAN_FILES: usize = 500;
+
+/// One piece of machine-checkable evidence for a must-have, written as
+/// `kind:value` on the command line (e.g. `evidence.1=test:parses_empty_input`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Evidence {
+    /// A test that must pass when run through the detected test runner.
+    Test(String),
+    /// A commit that must exist and touch at least one in-scope file.
+    Commit(String),
+    /// A file that must exist.
+    File(String),
+    /// A definition that must exist, optionally pinned to a file (`path#name`).
+    Symbol { path: Option<String>, name: String },
+    /// A shell command that must exit 0 within the timeout.
+    Command(String),
+}
+
+impl Evidence {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (kind, value) = spec
+            .split_once(':')
+            .ok_or_else(|| format!("evidence '{}' must be kind:value", spec))?;
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(format!("evidence '{}' has an empty value", spec));
+        }
+        match kind.trim() {
+            "test" => Ok(Evidence::Test(value.to_string())),
+            "commit" => Ok(Evidence::Commit(value.to_string())),
+            "file" => Ok(Evidence::File(value.to_string())),
+            "symbol" => Ok(match value.split_once('#') {
+                Some((path, name)) => Evidence::Symbol { path: Some(path.to_string()), name: name.to_string() },
+                None => Evidence::Symbol { path: None, name: value.to_string() },
+            }),
+            "cmd" => Ok(Evidence::Command(value.to_string())),
+            other => Err(format!(
+                "unknown evidence kind '{}' (expected test, commit, file, symbol or cmd)",
+                other
+            )),
+        }
+    }
+}
+
+/// Outcome of verifying one must-have.
+#[derive(Debug, Clone)]
+pub struct Verdict {
+    pub must_have: String,
+    pub verified: bool,
+    /// What proved it, e.g. `file src/a.rs exists`.
+    pub evidence: Option<String>,
+    /// Why each attempted piece of evidence failed.
+    pub reasons: Vec<String>,
+}
+
+/// Checks evidence against the working tree, git history and the project's
+/// test runner. `scope` holds the contract's allowed paths plus any files the
+/// task reported modifying; commits and symbol searches are limited to it.
+pub struct Verifier<'a> {
+    cwd: &'a Path,
+    scope: Vec<String>,
+    timeout: Duration,
+}
+
+impl<'a> Verifier<'a> {
+    pub fn new(cwd: &'a Path, scope: Vec<String>, timeout: Duration) -> Self {
+        Verifier { cwd, scope, timeout }
+    }
+
+    /// Verify a must-have against its explicit evidence. The first piece that
+    /// checks out verifies it; text alone never does.
+    pub fn verify(&self, must_have: &str, explicit: &[Evidence]) -> Verdict {
+        let mut reasons = Vec::new();
+        for ev in explicit {
+            match self.check(ev) {
+                Ok(detail) => {
+                    return Verdict { must_have: must_have.to_string(), verified: true, evidence: Some(detail), reasons };
+                }
+                Err(reason) => reasons.push(reason),
+            }
+        }
+        if explicit.is_empty() {
+            reasons.push("no machine-checkable evidence (pass test:, commit:, file:, symbol: or cmd:)".to_string());
+        }
+        Verdict { must_have: must_have.to_string(), verified: false, evidence: None, reasons }
+    }
+
+    /// Check one piece of evidence. `Ok` carries a short description of what was found.
+    pub fn check(&self, ev: &Evidence) -> Result<String, String> {
+        match ev {
+            Evidence::File(path) => {
+                if self.cwd.join(path).exists() {
+                    Ok(format!("file {} exists", path))
+                } else {
+                    Err(format!("file {} not found", path))
+                }
+            }
+            Evidence::Symbol { path: Some(path), name } => {
+                let full = self.cwd.join(path);
+                if !full.is_file() {
+                    return Err(format!("file {} not found", path));
+                }
+                if defines(&full, name) {
+                    Ok(format!("symbol {} defined in {}", name, path))
+                } else {
+                    Err(format!("symbol {} not defined in {}", name, path))
+                }
+            }
+            Evidence::Symbol { path: None, name } => match self.find_symbol(symbols::leaf(name)) {
+                Some(path) => Ok(format!("symbol {} defined in {}", name, path)),
+                None => Err(format!("symbol {} not found in scope", name)),
+            },
+            Evidence::Commit(rev) => self.check_commit(rev),
+            Evidence::Command(cmd) => {
+                let mut c = Command::new("sh");
+                c.arg("-c").arg(cmd).current_dir(self.cwd);
+                match run_with_timeout(&mut c, self.timeout) {
+                    Ok(o) if o.status.success() => Ok(format!("command `{}` succeeded", cmd)),
+                    Ok(o) => Err(format!("command `{}` exited {}", cmd, o.status.code().unwrap_or(-1))),
+                    Err(e) => Err(format!("command `{}`: {}", cmd, e)),
+                }
+            }
+            Evidence::Test(name) => self.check_test(name),
+        }
+    }
+
+    fn check_commit(&self, rev: &str) -> Result<String, String> {
+        let output = Command::new("git")
+            .args(["show", "--name-only", "--format=", "--no-renames", rev, "--"])
+            .current_dir(self.cwd)
+            .output()
+            .map_err(|e| format!("commit {}: git unavailable: {}", rev, e))?;
+        if !output.status.success() {
+            return Err(format!("commit {} not found", rev));
+        }
+        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if files.is_empty() {
+            return Err(format!("commit {} touches no files", rev));
+        }
+        if self.scope.is_empty() {
+            return Ok(format!("commit {} touches {}", rev, files[0]));
+        }
+        match files.iter().find(|f| in_scope(f, &self.scope)) {
+            Some(f) => Ok(format!("commit {} touches {}", rev, f)),
+            None => Err(format!("commit {} touches no file in scope", rev)),
+        }
+    }
+
+    fn check_test(&self, name: &str) -> Result<String, String> {
+        let (mut cmd, runner) = test_command(self.cwd, name)
+            .ok_or_else(|| format!("test {}: no test runner detected", name))?;
+        cmd.current_dir(self.cwd);
+        let output = run_with_timeout(&mut cmd, self.timeout).map_err(|e| format!("test {}: {}", name, e))?;
+        if !output.status.success() {
+            return Err(format!("test {} failed ({})", name, runner));
+        }
+        // A filter that matches nothing still exits 0 under cargo.
+        if runner == "cargo" {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let ran = stdout
+                .lines()
+                .any(|l| l.starts_with("test ") && l.contains(name) && l.ends_with(" ... ok"));
+            if !ran {
+                return Err(format!("test {} did not run ({})", name, runner));
+            }
+        }
+        Ok(format!("test {} passed ({})", name, runner))
+    }
+
+    /// First in-scope file that defines `name`.
+    fn find_symbol(&self, name: &str) -> Option<String> {
+        if name.is_empty() {
+            return None;
+        }
+        let mut scanned = 0;
+        for entry in &self.scope {
+            let full = self.cwd.join(entry);
+            if full.is_file() {
+                if defines(&full, name) {
+                    return Some(entry.clone());
+                }
+            } else if full.is_dir() {
+                let mut stack = vec![full];
+                while let Some(dir) = stack.pop() {
+                    let Ok(rd) = fs::read_dir(&dir) else { continue };
+                    for e in rd.flatten() {
+                        let p = e.path();
+                        if p.is_dir() {
+                            stack.push(p);
+                        } else if Language::from_path(&p).is_some() {
+                            scanned += 1;
+                            if scanned > MAX_SCAN_FILES {
+                                return None;
+                            }
+                            if defines(&p, name) {
+                                let rel = p.strip_prefix(self.cwd).unwrap_or(&p);
+                                return Some(rel.to_string_lossy().to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+fn defines(path: &Path, name: &str) -> bool {
+    let Some(lang) = Language::from_path(path) else { return false };
+    let Ok(content) = fs::read_to_string(path) else { return false };
+    symbols::extract(lang, &content).iter().any(|s| s.name == name)
+}
+
+/// Whether `file` falls under one of the scope entries: exact match,
+/// directory prefix, or glob.
+pub fn in_scope(file: &str, scope: &[String]) -> bool {
+    scope.iter().any(|s| {
+        let s = s.trim_end_matches('/');
+        file == s || file.starts_with(&format!("{}/", s)) || patterns_overlap(file, s)
+    })
+}
+
+/// Build the test command for the runner detected in `cwd`.
+fn test_command(cwd: &Path, name: &str) -> Option<(Command, &'static str)> {
+    if cwd.join("Cargo.toml").exists() {
+        let mut c = Command::new("cargo");
+        c.args(["test", name]);
+        Some((c, "cargo"))
+    } else if cwd.join("pytest.ini").exists() || cwd.join("pyproject.toml").exists() {
+        let mut c = Command::new("pytest");
+        c.args(["-k", name]);
+        Some((c, "pytest"))
+    } else if cwd.join("package.json").exists() {
+        let mut c = Command::new("npm");
+        c.args(["test", "--", "-t", name]);
+        Some((c, "npm"))
+    } else if cwd.join("tests").is_dir() {
+        let mut c = Command::new("bats");
+        c.args(["-f", name, "tests"]);
+        Some((c, "bats"))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let ok = Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@t", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status
+            .success();
+        assert!(ok, "git {:?}", args);
+    }
+
+    fn verifier<'a>(dir: &'a TempDir, scope: &[&str]) -> Verifier<'a> {
+        Verifier::new(dir.path(), scope.iter().map(|s| s.to_string()).collect(), Duration::from_secs(5))
+    }
+
+    #[test]
+    fn test_parse_evidence() {
+        assert_eq!(Evidence::parse("test:parses_input").unwrap(), Evidence::Test("parses_input".into()));
+        assert_eq!(Evidence::parse("cmd:test -f a:b").unwrap(), Evidence::Command("test -f a:b".into()));
+        assert_eq!(
+            Evidence::parse("symbol:src/lib.rs#Client").unwrap(),
+            Evidence::Symbol { path: Some("src/lib.rs".into()), name: "Client".into() }
+        );
+        assert!(Evidence::parse("looks good").is_err());
+        assert!(Evidence::parse("vibes:yes").is_err());
+        assert!(Evidence::parse("file:").is_err());
+    }
+
+    #[test]
+    fn test_file_and_symbol_evidence() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub fn parse_config() {}\n").unwrap();
+        let v = verifier(&dir, &["src"]);
+
+        assert!(v.check(&Evidence::File("src/lib.rs".into())).is_ok());
+        assert!(v.check(&Evidence::File("src/missing.rs".into())).is_err());
+        assert!(v.check(&Evidence::parse("symbol:src/lib.rs#parse_config").unwrap()).is_ok());
+        assert!(v.check(&Evidence::parse("symbol:parse_config").unwrap()).is_ok());
+        assert!(v.check(&Evidence::parse("symbol:render").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_command_evidence_and_timeout() {
+        let dir = TempDir::new().unwrap();
+        let v = Verifier::new(dir.path(), vec![], Duration::from_millis(200));
+        assert!(v.check(&Evidence::Command("true".into())).is_ok());
+        assert!(v.check(&Evidence::Command("exit 3".into())).unwrap_err().contains("exited 3"));
+
+        let start = Instant::now();
+        let err = v.check(&Evidence::Command("sleep 5".into())).unwrap_err();
+        assert!(err.contains("timed out"), "{}", err);
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_commit_evidence_requires_scope() {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/a.rs"), "fn a() {}\n").unwrap();
+        fs::write(dir.path().join("README.md"), "x\n").unwrap();
+        git(dir.path(), &["add", "src/a.rs"]);
+        git(dir.path(), &["commit", "-qm", "feat: add retry backoff"]);
+        git(dir.path(), &["add", "README.md"]);
+        git(dir.path(), &["commit", "-qm", "docs: readme"]);
+
+        let v = verifier(&dir, &["src"]);
+        assert!(v.check(&Evidence::Commit("HEAD~1".into())).is_ok());
+        assert!(v.check(&Evidence::Commit("HEAD".into())).unwrap_err().contains("no file in scope"));
+        assert!(v.check(&Evidence::Commit("deadbeef".into())).unwrap_err().contains("not found"));
+
+        // A commit subject that matches the must-have is not evidence by itself.
+        assert!(!v.verify("Retry backoff implemented", &[]).verified);
+    }
+
+    #[test]
+    fn test_verify_requires_explicit_evidence() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/cache.py"), "def evict_stale(entries):\n    pass\n").unwrap();
+        let v = verifier(&dir, &["src/cache.py"]);
+
+        // Naming an existing file or symbol does not verify the must-have.
+        let verdict = v.verify("`evict_stale` drops expired entries", &[]);
+        assert!(!verdict.verified);
+        assert!(verdict.reasons[0].contains("no machine-checkable evidence"));
+        assert!(!v.verify("src/cache.py is created", &[]).verified);
+
+        let verdict = v.verify("`evict_stale` drops expired entries", &[Evidence::parse("symbol:evict_stale").unwrap()]);
+        assert!(verdict.verified);
+        assert!(verdict.evidence.unwrap().contains("src/cache.py"));
+    }
+
+    #[test]
+    fn test_explicit_failure_reported() {
+        let dir = TempDir::new().unwrap();
+        let v = verifier(&dir, &[]);
+        let verdict = v.verify("Feature works", &[Evidence::File("nope.txt".into())]);
+        assert!(!verdict.verified);
+        assert_eq!(verdict.reasons, vec!["file nope.txt not found".to_string()]);
+    }
+
+
+    #[test]
+    fn test_in_scope() {
+        let scope = vec!["src/".to_string(), "docs/*.md".to_string()];
+        assert!(in_scope("src/a/b.rs", &scope));
+        assert!(in_scope("docs/x.md", &scope));
+        assert!(!in_scope("srcx/a.rs", &scope));
+        assert!(!in_scope("docs/a/x.md", &scope));
+    }
+}
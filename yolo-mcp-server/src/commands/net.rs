@@ -0,0 +1,368 @@
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::event_bus::is_loopback_url;
+use super::layered_config::{Config, Layer};
+
+/// How much network access YOLO may use (`network` config key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// No requests and no cached results.
+    Off,
+    /// Serve previously cached results of any age, never touch the network.
+    CacheOnly,
+    /// Fetch when the cached result is older than the caller's max age.
+    On,
+}
+
+impl Policy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(Self::Off),
+            "cache-only" => Some(Self::CacheOnly),
+            "on" => Some(Self::On),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::CacheOnly => "cache-only",
+            Self::On => "on",
+        }
+    }
+
+    /// `YOLO_OFFLINE` (any value but empty, `0` or `false`) wins over the
+    /// configured mode; an unknown mode falls back to `on`.
+    pub fn resolve(configured: Option<&str>, offline_env: Option<&str>) -> Self {
+        if offline_env.is_some_and(|v| !matches!(v.trim(), "" | "0" | "false")) {
+            return Self::Off;
+        }
+        configured.and_then(Self::parse).unwrap_or(Self::On)
+    }
+}
+
+/// Every outbound endpoint YOLO calls. Names are what `network_allow` and
+/// `network_endpoints` refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    /// Published plugin VERSION, for update checks and bump-version.
+    YoloVersion,
+    /// Claude OAuth usage limits shown in the statusline.
+    OauthUsage,
+}
+
+impl Endpoint {
+    pub const ALL: &[Endpoint] = &[Self::YoloVersion, Self::OauthUsage];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::YoloVersion => "yolo-version",
+            Self::OauthUsage => "oauth-usage",
+        }
+    }
+
+    /// Endpoints that are sent the user's credentials. Their URL can only be
+    /// overridden from the user or env config layers, and only to loopback, so
+    /// a checked-in project config cannot redirect the token elsewhere.
+    pub fn credentialed(&self) -> bool {
+        matches!(self, Self::OauthUsage)
+    }
+
+    pub fn default_url(&self) -> &'static str {
+        match self {
+            Self::YoloVersion => "https://raw.githubusercontent.com/slavpetroff/yolo/main/VERSION",
+            Self::OauthUsage => "https://api.anthropic.com/api/oauth/usage",
+        }
+    }
+}
+
+/// Result of a `Net::get`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fetched {
+    /// Fresh from the network (and now cached).
+    Network(String),
+    /// From the cache: still fresh, any age under cache-only, or stale after a failed fetch.
+    Cached(String),
+    /// The server answered with a non-2xx status.
+    Status(u16),
+    /// The request failed (timeout, DNS, refused) and nothing was cached.
+    Failed,
+    /// Not attempted: network off, cache-only miss, or endpoint not allowlisted.
+    Blocked,
+}
+
+impl Fetched {
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Self::Network(b) | Self::Cached(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+/// The one HTTP client wrapper for outbound calls: applies the network policy
+/// and endpoint allowlist, resolves endpoint URL overrides, and caches every
+/// successful response on disk.
+#[derive(Debug, Clone)]
+pub struct Net {
+    pub policy: Policy,
+    /// Endpoint names that may be fetched; None allows all of them.
+    allow: Option<Vec<String>>,
+    /// URL overrides by endpoint name (mirrors, or a local stand-in in tests).
+    urls: BTreeMap<String, String>,
+    cache_dir: PathBuf,
+    timeout: Duration,
+}
+
+impl Net {
+    /// Policy from `network` / `network_allow` / `network_endpoints` in the
+    /// layered config (so `YOLO_NETWORK=off` works too) plus `YOLO_OFFLINE`.
+    pub fn load(cwd: &Path) -> Self {
+        let config = Config::load(cwd);
+        let policy = Policy::resolve(
+            config.get("network").and_then(|v| v.as_str()),
+            std::env::var("YOLO_OFFLINE").ok().as_deref(),
+        );
+        let allow = config.get("network_allow").and_then(|v| v.as_array()).map(|a| {
+            a.iter().filter_map(|n| n.as_str().map(str::to_string)).collect()
+        });
+        let urls = endpoint_overrides(&config);
+        let uid = unsafe { libc::getuid() };
+        Self::new(policy, allow, urls, std::env::temp_dir().join(format!("yolo-net-{}", uid)))
+    }
+
+    pub fn new(policy: Policy, allow: Option<Vec<String>>, urls: BTreeMap<String, String>, cache_dir: PathBuf) -> Self {
+        Self { policy, allow, urls, cache_dir, timeout: Duration::from_secs(3) }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn allowed(&self, endpoint: Endpoint) -> bool {
+        self.policy != Policy::Off
+            && self.allow.as_ref().is_none_or(|a| a.iter().any(|n| n == endpoint.name()))
+    }
+
+    pub fn url(&self, endpoint: Endpoint) -> String {
+        self.urls.get(endpoint.name()).cloned().unwrap_or_else(|| endpoint.default_url().to_string())
+    }
+
+    fn cache_path(&self, endpoint: Endpoint) -> PathBuf {
+        self.cache_dir.join(format!("{}.json", endpoint.name()))
+    }
+
+    /// Cached body and its age, if the cache was filled from the current URL.
+    fn cached(&self, endpoint: Endpoint) -> Option<(String, Duration)> {
+        let entry: Value = serde_json::from_str(&fs::read_to_string(self.cache_path(endpoint)).ok()?).ok()?;
+        if entry["url"].as_str()? != self.url(endpoint) {
+            return None;
+        }
+        let fetched_at = entry["fetched_at"].as_u64()?;
+        let age = now_secs().saturating_sub(fetched_at);
+        Some((entry["body"].as_str()?.to_string(), Duration::from_secs(age)))
+    }
+
+    fn store(&self, endpoint: Endpoint, body: &str) {
+        let entry = json!({"url": self.url(endpoint), "fetched_at": now_secs(), "body": body});
+        let _ = fs::create_dir_all(&self.cache_dir);
+        let _ = fs::write(self.cache_path(endpoint), entry.to_string());
+    }
+
+    /// GET `endpoint`, reusing a cached response younger than `max_age`.
+    pub fn get(&self, endpoint: Endpoint, max_age: Duration, headers: &[(&str, &str)]) -> Fetched {
+        if !self.allowed(endpoint) {
+            return Fetched::Blocked;
+        }
+        let cached = self.cached(endpoint);
+        match (&cached, self.policy) {
+            (Some((body, _)), Policy::CacheOnly) => return Fetched::Cached(body.clone()),
+            (None, Policy::CacheOnly) => return Fetched::Blocked,
+            (Some((body, age)), _) if *age < max_age => return Fetched::Cached(body.clone()),
+            _ => {}
+        }
+
+        let url = self.url(endpoint);
+        let timeout = self.timeout;
+        let headers: Vec<(String, String)> = headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        // Blocking reqwest must not run on a tokio worker; a dedicated thread keeps it isolated.
+        let response = std::thread::spawn(move || -> Option<(u16, String)> {
+            let client = reqwest::blocking::Client::builder().timeout(timeout).build().ok()?;
+            let mut req = client.get(&url);
+            for (k, v) in &headers {
+                req = req.header(k.as_str(), v.as_str());
+            }
+            let resp = req.send().ok()?;
+            let status = resp.status().as_u16();
+            Some((status, resp.text().ok()?))
+        })
+        .join()
+        .ok()
+        .flatten();
+
+        match response {
+            Some((status, body)) if (200..300).contains(&status) => {
+                self.store(endpoint, &body);
+                Fetched::Network(body)
+            }
+            Some((status, _)) => Fetched::Status(status),
+            None => cached.map(|(body, _)| Fetched::Cached(body)).unwrap_or(Fetched::Failed),
+        }
+    }
+}
+
+/// `network_endpoints` URL overrides. A credentialed endpoint only takes an
+/// override from the user or env layer, and only when it points at loopback.
+fn endpoint_overrides(config: &Config) -> BTreeMap<String, String> {
+    let entries = |v: Option<&Value>| -> Vec<(String, String)> {
+        v.and_then(|v| v.as_object())
+            .map(|m| m.iter().filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string()))).collect())
+            .unwrap_or_default()
+    };
+    let credentialed = |name: &str| Endpoint::ALL.iter().any(|e| e.name() == name && e.credentialed());
+
+    let mut urls: BTreeMap<String, String> = entries(config.get("network_endpoints"))
+        .into_iter()
+        .filter(|(name, _)| !credentialed(name))
+        .collect();
+    for layer in [Layer::User, Layer::Env] {
+        let values = config.layer(layer).and_then(|l| l.values.get("network_endpoints"));
+        for (name, url) in entries(values) {
+            if credentialed(&name) && is_loopback_url(&url) {
+                urls.insert(name, url);
+            }
+        }
+    }
+    urls
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// Local stand-in server answering every request with `status` and `body`.
+    fn stand_in(status: u16, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/VERSION", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (url, hits)
+    }
+
+    fn net(policy: Policy, url: &str, cache: &TempDir) -> Net {
+        let urls = BTreeMap::from([("yolo-version".to_string(), url.to_string())]);
+        Net::new(policy, None, urls, cache.path().to_path_buf())
+    }
+
+    #[test]
+    fn test_policy_resolve() {
+        assert_eq!(Policy::resolve(None, None), Policy::On);
+        assert_eq!(Policy::resolve(Some("cache-only"), None), Policy::CacheOnly);
+        assert_eq!(Policy::resolve(Some("bogus"), None), Policy::On);
+        assert_eq!(Policy::resolve(Some("on"), Some("1")), Policy::Off);
+        assert_eq!(Policy::resolve(Some("cache-only"), Some("0")), Policy::CacheOnly);
+    }
+
+    #[test]
+    fn test_credentialed_overrides_only_from_user_layers_to_loopback() {
+        let dir = TempDir::new().unwrap();
+        let endpoints = |urls: Value| serde_json::from_value(json!({"network_endpoints": urls})).unwrap();
+        let config = Config::load(dir.path()).with_layer_values(
+            Layer::Project,
+            endpoints(json!({"oauth-usage": "http://127.0.0.1:9/usage", "yolo-version": "https://mirror.test/VERSION"})),
+        );
+        let urls = endpoint_overrides(&config);
+        assert_eq!(urls.get("yolo-version").map(String::as_str), Some("https://mirror.test/VERSION"));
+        assert!(!urls.contains_key("oauth-usage"), "the project layer cannot redirect the OAuth token");
+
+        let config = config.with_layer_values(Layer::User, endpoints(json!({"oauth-usage": "https://evil.test/usage"})));
+        assert!(!endpoint_overrides(&config).contains_key("oauth-usage"));
+        let config = config.with_layer_values(Layer::User, endpoints(json!({"oauth-usage": "http://127.0.0.1:9/usage"})));
+        assert_eq!(endpoint_overrides(&config).get("oauth-usage").map(String::as_str), Some("http://127.0.0.1:9/usage"));
+    }
+
+    #[test]
+    fn test_on_fetches_once_then_serves_cache() {
+        let cache = TempDir::new().unwrap();
+        let (url, hits) = stand_in(200, "9.9.9\n");
+        let n = net(Policy::On, &url, &cache);
+        assert_eq!(n.get(Endpoint::YoloVersion, Duration::from_secs(60), &[]), Fetched::Network("9.9.9\n".into()));
+        assert_eq!(n.get(Endpoint::YoloVersion, Duration::from_secs(60), &[]), Fetched::Cached("9.9.9\n".into()));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        // max_age zero always refetches
+        assert!(matches!(n.get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Network(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_off_and_cache_only_never_hit_the_network() {
+        let cache = TempDir::new().unwrap();
+        let (url, hits) = stand_in(200, "1.0.0");
+        assert_eq!(net(Policy::CacheOnly, &url, &cache).get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Blocked);
+
+        net(Policy::On, &url, &cache).get(Endpoint::YoloVersion, Duration::ZERO, &[]);
+        assert_eq!(
+            net(Policy::CacheOnly, &url, &cache).get(Endpoint::YoloVersion, Duration::ZERO, &[]),
+            Fetched::Cached("1.0.0".into())
+        );
+        assert_eq!(net(Policy::Off, &url, &cache).get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Blocked);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_allowlist_blocks_other_endpoints() {
+        let cache = TempDir::new().unwrap();
+        let (url, hits) = stand_in(200, "1.0.0");
+        let mut n = net(Policy::On, &url, &cache);
+        n.allow = Some(vec!["oauth-usage".to_string()]);
+        assert_eq!(n.get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Blocked);
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+        assert!(n.allowed(Endpoint::OauthUsage));
+    }
+
+    #[test]
+    fn test_failure_falls_back_to_stale_cache() {
+        let cache = TempDir::new().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed = format!("http://{}/VERSION", listener.local_addr().unwrap());
+        drop(listener);
+
+        let n = net(Policy::On, &closed, &cache).with_timeout(Duration::from_millis(500));
+        assert_eq!(n.get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Failed);
+        n.store(Endpoint::YoloVersion, "2.0.0");
+        assert_eq!(n.get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Cached("2.0.0".into()));
+
+        // A non-2xx answer is reported, not masked; cache entries are keyed to their URL.
+        let (bad, _) = stand_in(401, "nope");
+        assert_eq!(net(Policy::On, &bad, &cache).get(Endpoint::YoloVersion, Duration::ZERO, &[]), Fetched::Status(401));
+    }
+}
@@ -3,6 +3,8 @@ use std::path::Path;
 use std::time::Instant;
 use serde_json::json;
 
+use super::frontmatter;
+
 /// Parse YAML frontmatter from a markdown file and return all key-value pairs as JSON.
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let start = Instant::now();
@@ -33,9 +35,24 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         }
     };
 
-    let frontmatter = parse_frontmatter_content(&content);
+    let frontmatter = match frontmatter::parse(&content) {
+        Ok(fm) => fm,
+        Err(e) => {
+            let out = json!({
+                "ok": false,
+                "cmd": "parse-frontmatter",
+                "error": e.message,
+                "line": e.line,
+                "column": e.column,
+            });
+            return Ok((serde_json::to_string(&out).map_err(|e| e.to_string())? + "\n", 1));
+        }
+    };
     let has_frontmatter = frontmatter.is_some();
-    let fm_value = frontmatter.unwrap_or_default();
+    // Scalars are reported as strings so `01` and `1` stay distinguishable for callers
+    let fm_value = frontmatter
+        .map(|fm| frontmatter::stringify_scalars(&serde_json::Value::Object(fm.fields)))
+        .unwrap_or_else(|| json!({}));
 
     let elapsed = start.elapsed().as_millis();
     let out = json!({
@@ -49,86 +66,6 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     Ok((serde_json::to_string(&out).map_err(|e| e.to_string())? + "\n", 0))
 }
 
-/// Parse frontmatter from raw content string. Returns None if no frontmatter block found.
-fn parse_frontmatter_content(content: &str) -> Option<serde_json::Map<String, serde_json::Value>> {
-    let mut lines = content.lines();
-
-    // First line must be "---"
-    let first = lines.next()?;
-    if first.trim() != "---" {
-        return None;
-    }
-
-    let mut map = serde_json::Map::new();
-    let mut current_key: Option<String> = None;
-    let mut current_list: Vec<serde_json::Value> = Vec::new();
-    let mut found_end = false;
-
-    for line in lines {
-        if line.trim() == "---" {
-            // Flush any pending list
-            if let Some(key) = current_key.take()
-                && !current_list.is_empty()
-            {
-                map.insert(key, serde_json::Value::Array(std::mem::take(&mut current_list)));
-            }
-            found_end = true;
-            break;
-        }
-
-        // Check for list item (  - "value" or  - value)
-        let trimmed = line.trim();
-        if trimmed.starts_with("- ") {
-            if current_key.is_some() {
-                let item = trimmed.strip_prefix("- ").unwrap().trim();
-                let item = item.trim_matches('"').trim_matches('\'');
-                current_list.push(serde_json::Value::String(item.to_string()));
-            }
-            continue;
-        }
-
-        // Key-value line
-        if let Some((key_part, val_part)) = line.split_once(':') {
-            // Flush any pending list
-            if let Some(key) = current_key.take()
-                && !current_list.is_empty()
-            {
-                map.insert(key, serde_json::Value::Array(std::mem::take(&mut current_list)));
-            }
-
-            let key = key_part.trim().to_string();
-            let val_raw = val_part.trim();
-
-            if val_raw.is_empty() {
-                // Multi-line value (list) coming next
-                current_key = Some(key);
-                current_list.clear();
-            } else if let Some(inner) = val_raw.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
-                // Inline array: key: [a, b, c]
-                let items: Vec<serde_json::Value> = inner
-                    .split(',')
-                    .map(|s| {
-                        let s = s.trim().trim_matches('"').trim_matches('\'');
-                        serde_json::Value::String(s.to_string())
-                    })
-                    .collect();
-                map.insert(key, serde_json::Value::Array(items));
-            } else {
-                // Simple scalar value
-                let val = val_raw.trim_matches('"').trim_matches('\'');
-                map.insert(key, serde_json::Value::String(val.to_string()));
-            }
-        }
-    }
-
-    if !found_end {
-        // No closing --- found
-        return None;
-    }
-
-    Some(map)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +170,38 @@ mod tests {
         assert_eq!(arr[0], "01");
         assert_eq!(arr[1], "02");
     }
+
+    #[test]
+    fn test_nested_and_typed_values() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("nested.md");
+        fs::write(&file, "---\nwave: 2\nautonomous: true\nmust_haves:\n  truths: [\"a\"]\n---\n").unwrap();
+
+        let (out, code) = execute(
+            &["yolo".into(), "parse-frontmatter".into(), file.to_string_lossy().to_string()],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["frontmatter"]["wave"], "2");
+        assert_eq!(parsed["frontmatter"]["autonomous"], "true");
+        assert_eq!(parsed["frontmatter"]["must_haves"]["truths"][0], "a");
+    }
+
+    #[test]
+    fn test_invalid_yaml_reports_position() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("bad.md");
+        fs::write(&file, "---\nphase: 1\ndepends_on: [01, 02\n---\n").unwrap();
+
+        let (out, code) = execute(
+            &["yolo".into(), "parse-frontmatter".into(), file.to_string_lossy().to_string()],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(code, 1);
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["ok"], false);
+        assert!(parsed["line"].as_u64().unwrap() >= 3);
+        assert!(parsed["column"].is_number());
+    }
 }
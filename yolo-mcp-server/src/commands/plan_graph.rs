@@ -0,0 +1,848 @@
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::frontmatter::{self, PlanFrontmatter};
+use super::generate_contract::count_tasks;
+use super::utils::sorted_phase_dirs;
+
+/// Builds the plan dependency graph and derives a wave schedule from it.
+///
+/// Usage: yolo plan-graph [<phase_dir|phases_dir>] [--format json|dot|mermaid]
+///
+/// A directory holding `*-PLAN.md` files is graphed as a single phase; any other
+/// directory is treated as the phases root and every `{NN}-{slug}/` under it is
+/// loaded (default: `.yolo-planning/phases`).
+///
+/// Checks:
+/// 1. depends_on / cross_phase_deps resolve to loaded plans
+/// 2. No dependency cycles (reported as strongly connected components)
+/// 3. Cross-phase deps only point at earlier phases
+/// 4. Declared `wave:` comes after every same-phase dependency's wave
+/// 5. Declared waves later than the computed optimum (needlessly serialised)
+///
+/// Computed waves are per phase (cross-phase deps are satisfied by phase order).
+/// The critical path is the longest chain weighted by task count.
+///
+/// Exit codes: 0=ok (warnings allowed), 1=errors (cycles, missing deps, wave conflicts)
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let mut format = "json".to_string();
+    let mut target: Option<PathBuf> = None;
+    let mut i = 2;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--format" {
+            format = args.get(i + 1).cloned().unwrap_or_default();
+            i += 2;
+            continue;
+        }
+        if let Some(v) = arg.strip_prefix("--format=") {
+            format = v.to_string();
+        } else if target.is_none() {
+            let p = PathBuf::from(arg);
+            target = Some(if p.is_absolute() { p } else { cwd.join(p) });
+        }
+        i += 1;
+    }
+    if !matches!(format.as_str(), "json" | "dot" | "mermaid") {
+        return Err(format!(
+            "Unknown format '{}'. Usage: yolo plan-graph [<dir>] [--format json|dot|mermaid]",
+            format
+        ));
+    }
+
+    let dir = target.unwrap_or_else(|| cwd.join(".yolo-planning/phases"));
+    if !dir.is_dir() {
+        let resp = json!({
+            "ok": false,
+            "cmd": "plan-graph",
+            "error": format!("Directory not found: {}", dir.display()),
+        });
+        return Ok((resp.to_string(), 1));
+    }
+
+    let graph = PlanGraph::load(&dir);
+    let analysis = graph.analyze();
+    let code = if analysis.ok() { 0 } else { 1 };
+
+    let out = match format.as_str() {
+        "dot" => graph.to_dot(&analysis),
+        "mermaid" => graph.to_mermaid(&analysis),
+        _ => graph.to_json(&dir, &analysis).to_string(),
+    };
+    Ok((out, code))
+}
+
+/// One plan in the graph. `id` is the canonical `{phase}-{plan}` form.
+#[derive(Debug, Clone)]
+pub struct PlanNode {
+    pub id: String,
+    pub phase: String,
+    pub plan: String,
+    pub path: PathBuf,
+    pub title: Option<String>,
+    pub declared_wave: Option<u32>,
+    pub tasks: usize,
+    pub frontmatter: PlanFrontmatter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanEdge {
+    /// Index of the dependency (must finish first).
+    pub from: usize,
+    /// Index of the dependent plan.
+    pub to: usize,
+    pub cross_phase: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphIssue {
+    /// "error" or "warning".
+    pub severity: &'static str,
+    pub kind: &'static str,
+    pub plan: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PlanGraph {
+    pub nodes: Vec<PlanNode>,
+    pub edges: Vec<PlanEdge>,
+    /// Problems found while loading (unreadable files, bad YAML, unresolved deps).
+    pub load_issues: Vec<GraphIssue>,
+}
+
+#[derive(Debug, Default)]
+pub struct GraphAnalysis {
+    pub cycles: Vec<Vec<usize>>,
+    /// Computed wave per node; empty when the graph has cycles.
+    pub waves: Vec<u32>,
+    pub critical_path: Vec<usize>,
+    pub critical_tasks: usize,
+    pub issues: Vec<GraphIssue>,
+}
+
+impl GraphAnalysis {
+    pub fn ok(&self) -> bool {
+        !self.issues.iter().any(|i| i.severity == "error")
+    }
+}
+
+/// Zero-pad numeric ids to two digits ("1" -> "01"); leave anything else alone.
+fn pad(s: &str) -> String {
+    let t = s.trim().trim_matches('"');
+    match t.parse::<u32>() {
+        Ok(n) => format!("{:02}", n),
+        Err(_) => t.to_string(),
+    }
+}
+
+/// Resolve a `depends_on` entry to a canonical id. `"2"` and `"02"` are plans
+/// in the same phase; `"03-02"` names phase 03 plan 02.
+fn dep_id(dep: &str, phase: &str) -> String {
+    match dep.trim().rsplit_once('-') {
+        Some((ph, pl)) => format!("{}-{}", pad(ph), pad(pl)),
+        None => format!("{}-{}", phase, pad(dep)),
+    }
+}
+
+/// Plan files in a directory, sorted by name.
+fn plan_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.file_name()
+                            .map(|n| n.to_string_lossy().ends_with("-PLAN.md"))
+                            .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+impl PlanGraph {
+    /// Load a single phase directory, or every phase under a phases root.
+    pub fn load(dir: &Path) -> Self {
+        let phase_dirs: Vec<PathBuf> = if plan_files(dir).is_empty() {
+            sorted_phase_dirs(dir).into_iter().map(|(_, p)| p).collect()
+        } else {
+            vec![dir.to_path_buf()]
+        };
+
+        let mut graph = PlanGraph::default();
+        for phase_dir in &phase_dirs {
+            graph.load_phase(phase_dir);
+        }
+        graph.nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        graph.link();
+        graph
+    }
+
+    fn load_phase(&mut self, phase_dir: &Path) {
+        let dir_name = phase_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let dir_phase = dir_name
+            .split_once('-')
+            .map(|(p, _)| p)
+            .filter(|p| p.chars().all(|c| c.is_ascii_digit()) && !p.is_empty())
+            .map(pad);
+
+        for path in plan_files(phase_dir) {
+            let stem = path
+                .file_name()
+                .map(|n| n.to_string_lossy().trim_end_matches("-PLAN.md").to_string())
+                .unwrap_or_default();
+            let content = match fs::read_to_string(&path) {
+                Ok(c) => c,
+                Err(e) => {
+                    self.load_issues.push(GraphIssue {
+                        severity: "error",
+                        kind: "unreadable",
+                        plan: stem,
+                        message: format!("Failed to read {}: {}", path.display(), e),
+                    });
+                    continue;
+                }
+            };
+            let (fm, body) = match frontmatter::parse(&content) {
+                Ok(Some(fm)) => (fm.plan(), fm.body),
+                Ok(None) => (PlanFrontmatter::default(), content.clone()),
+                Err(e) => {
+                    self.load_issues.push(GraphIssue {
+                        severity: "error",
+                        kind: "invalid_frontmatter",
+                        plan: stem.clone(),
+                        message: format!("Invalid YAML frontmatter at {}", e),
+                    });
+                    (PlanFrontmatter::default(), content.clone())
+                }
+            };
+
+            let phase = dir_phase
+                .clone()
+                .or_else(|| fm.phase.as_deref().map(pad))
+                .unwrap_or_else(|| "00".to_string());
+            let plan_raw = fm
+                .plan
+                .clone()
+                .unwrap_or_else(|| stem.rsplit('-').next().unwrap_or(&stem).to_string());
+            let plan = pad(plan_raw.rsplit('-').next().unwrap_or(&plan_raw));
+
+            self.nodes.push(PlanNode {
+                id: format!("{}-{}", phase, plan),
+                phase,
+                plan,
+                path,
+                title: fm.title.clone(),
+                declared_wave: fm.wave_number(),
+                tasks: count_tasks(&body),
+                frontmatter: fm,
+            });
+        }
+    }
+
+    /// Resolve every declared dependency into an edge.
+    fn link(&mut self) {
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if index.insert(node.id.clone(), i).is_some() {
+                self.load_issues.push(GraphIssue {
+                    severity: "error",
+                    kind: "duplicate_plan",
+                    plan: node.id.clone(),
+                    message: format!("Plan id {} is declared by more than one file", node.id),
+                });
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (to, node) in self.nodes.iter().enumerate() {
+            let deps = node
+                .frontmatter
+                .depends_on
+                .iter()
+                .map(|d| (dep_id(d, &node.phase), false))
+                .chain(node.frontmatter.cross_phase_deps.iter().map(|d| {
+                    let plan = d.plan.rsplit('-').next().unwrap_or(&d.plan);
+                    (format!("{}-{}", pad(&d.phase), pad(plan)), true)
+                }));
+            for (id, declared_cross) in deps {
+                match index.get(&id) {
+                    Some(&from) => {
+                        let cross_phase = declared_cross || self.nodes[from].phase != node.phase;
+                        let edge = PlanEdge { from, to, cross_phase };
+                        if !edges.contains(&edge) {
+                            edges.push(edge);
+                        }
+                    }
+                    // Single-phase graphs can't see other phases; validate-plan covers those.
+                    None if declared_cross || !id.starts_with(&format!("{}-", node.phase)) => {
+                        let loaded = self.nodes.iter().any(|n| id.starts_with(&format!("{}-", n.phase)));
+                        if loaded {
+                            self.load_issues.push(GraphIssue {
+                                severity: "error",
+                                kind: "missing_dependency",
+                                plan: node.id.clone(),
+                                message: format!("Plan {} depends on {}, which does not exist", node.id, id),
+                            });
+                        }
+                    }
+                    None => self.load_issues.push(GraphIssue {
+                        severity: "error",
+                        kind: "missing_dependency",
+                        plan: node.id.clone(),
+                        message: format!("Plan {} depends on {}, which does not exist", node.id, id),
+                    }),
+                }
+            }
+        }
+        self.edges = edges;
+    }
+
+    fn deps_of(&self, node: usize) -> impl Iterator<Item = &PlanEdge> {
+        self.edges.iter().filter(move |e| e.to == node)
+    }
+
+    pub fn analyze(&self) -> GraphAnalysis {
+        let mut analysis = GraphAnalysis {
+            issues: self.load_issues.clone(),
+            ..Default::default()
+        };
+
+        analysis.cycles = self.cycles();
+        for cycle in &analysis.cycles {
+            let ids: Vec<&str> = cycle.iter().map(|&i| self.nodes[i].id.as_str()).collect();
+            analysis.issues.push(GraphIssue {
+                severity: "error",
+                kind: "cycle",
+                plan: ids[0].to_string(),
+                message: format!("Dependency cycle: {}", ids.join(" -> ")),
+            });
+        }
+
+        for edge in self.edges.iter().filter(|e| e.cross_phase) {
+            let (dep, node) = (&self.nodes[edge.from], &self.nodes[edge.to]);
+            if dep.phase >= node.phase {
+                analysis.issues.push(GraphIssue {
+                    severity: "error",
+                    kind: "phase_order",
+                    plan: node.id.clone(),
+                    message: format!(
+                        "Plan {} depends on {}, which is not in an earlier phase",
+                        node.id, dep.id
+                    ),
+                });
+            }
+        }
+
+        if !analysis.cycles.is_empty() {
+            return analysis;
+        }
+
+        let order = self.topo_order();
+
+        // Waves: per phase, one after the latest same-phase dependency.
+        let mut waves = vec![1u32; self.nodes.len()];
+        for &n in &order {
+            let wave = self
+                .deps_of(n)
+                .filter(|e| !e.cross_phase)
+                .map(|e| waves[e.from] + 1)
+                .max()
+                .unwrap_or(1);
+            waves[n] = wave;
+        }
+
+        // Critical path: longest chain by task count (a plan with no tasks counts as 1).
+        let mut dist = vec![0usize; self.nodes.len()];
+        let mut prev: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        for &n in &order {
+            let best = self.deps_of(n).max_by_key(|e| (dist[e.from], std::cmp::Reverse(e.from)));
+            let base = best.map(|e| dist[e.from]).unwrap_or(0);
+            prev[n] = best.map(|e| e.from);
+            dist[n] = base + self.nodes[n].tasks.max(1);
+        }
+        if let Some(end) = (0..self.nodes.len()).max_by_key(|&n| (dist[n], std::cmp::Reverse(n))) {
+            let mut path = vec![end];
+            while let Some(p) = prev[*path.last().unwrap()] {
+                path.push(p);
+            }
+            path.reverse();
+            analysis.critical_tasks = dist[end];
+            analysis.critical_path = path;
+        }
+
+        for (n, node) in self.nodes.iter().enumerate() {
+            let Some(declared) = node.declared_wave else {
+                analysis.issues.push(GraphIssue {
+                    severity: "warning",
+                    kind: "wave_missing",
+                    plan: node.id.clone(),
+                    message: format!("Plan {} declares no wave; computed wave is {}", node.id, waves[n]),
+                });
+                continue;
+            };
+            let mut conflict = false;
+            for edge in self.deps_of(n).filter(|e| !e.cross_phase) {
+                let dep = &self.nodes[edge.from];
+                if let Some(dep_wave) = dep.declared_wave
+                    && declared <= dep_wave
+                {
+                    conflict = true;
+                    analysis.issues.push(GraphIssue {
+                        severity: "error",
+                        kind: "wave_conflict",
+                        plan: node.id.clone(),
+                        message: format!(
+                            "Plan {} (wave {}) depends on {} (wave {}) but is not in a later wave",
+                            node.id, declared, dep.id, dep_wave
+                        ),
+                    });
+                }
+            }
+            if !conflict && declared > waves[n] {
+                analysis.issues.push(GraphIssue {
+                    severity: "warning",
+                    kind: "wave_serialized",
+                    plan: node.id.clone(),
+                    message: format!(
+                        "Plan {} declares wave {} but its dependencies allow wave {}",
+                        node.id, declared, waves[n]
+                    ),
+                });
+            }
+        }
+
+        analysis.waves = waves;
+        analysis
+    }
+
+    /// Strongly connected components with more than one node, plus self-loops (Tarjan).
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        struct State {
+            index: usize,
+            indices: Vec<Option<usize>>,
+            lowlink: Vec<usize>,
+            on_stack: Vec<bool>,
+            stack: Vec<usize>,
+            sccs: Vec<Vec<usize>>,
+        }
+
+        fn connect(g: &PlanGraph, v: usize, s: &mut State) {
+            s.indices[v] = Some(s.index);
+            s.lowlink[v] = s.index;
+            s.index += 1;
+            s.stack.push(v);
+            s.on_stack[v] = true;
+
+            for w in g.edges.iter().filter(|e| e.from == v).map(|e| e.to) {
+                match s.indices[w] {
+                    None => {
+                        connect(g, w, s);
+                        s.lowlink[v] = s.lowlink[v].min(s.lowlink[w]);
+                    }
+                    Some(wi) if s.on_stack[w] => s.lowlink[v] = s.lowlink[v].min(wi),
+                    _ => {}
+                }
+            }
+
+            if Some(s.lowlink[v]) == s.indices[v] {
+                let mut scc = Vec::new();
+                while let Some(w) = s.stack.pop() {
+                    s.on_stack[w] = false;
+                    scc.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                s.sccs.push(scc);
+            }
+        }
+
+        let n = self.nodes.len();
+        let mut state = State {
+            index: 0,
+            indices: vec![None; n],
+            lowlink: vec![0; n],
+            on_stack: vec![false; n],
+            stack: Vec::new(),
+            sccs: Vec::new(),
+        };
+        for v in 0..n {
+            if state.indices[v].is_none() {
+                connect(self, v, &mut state);
+            }
+        }
+
+        let mut cycles: Vec<Vec<usize>> = state
+            .sccs
+            .into_iter()
+            .filter(|scc| {
+                scc.len() > 1 || self.edges.iter().any(|e| e.from == scc[0] && e.to == scc[0])
+            })
+            .map(|mut scc| {
+                scc.sort();
+                scc
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Kahn's algorithm, lowest id first among ready nodes. Assumes no cycles.
+    fn topo_order(&self) -> Vec<usize> {
+        let mut indegree = vec![0usize; self.nodes.len()];
+        for e in &self.edges {
+            indegree[e.to] += 1;
+        }
+        let mut ready: std::collections::BTreeSet<usize> =
+            (0..self.nodes.len()).filter(|&n| indegree[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(n) = ready.pop_first() {
+            order.push(n);
+            for e in self.edges.iter().filter(|e| e.from == n) {
+                indegree[e.to] -= 1;
+                if indegree[e.to] == 0 {
+                    ready.insert(e.to);
+                }
+            }
+        }
+        order
+    }
+
+    fn to_json(&self, dir: &Path, analysis: &GraphAnalysis) -> Value {
+        let ids = |list: &[usize]| -> Vec<String> {
+            list.iter().map(|&i| self.nodes[i].id.clone()).collect()
+        };
+
+        let plans: Vec<Value> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(n, node)| {
+                let deps: Vec<String> = self.deps_of(n).map(|e| self.nodes[e.from].id.clone()).collect();
+                json!({
+                    "id": node.id,
+                    "phase": node.phase,
+                    "plan": node.plan,
+                    "title": node.title,
+                    "path": node.path.to_string_lossy(),
+                    "tasks": node.tasks,
+                    "declared_wave": node.declared_wave,
+                    "computed_wave": analysis.waves.get(n),
+                    "depends_on": deps,
+                })
+            })
+            .collect();
+
+        let edges: Vec<Value> = self
+            .edges
+            .iter()
+            .map(|e| {
+                json!({
+                    "from": self.nodes[e.from].id,
+                    "to": self.nodes[e.to].id,
+                    "cross_phase": e.cross_phase,
+                })
+            })
+            .collect();
+
+        let mut grouped: BTreeMap<(String, u32), Vec<String>> = BTreeMap::new();
+        for (n, &wave) in analysis.waves.iter().enumerate() {
+            grouped
+                .entry((self.nodes[n].phase.clone(), wave))
+                .or_default()
+                .push(self.nodes[n].id.clone());
+        }
+        let waves: Vec<Value> = grouped
+            .into_iter()
+            .map(|((phase, wave), plans)| json!({"phase": phase, "wave": wave, "plans": plans}))
+            .collect();
+
+        let issues: Vec<Value> = analysis
+            .issues
+            .iter()
+            .map(|i| json!({"severity": i.severity, "type": i.kind, "plan": i.plan, "message": i.message}))
+            .collect();
+
+        let critical = if analysis.cycles.is_empty() {
+            json!({"plans": ids(&analysis.critical_path), "tasks": analysis.critical_tasks})
+        } else {
+            Value::Null
+        };
+
+        json!({
+            "ok": analysis.ok(),
+            "cmd": "plan-graph",
+            "dir": dir.to_string_lossy(),
+            "plans": plans,
+            "edges": edges,
+            "cycles": analysis.cycles.iter().map(|c| ids(c)).collect::<Vec<_>>(),
+            "waves": waves,
+            "critical_path": critical,
+            "issues": issues,
+        })
+    }
+
+    fn on_critical_path(analysis: &GraphAnalysis, edge: &PlanEdge) -> bool {
+        analysis
+            .critical_path
+            .windows(2)
+            .any(|w| w[0] == edge.from && w[1] == edge.to)
+    }
+
+    fn phases(&self) -> Vec<&str> {
+        let mut phases: Vec<&str> = self.nodes.iter().map(|n| n.phase.as_str()).collect();
+        phases.dedup();
+        phases
+    }
+
+    fn to_dot(&self, analysis: &GraphAnalysis) -> String {
+        let esc = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut out = String::from("digraph plans {\n  rankdir=LR;\n  node [shape=box];\n");
+        for phase in self.phases() {
+            out.push_str(&format!("  subgraph cluster_{} {{\n    label=\"Phase {}\";\n", phase, phase));
+            for (n, node) in self.nodes.iter().enumerate().filter(|(_, x)| x.phase == phase) {
+                let mut label = node.id.clone();
+                if let Some(title) = &node.title {
+                    label.push_str(&format!("\\n{}", esc(title)));
+                }
+                if let Some(w) = analysis.waves.get(n) {
+                    label.push_str(&format!("\\nwave {}", w));
+                }
+                let style = if analysis.critical_path.contains(&n) {
+                    ", color=red, penwidth=2"
+                } else {
+                    ""
+                };
+                out.push_str(&format!("    \"{}\" [label=\"{}\"{}];\n", node.id, label, style));
+            }
+            out.push_str("  }\n");
+        }
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            if edge.cross_phase {
+                attrs.push("style=dashed");
+            }
+            if Self::on_critical_path(analysis, edge) {
+                attrs.push("color=red");
+                attrs.push("penwidth=2");
+            }
+            let attrs = if attrs.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attrs.join(", "))
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\"{};\n",
+                self.nodes[edge.from].id, self.nodes[edge.to].id, attrs
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_mermaid(&self, analysis: &GraphAnalysis) -> String {
+        let node_id = |n: usize| format!("p{}", self.nodes[n].id.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+        let mut out = String::from("flowchart LR\n");
+        for phase in self.phases() {
+            out.push_str(&format!("  subgraph phase_{}[\"Phase {}\"]\n", phase, phase));
+            for (n, node) in self.nodes.iter().enumerate().filter(|(_, x)| x.phase == phase) {
+                let mut label = node.id.clone();
+                if let Some(title) = &node.title {
+                    label.push_str(&format!(": {}", title.replace('"', "#quot;")));
+                }
+                out.push_str(&format!("    {}[\"{}\"]\n", node_id(n), label));
+            }
+            out.push_str("  end\n");
+        }
+        for edge in &self.edges {
+            let arrow = if edge.cross_phase { "-.->" } else { "-->" };
+            out.push_str(&format!("  {} {} {}\n", node_id(edge.from), arrow, node_id(edge.to)));
+        }
+        if !analysis.critical_path.is_empty() {
+            let critical: Vec<String> = analysis.critical_path.iter().map(|&n| node_id(n)).collect();
+            out.push_str("  classDef critical stroke:#d33,stroke-width:2px\n");
+            out.push_str(&format!("  class {} critical\n", critical.join(",")));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_plan(dir: &Path, name: &str, fm: &str, tasks: usize) {
+        let mut body = String::from("# Plan\n\n## Tasks\n\n");
+        for t in 1..=tasks {
+            body.push_str(&format!("### Task {}: Step {}\n\nDo it.\n\n", t, t));
+        }
+        fs::write(dir.join(name), format!("---\n{}\n---\n{}", fm, body)).unwrap();
+    }
+
+    fn run(dir: &Path, extra: &[&str]) -> (Value, i32) {
+        let mut args = vec!["yolo".to_string(), "plan-graph".to_string(), dir.to_string_lossy().to_string()];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        let (out, code) = execute(&args, dir).unwrap();
+        (serde_json::from_str(&out).unwrap(), code)
+    }
+
+    fn phase_with_chain(root: &Path) -> PathBuf {
+        let phase = root.join("01-core");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "01-01-PLAN.md", "phase: 1\nplan: 1\ntitle: Base\nwave: 1\ndepends_on: []", 2);
+        write_plan(&phase, "01-02-PLAN.md", "phase: 1\nplan: 2\ntitle: Side\nwave: 1\ndepends_on: []", 1);
+        write_plan(&phase, "01-03-PLAN.md", "phase: 1\nplan: 3\ntitle: Top\nwave: 2\ndepends_on: [1, \"01-02\"]", 3);
+        phase
+    }
+
+    #[test]
+    fn test_dep_id_normalization() {
+        assert_eq!(dep_id("1", "03"), "03-01");
+        assert_eq!(dep_id("02", "03"), "03-02");
+        assert_eq!(dep_id("05-01", "03"), "05-01");
+    }
+
+    #[test]
+    fn test_waves_and_critical_path() {
+        let dir = tempdir().unwrap();
+        let phase = phase_with_chain(dir.path());
+        let (v, code) = run(&phase, &[]);
+        assert_eq!(code, 0, "{}", v);
+        assert_eq!(v["ok"], true);
+        assert_eq!(v["waves"][0]["plans"], json!(["01-01", "01-02"]));
+        assert_eq!(v["waves"][1]["plans"], json!(["01-03"]));
+        assert_eq!(v["critical_path"]["plans"], json!(["01-01", "01-03"]));
+        assert_eq!(v["critical_path"]["tasks"], 5);
+        assert!(v["issues"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detected() {
+        let dir = tempdir().unwrap();
+        let phase = dir.path().join("02-loop");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "02-01-PLAN.md", "plan: 1\nwave: 1\ndepends_on: [\"03\"]", 1);
+        write_plan(&phase, "02-02-PLAN.md", "plan: 2\nwave: 1\ndepends_on: [\"01\"]", 1);
+        write_plan(&phase, "02-03-PLAN.md", "plan: 3\nwave: 1\ndepends_on: [\"02\"]", 1);
+        write_plan(&phase, "02-04-PLAN.md", "plan: 4\nwave: 1\ndepends_on: [\"04\"]", 1);
+        let (v, code) = run(&phase, &[]);
+        assert_eq!(code, 1);
+        assert_eq!(v["cycles"], json!([["02-01", "02-02", "02-03"], ["02-04"]]));
+        assert!(v["critical_path"].is_null());
+    }
+
+    #[test]
+    fn test_wave_conflict_and_serialized() {
+        let dir = tempdir().unwrap();
+        let phase = dir.path().join("01-core");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "01-01-PLAN.md", "plan: 1\nwave: 2\ndepends_on: []", 1);
+        write_plan(&phase, "01-02-PLAN.md", "plan: 2\nwave: 2\ndepends_on: [1]", 1);
+        write_plan(&phase, "01-03-PLAN.md", "plan: 3\nwave: 3\ndepends_on: []", 1);
+        let (v, code) = run(&phase, &[]);
+        assert_eq!(code, 1);
+        let kinds: Vec<(&str, &str)> = v["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| (i["type"].as_str().unwrap(), i["plan"].as_str().unwrap()))
+            .collect();
+        assert!(kinds.contains(&("wave_serialized", "01-01")));
+        assert!(kinds.contains(&("wave_conflict", "01-02")));
+        assert!(kinds.contains(&("wave_serialized", "01-03")));
+    }
+
+    #[test]
+    fn test_missing_dependency() {
+        let dir = tempdir().unwrap();
+        let phase = dir.path().join("01-core");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "01-01-PLAN.md", "plan: 1\nwave: 1\ndepends_on: [\"07\"]", 1);
+        let (v, code) = run(&phase, &[]);
+        assert_eq!(code, 1);
+        assert_eq!(v["issues"][0]["type"], "missing_dependency");
+    }
+
+    #[test]
+    fn test_cross_phase_edges_across_root() {
+        let dir = tempdir().unwrap();
+        phase_with_chain(dir.path());
+        let phase2 = dir.path().join("02-next");
+        fs::create_dir_all(&phase2).unwrap();
+        write_plan(
+            &phase2,
+            "02-01-PLAN.md",
+            "plan: 1\nwave: 1\ndepends_on: []\ncross_phase_deps:\n  - \"phase:01:plan:03\"",
+            1,
+        );
+        let (v, code) = run(dir.path(), &[]);
+        assert_eq!(code, 0, "{}", v);
+        let edge = v["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["to"] == "02-01")
+            .unwrap();
+        assert_eq!(edge["from"], "01-03");
+        assert_eq!(edge["cross_phase"], true);
+        // Cross-phase deps don't push the plan into a later wave of its own phase.
+        assert_eq!(v["plans"][3]["computed_wave"], 1);
+        assert_eq!(v["critical_path"]["plans"], json!(["01-01", "01-03", "02-01"]));
+    }
+
+    #[test]
+    fn test_cross_phase_dep_outside_single_phase_is_ignored() {
+        let dir = tempdir().unwrap();
+        let phase = dir.path().join("02-next");
+        fs::create_dir_all(&phase).unwrap();
+        write_plan(&phase, "02-01-PLAN.md", "plan: 1\nwave: 1\ndepends_on: [\"01-03\"]", 1);
+        let (v, code) = run(&phase, &[]);
+        assert_eq!(code, 0, "{}", v);
+        assert!(v["edges"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_dot_and_mermaid_output() {
+        let dir = tempdir().unwrap();
+        let phase = phase_with_chain(dir.path());
+        let args = |f: &str| {
+            vec!["yolo".to_string(), "plan-graph".to_string(), phase.to_string_lossy().to_string(), "--format".to_string(), f.to_string()]
+        };
+
+        let (dot, code) = execute(&args("dot"), dir.path()).unwrap();
+        assert_eq!(code, 0);
+        assert!(dot.starts_with("digraph plans {"));
+        assert!(dot.contains("subgraph cluster_01"));
+        assert!(dot.contains("\"01-01\" -> \"01-03\" [color=red, penwidth=2];"));
+        assert!(dot.contains("\"01-02\" -> \"01-03\";"));
+
+        let (mmd, _) = execute(&args("mermaid"), dir.path()).unwrap();
+        assert!(mmd.starts_with("flowchart LR\n"));
+        assert!(mmd.contains("p01_01[\"01-01: Base\"]"));
+        assert!(mmd.contains("p01_02 --> p01_03"));
+        assert!(mmd.contains("class p01_01,p01_03 critical"));
+    }
+
+    #[test]
+    fn test_unknown_format_and_missing_dir() {
+        let dir = tempdir().unwrap();
+        let args: Vec<String> = vec!["yolo".into(), "plan-graph".into(), "--format".into(), "svg".into()];
+        assert!(execute(&args, dir.path()).is_err());
+
+        let (v, code) = run(&dir.path().join("nope"), &[]);
+        assert_eq!(code, 1);
+        assert_eq!(v["ok"], false);
+    }
+}
@@ -63,6 +63,7 @@ fn ensure_transient_ignore(cwd: &Path) {
 # Metrics & cost tracking
 .metrics/
 .cost-ledger.json
+.cost-forecast.json
 
 # Caching
 .cache/
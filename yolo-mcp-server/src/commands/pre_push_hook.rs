@@ -16,10 +16,12 @@ pub fn execute(_args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
         }
     };
 
-    // Guard: skip if Cargo.toml doesn't exist (not a YOLO repo)
-    if !root.join("Cargo.toml").exists() {
-        return Ok(("".to_string(), 0));
-    }
+    // Guard: skip when none of the version files exist (nothing to keep in sync)
+    let file_count = match super::version_files::load(&root) {
+        Ok((files, _)) if files.iter().any(|f| f.exists(&root)) => files.len(),
+        Ok(_) => return Ok(("".to_string(), 0)),
+        Err(e) => return Ok((format!("WARNING: version check failed: {e}"), 0)),
+    };
 
     // Call bump_version::execute with --verify
     let verify_args = vec![
@@ -42,7 +44,7 @@ pub fn execute(_args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
                     }
                 }
                 msg.push_str("\n  Run: yolo bump-version --verify\n");
-                msg.push_str("  to see details, then manually sync the 4 version files.\n");
+                msg.push_str(&format!("  to see details, then manually sync the {} version files.\n", file_count));
                 Ok((msg, 1))
             } else {
                 Ok(("".to_string(), 0))
@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Map, Value};
+
+use super::atomic_io;
+use super::utils;
+
+const USAGE: &str = "Usage: yolo pricing [show [model]] | set <model> input=<usd> output=<usd> cache_read=<usd> cache_write=<usd> | alias <name> <model>";
+
+const BUILTIN: &str = include_str!("../../../config/pricing.json");
+
+/// USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rates {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
+impl Rates {
+    fn from_json(v: &Value) -> Option<Self> {
+        Some(Self {
+            input: v.get("input")?.as_f64()?,
+            output: v.get("output")?.as_f64()?,
+            cache_read: v.get("cache_read")?.as_f64()?,
+            cache_write: v.get("cache_write")?.as_f64()?,
+        })
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "input": self.input,
+            "output": self.output,
+            "cache_read": self.cache_read,
+            "cache_write": self.cache_write,
+        })
+    }
+
+    /// Dollar cost of the given token counts.
+    pub fn cost(&self, input: f64, output: f64, cache_read: f64, cache_write: f64) -> f64 {
+        (input * self.input + output * self.output + cache_read * self.cache_read + cache_write * self.cache_write)
+            / 1_000_000.0
+    }
+}
+
+/// Per-model token rates: the table shipped in `config/pricing.json`, with
+/// `.yolo-planning/pricing.json` layered on top so a project can add models
+/// or track price changes without waiting for a plugin release.
+#[derive(Debug, Default)]
+pub struct Pricing {
+    pub updated: Option<String>,
+    aliases: BTreeMap<String, String>,
+    models: BTreeMap<String, Rates>,
+}
+
+impl Pricing {
+    pub fn load(cwd: &Path) -> Self {
+        let base = utils::plugin_root(cwd)
+            .and_then(|root| fs::read_to_string(root.join("config").join("pricing.json")).ok())
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+            .unwrap_or_else(|| serde_json::from_str(BUILTIN).unwrap_or_default());
+        let mut pricing = Self::default();
+        pricing.merge(&base);
+        if let Some(project) = fs::read_to_string(project_path(cwd))
+            .ok()
+            .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        {
+            pricing.merge(&project);
+        }
+        pricing
+    }
+
+    fn merge(&mut self, table: &Value) {
+        if let Some(updated) = table.get("updated").and_then(Value::as_str) {
+            self.updated = Some(updated.to_string());
+        }
+        for (name, model) in table.get("aliases").and_then(Value::as_object).into_iter().flatten() {
+            if let Some(model) = model.as_str() {
+                self.aliases.insert(name.clone(), model.to_string());
+            }
+        }
+        for (model, rates) in table.get("models").and_then(Value::as_object).into_iter().flatten() {
+            if let Some(rates) = Rates::from_json(rates) {
+                self.models.insert(model.clone(), rates);
+            }
+        }
+    }
+
+    /// The table entry for a model ID, an alias (`sonnet`) or a known ID's
+    /// `-YYYYMMDD`/`-latest` variant (`claude-sonnet-4-5-20250929`). Any other
+    /// suffix is a different model (`claude-opus-4-7` is not `claude-opus-4`)
+    /// and resolves to None so reports flag it as unpriced.
+    pub fn resolve(&self, model: &str) -> Option<(&str, Rates)> {
+        let model = self.canonical(model);
+        if let Some((id, rates)) = self.models.get_key_value(model) {
+            return Some((id.as_str(), *rates));
+        }
+        self.models
+            .iter()
+            .find(|(id, _)| model.strip_prefix(id.as_str()).is_some_and(is_variant_suffix))
+            .map(|(id, rates)| (id.as_str(), *rates))
+    }
+
+    /// The model ID an alias stands for, or `model` itself.
+    pub fn canonical<'a>(&'a self, model: &'a str) -> &'a str {
+        self.aliases.get(model).map(String::as_str).unwrap_or(model)
+    }
+
+    pub fn rates(&self, model: &str) -> Option<Rates> {
+        self.resolve(model).map(|(_, rates)| rates)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let models: Map<String, Value> = self.models.iter().map(|(k, r)| (k.clone(), r.to_json())).collect();
+        json!({
+            "updated": self.updated,
+            "unit": "USD per million tokens",
+            "aliases": self.aliases,
+            "models": models,
+        })
+    }
+}
+
+/// `-latest` or a `-YYYYMMDD` snapshot date.
+fn is_variant_suffix(suffix: &str) -> bool {
+    match suffix.strip_prefix('-') {
+        Some("latest") => true,
+        Some(date) => date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()),
+        None => false,
+    }
+}
+
+fn project_path(cwd: &Path) -> PathBuf {
+    cwd.join(".yolo-planning").join("pricing.json")
+}
+
+/// Applies `edit` to the project override table and writes it back.
+fn edit_project_table(cwd: &Path, edit: impl FnOnce(&mut Map<String, Value>)) -> Result<(), String> {
+    let path = project_path(cwd);
+    let mut table: Map<String, Value> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    edit(&mut table);
+    table.insert("updated".into(), json!(chrono::Utc::now().format("%Y-%m-%d").to_string()));
+    let body = serde_json::to_string_pretty(&Value::Object(table)).unwrap_or_default() + "\n";
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    atomic_io::atomic_write(&path, body.as_bytes()).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// CLI entry point: `yolo pricing [show [model] | set <model> k=v... | alias <name> <model>]`
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let action = args.get(2).map(String::as_str).unwrap_or("show");
+    match action {
+        "show" => {
+            let pricing = Pricing::load(cwd);
+            let Some(model) = args.get(3) else {
+                return Ok((serde_json::to_string_pretty(&pricing.to_json()).unwrap_or_default() + "\n", 0));
+            };
+            match pricing.resolve(model) {
+                Some((id, rates)) => {
+                    let out = json!({"model": model, "priced_as": id, "rates": rates.to_json()});
+                    Ok((out.to_string() + "\n", 0))
+                }
+                None => Ok((format!("No pricing for model '{}'\n", model), 1)),
+            }
+        }
+        "set" => {
+            let model = args.get(3).filter(|m| !m.contains('=')).ok_or(USAGE)?;
+            let pricing = Pricing::load(cwd);
+            let model = pricing.canonical(model).to_string();
+            let mut rates = pricing.rates(&model).map(Rates::to_json).unwrap_or_else(|| json!({}));
+            for pair in &args[4..] {
+                let (key, value) = pair.split_once('=').ok_or(USAGE)?;
+                if !["input", "output", "cache_read", "cache_write"].contains(&key) {
+                    return Err(format!("unknown rate '{}'. Valid: input, output, cache_read, cache_write", key));
+                }
+                let usd: f64 = value.parse().map_err(|_| format!("rate '{}' is not a number: {}", key, value))?;
+                if usd < 0.0 {
+                    return Err(format!("rate '{}' must not be negative", key));
+                }
+                rates[key] = json!(usd);
+            }
+            let rates = Rates::from_json(&rates).ok_or_else(|| {
+                format!("'{}' is not in the pricing table yet; set all of input, output, cache_read and cache_write", model)
+            })?;
+            edit_project_table(cwd, |table| {
+                let models = table.entry("models").or_insert_with(|| json!({}));
+                if let Some(models) = models.as_object_mut() {
+                    models.insert(model.clone(), rates.to_json());
+                }
+            })?;
+            Ok((json!({"model": model, "rates": rates.to_json()}).to_string() + "\n", 0))
+        }
+        "alias" => {
+            let (name, model) = match (args.get(3), args.get(4)) {
+                (Some(name), Some(model)) => (name, model),
+                _ => return Err(USAGE.to_string()),
+            };
+            if Pricing::load(cwd).rates(model).is_none() {
+                return Err(format!("no pricing for model '{}'", model));
+            }
+            edit_project_table(cwd, |table| {
+                let aliases = table.entry("aliases").or_insert_with(|| json!({}));
+                if let Some(aliases) = aliases.as_object_mut() {
+                    aliases.insert(name.clone(), json!(model));
+                }
+            })?;
+            Ok((json!({"alias": name, "model": model}).to_string() + "\n", 0))
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(rest: &[&str]) -> Vec<String> {
+        ["yolo", "pricing"].iter().chain(rest).map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolve_ids_aliases_and_dated_variants() {
+        let dir = tempfile::tempdir().unwrap();
+        let pricing = Pricing::load(dir.path());
+        assert_eq!(pricing.resolve("sonnet").unwrap().0, "claude-sonnet-4-5");
+        assert_eq!(pricing.resolve("claude-sonnet-4-5-20250929").unwrap().0, "claude-sonnet-4-5");
+        // The longest prefix wins: opus-4-1 is not priced as opus-4.
+        assert_eq!(pricing.resolve("claude-opus-4-1-20250805").unwrap().0, "claude-opus-4-1");
+        assert_eq!(pricing.resolve("claude-sonnet-4-latest").unwrap().0, "claude-sonnet-4");
+        assert!(pricing.resolve("claude-sonnet-5").is_none());
+        // A newer, unlisted model is not priced as its family's oldest entry.
+        assert!(pricing.resolve("claude-opus-4-7").is_none());
+        assert!(pricing.resolve("claude-opus-4-7-20261001").is_none());
+        assert!(pricing.resolve("gpt-4").is_none());
+
+        let rates = pricing.rates("haiku").unwrap();
+        assert!((rates.cost(1_000_000.0, 1_000_000.0, 0.0, 0.0) - 4.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_set_and_alias_write_the_project_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, code) = execute(&args(&["set", "sonnet", "output=12"]), dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let rates = Pricing::load(dir.path()).rates("sonnet").unwrap();
+        assert_eq!((rates.input, rates.output), (3.0, 12.0), "unset rates keep their current value");
+
+        assert!(execute(&args(&["set", "my-model", "input=1"]), dir.path()).is_err());
+        execute(&args(&["set", "my-model", "input=1", "output=2", "cache_read=0.1", "cache_write=1.5"]), dir.path()).unwrap();
+        execute(&args(&["alias", "mine", "my-model"]), dir.path()).unwrap();
+        assert_eq!(Pricing::load(dir.path()).resolve("mine").unwrap().0, "my-model");
+
+        let table: Value = serde_json::from_str(&fs::read_to_string(project_path(dir.path())).unwrap()).unwrap();
+        assert!(table["updated"].is_string());
+        assert!(execute(&args(&["set", "sonnet", "turbo=1"]), dir.path()).is_err());
+        assert!(execute(&args(&["alias", "x", "unknown-model"]), dir.path()).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use super::lease_lock;
+use super::feature_flags::{self, FeatureFlag};
+use super::{event_bus, journal, lease_lock};
 use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
@@ -24,26 +25,16 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let phase: i64 = phase_str.parse().unwrap_or(0);
 
     let planning_dir = cwd.join(".yolo-planning");
-    let config_path = planning_dir.join("config.json");
 
     // Check v3_event_recovery feature flag
-    if config_path.exists()
-        && let Ok(content) = fs::read_to_string(&config_path)
-        && let Ok(config) = serde_json::from_str::<Value>(&content)
-    {
-        let enabled = config
-            .get("v3_event_recovery")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
-        if !enabled {
-            let envelope = json!({
-                "ok": true,
-                "cmd": "recover-state",
-                "delta": { "recovered": false, "reason": "v3_event_recovery disabled" },
-                "elapsed_ms": start.elapsed().as_millis() as u64
-            });
-            return Ok((serde_json::to_string(&envelope).unwrap_or_default(), 3));
-        }
+    if !feature_flags::is_enabled(FeatureFlag::V3EventRecovery, cwd) {
+        let envelope = json!({
+            "ok": true,
+            "cmd": "recover-state",
+            "delta": { "recovered": false, "reason": "v3_event_recovery disabled" },
+            "elapsed_ms": start.elapsed().as_millis() as u64
+        });
+        return Ok((serde_json::to_string(&envelope).unwrap_or_default(), 3));
     }
 
     let phases_dir = if args.len() > 1 {
@@ -52,7 +43,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         planning_dir.join("phases")
     };
 
-    let events_file = planning_dir.join(".events").join("event-log.jsonl");
+    let events_file = event_bus::log_path(&planning_dir);
 
     // Find phase directory matching NN-slug pattern
     let phase_prefix = format!("{:02}-", phase);
@@ -78,6 +69,9 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         .trim_start_matches(&phase_prefix)
         .to_string();
 
+    // Finish any interrupted journaled state write before reading state back
+    let replayed = journal::recover(&planning_dir).map(|r| r.rolled_forward.len()).unwrap_or(0);
+
     // Collect plans from *-PLAN.md files
     let plans = collect_plans(&phase_dir, phase, &events_file, cwd);
 
@@ -128,7 +122,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         "status": status,
         "wave": current_wave,
         "total_waves": max_wave,
-        "plans": plans_json
+        "plans": plans_json,
+        "journals_replayed": replayed
     });
 
     let envelope = json!({
@@ -1,24 +1,42 @@
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Instant;
 
+use crate::commands::atomic_io;
 use crate::commands::bump_version;
+use crate::commands::changelog;
 use crate::commands::extract_changelog;
+use crate::commands::version_files;
 
 fn s(v: &str) -> String {
     v.to_string()
 }
 
-/// Files that should be staged in a release commit.
-const RELEASE_FILES: &[&str] = &[
-    "VERSION",
-    ".claude-plugin/plugin.json",
-    ".claude-plugin/marketplace.json",
-    "yolo-mcp-server/Cargo.toml",
-    "yolo-mcp-server/Cargo.lock",
-    "CHANGELOG.md",
-];
+/// Files that should be staged in a release commit: every version file, the
+/// lockfile next to each manifest, and the changelog.
+fn release_files(cwd: &Path) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let versioned = version_files::load(cwd).map(|(f, _)| f).unwrap_or_default();
+    for file in versioned {
+        let lock = match Path::new(&file.path).file_name().and_then(|n| n.to_str()) {
+            Some("Cargo.toml") => Some("Cargo.lock"),
+            Some("package.json") => Some("package-lock.json"),
+            _ => None,
+        };
+        let lock = lock.map(|l| Path::new(&file.path).with_file_name(l).to_string_lossy().to_string());
+        for path in std::iter::once(file.path).chain(lock) {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.push("CHANGELOG.md".to_string());
+    files
+}
 
 /// Run a git command in the given working directory, returning (stdout, stderr, exit_code).
 fn run_git(cwd: &Path, git_args: &[&str]) -> (String, String, i32) {
@@ -37,332 +55,405 @@ fn run_git(cwd: &Path, git_args: &[&str]) -> (String, String, i32) {
     }
 }
 
-/// Facade command that orchestrates a full release: bump-version, git add, commit, tag, push.
-///
-/// Usage: yolo release-suite [--major|--minor] [--dry-run] [--no-push] [--no-release] [--offline]
-///
-/// Exit codes: 0=all pass, 1=any step failed
-pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
-    let start = Instant::now();
+/// Release steps in execution order. The journal records how many completed.
+const STEPS: &[&str] = &["bump-version", "changelog", "git-add", "git-commit", "git-tag", "git-push", "gh-release"];
 
-    // Parse flags
-    let dry_run = args.iter().any(|a| a == "--dry-run");
-    let no_push = args.iter().any(|a| a == "--no-push");
-    let major = args.iter().any(|a| a == "--major");
-    let minor = args.iter().any(|a| a == "--minor");
-    let offline = args.iter().any(|a| a == "--offline");
-    let no_release = args.iter().any(|a| a == "--no-release");
+/// Journal file, kept in the git dir so it is never staged or committed.
+const JOURNAL_FILE: &str = "yolo-release-journal.json";
 
-    if major && minor {
-        return Err("Cannot use both --major and --minor".to_string());
+/// On-disk record of a release in progress, rewritten after every step so a
+/// failed release can be resumed from the failed step or rolled back.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ReleaseJournal {
+    bump_type: String,
+    major: bool,
+    minor: bool,
+    offline: bool,
+    no_push: bool,
+    no_release: bool,
+    no_changelog: bool,
+    /// HEAD before the release started; None in a repo without commits.
+    base_commit: Option<String>,
+    /// Release files before the bump. None marks a file that did not exist.
+    originals: BTreeMap<String, Option<String>>,
+    old_version: Option<String>,
+    new_version: Option<String>,
+    release_commit: Option<String>,
+    tag: Option<String>,
+    /// Set once the branch push lands: the release commit is public from then on.
+    branch_pushed: bool,
+    /// Results of the completed steps, in `STEPS` order.
+    steps: Vec<Value>,
+    /// Step that failed on the last run, if any.
+    failed: Option<String>,
+}
+
+/// Location of the release journal, or None outside a git repo.
+fn journal_path(cwd: &Path) -> Option<PathBuf> {
+    let (stdout, _, code) = run_git(cwd, &["rev-parse", "--git-path", JOURNAL_FILE]);
+    (code == 0).then(|| cwd.join(stdout.trim()))
+}
+
+fn load_journal(cwd: &Path) -> Result<(ReleaseJournal, PathBuf), String> {
+    let path = journal_path(cwd)
+        .filter(|p| p.exists())
+        .ok_or_else(|| "No release in progress (no release journal found)".to_string())?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let journal = serde_json::from_str(&content).map_err(|e| format!("Corrupt release journal {}: {}", path.display(), e))?;
+    Ok((journal, path))
+}
+
+fn rev_parse(cwd: &Path, rev: &str) -> Option<String> {
+    let (stdout, _, code) = run_git(cwd, &["rev-parse", "--verify", "-q", rev]);
+    (code == 0).then(|| stdout.trim().to_string())
+}
+
+/// Put release files back to their pre-release content, deleting ones the release created.
+fn restore_files(cwd: &Path, originals: &BTreeMap<String, Option<String>>) -> Result<(), String> {
+    for (rel, content) in originals {
+        let path = cwd.join(rel);
+        match content {
+            Some(content) => fs::write(&path, content),
+            None if path.exists() => fs::remove_file(&path),
+            None => Ok(()),
+        }
+        .map_err(|e| format!("Failed to restore {}: {}", rel, e))?;
     }
+    Ok(())
+}
 
-    let bump_type = if major {
-        "major"
-    } else if minor {
-        "minor"
-    } else {
-        "patch"
-    };
+/// One run of the release steps, fresh or resumed.
+struct Release<'a> {
+    cwd: &'a Path,
+    dry_run: bool,
+    journal: ReleaseJournal,
+    journal_path: Option<PathBuf>,
+}
 
-    let mut steps: Vec<Value> = Vec::new();
+impl Release<'_> {
+    fn save(&self) {
+        if let (false, Some(path)) = (self.dry_run, &self.journal_path)
+            && let Ok(content) = serde_json::to_string_pretty(&self.journal)
+        {
+            let _ = atomic_io::atomic_write(path, content.as_bytes());
+        }
+    }
 
-    // --- Step 1: bump-version ---
-    let (old_version, new_version) = if dry_run {
-        // In dry-run mode, call --verify to read current state, then compute what would happen
-        let verify_args = vec![s("yolo"), s("bump-version"), s("--verify")];
-        let verify_result = bump_version::execute(&verify_args, cwd);
+    fn version(&self) -> String {
+        self.journal.new_version.clone().unwrap_or_default()
+    }
 
-        match verify_result {
-            Ok((json_str, code)) => {
-                let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
-                let version_str = parsed["delta"]["versions"]
-                    .as_array()
-                    .and_then(|arr| arr.first())
-                    .and_then(|v| v["version"].as_str());
-
-                if code != 0 || version_str.is_none() {
-                    steps.push(json!({
-                        "name": "bump-version",
-                        "status": "fail",
-                        "detail": parsed
-                    }));
-                    let response = json!({
-                        "ok": false,
-                        "cmd": "release-suite",
-                        "delta": {
-                            "old_version": null,
-                            "new_version": null,
-                            "bump_type": bump_type,
-                            "dry_run": dry_run,
-                            "steps": steps
-                        },
-                        "elapsed_ms": start.elapsed().as_millis() as u64
-                    });
-                    return Ok((response.to_string(), 1));
+    /// Run `STEPS[from..]`, journaling each result. Stops at the first failure,
+    /// leaving the journal behind for `--resume`/`--rollback`.
+    fn run(&mut self, from: usize, start: &Instant) -> (String, i32) {
+        for name in &STEPS[from..] {
+            let result = match *name {
+                "bump-version" => self.bump(),
+                "changelog" => self.changelog(),
+                "git-add" => self.git_add(),
+                "git-commit" => self.git_commit(),
+                "git-tag" => self.git_tag(),
+                "git-push" => self.git_push(),
+                _ => Ok(self.gh_release()),
+            };
+            match result {
+                Ok(step) => {
+                    self.journal.steps.push(step);
+                    self.journal.failed = None;
+                    self.save();
+                }
+                Err(step) => {
+                    self.journal.failed = Some(name.to_string());
+                    self.save();
+                    let mut steps = self.journal.steps.clone();
+                    steps.push(step);
+                    return (self.response(false, &steps, from, start), 1);
                 }
-
-                let old = version_str.unwrap().to_string();
-                let new = compute_next_version(&old, major, minor);
-                steps.push(json!({
-                    "name": "bump-version",
-                    "status": "dry-run",
-                    "detail": format!("Would bump {} -> {}", old, new)
-                }));
-                (old, new)
-            }
-            Err(e) => {
-                steps.push(json!({
-                    "name": "bump-version",
-                    "status": "fail",
-                    "detail": e
-                }));
-                let response = json!({
-                    "ok": false,
-                    "cmd": "release-suite",
-                    "delta": {
-                        "old_version": null,
-                        "new_version": null,
-                        "bump_type": bump_type,
-                        "dry_run": dry_run,
-                        "steps": steps
-                    },
-                    "elapsed_ms": start.elapsed().as_millis() as u64
-                });
-                return Ok((response.to_string(), 1));
             }
         }
-    } else {
-        // Normal mode: actually bump
+        if let (false, Some(path)) = (self.dry_run, &self.journal_path) {
+            let _ = fs::remove_file(path);
+        }
+        (self.response(true, &self.journal.steps, from, start), 0)
+    }
+
+    fn response(&self, ok: bool, steps: &[Value], from: usize, start: &Instant) -> String {
+        let mut delta = json!({
+            "old_version": self.journal.old_version,
+            "new_version": self.journal.new_version,
+            "bump_type": self.journal.bump_type,
+            "dry_run": self.dry_run,
+            "steps": steps
+        });
+        if from > 0 {
+            delta["resumed_from"] = json!(STEPS[from]);
+        }
+        if let (false, false, Some(path)) = (ok, self.dry_run, &self.journal_path) {
+            delta["journal"] = json!(path.to_string_lossy());
+            delta["hint"] = json!("Fix the failure and run `yolo release-suite --resume`, or undo the release with `yolo release-suite --rollback`");
+        }
+        json!({
+            "ok": ok,
+            "cmd": "release-suite",
+            "delta": delta,
+            "elapsed_ms": start.elapsed().as_millis() as u64
+        })
+        .to_string()
+    }
+
+    // --- Step 1: bump-version ---
+    fn bump(&mut self) -> Result<Value, Value> {
+        let j = &mut self.journal;
+        if self.dry_run {
+            // In dry-run mode, call --verify to read current state, then compute what would happen
+            let verify_args = vec![s("yolo"), s("bump-version"), s("--verify")];
+            let (json_str, code) = bump_version::execute(&verify_args, self.cwd)
+                .map_err(|e| json!({"name": "bump-version", "status": "fail", "detail": e}))?;
+            let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
+            let version_str = parsed["delta"]["versions"]
+                .as_array()
+                .and_then(|arr| arr.first())
+                .and_then(|v| v["version"].as_str());
+            let Some(old) = version_str.filter(|_| code == 0).map(str::to_string) else {
+                return Err(json!({"name": "bump-version", "status": "fail", "detail": parsed}));
+            };
+            let new = compute_next_version(&old, j.major, j.minor);
+            let step = json!({
+                "name": "bump-version",
+                "status": "dry-run",
+                "detail": format!("Would bump {} -> {}", old, new)
+            });
+            (j.old_version, j.new_version) = (Some(old), Some(new));
+            return Ok(step);
+        }
+
+        // A bump that failed part-way may have rewritten some files; start over.
+        if j.failed.as_deref() == Some("bump-version") {
+            restore_files(self.cwd, &j.originals)
+                .map_err(|e| json!({"name": "bump-version", "status": "fail", "detail": e}))?;
+        }
         let mut bump_args = vec![s("yolo"), s("bump-version")];
-        if major {
+        if j.major {
             bump_args.push(s("--major"));
         }
-        if minor {
+        if j.minor {
             bump_args.push(s("--minor"));
         }
-        if offline {
+        if j.offline {
             bump_args.push(s("--offline"));
         }
+        let (json_str, code) = bump_version::execute(&bump_args, self.cwd)
+            .map_err(|e| json!({"name": "bump-version", "status": "fail", "detail": e}))?;
+        let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
+        if code != 0 {
+            return Err(json!({"name": "bump-version", "status": "fail", "detail": parsed}));
+        }
+        let old = parsed["delta"]["old_version"].as_str().unwrap_or("unknown").to_string();
+        let new = parsed["delta"]["new_version"].as_str().unwrap_or("unknown").to_string();
+        let step = json!({
+            "name": "bump-version",
+            "status": "ok",
+            "detail": format!("Bumped {} -> {}", old, new)
+        });
+        (j.old_version, j.new_version) = (Some(old), Some(new));
+        Ok(step)
+    }
 
-        match bump_version::execute(&bump_args, cwd) {
-            Ok((json_str, code)) => {
-                if code != 0 {
-                    let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
-                    steps.push(json!({
-                        "name": "bump-version",
-                        "status": "fail",
-                        "detail": parsed
-                    }));
-                    let response = json!({
-                        "ok": false,
-                        "cmd": "release-suite",
-                        "delta": {
-                            "old_version": null,
-                            "new_version": null,
-                            "bump_type": bump_type,
-                            "dry_run": false,
-                            "steps": steps
-                        },
-                        "elapsed_ms": start.elapsed().as_millis() as u64
-                    });
-                    return Ok((response.to_string(), 1));
-                }
-                let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
-                let old = parsed["delta"]["old_version"]
-                    .as_str()
-                    .unwrap_or("unknown")
-                    .to_string();
-                let new = parsed["delta"]["new_version"]
-                    .as_str()
-                    .unwrap_or("unknown")
-                    .to_string();
-                steps.push(json!({
-                    "name": "bump-version",
-                    "status": "ok",
-                    "detail": format!("Bumped {} -> {}", old, new)
-                }));
-                (old, new)
-            }
-            Err(e) => {
-                steps.push(json!({
-                    "name": "bump-version",
-                    "status": "fail",
-                    "detail": e
-                }));
-                let response = json!({
-                    "ok": false,
-                    "cmd": "release-suite",
-                    "delta": {
-                        "old_version": null,
-                        "new_version": null,
-                        "bump_type": bump_type,
-                        "dry_run": false,
-                        "steps": steps
-                    },
-                    "elapsed_ms": start.elapsed().as_millis() as u64
-                });
-                return Ok((response.to_string(), 1));
-            }
+    // --- Step 2: changelog from conventional commits since the last tag ---
+    fn changelog(&mut self) -> Result<Value, Value> {
+        if self.journal.no_changelog {
+            return Ok(json!({
+                "name": "changelog",
+                "status": "skipped",
+                "detail": "Skipped (--no-changelog)"
+            }));
         }
-    };
+        Ok(match changelog::generate(self.cwd, &self.version(), None, self.dry_run) {
+            Ok(delta) if delta["skipped"].is_string() => json!({
+                "name": "changelog",
+                "status": "skipped",
+                "detail": delta["skipped"]
+            }),
+            Ok(delta) => json!({
+                "name": "changelog",
+                "status": if self.dry_run { "dry-run" } else { "ok" },
+                "since": delta["since"],
+                "commits": delta["commits"],
+                "breaking": delta["breaking"],
+                "preview": delta["section"]
+            }),
+            Err(e) => json!({
+                "name": "changelog",
+                "status": "warn",
+                "detail": e
+            }),
+        })
+    }
 
-    // --- Step 2: git add ---
-    let existing_files: Vec<&str> = RELEASE_FILES
-        .iter()
-        .filter(|f| cwd.join(f).exists())
-        .copied()
-        .collect();
+    // --- Step 3: git add ---
+    fn git_add(&mut self) -> Result<Value, Value> {
+        let existing_files: Vec<String> = release_files(self.cwd)
+            .into_iter()
+            .filter(|f| self.cwd.join(f).exists())
+            .collect();
 
-    if dry_run {
-        steps.push(json!({
-            "name": "git-add",
-            "status": "dry-run",
-            "files": existing_files
-        }));
-    } else if existing_files.is_empty() {
-        steps.push(json!({
-            "name": "git-add",
-            "status": "ok",
-            "files": [],
-            "detail": "No release files found to stage"
-        }));
-    } else {
+        if self.dry_run {
+            return Ok(json!({
+                "name": "git-add",
+                "status": "dry-run",
+                "files": existing_files
+            }));
+        }
+        if existing_files.is_empty() {
+            return Ok(json!({
+                "name": "git-add",
+                "status": "ok",
+                "files": [],
+                "detail": "No release files found to stage"
+            }));
+        }
         let mut git_args: Vec<&str> = vec!["add"];
-        git_args.extend(&existing_files);
-        let (_, stderr, code) = run_git(cwd, &git_args);
+        git_args.extend(existing_files.iter().map(String::as_str));
+        let (_, stderr, code) = run_git(self.cwd, &git_args);
         if code != 0 {
-            steps.push(json!({
+            return Err(json!({
                 "name": "git-add",
                 "status": "fail",
                 "detail": stderr.trim()
             }));
-            return Ok((build_fail_response(
-                bump_type, dry_run, &old_version, &new_version, &steps, &start,
-            ), 1));
         }
-        steps.push(json!({
+        Ok(json!({
             "name": "git-add",
             "status": "ok",
             "files": existing_files
-        }));
+        }))
     }
 
-    // --- Step 3: git commit ---
-    let commit_msg = format!("chore: release v{}", new_version);
-    if dry_run {
-        steps.push(json!({
-            "name": "git-commit",
-            "status": "dry-run",
-            "message": commit_msg
-        }));
-    } else {
-        let (_, stderr, code) = run_git(cwd, &["commit", "-m", &commit_msg]);
-        if code != 0 {
-            steps.push(json!({
+    // --- Step 4: git commit ---
+    fn git_commit(&mut self) -> Result<Value, Value> {
+        let commit_msg = format!("chore: release v{}", self.version());
+        if self.dry_run {
+            return Ok(json!({
                 "name": "git-commit",
-                "status": "fail",
-                "detail": stderr.trim()
+                "status": "dry-run",
+                "message": commit_msg
             }));
-            return Ok((build_fail_response(
-                bump_type, dry_run, &old_version, &new_version, &steps, &start,
-            ), 1));
         }
-        steps.push(json!({
+        // A resumed run may find the commit already made before the journal caught up.
+        let head = rev_parse(self.cwd, "HEAD");
+        let (subject, _, _) = run_git(self.cwd, &["log", "-1", "--format=%s"]);
+        let already = head.is_some() && head != self.journal.base_commit && subject.trim() == commit_msg;
+        if !already {
+            let (_, stderr, code) = run_git(self.cwd, &["commit", "-m", &commit_msg]);
+            if code != 0 {
+                return Err(json!({
+                    "name": "git-commit",
+                    "status": "fail",
+                    "detail": stderr.trim()
+                }));
+            }
+        }
+        self.journal.release_commit = rev_parse(self.cwd, "HEAD");
+        Ok(json!({
             "name": "git-commit",
             "status": "ok",
             "message": commit_msg
-        }));
+        }))
     }
 
-    // --- Step 4: git tag ---
-    let tag_name = format!("v{}", new_version);
-    if dry_run {
-        steps.push(json!({
-            "name": "git-tag",
-            "status": "dry-run",
-            "tag": tag_name
-        }));
-    } else {
-        let (_, stderr, code) = run_git(cwd, &["tag", &tag_name]);
-        if code != 0 {
-            steps.push(json!({
+    // --- Step 5: git tag ---
+    fn git_tag(&mut self) -> Result<Value, Value> {
+        let tag_name = format!("v{}", self.version());
+        if self.dry_run {
+            return Ok(json!({
                 "name": "git-tag",
-                "status": "fail",
-                "detail": stderr.trim()
+                "status": "dry-run",
+                "tag": tag_name
             }));
-            return Ok((build_fail_response(
-                bump_type, dry_run, &old_version, &new_version, &steps, &start,
-            ), 1));
         }
-        steps.push(json!({
+        let existing = rev_parse(self.cwd, &format!("refs/tags/{}^{{commit}}", tag_name));
+        if existing.is_none() || existing != self.journal.release_commit {
+            let (_, stderr, code) = run_git(self.cwd, &["tag", &tag_name]);
+            if code != 0 {
+                return Err(json!({
+                    "name": "git-tag",
+                    "status": "fail",
+                    "detail": stderr.trim()
+                }));
+            }
+        }
+        self.journal.tag = Some(tag_name.clone());
+        Ok(json!({
             "name": "git-tag",
             "status": "ok",
             "tag": tag_name
-        }));
+        }))
     }
 
-    // --- Step 5: git push (unless --no-push) ---
-    if no_push || dry_run {
-        let status = if dry_run { "dry-run" } else { "skipped" };
-        steps.push(json!({
-            "name": "git-push",
-            "status": status
-        }));
-    } else {
-        let (_, stderr, code) = run_git(cwd, &["push"]);
-        if code != 0 {
-            steps.push(json!({
+    // --- Step 6: git push (unless --no-push) ---
+    fn git_push(&mut self) -> Result<Value, Value> {
+        if self.journal.no_push || self.dry_run {
+            let status = if self.dry_run { "dry-run" } else { "skipped" };
+            return Ok(json!({
                 "name": "git-push",
-                "status": "fail",
-                "detail": stderr.trim()
+                "status": status
             }));
-            return Ok((build_fail_response(
-                bump_type, dry_run, &old_version, &new_version, &steps, &start,
-            ), 1));
         }
-        let (_, stderr2, code2) = run_git(cwd, &["push", "--tags"]);
+        if !self.journal.branch_pushed {
+            let (_, stderr, code) = run_git(self.cwd, &["push"]);
+            if code != 0 {
+                return Err(json!({
+                    "name": "git-push",
+                    "status": "fail",
+                    "detail": stderr.trim()
+                }));
+            }
+            self.journal.branch_pushed = true;
+            self.save();
+        }
+        let (_, stderr2, code2) = run_git(self.cwd, &["push", "--tags"]);
         if code2 != 0 {
-            steps.push(json!({
+            return Err(json!({
                 "name": "git-push",
                 "status": "fail",
                 "detail": format!("push --tags failed: {}", stderr2.trim())
             }));
-            return Ok((build_fail_response(
-                bump_type, dry_run, &old_version, &new_version, &steps, &start,
-            ), 1));
         }
-        steps.push(json!({
+        Ok(json!({
             "name": "git-push",
             "status": "ok"
-        }));
-    }
-
-    // --- Step 6: gh release create (unless --no-push, --no-release, or dry-run) ---
-    let push_ok = steps.iter().any(|s| s["name"] == "git-push" && s["status"] == "ok");
-    if dry_run {
-        steps.push(json!({
-            "name": "gh-release",
-            "status": "dry-run",
-            "detail": format!("Would create release v{}", new_version)
-        }));
-    } else if no_push || no_release || !push_ok {
-        let reason = if no_release {
-            "Skipped (--no-release)"
-        } else if no_push {
-            "Skipped (no push)"
-        } else {
-            "Skipped (push did not succeed)"
-        };
-        steps.push(json!({
-            "name": "gh-release",
-            "status": "skipped",
-            "detail": reason
-        }));
-    } else {
+        }))
+    }
+
+    // --- Step 7: gh release create (unless --no-push, --no-release, or dry-run) ---
+    fn gh_release(&mut self) -> Value {
+        let new_version = self.version();
+        let push_ok = self.journal.steps.iter().any(|s| s["name"] == "git-push" && s["status"] == "ok");
+        if self.dry_run {
+            return json!({
+                "name": "gh-release",
+                "status": "dry-run",
+                "detail": format!("Would create release v{}", new_version)
+            });
+        }
+        if self.journal.no_push || self.journal.no_release || !push_ok {
+            let reason = if self.journal.no_release {
+                "Skipped (--no-release)"
+            } else if self.journal.no_push {
+                "Skipped (no push)"
+            } else {
+                "Skipped (push did not succeed)"
+            };
+            return json!({
+                "name": "gh-release",
+                "status": "skipped",
+                "detail": reason
+            });
+        }
+
         // Extract changelog body for release notes
         let changelog_args = vec![s("yolo"), s("extract-changelog")];
-        let release_body = match extract_changelog::execute(&changelog_args, cwd) {
+        let release_body = match extract_changelog::execute(&changelog_args, self.cwd) {
             Ok((json_str, _)) => {
                 let parsed: Value = serde_json::from_str(&json_str).unwrap_or(json!({}));
                 if parsed["delta"]["found"] == true {
@@ -380,7 +471,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         let tag = format!("v{}", new_version);
         match Command::new("gh")
             .args(["release", "create", &tag, "--title", &tag, "--notes", &release_body])
-            .current_dir(cwd)
+            .current_dir(self.cwd)
             .output()
         {
             Ok(output) => {
@@ -389,43 +480,168 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
                 let code = output.status.code().unwrap_or(1);
                 if code == 0 {
                     let url = stdout.trim().to_string();
-                    steps.push(json!({
+                    json!({
                         "name": "gh-release",
                         "status": "ok",
                         "url": url
-                    }));
+                    })
                 } else {
-                    steps.push(json!({
+                    json!({
                         "name": "gh-release",
                         "status": "warn",
                         "detail": format!("gh release failed: {}", stderr.trim())
-                    }));
+                    })
                 }
             }
-            Err(_) => {
-                steps.push(json!({
-                    "name": "gh-release",
-                    "status": "warn",
-                    "detail": "gh CLI not found"
-                }));
-            }
+            Err(_) => json!({
+                "name": "gh-release",
+                "status": "warn",
+                "detail": "gh CLI not found"
+            }),
+        }
+    }
+}
+
+/// Facade command that orchestrates a full release: bump-version, changelog, git add, commit, tag, push.
+///
+/// Usage: yolo release-suite [--major|--minor] [--dry-run] [--no-push] [--no-release] [--no-changelog] [--offline]
+///        yolo release-suite --resume     continue a failed release from the failed step
+///        yolo release-suite --rollback   delete the local tag, reset the release commit, restore version files
+///
+/// Every step is journaled to `.git/yolo-release-journal.json`; a failed
+/// release leaves the journal behind and a new release is refused until it
+/// is resumed or rolled back.
+///
+/// Exit codes: 0=all pass, 1=any step failed
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+
+    if args.iter().any(|a| a == "--rollback") {
+        return rollback(cwd, &start);
+    }
+    if args.iter().any(|a| a == "--resume") {
+        let (journal, path) = load_journal(cwd)?;
+        let from = journal.steps.len().min(STEPS.len());
+        let mut release = Release { cwd, dry_run: false, journal, journal_path: Some(path) };
+        return Ok(release.run(from, &start));
+    }
+
+    // Parse flags
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let major = args.iter().any(|a| a == "--major");
+    let minor = args.iter().any(|a| a == "--minor");
+
+    if major && minor {
+        return Err("Cannot use both --major and --minor".to_string());
+    }
+
+    let bump_type = if major {
+        "major"
+    } else if minor {
+        "minor"
+    } else {
+        "patch"
+    };
+
+    let journal_path = journal_path(cwd);
+    if !dry_run && let Ok((pending, _)) = load_journal(cwd) {
+        return Err(format!(
+            "A release to v{} is already in progress (failed at {}); run `yolo release-suite --resume` or `--rollback`",
+            pending.new_version.as_deref().unwrap_or("?"),
+            pending.failed.as_deref().unwrap_or("unknown step")
+        ));
+    }
+
+    let originals = if dry_run {
+        BTreeMap::new()
+    } else {
+        release_files(cwd)
+            .into_iter()
+            .map(|f| {
+                let content = fs::read_to_string(cwd.join(&f)).ok();
+                (f, content)
+            })
+            .collect()
+    };
+    let journal = ReleaseJournal {
+        bump_type: bump_type.to_string(),
+        major,
+        minor,
+        offline: args.iter().any(|a| a == "--offline"),
+        no_push: args.iter().any(|a| a == "--no-push"),
+        no_release: args.iter().any(|a| a == "--no-release"),
+        no_changelog: args.iter().any(|a| a == "--no-changelog"),
+        base_commit: rev_parse(cwd, "HEAD"),
+        originals,
+        ..Default::default()
+    };
+    let mut release = Release { cwd, dry_run, journal, journal_path };
+    Ok(release.run(0, &start))
+}
+
+/// Undo a failed release: delete the local tag, reset the release commit and
+/// restore the version files and changelog. Refused once the branch push
+/// landed, since the release commit is then public.
+fn rollback(cwd: &Path, start: &Instant) -> Result<(String, i32), String> {
+    let (journal, path) = load_journal(cwd)?;
+    let version = journal.new_version.clone().unwrap_or_default();
+    if journal.branch_pushed {
+        return Err(format!(
+            "The v{} release commit was already pushed; finish it with `yolo release-suite --resume` or revert it on the remote",
+            version
+        ));
+    }
+    if let Some(commit) = &journal.release_commit
+        && rev_parse(cwd, "HEAD").as_ref() != Some(commit)
+    {
+        return Err(format!("HEAD has moved past the release commit {}; rollback needs it at HEAD", commit));
+    }
+
+    let mut steps: Vec<Value> = Vec::new();
+    if let Some(tag) = journal.tag.as_deref().filter(|t| rev_parse(cwd, &format!("refs/tags/{}", t)).is_some()) {
+        let (_, stderr, code) = run_git(cwd, &["tag", "-d", tag]);
+        if code != 0 {
+            return Err(format!("Failed to delete tag {}: {}", tag, stderr.trim()));
+        }
+        steps.push(json!({"name": "delete-tag", "status": "ok", "tag": tag}));
+    }
+    if let Some(commit) = &journal.release_commit {
+        let (_, stderr, code) = match &journal.base_commit {
+            Some(base) => run_git(cwd, &["reset", "-q", "--soft", base]),
+            None => run_git(cwd, &["update-ref", "-d", "HEAD"]),
+        };
+        if code != 0 {
+            return Err(format!("Failed to reset release commit: {}", stderr.trim()));
         }
+        steps.push(json!({"name": "reset-commit", "status": "ok", "commit": commit}));
     }
 
-    // --- Build success response ---
+    // Unstage, then rewrite the release files as they were before the bump.
+    let files: Vec<&str> = journal.originals.keys().map(String::as_str).collect();
+    if !files.is_empty() {
+        let mut unstage: Vec<&str> = if journal.base_commit.is_some() {
+            vec!["reset", "-q", "--"]
+        } else {
+            vec!["rm", "-q", "--cached", "--ignore-unmatch", "--"]
+        };
+        unstage.extend(&files);
+        run_git(cwd, &unstage);
+    }
+    restore_files(cwd, &journal.originals)?;
+    steps.push(json!({"name": "restore-files", "status": "ok", "files": files}));
+    fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+
     let response = json!({
         "ok": true,
         "cmd": "release-suite",
         "delta": {
-            "old_version": old_version,
-            "new_version": new_version,
-            "bump_type": bump_type,
-            "dry_run": dry_run,
+            "action": "rollback",
+            "old_version": journal.old_version,
+            "new_version": journal.new_version,
             "steps": steps
         },
         "elapsed_ms": start.elapsed().as_millis() as u64
     });
-
     Ok((response.to_string(), 0))
 }
 
@@ -448,34 +664,9 @@ fn compute_next_version(current: &str, major: bool, minor: bool) -> String {
     }
 }
 
-/// Build a failure response JSON string.
-fn build_fail_response(
-    bump_type: &str,
-    dry_run: bool,
-    old_version: &str,
-    new_version: &str,
-    steps: &[Value],
-    start: &Instant,
-) -> String {
-    json!({
-        "ok": false,
-        "cmd": "release-suite",
-        "delta": {
-            "old_version": old_version,
-            "new_version": new_version,
-            "bump_type": bump_type,
-            "dry_run": dry_run,
-            "steps": steps
-        },
-        "elapsed_ms": start.elapsed().as_millis() as u64
-    })
-    .to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use tempfile::tempdir;
 
     /// Set up a minimal test env with VERSION file and git repo.
@@ -517,21 +708,48 @@ mod tests {
         )
         .unwrap();
 
-        // Initialize git repo so git commands work
+        // Initialize git repo so git commands work; the release commit and tag
+        // need an identity even when no global git config exists.
         run_git(dir.path(), &["init"]);
+        run_git(dir.path(), &["config", "user.email", "test@test.com"]);
+        run_git(dir.path(), &["config", "user.name", "Test"]);
         run_git(dir.path(), &["add", "."]);
-        run_git(
-            dir.path(),
-            &[
-                "-c", "user.email=test@test.com",
-                "-c", "user.name=Test",
-                "commit", "-m", "initial",
-            ],
-        );
+        run_git(dir.path(), &["commit", "-m", "initial"]);
 
         dir
     }
 
+    /// Attach a local bare repo as `origin` (upstream of the current branch),
+    /// optionally with a pre-receive hook that controls what it accepts.
+    fn setup_remote(dir: &Path, pre_receive: Option<&str>) -> tempfile::TempDir {
+        let remote = tempdir().unwrap();
+        run_git(remote.path(), &["init", "-q", "--bare"]);
+        run_git(dir, &["remote", "add", "origin", remote.path().to_str().unwrap()]);
+        run_git(dir, &["push", "-q", "-u", "origin", "HEAD"]);
+        if let Some(script) = pre_receive {
+            set_pre_receive(remote.path(), script);
+        }
+        remote
+    }
+
+    fn set_pre_receive(remote: &Path, script: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        let hook = remote.join("hooks/pre-receive");
+        fs::write(&hook, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn remote_tags(remote: &Path) -> String {
+        run_git(remote, &["tag", "-l"]).0
+    }
+
+    fn release(dir: &Path, extra: &[&str]) -> (Value, i32) {
+        let mut args = vec![s("yolo"), s("release-suite"), s("--offline"), s("--no-release")];
+        args.extend(extra.iter().map(|a| s(a)));
+        let (out, code) = execute(&args, dir).unwrap();
+        (serde_json::from_str(&out).unwrap(), code)
+    }
+
     #[test]
     fn test_major_minor_conflict() {
         let dir = tempdir().unwrap();
@@ -696,19 +914,27 @@ mod tests {
         assert_eq!(parsed["delta"]["bump_type"], "patch");
 
         let steps = parsed["delta"]["steps"].as_array().unwrap();
-        assert_eq!(steps.len(), 6);
+        assert_eq!(steps.len(), 7);
         assert_eq!(steps[0]["name"], "bump-version");
         assert_eq!(steps[0]["status"], "ok");
-        assert_eq!(steps[1]["name"], "git-add");
+        assert_eq!(steps[1]["name"], "changelog");
         assert_eq!(steps[1]["status"], "ok");
-        assert_eq!(steps[2]["name"], "git-commit");
+        assert_eq!(steps[2]["name"], "git-add");
         assert_eq!(steps[2]["status"], "ok");
-        assert_eq!(steps[3]["name"], "git-tag");
+        assert_eq!(steps[3]["name"], "git-commit");
         assert_eq!(steps[3]["status"], "ok");
-        assert_eq!(steps[4]["name"], "git-push");
-        assert_eq!(steps[4]["status"], "skipped");
-        assert_eq!(steps[5]["name"], "gh-release");
+        assert_eq!(steps[4]["name"], "git-tag");
+        assert_eq!(steps[4]["status"], "ok");
+        assert_eq!(steps[5]["name"], "git-push");
         assert_eq!(steps[5]["status"], "skipped");
+        assert_eq!(steps[6]["name"], "gh-release");
+        assert_eq!(steps[6]["status"], "skipped");
+        assert!(steps[2]["files"].as_array().unwrap().contains(&json!("CHANGELOG.md")));
+
+        // Changelog section prepended from the commits since the last tag
+        let changelog = fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(changelog.starts_with("# Changelog\n\n## v1.2.4 ("));
+        assert!(changelog.contains("### Other\n- initial\n"));
 
         // Verify VERSION was actually bumped
         let ver = fs::read_to_string(dir.path().join("VERSION")).unwrap();
@@ -719,4 +945,80 @@ mod tests {
         assert_eq!(tag_code, 0);
         assert!(tag_out.contains("v1.2.4"));
     }
+
+    #[test]
+    fn test_no_changelog_skips_generation() {
+        let dir = setup_test_env();
+        let (out, _) = execute(
+            &[s("yolo"), s("release-suite"), s("--no-push"), s("--offline"), s("--no-changelog")],
+            dir.path(),
+        )
+        .unwrap();
+        let parsed: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["delta"]["steps"][1]["name"], "changelog");
+        assert_eq!(parsed["delta"]["steps"][1]["status"], "skipped");
+        assert!(!dir.path().join("CHANGELOG.md").exists());
+    }
+
+    #[test]
+    fn test_push_to_bare_remote() {
+        let dir = setup_test_env();
+        let remote = setup_remote(dir.path(), None);
+        let (parsed, code) = release(dir.path(), &[]);
+        assert_eq!(code, 0, "{}", parsed);
+        let push = parsed["delta"]["steps"].as_array().unwrap().iter().find(|s| s["name"] == "git-push").unwrap().clone();
+        assert_eq!(push["status"], "ok");
+        assert!(remote_tags(remote.path()).contains("v1.2.4"));
+        assert!(!journal_path(dir.path()).unwrap().exists());
+    }
+
+    #[test]
+    fn test_resume_after_tag_push_rejected() {
+        let dir = setup_test_env();
+        let remote = setup_remote(dir.path(), Some(
+            "while read old new ref; do case $ref in refs/tags/*) exit 1;; esac; done",
+        ));
+        let (parsed, code) = release(dir.path(), &[]);
+        assert_eq!(code, 1);
+        let steps = parsed["delta"]["steps"].as_array().unwrap();
+        assert_eq!(steps.last().unwrap()["name"], "git-push");
+        assert_eq!(steps.last().unwrap()["status"], "fail");
+        assert!(parsed["delta"]["hint"].as_str().unwrap().contains("--resume"));
+
+        // The branch is public now: no rollback, and no second release on top.
+        assert!(execute(&[s("yolo"), s("release-suite"), s("--rollback")], dir.path()).unwrap_err().contains("already pushed"));
+        assert!(execute(&[s("yolo"), s("release-suite"), s("--offline")], dir.path()).unwrap_err().contains("in progress"));
+
+        set_pre_receive(remote.path(), "exit 0");
+        let (parsed, code) = release(dir.path(), &["--resume"]);
+        assert_eq!(code, 0, "{}", parsed);
+        assert_eq!(parsed["delta"]["resumed_from"], "git-push");
+        assert_eq!(parsed["delta"]["steps"].as_array().unwrap().len(), 7);
+        assert!(remote_tags(remote.path()).contains("v1.2.4"));
+        assert!(!journal_path(dir.path()).unwrap().exists());
+    }
+
+    #[test]
+    fn test_rollback_after_push_rejected() {
+        let dir = setup_test_env();
+        let base = rev_parse(dir.path(), "HEAD").unwrap();
+        let remote = setup_remote(dir.path(), Some("exit 1"));
+        let (_, code) = release(dir.path(), &[]);
+        assert_eq!(code, 1);
+        assert!(rev_parse(dir.path(), "refs/tags/v1.2.4").is_some());
+
+        let (out, code) = execute(&[s("yolo"), s("release-suite"), s("--rollback")], dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let parsed: Value = serde_json::from_str(&out).unwrap();
+        let names: Vec<&str> = parsed["delta"]["steps"].as_array().unwrap().iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["delete-tag", "reset-commit", "restore-files"]);
+
+        assert!(rev_parse(dir.path(), "refs/tags/v1.2.4").is_none());
+        assert_eq!(rev_parse(dir.path(), "HEAD").unwrap(), base);
+        assert_eq!(fs::read_to_string(dir.path().join("VERSION")).unwrap(), "1.2.3\n");
+        assert!(!dir.path().join("CHANGELOG.md").exists());
+        assert_eq!(run_git(dir.path(), &["status", "--porcelain"]).0, "");
+        assert!(!journal_path(dir.path()).unwrap().exists());
+        assert!(remote_tags(remote.path()).is_empty());
+    }
 }
@@ -2,16 +2,13 @@ use serde_json::{json, Value};
 use std::path::Path;
 use std::time::Instant;
 
+use crate::commands::role_registry::RoleRegistry;
 use crate::commands::{resolve_model, resolve_turns};
 
 fn s(v: &str) -> String {
     v.to_string()
 }
 
-const AGENTS: &[&str] = &[
-    "lead", "dev", "qa", "scout", "debugger", "architect", "docs", "researcher", "reviewer",
-];
-
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let start = Instant::now();
 
@@ -34,10 +31,12 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
             "balanced".to_string()
         };
 
+        let registry = RoleRegistry::load_with_config(cwd, Path::new(&config_path));
+        let agents = registry.names();
         let mut agents_map = serde_json::Map::new();
         let mut all_ok = true;
 
-        for &agent in AGENTS {
+        for &agent in &agents {
             let model_result = resolve_model::execute(
                 &[s("yolo"), s("resolve-model"), s(agent), config_path.clone(), profiles_path.clone()],
                 cwd,
@@ -88,7 +87,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
             "cmd": "resolve-agent",
             "delta": {
                 "agents": Value::Object(agents_map),
-                "count": AGENTS.len()
+                "count": agents.len()
             },
             "elapsed_ms": start.elapsed().as_millis() as u64
         });
@@ -224,10 +223,12 @@ mod tests {
         assert_eq!(code, 0);
         let parsed: Value = serde_json::from_str(&out).unwrap();
         assert_eq!(parsed["ok"], true);
-        assert_eq!(parsed["delta"]["count"], AGENTS.len());
+        let registry = RoleRegistry::builtin();
+        let names = registry.names();
+        assert_eq!(parsed["delta"]["count"], names.len());
         let agents = parsed["delta"]["agents"].as_object().unwrap();
-        assert_eq!(agents.len(), AGENTS.len());
-        for &agent in AGENTS {
+        assert_eq!(agents.len(), names.len());
+        for &agent in &names {
             assert!(agents.contains_key(agent), "Missing agent: {}", agent);
             assert!(agents[agent]["model"].is_string());
             assert!(agents[agent]["turns"].is_number());
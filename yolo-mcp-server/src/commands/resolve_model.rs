@@ -1,57 +1,10 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
-#[derive(Clone, Copy)]
-enum AgentRole {
-    Lead,
-    Dev,
-    Qa,
-    Scout,
-    Debugger,
-    Architect,
-    Docs,
-    Researcher,
-    Reviewer,
-}
-
-impl AgentRole {
-    fn from_str(s: &str) -> Option<AgentRole> {
-        match s {
-            "lead" => Some(AgentRole::Lead),
-            "dev" => Some(AgentRole::Dev),
-            "qa" => Some(AgentRole::Qa),
-            "scout" => Some(AgentRole::Scout),
-            "debugger" => Some(AgentRole::Debugger),
-            "architect" => Some(AgentRole::Architect),
-            "docs" => Some(AgentRole::Docs),
-            "researcher" => Some(AgentRole::Researcher),
-            "reviewer" => Some(AgentRole::Reviewer),
-            _ => None,
-        }
-    }
-
-    fn as_str(&self) -> &'static str {
-        match self {
-            AgentRole::Lead => "lead",
-            AgentRole::Dev => "dev",
-            AgentRole::Qa => "qa",
-            AgentRole::Scout => "scout",
-            AgentRole::Debugger => "debugger",
-            AgentRole::Architect => "architect",
-            AgentRole::Docs => "docs",
-            AgentRole::Researcher => "researcher",
-            AgentRole::Reviewer => "reviewer",
-        }
-    }
-
-    fn all() -> &'static [AgentRole] {
-        &[
-            AgentRole::Lead, AgentRole::Dev, AgentRole::Qa, AgentRole::Scout,
-            AgentRole::Debugger, AgentRole::Architect, AgentRole::Docs,
-            AgentRole::Researcher, AgentRole::Reviewer,
-        ]
-    }
-}
+use super::layered_config::Config;
+use super::role_registry::RoleRegistry;
+use super::utils;
 
 #[derive(Clone, Copy)]
 enum Model {
@@ -79,7 +32,7 @@ impl Model {
     }
 }
 
-pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     // Detect flags
     let with_cost = args.iter().any(|a| a == "--with-cost");
     let all_agents = args.iter().any(|a| a == "--all");
@@ -103,13 +56,16 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
         (Path::new(positional[3].as_str()), Path::new(positional[4].as_str()))
     };
 
+    let registry = RoleRegistry::load_with_config(cwd, config_path);
+
     // Validate single agent name (non --all mode)
     if !all_agents {
         let agent = positional[2].as_str();
-        if AgentRole::from_str(agent).is_none() {
+        if !registry.contains(agent) {
             return Err(format!(
-                "Invalid agent name '{}'. Valid: lead, dev, qa, scout, debugger, architect, docs, researcher, reviewer",
-                agent
+                "Invalid agent name '{}'. Valid: {}",
+                agent,
+                registry.names().join(", ")
             ));
         }
     }
@@ -132,7 +88,8 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
 
     // Session-level cache using file mtime + path hash for isolation
     let config_mtime = get_mtime(config_path);
-    let path_hash = simple_hash(&config_path.to_string_lossy());
+    // Roles from agent files change the answer without touching config.json.
+    let path_hash = simple_hash(&format!("{}|{}", config_path.to_string_lossy(), registry.names().join(",")));
 
     if all_agents {
         let cache_tag = if with_cost { "ALL-COST" } else { "ALL" };
@@ -149,16 +106,17 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
 
         // Build JSON object for all agents
         let mut map = serde_json::Map::new();
-        for &agent in AgentRole::all() {
-            let model = resolve_agent_model(agent.as_str(), &config, &profiles, &profile_name)?;
+        for role in registry.roles() {
+            let agent = role.name.as_str();
+            let model = resolve_agent_model(agent, &config, &profiles, &profile_name, &registry)?;
             if with_cost {
                 let resolved = Model::from_str(&model).unwrap();
                 let mut inner = serde_json::Map::new();
                 inner.insert("model".to_string(), serde_json::Value::String(model.clone()));
                 inner.insert("cost_weight".to_string(), serde_json::Value::Number(resolved.cost_weight().into()));
-                map.insert(agent.as_str().to_string(), serde_json::Value::Object(inner));
+                map.insert(agent.to_string(), serde_json::Value::Object(inner));
             } else {
-                map.insert(agent.as_str().to_string(), serde_json::Value::String(model));
+                map.insert(agent.to_string(), serde_json::Value::String(model));
             }
         }
         let result = serde_json::to_string(&serde_json::Value::Object(map))
@@ -179,7 +137,7 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     }
 
     let (config, profiles, profile_name) = load_config_and_profiles(config_path, profiles_path)?;
-    let model = resolve_agent_model(agent, &config, &profiles, &profile_name)?;
+    let model = resolve_agent_model(agent, &config, &profiles, &profile_name, &registry)?;
 
     if with_cost {
         let resolved = Model::from_str(&model).unwrap();
@@ -226,16 +184,49 @@ fn load_config_and_profiles<'a>(
     Ok((config, profiles, profile_name))
 }
 
+/// Every profile in `model-profiles.json` mapped to each role's model under it,
+/// with `model_overrides` applied as `resolve-model` would. Cost attribution
+/// prices agent token usage with this.
+pub fn profile_models(cwd: &Path) -> BTreeMap<String, BTreeMap<String, String>> {
+    let profiles: serde_json::Value = utils::plugin_root(cwd)
+        .and_then(|root| fs::read_to_string(root.join("config").join("model-profiles.json")).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_else(|| serde_json::from_str(include_str!("../../../config/model-profiles.json")).unwrap_or_default());
+    let config = Config::load(cwd);
+    let registry = RoleRegistry::load(cwd);
+    profiles
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(profile, _)| {
+            let models = registry
+                .roles()
+                .filter_map(|role| {
+                    resolve_agent_model(&role.name, config.merged(), &profiles, profile, &registry)
+                        .ok()
+                        .map(|model| (role.name.clone(), model))
+                })
+                .collect();
+            (profile.clone(), models)
+        })
+        .collect()
+}
+
 fn resolve_agent_model(
     agent: &str,
     config: &serde_json::Value,
     profiles: &serde_json::Value,
     profile_name: &str,
+    registry: &RoleRegistry,
 ) -> Result<String, String> {
+    // model-profiles.json first; roles it does not list fall back to the registry.
+    let role = registry.get(agent);
+    let agent = role.map(|r| r.name.as_str()).unwrap_or(agent);
     let mut model = profiles
         .get(profile_name)
         .and_then(|p| p.get(agent))
         .and_then(|v| v.as_str())
+        .or_else(|| role.and_then(|r| r.model(profile_name)))
         .unwrap_or("")
         .to_string();
 
@@ -554,6 +545,21 @@ mod tests {
         assert!(parsed.as_object().unwrap().contains_key("dev"));
     }
 
+    #[test]
+    fn test_profile_models_apply_overrides() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        fs::write(
+            dir.path().join(".yolo-planning/config.json"),
+            r#"{"model_overrides": {"scout": "sonnet"}}"#,
+        )
+        .unwrap();
+        let models = profile_models(dir.path());
+        assert_eq!(models["quality"]["dev"], "opus");
+        assert_eq!(models["balanced"]["dev"], "sonnet");
+        assert_eq!(models["budget"]["scout"], "sonnet");
+    }
+
     #[test]
     fn test_cost_weight_values() {
         assert_eq!(Model::Opus.cost_weight(), 100);
@@ -562,17 +568,30 @@ mod tests {
     }
 
     #[test]
-    fn test_agent_role_from_str_valid() {
-        for &role in AgentRole::all() {
-            assert!(AgentRole::from_str(role.as_str()).is_some(), "AgentRole::from_str failed for '{}'", role.as_str());
-        }
-    }
+    fn test_role_from_config_registry() {
+        let dir = tempdir().unwrap();
+        let profiles = write_profiles(dir.path());
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        let config_path = dir.path().join(".yolo-planning/config.json");
+        fs::write(
+            &config_path,
+            r#"{"model_profile": "budget", "roles": {"security-auditor": {"models": {"quality": "opus", "balanced": "sonnet", "budget": "haiku"}}}}"#,
+        ).unwrap();
+        let config = config_path.to_string_lossy().to_string();
 
-    #[test]
-    fn test_agent_role_from_str_invalid() {
-        assert!(AgentRole::from_str("invalid").is_none());
-        assert!(AgentRole::from_str("").is_none());
-        assert!(AgentRole::from_str("LEAD").is_none());
+        let (out, _) = execute(
+            &["yolo".into(), "resolve-model".into(), "security-auditor".into(), config.clone(), profiles.clone()],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(out.trim(), "haiku");
+
+        let (out, _) = execute(
+            &["yolo".into(), "resolve-model".into(), "--all".into(), config, profiles],
+            dir.path(),
+        ).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(out.trim()).unwrap();
+        assert_eq!(parsed["security-auditor"], "haiku");
+        assert_eq!(parsed["dev"], "sonnet");
     }
 
     #[test]
@@ -588,9 +607,4 @@ mod tests {
         assert!(Model::from_str("").is_none());
         assert!(Model::from_str("OPUS").is_none());
     }
-
-    #[test]
-    fn test_agent_role_all_count() {
-        assert_eq!(AgentRole::all().len(), 9);
-    }
 }
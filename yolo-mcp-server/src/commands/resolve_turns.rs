@@ -1,22 +1,7 @@
 use std::fs;
 use std::path::Path;
 
-const VALID_AGENTS: &[&str] = &["lead", "dev", "qa", "scout", "debugger", "architect", "docs", "researcher", "reviewer"];
-
-fn default_base_turns(agent: &str) -> u32 {
-    match agent {
-        "scout" => 15,
-        "qa" => 25,
-        "architect" => 30,
-        "debugger" => 80,
-        "lead" => 50,
-        "dev" => 75,
-        "docs" => 30,
-        "researcher" => 15,
-        "reviewer" => 15,
-        _ => 0,
-    }
-}
+use super::role_registry::RoleRegistry;
 
 fn normalize_effort(raw: &str) -> Option<String> {
     let lower = raw.to_lowercase();
@@ -86,7 +71,7 @@ fn normalize_turn_value(value: &serde_json::Value) -> Option<u32> {
     }
 }
 
-pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     // args[0] = "yolo", args[1] = "resolve-turns", args[2..] = actual args
     // Required: agent-name, config-path. Optional: effort
     if args.len() < 4 || args.len() > 5 {
@@ -98,12 +83,14 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     let effort_input = if args.len() > 4 { &args[4] } else { "" };
 
     // Validate agent
-    if !VALID_AGENTS.contains(&agent.as_str()) {
+    let registry = RoleRegistry::load_with_config(cwd, config_path);
+    let Some(role) = registry.get(agent) else {
         return Err(format!(
-            "Invalid agent name '{}'. Valid: lead, dev, qa, scout, debugger, architect, docs",
-            agent
+            "Invalid agent name '{}'. Valid: {}",
+            agent,
+            registry.names().join(", ")
         ));
-    }
+    };
 
     // Read config if valid
     let config: Option<serde_json::Value> = if config_path.exists() {
@@ -165,7 +152,7 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Default base turns
-    let base = default_base_turns(agent);
+    let base = role.base_turns;
     if base == 0 {
         return Ok(("0\n".to_string(), 0));
     }
@@ -381,7 +368,7 @@ mod tests {
         let dir = tempdir().unwrap();
         let config = write_config(dir.path(), r#"{}"#);
 
-        for agent in VALID_AGENTS {
+        for agent in RoleRegistry::builtin().names() {
             let (out, code) = execute(
                 &["yolo".into(), "resolve-turns".into(), agent.to_string(), config.clone()],
                 dir.path(),
@@ -500,4 +487,25 @@ mod tests {
         ).unwrap();
         assert_eq!(out.trim(), "45");
     }
+
+    #[test]
+    fn test_custom_role_base_turns_from_config() {
+        let dir = tempdir().unwrap();
+        let config = write_config(
+            dir.path(),
+            r#"{"roles": {"migrator": {"base_turns": 40}, "scout": {"base_turns": 20}}}"#,
+        );
+
+        let (out, _) = execute(
+            &["yolo".into(), "resolve-turns".into(), "migrator".into(), config.clone(), "thorough".into()],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(out.trim(), "60");
+
+        let (out, _) = execute(
+            &["yolo".into(), "resolve-turns".into(), "scout".into(), config],
+            dir.path(),
+        ).unwrap();
+        assert_eq!(out.trim(), "20");
+    }
 }
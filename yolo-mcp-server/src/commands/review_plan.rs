@@ -2,9 +2,13 @@ use serde_json::json;
 use std::fs;
 use std::path::Path;
 
+use super::file_overlap;
+use super::frontmatter;
+use super::plan_graph::PlanGraph;
+
 /// Reviews a plan file for quality and completeness.
 ///
-/// Usage: yolo review-plan <plan_path> [<phase_dir>]
+/// Usage: yolo review-plan <plan_path> [<phase_dir>] [--suggest]
 ///
 /// Checks:
 /// 1. Frontmatter completeness (phase, plan, title, wave, depends_on, must_haves)
@@ -12,15 +16,21 @@ use std::path::Path;
 /// 3. Must-haves present and non-empty
 /// 4. Wave validity (positive integer)
 /// 5. File paths check (if phase_dir provided, verify referenced files exist)
+/// 6. File overlap (if phase_dir provided): files_modified vs other plans in the same wave.
+///    Same literal path is high severity; glob-only overlaps are medium.
+///
+/// `--suggest` adds `suggestions`: wave reassignments that remove the overlaps.
 ///
 /// Exit codes: 0=approve, 1=reject (critical findings), 2=conditional (warnings only)
 pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
-    if args.len() < 3 {
-        return Err("Usage: yolo review-plan <plan_path> [<phase_dir>]".to_string());
+    let suggest = args.iter().any(|a| a == "--suggest");
+    let positional: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+    if positional.len() < 3 {
+        return Err("Usage: yolo review-plan <plan_path> [<phase_dir>] [--suggest]".to_string());
     }
 
-    let plan_path = Path::new(&args[2]);
-    let phase_dir = args.get(3).map(|s| Path::new(s.as_str()));
+    let plan_path = Path::new(positional[2]);
+    let phase_dir = positional.get(3).map(|s| Path::new(s.as_str()));
 
     // Check plan file exists
     if !plan_path.exists() {
@@ -41,17 +51,22 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     let mut findings = Vec::new();
 
     // Check 1: Frontmatter completeness
-    let frontmatter = extract_frontmatter(&content);
+    let frontmatter = frontmatter::parse(&content);
+    let plan = frontmatter.as_ref().ok().and_then(|fm| fm.as_ref()).map(|fm| fm.plan());
     match &frontmatter {
-        None => {
+        Err(e) => {
+            checks.push(json!({"name": "frontmatter", "status": "fail"}));
+            findings.push(json!({"severity": "high", "check": "frontmatter", "issue": format!("Invalid YAML frontmatter at {}", e), "line": e.line, "column": e.column, "suggested_fix": "Fix the YAML syntax at the reported line and column", "auto_fixable": false}));
+        }
+        Ok(None) => {
             checks.push(json!({"name": "frontmatter", "status": "fail"}));
             findings.push(json!({"severity": "high", "check": "frontmatter", "issue": "No valid YAML frontmatter found", "suggested_fix": "Add YAML frontmatter with required fields: phase, plan, title, wave, depends_on, must_haves", "auto_fixable": true}));
         }
-        Some(fm) => {
+        Ok(Some(fm)) => {
             let required_fields = ["phase", "plan", "title", "wave", "depends_on", "must_haves"];
             let mut missing: Vec<&str> = Vec::new();
             for field in &required_fields {
-                if !has_field(fm, field) {
+                if !fm.has(field) {
                     missing.push(field);
                 }
             }
@@ -86,8 +101,8 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Check 3: Must-haves present
-    if let Some(ref fm) = frontmatter {
-        let must_haves = parse_list_field(fm, "must_haves");
+    if let Some(ref plan) = plan {
+        let must_haves = &plan.must_haves;
         if must_haves.is_empty() {
             checks.push(json!({"name": "must_haves", "status": "fail", "count": 0}));
             findings.push(json!({
@@ -112,8 +127,8 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Check 4: Wave validity
-    if let Some(ref fm) = frontmatter {
-        let wave_val = field_value(fm, "wave");
+    if let Some(ref plan) = plan {
+        let wave_val = plan.wave.clone();
         match wave_val {
             Some(v) => {
                 if let Ok(n) = v.parse::<u32>() {
@@ -184,6 +199,52 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
         }
     }
 
+    // Check 6: File overlap with same-wave plans (only if phase_dir provided)
+    let mut suggestions = None;
+    if let Some(pd) = phase_dir {
+        let graph = PlanGraph::load(pd);
+        let analysis = graph.analyze();
+        let waves = file_overlap::effective_waves(&graph, &analysis);
+        if let Some(n) = file_overlap::node_for_path(&graph, plan_path) {
+            let id = graph.nodes[n].id.clone();
+            let moves = file_overlap::suggest_waves(&graph, &analysis).unwrap_or_default();
+            let overlaps: Vec<_> = file_overlap::find_overlaps(&graph, &waves)
+                .into_iter()
+                .filter(|o| o.involves(&id))
+                .collect();
+            for overlap in &overlaps {
+                let fix = moves
+                    .iter()
+                    .find(|m| overlap.involves(&m.plan))
+                    .map(|m| format!("Move plan {} from wave {} to wave {}", m.plan, m.from, m.to))
+                    .unwrap_or_else(|| format!(
+                        "Give {} and {} disjoint files_modified, or move one to a later wave",
+                        id,
+                        overlap.other(&id)
+                    ));
+                findings.push(json!({
+                    "severity": if overlap.exact { "high" } else { "medium" },
+                    "check": "file_overlap",
+                    "issue": overlap.describe(),
+                    "overlap": overlap.to_json(),
+                    "suggested_fix": fix,
+                    "auto_fixable": true
+                }));
+            }
+            let status = if overlaps.iter().any(|o| o.exact) {
+                "fail"
+            } else if overlaps.is_empty() {
+                "pass"
+            } else {
+                "warn"
+            };
+            checks.push(json!({"name": "file_overlap", "status": status, "overlaps": overlaps.len()}));
+            if suggest {
+                suggestions = Some(moves);
+            }
+        }
+    }
+
     // Determine verdict
     let has_high = findings.iter().any(|f| f["severity"] == "high");
     let has_warnings = !findings.is_empty();
@@ -196,52 +257,18 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
         ("approve", 0)
     };
 
-    let resp = json!({
+    let mut resp = json!({
         "ok": !has_high,
         "cmd": "review-plan",
         "verdict": verdict,
         "checks": checks,
         "findings": findings,
     });
-
-    Ok((resp.to_string(), exit_code))
-}
-
-/// Extract frontmatter content between first `---` and second `---`.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-    let after_first = &content[3..];
-    let rest = after_first.trim_start_matches(|c: char| c != '\n');
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(rest[..end_idx].to_string())
-    } else if rest.ends_with("---") {
-        let trimmed = rest.trim_end_matches("---");
-        Some(trimmed.to_string())
-    } else {
-        None
+    if let Some(moves) = suggestions {
+        resp["suggestions"] = json!(moves.iter().map(|m| m.to_json()).collect::<Vec<_>>());
     }
-}
-
-/// Check if a frontmatter field exists.
-fn has_field(frontmatter: &str, field_name: &str) -> bool {
-    let prefix = format!("{}:", field_name);
-    frontmatter.lines().any(|line| line.trim().starts_with(&prefix))
-}
 
-/// Extract the value of a frontmatter field.
-fn field_value(frontmatter: &str, field_name: &str) -> Option<String> {
-    let prefix = format!("{}:", field_name);
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim().trim_matches('"').trim();
-            return Some(value.to_string());
-        }
-    }
-    None
+    Ok((resp.to_string(), exit_code))
 }
 
 /// Count `### Task N:` headers in the plan content.
@@ -254,56 +281,6 @@ fn count_tasks(content: &str) -> u32 {
         .count() as u32
 }
 
-/// Parse a YAML list field from frontmatter (supports inline array and block list).
-fn parse_list_field(frontmatter: &str, field_name: &str) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut in_field = false;
-    let prefix = format!("{}:", field_name);
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim();
-
-            // Inline array: ["a", "b"]
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                if inner.trim().is_empty() {
-                    return result;
-                }
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !cleaned.is_empty() {
-                        result.push(cleaned.to_string());
-                    }
-                }
-                return result;
-            }
-
-            if value == "[]" {
-                return result;
-            }
-
-            in_field = true;
-            continue;
-        }
-
-        if in_field {
-            if let Some(rest) = trimmed.strip_prefix("- ") {
-                let item = rest.trim().trim_matches('"').trim_matches('\'');
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() {
-                break;
-            }
-        }
-    }
-
-    result
-}
-
 /// Extract file paths from `**Files:**` lines in plan tasks.
 /// Skips entries marked with `(new)`.
 fn extract_file_paths(content: &str) -> Vec<String> {
@@ -659,4 +636,53 @@ Do stuff.
         assert_eq!(fp_finding["auto_fixable"], false, "file_paths findings should NOT be auto_fixable");
         assert!(fp_finding["suggested_fix"].as_str().unwrap().contains("Verify file paths"));
     }
+
+    #[test]
+    fn test_review_plan_file_overlap_same_wave() {
+        let dir = tempdir().unwrap();
+        let phase_dir = dir.path().join("phases").join("01-core");
+        fs::create_dir_all(&phase_dir).unwrap();
+        let plan = |n: &str, files: &str| {
+            format!(
+                "---\nphase: \"01\"\nplan: \"{}\"\ntitle: \"Plan {}\"\nwave: 1\ndepends_on: []\nmust_haves:\n  - \"works\"\nfiles_modified: {}\n---\n\n### Task 1: Work\n",
+                n, n, files
+            )
+        };
+        let plan_path = phase_dir.join("01-01-PLAN.md");
+        fs::write(&plan_path, plan("01", "[src/main.rs]")).unwrap();
+        fs::write(phase_dir.join("01-02-PLAN.md"), plan("02", "[src/main.rs, docs/a.md]")).unwrap();
+        fs::write(phase_dir.join("01-03-PLAN.md"), plan("03", "[\"src/**\"]")).unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "review-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            phase_dir.to_string_lossy().to_string(),
+            "--suggest".to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 1, "exact overlap should reject: {}", output);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let overlaps: Vec<&serde_json::Value> = parsed["findings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|f| f["check"] == "file_overlap")
+            .collect();
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0]["severity"], "high");
+        assert_eq!(overlaps[1]["severity"], "medium");
+        assert!(overlaps[0]["suggested_fix"].as_str().unwrap().contains("Move plan 01-02"));
+
+        let check = parsed["checks"].as_array().unwrap().iter().find(|c| c["name"] == "file_overlap").unwrap();
+        assert_eq!(check["status"], "fail");
+        // 01-02 and 01-03 each need their own wave.
+        assert_eq!(
+            parsed["suggestions"],
+            serde_json::json!([
+                {"plan": "01-02", "from": 1, "to": 2},
+                {"plan": "01-03", "from": 1, "to": 3}
+            ])
+        );
+    }
 }
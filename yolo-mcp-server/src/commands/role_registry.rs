@@ -0,0 +1,466 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::frontmatter;
+use super::layered_config::{Config, Layer};
+use super::utils::plugin_root;
+
+/// One effort-based routing rule. The first rule whose `efforts` contains the
+/// requested effort (or `"*"`) decides; roles without a matching rule are included.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RouteRule {
+    pub efforts: Vec<String>,
+    /// `"include"` or `"skip"`.
+    pub decision: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Everything the CLI and hooks need to know about an agent role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleSpec {
+    pub name: String,
+    pub aliases: Vec<String>,
+    /// Model per profile (`quality`, `balanced`, `budget`). Used when
+    /// model-profiles.json has no entry for the role.
+    pub models: BTreeMap<String, String>,
+    pub base_turns: u32,
+    /// Tier 2 context family: `planning`, `execution` or `default`.
+    pub family: String,
+    /// PreCompact summarization priorities.
+    pub compaction: Option<String>,
+    /// Files to re-read after compaction.
+    pub reread: Option<String>,
+    /// Message types the role may send/receive when message-schemas.json does
+    /// not list it.
+    pub can_send: Vec<String>,
+    pub can_receive: Vec<String>,
+    pub routing: Vec<RouteRule>,
+}
+
+impl RoleSpec {
+    fn new(name: &str) -> Self {
+        RoleSpec {
+            name: name.to_string(),
+            aliases: Vec::new(),
+            models: BTreeMap::new(),
+            base_turns: 0,
+            family: "default".to_string(),
+            compaction: None,
+            reread: None,
+            can_send: Vec::new(),
+            can_receive: Vec::new(),
+            routing: Vec::new(),
+        }
+    }
+
+    /// Model for a profile, if the role declares one.
+    pub fn model(&self, profile: &str) -> Option<&str> {
+        self.models.get(profile).map(String::as_str)
+    }
+
+    /// Apply the routing rules for an effort level: `(decision, reason)`.
+    pub fn route(&self, effort: &str) -> (&str, String) {
+        for rule in &self.routing {
+            if rule.efforts.iter().any(|e| e == effort || e == "*") {
+                let reason = match &rule.reason {
+                    Some(r) => format!("effort={}: {}", effort, r),
+                    None => format!("effort={}: {} {}", effort, self.name, if rule.decision == "skip" { "skipped" } else { "included" }),
+                };
+                return (rule.decision.as_str(), reason);
+            }
+        }
+        ("include", format!("role={}: always included", self.name))
+    }
+}
+
+/// Partial role definition from config.json `roles.<name>` or an agent file's
+/// `role:` frontmatter block. Present fields replace the inherited value.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoleOverride {
+    pub aliases: Option<Vec<String>>,
+    /// Same model for every profile; `models` entries take precedence.
+    pub model: Option<String>,
+    pub models: Option<BTreeMap<String, String>>,
+    pub base_turns: Option<u32>,
+    pub family: Option<String>,
+    pub compaction: Option<String>,
+    pub reread: Option<String>,
+    pub can_send: Option<Vec<String>>,
+    pub can_receive: Option<Vec<String>>,
+    pub routing: Option<Vec<RouteRule>>,
+}
+
+impl RoleOverride {
+    fn apply(self, spec: &mut RoleSpec) {
+        if let Some(v) = self.aliases { spec.aliases = v; }
+        if let Some(m) = self.model {
+            for profile in PROFILES {
+                spec.models.insert(profile.to_string(), m.clone());
+            }
+        }
+        if let Some(v) = self.models { spec.models.extend(v); }
+        if let Some(v) = self.base_turns { spec.base_turns = v; }
+        if let Some(v) = self.family { spec.family = v; }
+        if let Some(v) = self.compaction { spec.compaction = Some(v); }
+        if let Some(v) = self.reread { spec.reread = Some(v); }
+        if let Some(v) = self.can_send { spec.can_send = v; }
+        if let Some(v) = self.can_receive { spec.can_receive = v; }
+        if let Some(v) = self.routing { spec.routing = v; }
+    }
+}
+
+const PROFILES: &[&str] = &["quality", "balanced", "budget"];
+const FAMILIES: &[&str] = &["planning", "execution", "default"];
+const MODELS: &[&str] = &["opus", "sonnet", "haiku"];
+
+/// All known roles: the built-in nine, then `yolo-*.md` agent files under the
+/// plugin's `agents/`, then the layered config's `roles`. Later layers override earlier
+/// ones field by field, so a config entry can tweak a built-in role or define
+/// a new one outright.
+#[derive(Debug, Clone)]
+pub struct RoleRegistry {
+    roles: BTreeMap<String, RoleSpec>,
+    /// Problems found while loading; the offending definition is skipped.
+    pub warnings: Vec<String>,
+}
+
+impl RoleRegistry {
+    pub fn builtin() -> Self {
+        let mut roles = BTreeMap::new();
+        for (name, models, turns, family, aliases) in BUILTIN {
+            let mut spec = RoleSpec::new(name);
+            for (profile, model) in PROFILES.iter().zip(models.iter()) {
+                spec.models.insert(profile.to_string(), model.to_string());
+            }
+            spec.base_turns = *turns;
+            spec.family = family.to_string();
+            spec.aliases = aliases.iter().map(|a| a.to_string()).collect();
+            spec.compaction = builtin_compaction(name).map(str::to_string);
+            spec.reread = builtin_reread(name).map(str::to_string);
+            spec.routing = builtin_routing(name);
+            roles.insert(name.to_string(), spec);
+        }
+        RoleRegistry { roles, warnings: Vec::new() }
+    }
+
+    /// Built-ins plus agent files and the layered config for the project at `cwd`.
+    pub fn load(cwd: &Path) -> Self {
+        Self::from_config(cwd, &Config::load(cwd))
+    }
+
+    /// Like `load`, for commands that take an explicit config path: that file
+    /// stands in for the project layer.
+    pub fn load_with_config(cwd: &Path, config_path: &Path) -> Self {
+        let config = Config::load(cwd);
+        let project = fs::read_to_string(config_path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<Map<String, Value>>(&s).ok());
+        match project {
+            Some(values) => Self::from_config(cwd, &config.with_layer_values(Layer::Project, values)),
+            None => Self::from_config(cwd, &config),
+        }
+    }
+
+    fn from_config(cwd: &Path, config: &Config) -> Self {
+        let mut registry = Self::builtin();
+        if let Some(root) = plugin_root(cwd) {
+            registry.merge_agent_dir(&root.join("agents"));
+        }
+        if let Some(roles) = config.get("roles") {
+            registry.merge_config(roles);
+        }
+        registry
+    }
+
+    /// Look up a role by name or alias.
+    pub fn get(&self, name: &str) -> Option<&RoleSpec> {
+        self.roles
+            .get(name)
+            .or_else(|| self.roles.values().find(|r| r.aliases.iter().any(|a| a == name)))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Role names in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        self.roles.keys().map(String::as_str).collect()
+    }
+
+    pub fn roles(&self) -> impl Iterator<Item = &RoleSpec> {
+        self.roles.values()
+    }
+
+    /// Register every `yolo-<role>.md` in `dir`. A file's `maxTurns` seeds the
+    /// base turns of roles that have none yet; its `role:` block overrides the rest.
+    pub fn merge_agent_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            let Some(role) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("yolo-"))
+                .and_then(|n| n.strip_suffix(".md"))
+            else {
+                continue;
+            };
+            let fm = match frontmatter::parse_file(&path) {
+                Ok(Some(fm)) => fm,
+                Ok(None) => continue,
+                Err(e) => {
+                    self.warnings.push(format!("{}: {}", path.display(), e));
+                    continue;
+                }
+            };
+            let mut spec = self.roles.get(role).cloned().unwrap_or_else(|| RoleSpec::new(role));
+            if spec.base_turns == 0
+                && let Some(turns) = fm.scalar("maxTurns").and_then(|t| t.parse().ok())
+            {
+                spec.base_turns = turns;
+            }
+            if let Some(block) = fm.get("role") {
+                match self.parse_override(role, block) {
+                    Ok(o) => o.apply(&mut spec),
+                    Err(e) => {
+                        self.warnings.push(format!("{}: {}", path.display(), e));
+                        continue;
+                    }
+                }
+            }
+            self.roles.insert(role.to_string(), spec);
+        }
+    }
+
+    /// Apply config.json `roles`: an object of role name to partial definition.
+    pub fn merge_config(&mut self, roles: &Value) {
+        let Some(map) = roles.as_object() else {
+            self.warnings.push("config roles: expected an object".to_string());
+            return;
+        };
+        for (name, block) in map {
+            match self.parse_override(name, block) {
+                Ok(o) => {
+                    let spec = self.roles.entry(name.clone()).or_insert_with(|| RoleSpec::new(name));
+                    o.apply(spec);
+                }
+                Err(e) => self.warnings.push(format!("config roles.{}: {}", name, e)),
+            }
+        }
+    }
+
+    fn parse_override(&self, name: &str, block: &Value) -> Result<RoleOverride, String> {
+        let o: RoleOverride = serde_json::from_value(block.clone()).map_err(|e| e.to_string())?;
+        if let Some(f) = &o.family
+            && !FAMILIES.contains(&f.as_str())
+        {
+            return Err(format!("role {}: unknown family '{}'", name, f));
+        }
+        let models = o.model.iter().chain(o.models.iter().flat_map(|m| m.values()));
+        for m in models {
+            if !MODELS.contains(&m.as_str()) {
+                return Err(format!("role {}: invalid model '{}'", name, m));
+            }
+        }
+        for rule in o.routing.iter().flatten() {
+            if rule.decision != "include" && rule.decision != "skip" {
+                return Err(format!("role {}: routing decision must be include or skip", name));
+            }
+        }
+        Ok(o)
+    }
+}
+
+/// name, models (quality, balanced, budget), base turns, family, aliases.
+type BuiltinRole = (&'static str, [&'static str; 3], u32, &'static str, &'static [&'static str]);
+
+const BUILTIN: &[BuiltinRole] = &[
+    ("lead", ["opus", "sonnet", "sonnet"], 50, "planning", &["leader"]),
+    ("dev", ["opus", "sonnet", "sonnet"], 75, "execution", &[]),
+    ("qa", ["opus", "sonnet", "sonnet"], 25, "execution", &[]),
+    ("scout", ["haiku", "haiku", "haiku"], 15, "default", &[]),
+    ("debugger", ["opus", "sonnet", "sonnet"], 80, "execution", &["debug"]),
+    ("architect", ["opus", "sonnet", "sonnet"], 30, "planning", &["arch"]),
+    ("docs", ["sonnet", "sonnet", "sonnet"], 30, "default", &[]),
+    ("researcher", ["sonnet", "haiku", "haiku"], 15, "planning", &[]),
+    ("reviewer", ["opus", "sonnet", "sonnet"], 15, "planning", &[]),
+];
+
+fn builtin_compaction(role: &str) -> Option<&'static str> {
+    Some(match role {
+        "scout" => "Preserve research findings, URLs, confidence assessments",
+        "dev" => "Preserve commit hashes, file paths modified, deviation decisions, current task number. After compaction, if .yolo-planning/codebase/META.md exists, re-read CONVENTIONS.md, PATTERNS.md, STRUCTURE.md, and DEPENDENCIES.md (whichever exist) from .yolo-planning/codebase/",
+        "qa" => "Preserve pass/fail status, gap descriptions, verification results. After compaction, if .yolo-planning/codebase/META.md exists, re-read TESTING.md, CONCERNS.md, and ARCHITECTURE.md (whichever exist) from .yolo-planning/codebase/",
+        "lead" => "Preserve phase status, plan structure, coordination decisions. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md, CONCERNS.md, and STRUCTURE.md (whichever exist) from .yolo-planning/codebase/",
+        "architect" => "Preserve requirement IDs, phase structure, success criteria, key decisions. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md and STACK.md (whichever exist) from .yolo-planning/codebase/",
+        "debugger" => "Preserve reproduction steps, hypotheses, evidence gathered, diagnosis. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md, CONCERNS.md, PATTERNS.md, and DEPENDENCIES.md (whichever exist) from .yolo-planning/codebase/",
+        _ => return None,
+    })
+}
+
+fn builtin_reread(role: &str) -> Option<&'static str> {
+    Some(match role {
+        "lead" => "STATE.md, ROADMAP.md, config.json, and current phase plans",
+        "dev" => "your assigned plan file, SUMMARY.md template, and relevant source files",
+        "qa" => "SUMMARY.md files under review, verification criteria, and gap reports",
+        "scout" => "research notes, REQUIREMENTS.md, and any scout-specific findings",
+        "debugger" => "reproduction steps, hypothesis log, and related source files",
+        "architect" => "REQUIREMENTS.md, ROADMAP.md, phase structure, and architecture decisions",
+        _ => return None,
+    })
+}
+
+fn builtin_routing(role: &str) -> Vec<RouteRule> {
+    let rule = |efforts: &[&str], decision: &str, reason: &str| RouteRule {
+        efforts: efforts.iter().map(|e| e.to_string()).collect(),
+        decision: decision.to_string(),
+        reason: Some(reason.to_string()),
+    };
+    match role {
+        "scout" => vec![
+            rule(&["turbo", "fast"], "skip", "scout not needed"),
+            rule(&["*"], "include", "scout included"),
+        ],
+        "architect" => vec![
+            rule(&["thorough"], "include", "architect included"),
+            rule(&["*"], "skip", "architect only for thorough"),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_matches_model_profiles() {
+        let profiles: Value = serde_json::from_str(include_str!("../../../config/model-profiles.json")).unwrap();
+        let registry = RoleRegistry::builtin();
+        assert_eq!(registry.names().len(), 9);
+        for role in registry.roles() {
+            for profile in PROFILES {
+                assert_eq!(
+                    profiles[profile][&role.name].as_str(),
+                    role.model(profile),
+                    "{} {}",
+                    role.name,
+                    profile
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_aliases_resolve() {
+        let registry = RoleRegistry::builtin();
+        assert_eq!(registry.get("arch").unwrap().name, "architect");
+        assert_eq!(registry.get("leader").unwrap().name, "lead");
+        assert!(registry.get("security-auditor").is_none());
+    }
+
+    #[test]
+    fn test_route_rules() {
+        let registry = RoleRegistry::builtin();
+        let architect = registry.get("architect").unwrap();
+        assert_eq!(architect.route("thorough").0, "include");
+        assert_eq!(architect.route("fast"), ("skip", "effort=fast: architect only for thorough".to_string()));
+        assert_eq!(registry.get("dev").unwrap().route("turbo").0, "include");
+    }
+
+    #[test]
+    fn test_config_defines_and_overrides_roles() {
+        let mut registry = RoleRegistry::builtin();
+        registry.merge_config(&json!({
+            "security-auditor": {
+                "model": "opus",
+                "base_turns": 40,
+                "family": "execution",
+                "routing": [{"efforts": ["turbo"], "decision": "skip"}]
+            },
+            "dev": {"base_turns": 90},
+            "qa": {"family": "backstage"},
+        }));
+        let auditor = registry.get("security-auditor").unwrap();
+        assert_eq!(auditor.model("budget"), Some("opus"));
+        assert_eq!(auditor.base_turns, 40);
+        assert_eq!(auditor.route("turbo"), ("skip", "effort=turbo: security-auditor skipped".to_string()));
+
+        let dev = registry.get("dev").unwrap();
+        assert_eq!(dev.base_turns, 90);
+        assert_eq!(dev.family, "execution");
+
+        assert_eq!(registry.get("qa").unwrap().family, "execution");
+        assert_eq!(registry.warnings.len(), 1);
+        assert!(registry.warnings[0].contains("unknown family 'backstage'"));
+    }
+
+    #[test]
+    fn test_agent_files_register_roles() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("yolo-migrator.md"),
+            "---\nname: yolo-migrator\nmodel: inherit\nmaxTurns: 60\nrole:\n  family: execution\n  models:\n    quality: opus\n    balanced: sonnet\n    budget: haiku\n  can_send: [execution_update]\n  compaction: Preserve migration step\n---\n# Migrator\n",
+        ).unwrap();
+        fs::write(dir.path().join("yolo-docs.md"), "---\nname: yolo-docs\nmaxTurns: 20\n---\n").unwrap();
+        fs::write(dir.path().join("yolo-broken.md"), "---\nrole:\n  colour: red\n---\n").unwrap();
+        fs::write(dir.path().join("README.md"), "---\nname: readme\n---\n").unwrap();
+
+        let mut registry = RoleRegistry::builtin();
+        registry.merge_agent_dir(dir.path());
+
+        let migrator = registry.get("migrator").unwrap();
+        assert_eq!(migrator.base_turns, 60);
+        assert_eq!(migrator.family, "execution");
+        assert_eq!(migrator.model("budget"), Some("haiku"));
+        assert_eq!(migrator.can_send, vec!["execution_update"]);
+        assert_eq!(migrator.compaction.as_deref(), Some("Preserve migration step"));
+
+        // maxTurns only seeds roles without base turns.
+        assert_eq!(registry.get("docs").unwrap().base_turns, 30);
+        assert!(registry.get("broken").is_none());
+        assert!(registry.warnings[0].contains("yolo-broken.md"));
+        assert_eq!(registry.names().len(), 10);
+    }
+
+    #[test]
+    fn test_load_reads_project_config() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        fs::write(
+            dir.path().join(".yolo-planning/config.json"),
+            json!({"roles": {"migrator": {"model": "sonnet", "base_turns": 45}}}).to_string(),
+        ).unwrap();
+        let registry = RoleRegistry::load(dir.path());
+        assert_eq!(registry.get("migrator").unwrap().base_turns, 45);
+        assert!(registry.contains("dev"));
+    }
+
+    #[test]
+    fn test_explicit_config_stands_in_for_project_layer() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        fs::write(
+            dir.path().join(".yolo-planning/config.json"),
+            json!({"roles": {"migrator": {"model": "sonnet"}}}).to_string(),
+        ).unwrap();
+        let explicit = dir.path().join("other.json");
+        fs::write(&explicit, json!({"roles": {"auditor": {"model": "haiku"}}}).to_string()).unwrap();
+
+        let registry = RoleRegistry::load_with_config(dir.path(), &explicit);
+        assert!(registry.contains("auditor"));
+        assert!(!registry.contains("migrator"));
+
+        let fallback = RoleRegistry::load_with_config(dir.path(), &dir.path().join("missing.json"));
+        assert!(fallback.contains("migrator"));
+    }
+}
@@ -1,7 +1,13 @@
+use serde::Deserialize;
 use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
 
+use super::event_bus;
+use super::feature_flags::FeatureFlag;
+use super::layered_config::Config;
+use super::utils::plugin_root;
+
 /// Default rollout stages when no rollout-stages.json exists.
 const DEFAULT_STAGES: &[(&str, &str)] = &[
     ("canary", "Single agent, limited scope"),
@@ -9,52 +15,266 @@ const DEFAULT_STAGES: &[(&str, &str)] = &[
     ("full", "All agents, full scope"),
 ];
 
+/// One rollout stage: the flags it turns on and the completed phases it needs.
+#[derive(Debug, Clone, PartialEq)]
+struct Stage {
+    name: String,
+    description: String,
+    phases_required: usize,
+    flags: Vec<String>,
+}
+
+/// Health limits for the current stage, counted over events since the stage began.
+/// Exceeding any of them blocks advancement and rolls a non-first stage back.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+struct HealthThresholds {
+    max_gate_failure_rate: f64,
+    /// Gate results needed before the failure rate counts.
+    min_gate_samples: usize,
+    max_completion_rejections: usize,
+    max_hook_errors: usize,
+    max_token_overages: usize,
+    /// After a rollback, the stage must collect `min_gate_samples` gate results
+    /// or finish this many phases before it may advance again.
+    min_phases_after_rollback: usize,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            max_gate_failure_rate: 0.25,
+            min_gate_samples: 4,
+            max_completion_rejections: 3,
+            max_hook_errors: 5,
+            max_token_overages: 10,
+            min_phases_after_rollback: 2,
+        }
+    }
+}
+
+/// Stage definitions plus the `advancement` block of rollout-stages.json.
+#[derive(Debug, Clone)]
+struct Rollout {
+    stages: Vec<Stage>,
+    auto_advance: bool,
+    health: HealthThresholds,
+}
+
+/// Health signals counted from the event log.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Health {
+    gate_passed: usize,
+    gate_failed: usize,
+    completion_rejections: usize,
+    hook_errors: usize,
+    token_overages: usize,
+    phases_ended: usize,
+}
+
+impl Health {
+    fn gate_failure_rate(&self) -> f64 {
+        let total = self.gate_passed + self.gate_failed;
+        if total == 0 { 0.0 } else { self.gate_failed as f64 / total as f64 }
+    }
+
+    /// Threshold breaches, as human-readable reasons.
+    fn breaches(&self, t: &HealthThresholds) -> Vec<String> {
+        let mut out = Vec::new();
+        let samples = self.gate_passed + self.gate_failed;
+        if samples >= t.min_gate_samples && self.gate_failure_rate() > t.max_gate_failure_rate {
+            out.push(format!(
+                "gate failure rate {:.2} exceeds {:.2} ({} of {} gates failed)",
+                self.gate_failure_rate(), t.max_gate_failure_rate, self.gate_failed, samples
+            ));
+        }
+        if self.completion_rejections > t.max_completion_rejections {
+            out.push(format!("{} task_completion_rejected events exceed {}", self.completion_rejections, t.max_completion_rejections));
+        }
+        if self.hook_errors > t.max_hook_errors {
+            out.push(format!("{} hook errors exceed {}", self.hook_errors, t.max_hook_errors));
+        }
+        if self.token_overages > t.max_token_overages {
+            out.push(format!("{} token overages exceed {}", self.token_overages, t.max_token_overages));
+        }
+        out
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "gate_passed": self.gate_passed,
+            "gate_failed": self.gate_failed,
+            "gate_failure_rate": self.gate_failure_rate(),
+            "completion_rejections": self.completion_rejections,
+            "hook_errors": self.hook_errors,
+            "token_overages": self.token_overages,
+            "phases_ended": self.phases_ended,
+        })
+    }
+}
+
+/// What the health check says to do with the current stage.
+#[derive(Debug, Clone, PartialEq)]
+enum Decision {
+    Advance { to: usize },
+    Rollback { to: usize },
+    Hold,
+}
+
+impl Decision {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Advance { .. } => "advance",
+            Self::Rollback { .. } => "rollback",
+            Self::Hold => "hold",
+        }
+    }
+}
+
 /// CLI entry point: `yolo rollout <action> [stage]`
-/// Actions: check, advance, status
+/// Actions: check, advance, status, validate, evaluate [--apply]
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     if args.len() < 3 {
-        return Err("Usage: yolo rollout {check|advance|status} [stage]".to_string());
+        return Err("Usage: yolo rollout {check|advance|status|validate|evaluate [--apply]} [stage]".to_string());
     }
 
     let action = &args[2];
     let planning_dir = cwd.join(".yolo-planning");
+    let rollout = load_rollout(&planning_dir, cwd);
 
     match action.as_str() {
-        "check" => rollout_check(&planning_dir),
-        "advance" => rollout_advance(&planning_dir),
-        "status" => rollout_status(&planning_dir),
-        _ => Err(format!("Unknown rollout action: '{}'. Use: check, advance, status", action)),
+        "check" => rollout_check(&planning_dir, &rollout),
+        "advance" => {
+            let errors = validate_stage_flags(&rollout.stages);
+            if !errors.is_empty() {
+                let result = json!({"advanced": false, "errors": errors});
+                return Ok((serde_json::to_string_pretty(&result).unwrap_or_default(), 1));
+            }
+            rollout_advance(&planning_dir, &rollout)
+        }
+        "status" => rollout_status(&planning_dir, &rollout),
+        "validate" => rollout_validate(&rollout),
+        "evaluate" => {
+            let apply = rollout.auto_advance || args.iter().any(|a| a == "--apply");
+            let result = evaluate(&planning_dir, &rollout, apply)?;
+            Ok((serde_json::to_string_pretty(&result).unwrap_or_default(), 0))
+        }
+        _ => Err(format!("Unknown rollout action: '{}'. Use: check, advance, status, validate, evaluate", action)),
     }
 }
 
-/// Load rollout stage definitions from rollout-stages.json or use defaults.
-fn load_stages(planning_dir: &Path) -> Vec<(String, String)> {
-    let stages_file = planning_dir.join("rollout-stages.json");
-    if stages_file.exists()
-        && let Ok(content) = fs::read_to_string(&stages_file)
-        && let Ok(val) = serde_json::from_str::<Value>(&content)
-        && let Some(stages) = val.get("stages").and_then(|v| v.as_array())
-    {
-        return stages.iter().filter_map(|s| {
-            let name = s.get("name")?.as_str()?.to_string();
-            let desc = s.get("description").and_then(|d| d.as_str()).unwrap_or("").to_string();
-            Some((name, desc))
-        }).collect();
-    }
-    DEFAULT_STAGES.iter().map(|(n, d)| (n.to_string(), d.to_string())).collect()
+/// Re-evaluate the rollout after a phase ends. Acts only when
+/// `advancement.auto_advance` is set; every decision is published as `rollout_decision`.
+pub fn auto_evaluate(cwd: &Path) {
+    let planning_dir = cwd.join(".yolo-planning");
+    let rollout = load_rollout(&planning_dir, cwd);
+    if rollout.auto_advance {
+        let _ = evaluate(&planning_dir, &rollout, true);
+    }
 }
 
-/// Count completed phases from the event log.
-fn count_completed_phases(planning_dir: &Path) -> usize {
-    let events_file = planning_dir.join(".events/event-log.jsonl");
-    if !events_file.exists() {
-        return 0;
+/// The project's rollout-stages.json, else the plugin's `config/rollout-stages.json`.
+fn stages_file(planning_dir: &Path, cwd: &Path) -> Option<Value> {
+    let project = planning_dir.join("rollout-stages.json");
+    let path = if project.exists() {
+        Some(project)
+    } else {
+        plugin_root(cwd).map(|r| r.join("config").join("rollout-stages.json"))
+    };
+    path.and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str::<Value>(&c).ok())
+}
+
+/// Load stage definitions and advancement settings, falling back to the default
+/// canary/partial/full stages. Stages are named by `name` or `label`.
+fn load_rollout(planning_dir: &Path, cwd: &Path) -> Rollout {
+    let file = stages_file(planning_dir, cwd);
+    let stages: Vec<Stage> = file
+        .as_ref()
+        .and_then(|v| v.get("stages").and_then(|s| s.as_array()))
+        .map(|stages| {
+            stages
+                .iter()
+                .enumerate()
+                .map(|(i, s)| Stage {
+                    name: s
+                        .get("name")
+                        .or_else(|| s.get("label"))
+                        .and_then(|n| n.as_str())
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("stage {}", i + 1)),
+                    description: s.get("description").and_then(|d| d.as_str()).unwrap_or("").to_string(),
+                    phases_required: s.get("phases_required").and_then(|p| p.as_u64()).unwrap_or(0) as usize,
+                    flags: s
+                        .get("flags")
+                        .and_then(|f| f.as_array())
+                        .map(|a| a.iter().filter_map(|f| f.as_str().map(str::to_string)).collect())
+                        .unwrap_or_default(),
+                })
+                .collect()
+        })
+        .filter(|s: &Vec<Stage>| !s.is_empty())
+        .unwrap_or_else(|| {
+            DEFAULT_STAGES
+                .iter()
+                .map(|(n, d)| Stage { name: n.to_string(), description: d.to_string(), phases_required: 0, flags: Vec::new() })
+                .collect()
+        });
+    let advancement = file.as_ref().and_then(|v| v.get("advancement"));
+    Rollout {
+        stages,
+        auto_advance: advancement.and_then(|a| a.get("auto_advance")).and_then(|v| v.as_bool()).unwrap_or(false),
+        health: advancement
+            .and_then(|a| a.get("health"))
+            .and_then(|h| serde_json::from_value(h.clone()).ok())
+            .unwrap_or_default(),
     }
+}
 
-    let content = match fs::read_to_string(&events_file) {
-        Ok(c) => c,
-        Err(_) => return 0,
-    };
+/// Check stage batches against the flag dependency graph: every flag must be known,
+/// its requirements enabled by the same or an earlier stage, and no two flags
+/// enabled by the end of a stage may conflict.
+fn validate_stage_flags(stages: &[Stage]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut enabled: Vec<FeatureFlag> = Vec::new();
+    for stage in stages {
+        let mut batch = Vec::new();
+        for key in &stage.flags {
+            match FeatureFlag::from_key(key) {
+                Some(f) => batch.push(f),
+                None => errors.push(format!("stage '{}': unknown flag '{}'", stage.name, key)),
+            }
+        }
+        enabled.extend(&batch);
+        for flag in &batch {
+            for (dep, _) in flag.spec().requires.iter().filter(|(d, _)| !enabled.contains(d)) {
+                errors.push(format!(
+                    "stage '{}': {} requires {}, which no stage up to this one enables",
+                    stage.name, flag.key(), dep.key()
+                ));
+            }
+            for other in flag.conflicts_with().into_iter().filter(|o| enabled.contains(o)) {
+                errors.push(format!("stage '{}': {} conflicts with {}", stage.name, flag.key(), other.key()));
+            }
+        }
+    }
+    errors
+}
+
+/// Validate: check the stage flag batches against the dependency graph.
+fn rollout_validate(rollout: &Rollout) -> Result<(String, i32), String> {
+    let errors = validate_stage_flags(&rollout.stages);
+    let result = json!({
+        "valid": errors.is_empty(),
+        "stages": rollout.stages.iter().map(|s| json!({"name": s.name, "flags": s.flags})).collect::<Vec<_>>(),
+        "errors": errors,
+    });
+    Ok((serde_json::to_string_pretty(&result).unwrap_or_default(), if errors.is_empty() { 0 } else { 1 }))
+}
+
+/// Count completed phases from the event log.
+fn count_completed_phases(planning_dir: &Path) -> usize {
+    let content = event_bus::read_all(planning_dir);
 
     let mut completed_phases = std::collections::HashSet::new();
     for line in content.lines() {
@@ -69,20 +289,171 @@ fn count_completed_phases(planning_dir: &Path) -> usize {
     completed_phases.len()
 }
 
-/// Get the current rollout stage from config.json.
-fn get_current_stage(planning_dir: &Path) -> Option<String> {
-    let config_path = planning_dir.join("config.json");
-    if !config_path.exists() {
-        return None;
+/// Last stage change event, if the stage ever changed.
+fn last_stage_change(events: &[Value]) -> Option<&Value> {
+    events
+        .iter()
+        .rev()
+        .find(|e| matches!(e.get("event").and_then(|v| v.as_str()), Some("rollout_advance" | "rollout_rollback")))
+}
+
+/// Timestamp of the last stage change, or `None` if the stage never changed.
+fn stage_started_at(events: &[Value]) -> Option<String> {
+    last_stage_change(events)
+        .and_then(|e| e.get("ts").and_then(|t| t.as_str()))
+        .map(str::to_string)
+}
+
+/// Whether the current stage was entered by a rollback.
+fn entered_by_rollback(events: &[Value]) -> bool {
+    last_stage_change(events).is_some_and(|e| e.get("event").and_then(|v| v.as_str()) == Some("rollout_rollback"))
+}
+
+/// Count health signals in events at or after `since` (ISO-8601 timestamps compare as strings).
+fn collect_health(events: &[Value], since: Option<&str>) -> Health {
+    let mut health = Health::default();
+    for e in events {
+        if let Some(since) = since
+            && e.get("ts").and_then(|t| t.as_str()).is_none_or(|ts| ts < since)
+        {
+            continue;
+        }
+        match e.get("event").and_then(|v| v.as_str()) {
+            Some("gate_passed") => health.gate_passed += 1,
+            Some("gate_failed") => health.gate_failed += 1,
+            Some("task_completion_rejected") => health.completion_rejections += 1,
+            Some("hook_error") => health.hook_errors += 1,
+            Some("token_overage") => health.token_overages += 1,
+            Some("phase_end") => health.phases_ended += 1,
+            _ => {}
+        }
     }
-    let content = fs::read_to_string(&config_path).ok()?;
-    let config: Value = serde_json::from_str(&content).ok()?;
-    config.get("rollout_stage").and_then(|v| v.as_str()).map(|s| s.to_string())
+    health
 }
 
-/// Set the rollout stage in config.json.
-fn set_rollout_stage(planning_dir: &Path, stage: &str) -> Result<(), String> {
+/// Roll back a regressed stage; otherwise advance once the next stage's
+/// `phases_required` is met and the current stage is healthy. A stage entered
+/// by a rollback must first show fresh evidence of health, so an empty event
+/// window does not re-advance it straight away.
+fn decide(
+    rollout: &Rollout,
+    current_idx: usize,
+    completed: usize,
+    health: &Health,
+    after_rollback: bool,
+) -> (Decision, Vec<String>) {
+    let breaches = health.breaches(&rollout.health);
+    if !breaches.is_empty() {
+        return if current_idx > 0 {
+            (Decision::Rollback { to: current_idx - 1 }, breaches)
+        } else {
+            (Decision::Hold, breaches)
+        };
+    }
+    let t = &rollout.health;
+    let samples = health.gate_passed + health.gate_failed;
+    if after_rollback && samples < t.min_gate_samples && health.phases_ended < t.min_phases_after_rollback {
+        return (
+            Decision::Hold,
+            vec![format!(
+                "rolled back: needs {} gate results or {} completed phases before advancing ({} and {} so far)",
+                t.min_gate_samples, t.min_phases_after_rollback, samples, health.phases_ended
+            )],
+        );
+    }
+    match rollout.stages.get(current_idx + 1) {
+        None => (Decision::Hold, vec!["already at final stage".to_string()]),
+        Some(next) if completed < next.phases_required => (
+            Decision::Hold,
+            vec![format!("{} of {} phases required for '{}' completed", completed, next.phases_required, next.name)],
+        ),
+        Some(next) => (
+            Decision::Advance { to: current_idx + 1 },
+            vec![format!("healthy with {} completed phases (needs {} for '{}')", completed, next.phases_required, next.name)],
+        ),
+    }
+}
+
+/// Compute the decision for the current stage, apply it when `apply` is set,
+/// and publish a `rollout_decision` event either way.
+fn evaluate(planning_dir: &Path, rollout: &Rollout, apply: bool) -> Result<Value, String> {
+    let events = event_bus::load_events(planning_dir);
+    let since = stage_started_at(&events);
+    let health = collect_health(&events, since.as_deref());
+    let current_idx = current_index(planning_dir, rollout);
+    let completed = count_completed_phases(planning_dir);
+    let (decision, reasons) = decide(rollout, current_idx, completed, &health, entered_by_rollback(&events));
+
+    let mut applied = false;
+    let mut errors = Vec::new();
+    if apply && decision != Decision::Hold {
+        errors = validate_stage_flags(&rollout.stages);
+        if errors.is_empty() {
+            match decision {
+                Decision::Advance { to } => {
+                    set_rollout_stage(planning_dir, rollout, to)?;
+                    publish_stage_change(planning_dir, "rollout_advance", &rollout.stages[current_idx].name, &rollout.stages[to].name, &reasons);
+                }
+                Decision::Rollback { to } => {
+                    set_rollout_stage(planning_dir, rollout, to)?;
+                    publish_stage_change(planning_dir, "rollout_rollback", &rollout.stages[current_idx].name, &rollout.stages[to].name, &reasons);
+                }
+                Decision::Hold => {}
+            }
+            applied = true;
+        }
+    }
+
+    let to = match decision {
+        Decision::Advance { to } | Decision::Rollback { to } => Some(rollout.stages[to].name.as_str()),
+        Decision::Hold => None,
+    };
+    let data = json!({
+        "decision": decision.name(),
+        "from": rollout.stages[current_idx].name,
+        "to": to,
+        "applied": applied,
+        "reasons": reasons,
+        "health": health.to_json(),
+        "since": since,
+        "completed_phases": completed,
+    });
+    event_bus::publish(planning_dir, &json!({"event": "rollout_decision", "data": data}));
+
+    let mut result = data;
+    result["auto_advance"] = json!(rollout.auto_advance);
+    if !errors.is_empty() {
+        result["errors"] = json!(errors);
+    }
+    Ok(result)
+}
+
+fn publish_stage_change(planning_dir: &Path, event: &str, from: &str, to: &str, reasons: &[String]) {
+    event_bus::publish(planning_dir, &json!({
+        "event": event,
+        "data": {"from": from, "to": to, "reasons": reasons}
+    }));
+}
+
+/// Get the current rollout stage from the layered config.
+fn get_current_stage(planning_dir: &Path) -> Option<String> {
+    Config::load(planning_dir.parent().unwrap_or(planning_dir))
+        .get("rollout_stage")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+fn current_index(planning_dir: &Path, rollout: &Rollout) -> usize {
+    get_current_stage(planning_dir)
+        .and_then(|c| rollout.stages.iter().position(|s| s.name == c))
+        .unwrap_or(0)
+}
+
+/// Move config.json to stage `to`. Advancing enables the stage's flags;
+/// rolling back disables the flags of every stage after `to`.
+fn set_rollout_stage(planning_dir: &Path, rollout: &Rollout, to: usize) -> Result<(), String> {
     let config_path = planning_dir.join("config.json");
+    let stage = &rollout.stages[to].name;
 
     let mut config: Value = if config_path.exists() {
         let content = fs::read_to_string(&config_path)
@@ -96,7 +467,7 @@ fn set_rollout_stage(planning_dir: &Path, stage: &str) -> Result<(), String> {
     config["rollout_stage"] = json!(stage);
 
     // Apply stage-specific flags
-    match stage {
+    match stage.as_str() {
         "canary" => {
             config["max_agents"] = json!(1);
             config["rollout_scope"] = json!("limited");
@@ -111,6 +482,15 @@ fn set_rollout_stage(planning_dir: &Path, stage: &str) -> Result<(), String> {
         }
         _ => {}
     }
+    for (i, s) in rollout.stages.iter().enumerate() {
+        for flag in &s.flags {
+            if i <= to {
+                config[flag.as_str()] = json!(true);
+            } else if config.get(flag.as_str()).is_some() {
+                config[flag.as_str()] = json!(false);
+            }
+        }
+    }
 
     let output = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize config: {}", e))?;
@@ -120,15 +500,12 @@ fn set_rollout_stage(planning_dir: &Path, stage: &str) -> Result<(), String> {
     Ok(())
 }
 
-/// Check: report current stage and whether advancement criteria are met.
-fn rollout_check(planning_dir: &Path) -> Result<(String, i32), String> {
-    let stages = load_stages(planning_dir);
+/// Check: report current stage, stage health and whether advancement criteria are met.
+fn rollout_check(planning_dir: &Path, rollout: &Rollout) -> Result<(String, i32), String> {
+    let stages = &rollout.stages;
     let current = get_current_stage(planning_dir);
     let completed = count_completed_phases(planning_dir);
-
-    let current_idx = current.as_ref()
-        .and_then(|c| stages.iter().position(|(name, _)| name == c))
-        .unwrap_or(0);
+    let current_idx = current_index(planning_dir, rollout);
 
     let can_advance = current_idx + 1 < stages.len();
     let next_stage = if can_advance {
@@ -137,82 +514,63 @@ fn rollout_check(planning_dir: &Path) -> Result<(String, i32), String> {
         None
     };
 
+    let events = event_bus::load_events(planning_dir);
+    let health = collect_health(&events, stage_started_at(&events).as_deref());
+    let (decision, reasons) = decide(rollout, current_idx, completed, &health, entered_by_rollback(&events));
+
     let result = json!({
-        "current_stage": current.unwrap_or_else(|| stages[0].0.clone()),
+        "current_stage": current.unwrap_or_else(|| stages[0].name.clone()),
         "current_index": current_idx,
         "total_stages": stages.len(),
         "completed_phases": completed,
         "can_advance": can_advance,
-        "next_stage": next_stage.map(|(n, _)| n.as_str()),
+        "next_stage": next_stage.map(|s| s.name.as_str()),
+        "health": health.to_json(),
+        "recommendation": decision.name(),
+        "reasons": reasons,
+        "auto_advance": rollout.auto_advance,
     });
 
     Ok((serde_json::to_string_pretty(&result).unwrap_or_default(), 0))
 }
 
 /// Advance to the next rollout stage.
-fn rollout_advance(planning_dir: &Path) -> Result<(String, i32), String> {
-    let stages = load_stages(planning_dir);
-    let current = get_current_stage(planning_dir);
-
-    let current_idx = current.as_ref()
-        .and_then(|c| stages.iter().position(|(name, _)| name == c))
-        .unwrap_or(0);
+fn rollout_advance(planning_dir: &Path, rollout: &Rollout) -> Result<(String, i32), String> {
+    let stages = &rollout.stages;
+    let current_idx = current_index(planning_dir, rollout);
 
     if current_idx + 1 >= stages.len() {
         return Ok(("Already at final rollout stage".to_string(), 0));
     }
 
     let next = &stages[current_idx + 1];
-    set_rollout_stage(planning_dir, &next.0)?;
+    set_rollout_stage(planning_dir, rollout, current_idx + 1)?;
 
     // Log the advancement event
-    let events_dir = planning_dir.join(".events");
-    let _ = fs::create_dir_all(&events_dir);
-    let events_file = events_dir.join("event-log.jsonl");
-    let event = json!({
-        "event": "rollout_advance",
-        "data": {
-            "from": current.unwrap_or_else(|| stages[0].0.clone()),
-            "to": next.0,
-        }
-    });
-    if let Ok(line) = serde_json::to_string(&event) {
-        let _ = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&events_file)
-            .and_then(|mut f| {
-                use std::io::Write;
-                writeln!(f, "{}", line)
-            });
-    }
+    publish_stage_change(planning_dir, "rollout_advance", &stages[current_idx].name, &next.name, &["manual".to_string()]);
 
     let result = json!({
         "advanced": true,
-        "from_stage": stages[current_idx].0,
-        "to_stage": next.0,
-        "description": next.1,
+        "from_stage": stages[current_idx].name,
+        "to_stage": next.name,
+        "description": next.description,
     });
 
     Ok((serde_json::to_string_pretty(&result).unwrap_or_default(), 0))
 }
 
 /// Status: show all stages with current highlighted.
-fn rollout_status(planning_dir: &Path) -> Result<(String, i32), String> {
-    let stages = load_stages(planning_dir);
+fn rollout_status(planning_dir: &Path, rollout: &Rollout) -> Result<(String, i32), String> {
     let current = get_current_stage(planning_dir);
     let completed = count_completed_phases(planning_dir);
-
-    let current_name = current.unwrap_or_else(|| stages[0].0.clone());
+    let current_name = current.unwrap_or_else(|| rollout.stages[0].name.clone());
 
     let mut output = String::new();
     output.push_str("Rollout Stages:\n");
-
-    for (i, (name, desc)) in stages.iter().enumerate() {
-        let marker = if *name == current_name { ">>>" } else { "   " };
-        output.push_str(&format!("{} {}. {} — {}\n", marker, i + 1, name, desc));
+    for (i, stage) in rollout.stages.iter().enumerate() {
+        let marker = if stage.name == current_name { ">>>" } else { "   " };
+        output.push_str(&format!("{} {}. {} — {}\n", marker, i + 1, stage.name, stage.description));
     }
-
     output.push_str(&format!("\nCompleted phases: {}\n", completed));
     output.push_str(&format!("Current stage: {}\n", current_name));
 
@@ -268,22 +626,22 @@ mod tests {
         let dir = TempDir::new().unwrap();
         let planning = dir.path().join("planning");
         fs::create_dir(&planning).unwrap();
-        let stages = load_stages(&planning);
+        let stages = load_rollout(&planning, dir.path()).stages;
         assert_eq!(stages.len(), 3);
-        assert_eq!(stages[0].0, "canary");
-        assert_eq!(stages[1].0, "partial");
-        assert_eq!(stages[2].0, "full");
+        assert_eq!(stages[0].name, "canary");
+        assert_eq!(stages[1].name, "partial");
+        assert_eq!(stages[2].name, "full");
     }
 
     #[test]
     fn test_load_custom_stages() {
         let dir = setup_env_with_stages();
         let planning = dir.path().join(".yolo-planning");
-        let stages = load_stages(&planning);
+        let stages = load_rollout(&planning, dir.path()).stages;
         assert_eq!(stages.len(), 3);
-        assert_eq!(stages[0].0, "alpha");
-        assert_eq!(stages[1].0, "beta");
-        assert_eq!(stages[2].0, "ga");
+        assert_eq!(stages[0].name, "alpha");
+        assert_eq!(stages[1].name, "beta");
+        assert_eq!(stages[2].name, "ga");
     }
 
     #[test]
@@ -314,7 +672,7 @@ mod tests {
     fn test_rollout_check_default() {
         let dir = setup_env();
         let planning = dir.path().join(".yolo-planning");
-        let (output, code) = rollout_check(&planning).unwrap();
+        let (output, code) = rollout_check(&planning, &load_rollout(&planning, dir.path())).unwrap();
         assert_eq!(code, 0);
         let result: Value = serde_json::from_str(&output).unwrap();
         assert_eq!(result["current_stage"], "canary");
@@ -327,7 +685,7 @@ mod tests {
         let dir = setup_env();
         let planning = dir.path().join(".yolo-planning");
 
-        let (output, code) = rollout_advance(&planning).unwrap();
+        let (output, code) = rollout_advance(&planning, &load_rollout(&planning, dir.path())).unwrap();
         assert_eq!(code, 0);
         let result: Value = serde_json::from_str(&output).unwrap();
         assert_eq!(result["advanced"], true);
@@ -348,9 +706,9 @@ mod tests {
         let planning = dir.path().join(".yolo-planning");
 
         // Advance to partial
-        rollout_advance(&planning).unwrap();
+        rollout_advance(&planning, &load_rollout(&planning, dir.path())).unwrap();
         // Advance to full
-        let (output, _) = rollout_advance(&planning).unwrap();
+        let (output, _) = rollout_advance(&planning, &load_rollout(&planning, dir.path())).unwrap();
         let result: Value = serde_json::from_str(&output).unwrap();
         assert_eq!(result["to_stage"], "full");
 
@@ -368,9 +726,9 @@ mod tests {
         let planning = dir.path().join(".yolo-planning");
 
         // Set to final stage
-        set_rollout_stage(&planning, "full").unwrap();
+        set_rollout_stage(&planning, &load_rollout(&planning, dir.path()), 2).unwrap();
 
-        let (output, code) = rollout_advance(&planning).unwrap();
+        let (output, code) = rollout_advance(&planning, &load_rollout(&planning, dir.path())).unwrap();
         assert_eq!(code, 0);
         assert!(output.contains("Already at final"));
     }
@@ -379,9 +737,9 @@ mod tests {
     fn test_rollout_status() {
         let dir = setup_env();
         let planning = dir.path().join(".yolo-planning");
-        set_rollout_stage(&planning, "partial").unwrap();
+        set_rollout_stage(&planning, &load_rollout(&planning, dir.path()), 1).unwrap();
 
-        let (output, code) = rollout_status(&planning).unwrap();
+        let (output, code) = rollout_status(&planning, &load_rollout(&planning, dir.path())).unwrap();
         assert_eq!(code, 0);
         assert!(output.contains(">>> 2. partial"));
         assert!(output.contains("Current stage: partial"));
@@ -393,7 +751,7 @@ mod tests {
         let planning = dir.path().join("planning");
         fs::create_dir(&planning).unwrap();
 
-        set_rollout_stage(&planning, "canary").unwrap();
+        set_rollout_stage(&planning, &load_rollout(&planning, dir.path()), 0).unwrap();
 
         let config: Value = serde_json::from_str(
             &fs::read_to_string(planning.join("config.json")).unwrap()
@@ -428,4 +786,212 @@ mod tests {
         assert_eq!(code, 0);
         assert!(output.contains("advanced"));
     }
+
+    fn stage(name: &str, phases_required: usize, flags: &[&str]) -> Stage {
+        Stage {
+            name: name.to_string(),
+            description: String::new(),
+            phases_required,
+            flags: flags.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_validate_stage_flags_orders_dependencies() {
+        let stages = vec![
+            stage("one", 0, &["v3_event_recovery", "v3_metric"]),
+            stage("two", 0, &["v3_event_log", "v2_hard_contracts"]),
+            stage("three", 0, &["v3_contract_lite"]),
+        ];
+        let errors = validate_stage_flags(&stages);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("unknown flag 'v3_metric'"));
+        assert!(errors[1].contains("stage 'one': v3_event_recovery requires v3_event_log"));
+    }
+
+    #[test]
+    fn test_shipped_stages_match_flag_graph() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let rollout = load_rollout(&root.join("missing-planning"), root);
+        let stages = &rollout.stages;
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[0].name, "observability");
+        assert_eq!(stages[2].phases_required, 5);
+        assert!(validate_stage_flags(stages).is_empty());
+        for (i, s) in stages.iter().enumerate() {
+            for key in &s.flags {
+                let flag = FeatureFlag::from_key(key).unwrap();
+                assert_eq!(flag.spec().default_stage, Some(i as u8 + 1), "{}", key);
+            }
+        }
+        for flag in FeatureFlag::ALL {
+            if let Some(n) = flag.spec().default_stage {
+                assert!(stages[n as usize - 1].flags.iter().any(|k| k == flag.key()), "{}", flag.key());
+            }
+        }
+    }
+
+    #[test]
+    fn test_advance_refuses_invalid_stages() {
+        let dir = setup_env();
+        let planning = dir.path().join(".yolo-planning");
+        let stages = json!({"stages": [
+            {"name": "a", "flags": ["v2_hard_gates"]},
+            {"name": "b", "flags": []},
+        ]});
+        fs::write(planning.join("rollout-stages.json"), stages.to_string()).unwrap();
+        let args: Vec<String> = vec!["yolo".into(), "rollout".into(), "advance".into()];
+        let (out, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 1);
+        assert!(out.contains("v2_hard_gates requires v2_hard_contracts"));
+        assert_eq!(get_current_stage(&planning), None);
+    }
+
+    fn write_stages(planning: &Path, auto_advance: bool) {
+        let stages = json!({
+            "stages": [
+                {"name": "one", "phases_required": 0, "flags": ["v3_event_log"]},
+                {"name": "two", "phases_required": 1, "flags": ["v3_metrics"]},
+                {"name": "three", "phases_required": 3, "flags": ["v3_lock_lite"]},
+            ],
+            "advancement": {
+                "auto_advance": auto_advance,
+                "health": {"max_gate_failure_rate": 0.5, "min_gate_samples": 2, "max_hook_errors": 1}
+            }
+        });
+        fs::write(planning.join("rollout-stages.json"), stages.to_string()).unwrap();
+    }
+
+    fn write_events(planning: &Path, events: &[Value]) {
+        let dir = planning.join(".events");
+        fs::create_dir_all(&dir).unwrap();
+        let lines: Vec<String> = events.iter().map(|e| e.to_string()).collect();
+        fs::write(dir.join("event-log.jsonl"), lines.join("\n") + "\n").unwrap();
+    }
+
+    fn config(planning: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(planning.join("config.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_health_counts_only_events_since_stage_began() {
+        let events = vec![
+            json!({"ts": "2026-01-01T00:00:00Z", "event": "gate_failed"}),
+            json!({"ts": "2026-01-02T00:00:00Z", "event": "rollout_advance"}),
+            json!({"ts": "2026-01-03T00:00:00Z", "event": "gate_passed"}),
+            json!({"ts": "2026-01-03T00:00:00Z", "event": "gate_failed"}),
+            json!({"ts": "2026-01-03T00:00:00Z", "event": "task_completion_rejected"}),
+            json!({"ts": "2026-01-03T00:00:00Z", "event": "hook_error"}),
+            json!({"ts": "2026-01-03T00:00:00Z", "event": "token_overage"}),
+        ];
+        let since = stage_started_at(&events);
+        assert_eq!(since.as_deref(), Some("2026-01-02T00:00:00Z"));
+        let health = collect_health(&events, since.as_deref());
+        assert_eq!(
+            health,
+            Health { gate_passed: 1, gate_failed: 1, completion_rejections: 1, hook_errors: 1, token_overages: 1, phases_ended: 0 }
+        );
+        assert_eq!(health.gate_failure_rate(), 0.5);
+        assert_eq!(collect_health(&events, None).gate_failed, 2);
+    }
+
+    #[test]
+    fn test_decide_advances_holds_and_rolls_back() {
+        let rollout = Rollout {
+            stages: vec![stage("one", 0, &[]), stage("two", 2, &[])],
+            auto_advance: true,
+            health: HealthThresholds { max_hook_errors: 1, ..Default::default() },
+        };
+        let healthy = Health::default();
+        assert_eq!(decide(&rollout, 0, 1, &healthy, false).0, Decision::Hold);
+        assert_eq!(decide(&rollout, 0, 2, &healthy, false).0, Decision::Advance { to: 1 });
+        assert_eq!(decide(&rollout, 1, 9, &healthy, false).0, Decision::Hold);
+
+        let sick = Health { hook_errors: 2, ..Default::default() };
+        let (decision, reasons) = decide(&rollout, 1, 9, &sick, false);
+        assert_eq!(decision, Decision::Rollback { to: 0 });
+        assert!(reasons[0].contains("2 hook errors exceed 1"));
+        assert_eq!(decide(&rollout, 0, 9, &sick, false).0, Decision::Hold);
+
+        // Too few gate results to judge the failure rate.
+        let one_failure = Health { gate_failed: 1, ..Default::default() };
+        assert_eq!(decide(&rollout, 0, 2, &one_failure, false).0, Decision::Advance { to: 1 });
+    }
+
+    #[test]
+    fn test_decide_waits_for_evidence_after_rollback() {
+        let rollout = Rollout {
+            stages: vec![stage("one", 0, &[]), stage("two", 0, &[])],
+            auto_advance: true,
+            health: HealthThresholds { min_gate_samples: 2, min_phases_after_rollback: 2, ..Default::default() },
+        };
+        let (decision, reasons) = decide(&rollout, 0, 5, &Health::default(), true);
+        assert_eq!(decision, Decision::Hold);
+        assert!(reasons[0].contains("rolled back"));
+        let one_phase = Health { phases_ended: 1, gate_passed: 1, ..Default::default() };
+        assert_eq!(decide(&rollout, 0, 5, &one_phase, true).0, Decision::Hold);
+
+        let gated = Health { gate_passed: 2, ..Default::default() };
+        assert_eq!(decide(&rollout, 0, 5, &gated, true).0, Decision::Advance { to: 1 });
+        let phased = Health { phases_ended: 2, ..Default::default() };
+        assert_eq!(decide(&rollout, 0, 5, &phased, true).0, Decision::Advance { to: 1 });
+    }
+
+    #[test]
+    fn test_auto_evaluate_advances_and_enables_stage_flags() {
+        let dir = setup_env();
+        let planning = dir.path().join(".yolo-planning");
+        write_stages(&planning, true);
+        write_events(&planning, &[json!({"ts": "2026-01-01T00:00:00Z", "event": "phase_end", "phase": 1})]);
+
+        auto_evaluate(dir.path());
+        let cfg = config(&planning);
+        assert_eq!(cfg["rollout_stage"], "two");
+        assert_eq!(cfg["v3_metrics"], true);
+
+        let events = event_bus::load_events(&planning);
+        let decision = events.iter().find(|e| e["event"] == "rollout_decision").unwrap();
+        assert_eq!(decision["data"]["decision"], "advance");
+        assert_eq!(decision["data"]["applied"], true);
+        assert!(events.iter().any(|e| e["event"] == "rollout_advance"));
+    }
+
+    #[test]
+    fn test_evaluate_rolls_back_regressed_stage() {
+        let dir = setup_env();
+        let planning = dir.path().join(".yolo-planning");
+        write_stages(&planning, false);
+        let rollout = load_rollout(&planning, dir.path());
+        set_rollout_stage(&planning, &rollout, 1).unwrap();
+        assert_eq!(config(&planning)["v3_metrics"], true);
+        write_events(&planning, &[
+            json!({"ts": "2026-01-01T00:00:00Z", "event": "rollout_advance"}),
+            json!({"ts": "2026-01-02T00:00:00Z", "event": "hook_error"}),
+            json!({"ts": "2026-01-02T00:00:00Z", "event": "hook_error"}),
+        ]);
+
+        // auto_advance is off: the decision is reported and logged but not applied.
+        let result = evaluate(&planning, &rollout, false).unwrap();
+        assert_eq!(result["decision"], "rollback");
+        assert_eq!(result["applied"], false);
+        assert_eq!(config(&planning)["rollout_stage"], "two");
+
+        let args: Vec<String> = vec!["yolo".into(), "rollout".into(), "evaluate".into(), "--apply".into()];
+        let (out, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let result: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(result["to"], "one");
+        let cfg = config(&planning);
+        assert_eq!(cfg["rollout_stage"], "one");
+        assert_eq!(cfg["v3_metrics"], false);
+        assert_eq!(cfg["v3_event_log"], true);
+        let events = event_bus::load_events(&planning);
+        assert_eq!(events.iter().filter(|e| e["event"] == "rollout_decision").count(), 2);
+        assert!(events.iter().any(|e| e["event"] == "rollout_rollback"));
+
+        // The rolled-back stage has no fresh samples yet, so it must not flap back up.
+        let result = evaluate(&planning, &rollout, true).unwrap();
+        assert_eq!(result["decision"], "hold");
+        assert_eq!(config(&planning)["rollout_stage"], "one");
+    }
 }
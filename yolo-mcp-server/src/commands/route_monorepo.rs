@@ -3,6 +3,9 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use super::feature_flags::FeatureFlag;
+use super::layered_config::Config;
+
 const PACKAGE_MARKERS: &[&str] = &["package.json", "Cargo.toml", "go.mod", "pyproject.toml"];
 const SKIP_DIRS: &[&str] = &["node_modules", ".git", ".yolo-planning", ".planning", "target"];
 const MAX_DEPTH: usize = 4;
@@ -24,11 +27,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         cwd.join(phase_dir_str)
     };
 
-    let planning_dir = cwd.join(".yolo-planning");
-    let config_path = planning_dir.join("config.json");
-
     // Check feature flag
-    if !read_bool_flag(&config_path, "v3_monorepo_routing") {
+    if !Config::load(cwd).flag(FeatureFlag::V3MonorepoRouting) {
         return Ok(("[]\n".to_string(), 0));
     }
 
@@ -169,19 +169,6 @@ fn extract_files_line(line: &str) -> Option<&str> {
     }
 }
 
-/// Read a boolean flag from config.json. Returns false on any error.
-fn read_bool_flag(config_path: &Path, key: &str) -> bool {
-    let content = match fs::read_to_string(config_path) {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-    let config: serde_json::Value = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-    config.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,11 +3,16 @@ use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use sysinfo::System;
-use reqwest::blocking::Client;
 use std::time::Duration;
 
+use super::feature_flags::unmet_requirements;
+use super::layered_config::Config;
+use super::net::{Endpoint, Net};
+
 #[derive(Clone, Copy)]
 enum StepStatus {
     Ok,
@@ -33,86 +38,292 @@ struct StepResult {
     ms: u64,
 }
 
+/// A session-start step, its default time budget and where it runs.
+struct StepSpec {
+    name: &'static str,
+    budget_ms: u64,
+    lane: Lane,
+}
+
+/// Where a session-start step runs and what happens when it overruns its budget.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    /// On the main thread, in order, because each chain step reads what the
+    /// previous one wrote. Always runs to completion; overrunning is a warning.
+    Chain,
+    /// On its own thread. Read-only or idempotent, so once its budget is up it
+    /// is reported as skipped and its result is dropped.
+    Parallel,
+    /// On its own thread, but it writes (markers, hooks, a cleanup lock), so it
+    /// is joined before session start returns rather than abandoned half-done.
+    Writer,
+}
+
+impl Lane {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Lane::Chain => "chain",
+            Lane::Parallel => "parallel",
+            Lane::Writer => "writer",
+        }
+    }
+}
+
+/// Every step in report order. Budgets can be overridden per step with
+/// `session_start_budget_ms.<step>` in the config.
+const STEPS: &[StepSpec] = &[
+    StepSpec { name: "compaction_check", budget_ms: 500, lane: Lane::Chain },
+    StepSpec { name: "dependency_check", budget_ms: 2000, lane: Lane::Parallel },
+    StepSpec { name: "config_migration", budget_ms: 2000, lane: Lane::Chain },
+    StepSpec { name: "claude_md_migration", budget_ms: 500, lane: Lane::Chain },
+    StepSpec { name: "todos_migration", budget_ms: 1000, lane: Lane::Chain },
+    StepSpec { name: "orphaned_state_migration", budget_ms: 1000, lane: Lane::Chain },
+    StepSpec { name: "config_cache", budget_ms: 1000, lane: Lane::Chain },
+    StepSpec { name: "first_run_check", budget_ms: 500, lane: Lane::Writer },
+    StepSpec { name: "update_check", budget_ms: 4000, lane: Lane::Parallel },
+    StepSpec { name: "statusline_migration", budget_ms: 1000, lane: Lane::Writer },
+    StepSpec { name: "cache_cleanup", budget_ms: 5000, lane: Lane::Writer },
+    StepSpec { name: "hook_installation", budget_ms: 2000, lane: Lane::Writer },
+    StepSpec { name: "execution_state_reconcile", budget_ms: 2000, lane: Lane::Chain },
+    StepSpec { name: "tmux_watchdog", budget_ms: 1000, lane: Lane::Writer },
+    StepSpec { name: "build_context", budget_ms: 2000, lane: Lane::Chain },
+    StepSpec { name: "compile_progress", budget_ms: 3000, lane: Lane::Chain },
+    StepSpec { name: "git_state", budget_ms: 3000, lane: Lane::Parallel },
+];
+
+fn step_spec(name: &str) -> Option<&'static StepSpec> {
+    STEPS.iter().find(|s| s.name == name)
+}
+
+/// Record a step that ran to completion. Going over budget is a warning: its
+/// work is done, so reporting it as skipped would be wrong.
+fn record_completed(
+    name: &'static str,
+    status: StepStatus,
+    ms: u64,
+    budget: Duration,
+    steps: &mut Vec<StepResult>,
+    warnings: &mut Vec<String>,
+) {
+    let budget_ms = budget.as_millis() as u64;
+    let status = match status {
+        StepStatus::Ok if ms > budget_ms => StepStatus::Warn,
+        other => other,
+    };
+    if ms > budget_ms {
+        warnings.push(format!("{} took {}ms, over its {}ms budget", name, ms, budget_ms));
+    }
+    steps.push(StepResult { name, status, ms });
+}
+
+/// Run a chain step on the current thread.
+fn run_step<T>(
+    name: &'static str,
+    budget: Duration,
+    steps: &mut Vec<StepResult>,
+    warnings: &mut Vec<String>,
+    f: impl FnOnce() -> (StepStatus, T),
+) -> T {
+    let started = Instant::now();
+    let (status, value) = f();
+    record_completed(name, status, started.elapsed().as_millis() as u64, budget, steps, warnings);
+    value
+}
+
+/// A step running on its own thread, due back within its budget.
+struct Pending<T> {
+    name: &'static str,
+    budget: Duration,
+    started: Instant,
+    rx: mpsc::Receiver<(StepStatus, T, u64)>,
+}
+
+fn spawn_step<T, F>(name: &'static str, budget: Duration, f: F) -> Pending<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> (StepStatus, T) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let started = Instant::now();
+    thread::spawn(move || {
+        let (status, value) = f();
+        let _ = tx.send((status, value, started.elapsed().as_millis() as u64));
+    });
+    Pending { name, budget, started, rx }
+}
+
+impl<T> Pending<T> {
+    /// Wait out the rest of a `Lane::Parallel` step's budget. One that overruns
+    /// is reported as skipped and left to finish in the background (or to die
+    /// with the process); it writes nothing that matters, so either is safe.
+    fn wait(self, steps: &mut Vec<StepResult>, warnings: &mut Vec<String>) -> Option<T> {
+        let remaining = self.budget.saturating_sub(self.started.elapsed());
+        match self.rx.recv_timeout(remaining) {
+            // Checked against the step's own time so the verdict doesn't
+            // depend on how late the result was collected.
+            Ok((status, value, ms)) if ms <= self.budget.as_millis() as u64 => {
+                steps.push(StepResult { name: self.name, status, ms });
+                Some(value)
+            }
+            Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {
+                let ms = self.budget.as_millis() as u64;
+                steps.push(StepResult { name: self.name, status: StepStatus::Skip, ms });
+                warnings.push(format!("{} exceeded its {}ms budget -- skipped", self.name, ms));
+                None
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                self.failed(steps, warnings);
+                None
+            }
+        }
+    }
+
+    /// Block until a `Lane::Writer` step finishes and report what it did.
+    fn join(self, steps: &mut Vec<StepResult>, warnings: &mut Vec<String>) -> Option<T> {
+        match self.rx.recv() {
+            Ok((status, value, ms)) => {
+                record_completed(self.name, status, ms, self.budget, steps, warnings);
+                Some(value)
+            }
+            Err(_) => {
+                self.failed(steps, warnings);
+                None
+            }
+        }
+    }
+
+    fn failed(&self, steps: &mut Vec<StepResult>, warnings: &mut Vec<String>) {
+        steps.push(StepResult { name: self.name, status: StepStatus::Error, ms: self.started.elapsed().as_millis() as u64 });
+        warnings.push(format!("{} failed", self.name));
+    }
+}
+
 pub fn execute_session_start(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let start = Instant::now();
     let with_progress = args.iter().any(|a| a == "--with-progress");
     let with_git = args.iter().any(|a| a == "--with-git");
+    let profile = args.iter().any(|a| a == "--profile");
     let planning_dir = cwd.join(".yolo-planning");
-    let script_dir = cwd.join("scripts");
     let claude_dir = get_claude_dir(cwd);
+    let config = Config::load(cwd);
+    let budget = |name: &str| {
+        let default = step_spec(name).map_or(1000, |s| s.budget_ms);
+        Duration::from_millis(config.u64_or(&format!("session_start_budget_ms.{}", name), default))
+    };
     let mut steps: Vec<StepResult> = Vec::new();
     let mut warnings: Vec<String> = Vec::new();
-    let mut step_start: Instant;
-
-    // 1. Dependency check (jq)
-    step_start = Instant::now();
-    if Command::new("jq").arg("--version").output().is_err() {
-        let out = json!({
-            "hookSpecificOutput": {
-                "hookEventName": "SessionStart",
-                "additionalContext": "YOLO: jq not found. Install: brew install jq (macOS) / apt install jq (Linux). All 17 YOLO quality gates are disabled until jq is installed -- no commit validation, no security filtering, no file guarding."
-            },
-            "structuredResult": {
-                "ok": false,
-                "cmd": "session-start",
-                "delta": {
-                    "steps": [{"step": "dependency_check", "status": StepStatus::Error.as_str(), "ms": step_start.elapsed().as_millis() as u64}],
-                    "warnings": ["jq not found"],
-                    "next_action": "install jq"
-                },
-                "elapsed_ms": start.elapsed().as_millis() as u64
-            }
-        });
-        return Ok((out.to_string(), 0));
-    }
-    steps.push(StepResult { name: "dependency_check", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
 
-    // 2. Compaction check
-    step_start = Instant::now();
+    // 1. Compaction check: a fresh marker means this start follows a compaction
+    // and everything below is skipped, so nothing else may start before it.
     let cm_path = planning_dir.join(".compaction-marker");
-    let mut compaction_skipped = false;
-    if cm_path.exists() {
-        if let Ok(content) = fs::read_to_string(&cm_path) {
-            let ts_str = content.trim();
-            if let Ok(ts) = ts_str.parse::<u64>() {
-                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-                let age = now.saturating_sub(ts);
-                if age < 60 {
-                    return Ok(("".to_string(), 0));
-                }
+    let just_compacted = run_step("compaction_check", budget("compaction_check"), &mut steps, &mut warnings, move || {
+        if !cm_path.exists() {
+            return (StepStatus::Skip, false);
+        }
+        if let Ok(content) = fs::read_to_string(&cm_path)
+            && let Ok(ts) = content.trim().parse::<u64>()
+        {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            if now.saturating_sub(ts) < 60 {
+                return (StepStatus::Ok, true);
             }
         }
         let _ = fs::remove_file(&cm_path);
-    } else {
-        compaction_skipped = true;
+        (StepStatus::Ok, false)
+    });
+    if just_compacted {
+        return Ok(("".to_string(), 0));
     }
-    steps.push(StepResult {
-        name: "compaction_check",
-        status: if compaction_skipped { StepStatus::Skip } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+
+    // 2. Independent steps start now and run while the planning chain below
+    // does its work. None of them touches the planning files the chain writes.
+
+    // Dependency check: only tools YOLO still shells out to. A missing
+    // tool disables the features that need it; everything else keeps working.
+    let dependency_check = spawn_step("dependency_check", budget("dependency_check"), || {
+        let missing: Vec<String> = missing_tools()
+            .iter()
+            .map(|tool| format!("{} not found -- {} unavailable", tool.name, tool.degrades))
+            .collect();
+        (if missing.is_empty() { StepStatus::Ok } else { StepStatus::Warn }, missing)
     });
 
-    // 3. Config migration (native Rust)
-    step_start = Instant::now();
-    let config_path = planning_dir.join("config.json");
-    let config_migration_skipped = !(planning_dir.exists() && config_path.exists());
-    if !config_migration_skipped {
-        let defaults_path = cwd.join("config").join("defaults.json");
+    let dir = claude_dir.clone();
+    let first_run_check = spawn_step("first_run_check", budget("first_run_check"), move || (StepStatus::Ok, check_first_run(&dir)));
+
+    let net = Net::load(cwd);
+    let update_check = spawn_step("update_check", budget("update_check"), move || {
+        if !net.allowed(Endpoint::YoloVersion) {
+            return (StepStatus::Skip, String::new());
+        }
+        let msg = check_for_updates(&net);
+        (if msg.is_empty() { StepStatus::Ok } else { StepStatus::Warn }, msg)
+    });
+
+    let (dir, plan) = (claude_dir.clone(), planning_dir.clone());
+    let statusline_migration = spawn_step("statusline_migration", budget("statusline_migration"), move || {
+        migrate_statusline_and_tmux(&dir, &plan);
+        (StepStatus::Ok, ())
+    });
+
+    let dir = claude_dir.clone();
+    let cache_cleanup = spawn_step("cache_cleanup", budget("cache_cleanup"), move || {
+        cleanup_and_sync_cache(&dir);
+        (StepStatus::Ok, ())
+    });
+
+    let (dir, plan) = (claude_dir.clone(), planning_dir.clone());
+    let hook_installation = spawn_step("hook_installation", budget("hook_installation"), move || {
+        let _ = super::install_hooks::install_hooks();
+        super::clean_stale_teams::clean_stale_teams(&dir, &plan);
+        (StepStatus::Ok, ())
+    });
+
+    let plan = planning_dir.clone();
+    let tmux_watchdog = spawn_step("tmux_watchdog", budget("tmux_watchdog"), move || match super::tmux_watchdog::get_tmux_session() {
+        Some(session) => {
+            let _ = super::tmux_watchdog::spawn_watchdog(&plan, &session);
+            (StepStatus::Ok, ())
+        }
+        None => (StepStatus::Skip, ()),
+    });
+
+    let git_state = with_git.then(|| {
+        let root = cwd.to_path_buf();
+        spawn_step("git_state", budget("git_state"), move || {
+            if TOOLS.iter().any(|t| t.name == "git" && !tool_available(t)) {
+                return (StepStatus::Skip, None);
+            }
+            match super::git_state::execute(&["yolo".into(), "git-state".into()], &root) {
+                Ok((output, 0)) => (StepStatus::Ok, serde_json::from_str::<Value>(&output).ok()),
+                _ => (StepStatus::Error, None),
+            }
+        })
+    });
+
+    // 3. Planning chain, in order.
+
+    // Config migration (native Rust)
+    let (root, plan) = (cwd.to_path_buf(), planning_dir.clone());
+    run_step("config_migration", budget("config_migration"), &mut steps, &mut warnings, move || {
+        let config_path = plan.join("config.json");
+        if !(plan.exists() && config_path.exists()) {
+            return (StepStatus::Skip, ());
+        }
+        let defaults_path = root.join("config").join("defaults.json");
         let _ = super::migrate_config::migrate_config(&config_path, &defaults_path);
-    }
-    steps.push(StepResult {
-        name: "config_migration",
-        status: if config_migration_skipped { StepStatus::Skip } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+        (StepStatus::Ok, ())
     });
 
-    // 4. CLAUDE.md migration
-    step_start = Instant::now();
-    let claude_md_migrated = planning_dir.join(".claude-md-migrated");
-    let claude_md_skipped = !planning_dir.exists() || claude_md_migrated.exists();
-    if !claude_md_skipped {
-        let guard = cwd.join(".claude").join("CLAUDE.md");
-        let root_claude = cwd.join("CLAUDE.md");
+    // CLAUDE.md migration
+    let (root, plan) = (cwd.to_path_buf(), planning_dir.clone());
+    run_step("claude_md_migration", budget("claude_md_migration"), &mut steps, &mut warnings, move || {
+        let claude_md_migrated = plan.join(".claude-md-migrated");
+        if !plan.exists() || claude_md_migrated.exists() {
+            return (StepStatus::Skip, ());
+        }
+        let guard = root.join(".claude").join("CLAUDE.md");
+        let root_claude = root.join("CLAUDE.md");
         if guard.exists() {
             if !root_claude.exists() {
                 let _ = fs::rename(&guard, &root_claude);
@@ -121,152 +332,84 @@ pub fn execute_session_start(args: &[String], cwd: &Path) -> Result<(String, i32
             }
         }
         let _ = fs::write(&claude_md_migrated, "1");
-    }
-    steps.push(StepResult {
-        name: "claude_md_migration",
-        status: if claude_md_skipped { StepStatus::Skip } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+        (StepStatus::Ok, ())
     });
 
-    // 5. Todos hierarchy migration
-    step_start = Instant::now();
-    flatten_todos_migration(&planning_dir);
-    steps.push(StepResult { name: "todos_migration", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 6. Orphaned state migration (native Rust)
-    step_start = Instant::now();
-    let _ = super::migrate_orphaned_state::migrate_orphaned_state(&planning_dir);
-    steps.push(StepResult { name: "orphaned_state_migration", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 7. Config Cache & Warnings
-    step_start = Instant::now();
-    let (_config_cache_done, flag_warnings) = write_config_cache_and_validate(&planning_dir);
-    let config_cache_has_warnings = !flag_warnings.is_empty();
-    if config_cache_has_warnings {
-        warnings.push(flag_warnings.trim().to_string());
-    }
-    steps.push(StepResult {
-        name: "config_cache",
-        status: if config_cache_has_warnings { StepStatus::Warn } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+    // Todos hierarchy migration
+    let plan = planning_dir.clone();
+    run_step("todos_migration", budget("todos_migration"), &mut steps, &mut warnings, move || {
+        flatten_todos_migration(&plan);
+        (StepStatus::Ok, ())
     });
 
-    // 8. First run welcome
-    step_start = Instant::now();
-    let welcome_msg = check_first_run(&claude_dir);
-    steps.push(StepResult { name: "first_run_check", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 9. Update Check
-    step_start = Instant::now();
-    let update_msg = check_for_updates(&script_dir);
-    let update_has_warnings = !update_msg.is_empty();
-    if update_has_warnings {
-        warnings.push(update_msg.trim().to_string());
-    }
-    steps.push(StepResult {
-        name: "update_check",
-        status: if update_has_warnings { StepStatus::Warn } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+    // Orphaned state migration (native Rust)
+    let plan = planning_dir.clone();
+    run_step("orphaned_state_migration", budget("orphaned_state_migration"), &mut steps, &mut warnings, move || {
+        let _ = super::migrate_orphaned_state::migrate_orphaned_state(&plan);
+        (StepStatus::Ok, ())
     });
 
-    // 10. StatusLine & Tmux migration
-    step_start = Instant::now();
-    migrate_statusline_and_tmux(&claude_dir, &planning_dir);
-    steps.push(StepResult { name: "statusline_migration", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 11. Cache cleanup and syncing
-    step_start = Instant::now();
-    cleanup_and_sync_cache(&claude_dir);
-    steps.push(StepResult { name: "cache_cleanup", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 12. Hook installation & stale team cleanup (native Rust)
-    step_start = Instant::now();
-    let _ = super::install_hooks::install_hooks();
-    {
-        let log_file = planning_dir.join(".hook-errors.log");
-        super::clean_stale_teams::clean_stale_teams(&claude_dir, &log_file);
-    }
-    steps.push(StepResult { name: "hook_installation", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 13. Reconcile execution state & Orphan Agents
-    step_start = Instant::now();
-    let state_msg = reconcile_execution_state(&planning_dir);
-    cleanup_orphaned_agents(&planning_dir);
-    steps.push(StepResult { name: "execution_state_reconcile", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
-
-    // 14. Tmux watchdog (native Rust)
-    step_start = Instant::now();
-    let mut tmux_skipped = true;
-    if let Some(session) = super::tmux_watchdog::get_tmux_session() {
-        let _ = super::tmux_watchdog::spawn_watchdog(&planning_dir, &session);
-        tmux_skipped = false;
+    // Config Cache & Warnings
+    let plan = planning_dir.clone();
+    let flag_warnings = run_step("config_cache", budget("config_cache"), &mut steps, &mut warnings, move || {
+        let (_config_cache_done, flag_warnings) = write_config_cache_and_validate(&plan);
+        (if flag_warnings.is_empty() { StepStatus::Ok } else { StepStatus::Warn }, flag_warnings)
+    });
+    if !flag_warnings.is_empty() {
+        warnings.push(flag_warnings.trim().to_string());
     }
-    steps.push(StepResult {
-        name: "tmux_watchdog",
-        status: if tmux_skipped { StepStatus::Skip } else { StepStatus::Ok },
-        ms: step_start.elapsed().as_millis() as u64,
+
+    // Journal roll-forward, reconcile execution state & Orphan Agents
+    let plan = planning_dir.clone();
+    let state_msg = run_step("execution_state_reconcile", budget("execution_state_reconcile"), &mut steps, &mut warnings, move || {
+        let journal_msg = replay_state_journal(&plan);
+        let state_msg = journal_msg.clone() + &reconcile_execution_state(&plan);
+        cleanup_orphaned_agents(&plan);
+        (if journal_msg.contains("WARNING") { StepStatus::Warn } else { StepStatus::Ok }, state_msg)
     });
 
-    // 15. Determine Next Action & Build Context
-    step_start = Instant::now();
-    let ctx = build_context(cwd, &planning_dir, &state_msg);
-    steps.push(StepResult { name: "build_context", status: StepStatus::Ok, ms: step_start.elapsed().as_millis() as u64 });
+    // Determine Next Action & Build Context
+    let (root, plan) = (cwd.to_path_buf(), planning_dir.clone());
+    let ctx = run_step("build_context", budget("build_context"), &mut steps, &mut warnings, move || {
+        (StepStatus::Ok, build_context(&root, &plan, &state_msg))
+    });
 
-    // 16. Optional: compile progress data
+    // Optional: compile progress data
     let progress_data = if with_progress {
-        step_start = Instant::now();
-        match super::compile_progress::execute(
-            &vec!["yolo".into(), "compile-progress".into()],
-            cwd,
-        ) {
-            Ok((output, 0)) => {
-                steps.push(StepResult {
-                    name: "compile_progress",
-                    status: StepStatus::Ok,
-                    ms: step_start.elapsed().as_millis() as u64,
-                });
-                serde_json::from_str::<Value>(&output).ok()
-            }
-            _ => {
-                steps.push(StepResult {
-                    name: "compile_progress",
-                    status: StepStatus::Error,
-                    ms: step_start.elapsed().as_millis() as u64,
-                });
-                None
+        let root = cwd.to_path_buf();
+        run_step("compile_progress", budget("compile_progress"), &mut steps, &mut warnings, move || {
+            match super::compile_progress::execute(&["yolo".into(), "compile-progress".into()], &root) {
+                Ok((output, 0)) => (StepStatus::Ok, serde_json::from_str::<Value>(&output).ok()),
+                _ => (StepStatus::Error, None),
             }
-        }
+        })
     } else {
         None
     };
 
-    // 17. Optional: git state
-    let git_data = if with_git {
-        step_start = Instant::now();
-        match super::git_state::execute(
-            &vec!["yolo".into(), "git-state".into()],
-            cwd,
-        ) {
-            Ok((output, 0)) => {
-                steps.push(StepResult {
-                    name: "git_state",
-                    status: StepStatus::Ok,
-                    ms: step_start.elapsed().as_millis() as u64,
-                });
-                serde_json::from_str::<Value>(&output).ok()
-            }
-            _ => {
-                steps.push(StepResult {
-                    name: "git_state",
-                    status: StepStatus::Error,
-                    ms: step_start.elapsed().as_millis() as u64,
-                });
-                None
-            }
-        }
-    } else {
-        None
-    };
+    // 4. Collect the independent steps. Their budgets have been running since
+    // they started, so a slow read-only one costs at most what is left of it;
+    // writers are always waited for.
+    if let Some(missing) = dependency_check.wait(&mut steps, &mut warnings) {
+        warnings.extend(missing);
+    }
+    let welcome_msg = first_run_check.join(&mut steps, &mut warnings).unwrap_or_default();
+    let update_msg = update_check.wait(&mut steps, &mut warnings).unwrap_or_default();
+    if !update_msg.is_empty() {
+        warnings.push(update_msg.trim().to_string());
+    }
+    statusline_migration.join(&mut steps, &mut warnings);
+    cache_cleanup.join(&mut steps, &mut warnings);
+    hook_installation.join(&mut steps, &mut warnings);
+    tmux_watchdog.join(&mut steps, &mut warnings);
+    let git_data = git_state.and_then(|p| p.wait(&mut steps, &mut warnings)).flatten();
+
+    steps.sort_by_key(|s| STEPS.iter().position(|spec| spec.name == s.name));
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+
+    if profile {
+        return Ok((render_profile(&steps, &budget, elapsed_ms), 0));
+    }
 
     let steps_json: Vec<Value> = steps.iter().map(|s| json!({
         "step": s.name,
@@ -292,7 +435,7 @@ pub fn execute_session_start(args: &[String], cwd: &Path) -> Result<(String, i32
         },
         "progress": progress_data,
         "git": git_data,
-        "elapsed_ms": start.elapsed().as_millis() as u64
+        "elapsed_ms": elapsed_ms
     });
 
     let out = json!({
@@ -306,6 +449,37 @@ pub fn execute_session_start(args: &[String], cwd: &Path) -> Result<(String, i32
     Ok((out.to_string(), 0))
 }
 
+/// `--profile`: one row per step, slowest first, plus how much the parallel
+/// steps saved over running everything in sequence.
+fn render_profile(steps: &[StepResult], budget: &dyn Fn(&str) -> Duration, elapsed_ms: u64) -> String {
+    let mut rows: Vec<&StepResult> = steps.iter().collect();
+    rows.sort_by(|a, b| b.ms.cmp(&a.ms));
+    let summed: u64 = steps.iter().map(|s| s.ms).sum();
+    let mut out = format!(
+        "session-start: {}ms wall, {}ms summed across {} steps\n\n{:<26} {:<8} {:<6} {:>7} {:>8}\n",
+        elapsed_ms,
+        summed,
+        steps.len(),
+        "step",
+        "lane",
+        "status",
+        "ms",
+        "budget"
+    );
+    for s in rows {
+        let lane = step_spec(s.name).map_or("chain", |spec| spec.lane.as_str());
+        out.push_str(&format!(
+            "{:<26} {:<8} {:<6} {:>7} {:>8}\n",
+            s.name,
+            lane,
+            s.status.as_str(),
+            s.ms,
+            budget(s.name).as_millis()
+        ));
+    }
+    out
+}
+
 fn flatten_todos_migration(planning_dir: &Path) {
     let flag = planning_dir.join(".todo-flat-migrated");
     if !planning_dir.exists() || flag.exists() {
@@ -404,25 +578,45 @@ fn write_config_cache_and_validate(planning_dir: &Path) -> (bool, String) {
     let cache_done = fs::write(&cache_path, cache_content).is_ok();
 
     // Flag validations
-    let v2_hard_gates = get_bool("v2_hard_gates", false);
-    let v2_hard_contracts = get_bool("v2_hard_contracts", false);
-    let v3_event_recovery = get_bool("v3_event_recovery", false);
-    let v3_event_log = get_bool("v3_event_log", false);
-    let v2_two_phase = get_bool("v2_two_phase_completion", false);
-
-    if v2_hard_gates && !v2_hard_contracts {
-        warnings.push_str(" WARNING: v2_hard_gates requires v2_hard_contracts -- enable v2_hard_contracts first or contract_compliance gate will fail.");
-    }
-    if v3_event_recovery && !v3_event_log {
-        warnings.push_str(" WARNING: v3_event_recovery requires v3_event_log -- enable v3_event_log first or event recovery will find no events.");
-    }
-    if v2_two_phase && !v3_event_log {
-        warnings.push_str(" WARNING: v2_two_phase_completion requires v3_event_log -- enable v3_event_log first or completion events will be lost.");
+    for unmet in unmet_requirements(|f| get_bool(f.key(), false)) {
+        warnings.push_str(&format!(" WARNING: {}", unmet.message));
     }
 
     (cache_done, warnings)
 }
 
+/// An external tool and the features that degrade without it.
+struct Tool {
+    name: &'static str,
+    version_flag: &'static str,
+    degrades: &'static str,
+    /// Whether this session needs the tool at all.
+    needed: fn() -> bool,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "git",
+        version_flag: "--version",
+        degrades: "commit validation, planning git sync, snapshots and release-suite",
+        needed: || true,
+    },
+    Tool {
+        name: "tmux",
+        version_flag: "-V",
+        degrades: "agent pane mapping and the tmux watchdog",
+        needed: || env::var_os("TMUX").is_some(),
+    },
+];
+
+fn tool_available(tool: &Tool) -> bool {
+    Command::new(tool.name).arg(tool.version_flag).output().is_ok()
+}
+
+fn missing_tools() -> Vec<&'static Tool> {
+    TOOLS.iter().filter(|t| (t.needed)() && !tool_available(t)).collect()
+}
+
 fn check_first_run(claude_dir: &Path) -> String {
     let marker = claude_dir.join(".yolo-welcomed");
     if !marker.exists() {
@@ -433,53 +627,15 @@ fn check_first_run(claude_dir: &Path) -> String {
     String::new()
 }
 
-fn check_for_updates(_script_dir: &Path) -> String {
-    let cache_path = format!("/tmp/yolo-update-check-{}", get_uid());
-    let mut local_ver = "0.0.0".to_string();
-    let mut remote_ver = "0.0.0".to_string();
-
+/// Compare the installed version against the published one. The network
+/// policy decides whether that means a request, a cached answer, or nothing.
+fn check_for_updates(net: &Net) -> String {
     // Read installed version from plugin cache (authoritative for installed version)
-    if let Some(v) = read_installed_version() {
-        local_ver = v;
-    }
+    let local_ver = read_installed_version().unwrap_or_else(|| "0.0.0".to_string());
+    let fetched = net.get(Endpoint::YoloVersion, Duration::from_secs(86400), &[]);
+    let remote_ver = fetched.body().map(str::trim).unwrap_or("0.0.0");
 
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let mut fetch = true;
-
-    if let Ok(meta) = fs::metadata(&cache_path) {
-        if let Ok(mtime) = meta.modified() {
-            let mt = mtime.duration_since(UNIX_EPOCH).unwrap().as_secs();
-            if now.saturating_sub(mt) <= 86400 {
-                if let Ok(content) = fs::read_to_string(&cache_path) {
-                    let parts: Vec<&str> = content.split('|').collect();
-                    if parts.len() == 2 {
-                        let cached_local = parts[0].trim().to_string();
-                        let cached_remote = parts[1].trim().to_string();
-                        // Invalidate cache if installed version changed (user updated)
-                        if cached_local == local_ver {
-                            fetch = false;
-                            remote_ver = cached_remote;
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    if fetch {
-        let client = Client::builder().timeout(Duration::from_secs(3)).build().unwrap();
-        if let Ok(res) = client.get("https://raw.githubusercontent.com/slavpetroff/yolo/main/VERSION").send() {
-            if let Ok(content) = res.text() {
-                let trimmed = content.trim().to_string();
-                if !trimmed.is_empty() {
-                    remote_ver = trimmed;
-                }
-            }
-        }
-        let _ = fs::write(&cache_path, format!("{}|{}", local_ver, remote_ver));
-    }
-
-    if remote_ver != "0.0.0" && remote_ver != local_ver && version_gt(&remote_ver, &local_ver) {
+    if remote_ver != "0.0.0" && remote_ver != local_ver && version_gt(remote_ver, &local_ver) {
         return format!(" UPDATE AVAILABLE: v{} -> v{}. Run /yolo:update to upgrade.", local_ver, remote_ver);
     }
     String::new()
@@ -674,6 +830,27 @@ fn cleanup_and_sync_cache(claude_dir: &Path) {
     }
 }
 
+/// Roll forward state writes journaled by a session that crashed mid-commit.
+fn replay_state_journal(planning_dir: &Path) -> String {
+    match super::journal::recover(planning_dir) {
+        Ok(r) if r.is_empty() => String::new(),
+        Ok(r) => {
+            let mut msg = String::new();
+            if !r.rolled_forward.is_empty() {
+                msg.push_str(&format!(" State journal: rolled forward {} ({}).", r.rolled_forward.len(), r.files.join(", ")));
+            }
+            if !r.skipped.is_empty() {
+                msg.push_str(&format!(" WARNING: kept newer {} over stale journal writes.", r.skipped.join(", ")));
+            }
+            if !r.discarded.is_empty() {
+                msg.push_str(&format!(" WARNING: discarded {} corrupt state journal(s).", r.discarded.len()));
+            }
+            msg
+        }
+        Err(e) => format!(" WARNING: state journal replay failed: {}.", e),
+    }
+}
+
 fn reconcile_execution_state(planning_dir: &Path) -> String {
     let mut msg = String::new();
     let es = planning_dir.join(".execution-state.json");
@@ -751,19 +928,11 @@ fn cleanup_orphaned_agents(planning_dir: &Path) {
 
     if targets.is_empty() { return; }
     
-    let log_file = planning_dir.join(".hook-errors.log");
-    let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let _ = fs::OpenOptions::new().create(true).append(true).open(&log_file).map(|mut f| {
-        use std::io::Write;
-        let _ = writeln!(f, "[{}] Orphan cleanup: found {} orphaned claude process(es)", ts, targets.len());
-    });
+    super::event_bus::publish_message(planning_dir, "session-start", &format!("Orphan cleanup: found {} orphaned claude process(es)", targets.len()));
 
     for pid in &targets {
         if let Some(process) = sys.process(*pid) {
-            let _ = fs::OpenOptions::new().create(true).append(true).open(&log_file).map(|mut f| {
-                use std::io::Write;
-                let _ = writeln!(f, "[{}] Terminating orphan claude process PID={} (SIGTERM)", ts, pid);
-            });
+            super::event_bus::publish_message(planning_dir, "session-start", &format!("Terminating orphan claude process PID={} (SIGTERM)", pid));
             process.kill_with(sysinfo::Signal::Term);
         }
     }
@@ -773,15 +942,13 @@ fn cleanup_orphaned_agents(planning_dir: &Path) {
 
     for pid in &targets {
         if let Some(process) = sys.process(*pid) {
-            let _ = fs::OpenOptions::new().create(true).append(true).open(&log_file).map(|mut f| {
-                use std::io::Write;
-                let _ = writeln!(f, "[{}] Orphan claude process PID={} survived SIGTERM, sending SIGKILL", ts, pid);
-            });
+            super::event_bus::publish_message(planning_dir, "session-start", &format!("Orphan claude process PID={} survived SIGTERM, sending SIGKILL", pid));
             process.kill_with(sysinfo::Signal::Kill);
         }
     }
 }
 
+#[derive(Default)]
 struct ContextResult {
     text: String,
     next_action: String,
@@ -1150,7 +1317,7 @@ mod tests {
             StepResult { name: "dep", status: StepStatus::Ok, ms: 1 },
             StepResult { name: "compact", status: StepStatus::Skip, ms: 0 },
             StepResult { name: "cache", status: StepStatus::Warn, ms: 5 },
-            StepResult { name: "jq", status: StepStatus::Error, ms: 2 },
+            StepResult { name: "git_state", status: StepStatus::Error, ms: 2 },
         ];
         let steps_json: Vec<Value> = cases.iter().map(|s| json!({
             "step": s.name, "status": s.status.as_str(), "ms": s.ms
@@ -1181,6 +1348,81 @@ mod tests {
         assert!(warnings.contains("v2_hard_gates requires v2_hard_contracts"));
     }
 
+    #[test]
+    fn test_step_over_budget_is_skipped_with_warning() {
+        let (mut steps, mut warnings) = (Vec::new(), Vec::new());
+        let started = Instant::now();
+        let slow = spawn_step("update_check", Duration::from_millis(20), || {
+            thread::sleep(Duration::from_millis(500));
+            (StepStatus::Ok, "late".to_string())
+        });
+        assert!(slow.wait(&mut steps, &mut warnings).is_none());
+        assert!(started.elapsed() < Duration::from_millis(400));
+        assert_eq!(steps[0].status.as_str(), "skip");
+        assert_eq!(warnings, vec!["update_check exceeded its 20ms budget -- skipped"]);
+
+        let fast = spawn_step("git_state", Duration::from_secs(5), || (StepStatus::Warn, 7));
+        assert_eq!(fast.wait(&mut steps, &mut warnings), Some(7));
+        assert_eq!(steps[1].status.as_str(), "warn");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_chain_and_writer_steps_finish_past_their_budget() {
+        let (mut steps, mut warnings) = (Vec::new(), Vec::new());
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("written");
+
+        let m = marker.clone();
+        let writer = spawn_step("cache_cleanup", Duration::from_millis(10), move || {
+            thread::sleep(Duration::from_millis(100));
+            fs::write(&m, "1").unwrap();
+            (StepStatus::Ok, ())
+        });
+        assert_eq!(writer.join(&mut steps, &mut warnings), Some(()));
+        assert!(marker.exists(), "a writer is joined, never abandoned mid-write");
+        assert_eq!(steps[0].status.as_str(), "warn");
+        assert!(warnings[0].starts_with("cache_cleanup took "), "{}", warnings[0]);
+
+        let value = run_step("config_migration", Duration::from_millis(10), &mut steps, &mut warnings, || {
+            thread::sleep(Duration::from_millis(30));
+            (StepStatus::Ok, "done")
+        });
+        assert_eq!(value, "done");
+        assert_eq!(steps[1].status.as_str(), "warn");
+        assert_eq!(warnings.len(), 2);
+
+        for spec in STEPS.iter().filter(|s| s.lane == Lane::Parallel) {
+            assert!(["dependency_check", "update_check", "git_state"].contains(&spec.name), "{} must be read-only to be abandoned", spec.name);
+        }
+    }
+
+    #[test]
+    fn test_render_profile_slowest_first_with_lanes() {
+        let steps = vec![
+            StepResult { name: "config_migration", status: StepStatus::Ok, ms: 12 },
+            StepResult { name: "update_check", status: StepStatus::Skip, ms: 4000 },
+        ];
+        let out = render_profile(&steps, &|name: &str| Duration::from_millis(if name == "update_check" { 4000 } else { 2000 }), 4010);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "session-start: 4010ms wall, 4012ms summed across 2 steps");
+        assert!(lines[3].starts_with("update_check") && lines[3].contains("parallel") && lines[3].contains("skip"));
+        assert!(lines[4].starts_with("config_migration") && lines[4].contains("chain"));
+    }
+
+    #[test]
+    fn test_step_names_match_budget_schema() {
+        let schema_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../config/config.schema.json");
+        let schema: Value = serde_json::from_str(&fs::read_to_string(schema_path).unwrap()).unwrap();
+        let names: Vec<&str> = schema["properties"]["session_start_budget_ms"]["propertyNames"]["enum"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(names, STEPS.iter().map(|s| s.name).collect::<Vec<_>>());
+    }
+
     #[test]
     fn test_step_status_as_str() {
         assert_eq!(StepStatus::Ok.as_str(), "ok");
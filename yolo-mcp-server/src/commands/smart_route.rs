@@ -1,9 +1,13 @@
 use serde_json::json;
-use std::fs;
 use std::path::Path;
 
+use super::feature_flags::FeatureFlag;
+use super::layered_config::Config;
+use super::role_registry::RoleRegistry;
+
 /// Determine whether an agent should be included or skipped based on effort.
-/// When v3_smart_routing=true:
+/// When v3_smart_routing=true, the role's `routing` rules in the role registry
+/// decide. Built-in rules:
 ///   - Scout: skip for turbo/fast
 ///   - Architect: include only for thorough
 ///   - All others: always include
@@ -24,11 +28,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let agent_role = &args[2];
     let effort = &args[3];
 
-    let planning_dir = cwd.join(".yolo-planning");
-    let config_path = planning_dir.join("config.json");
-
     // Check feature flag
-    let smart_routing = read_bool_flag(&config_path, "v3_smart_routing");
+    let smart_routing = Config::load(cwd).flag(FeatureFlag::V3SmartRouting);
 
     if !smart_routing {
         let result = json!({
@@ -39,13 +40,14 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         return Ok((format!("{}\n", result), 0));
     }
 
-    let (decision, reason) = route_agent(agent_role, effort);
+    let registry = RoleRegistry::load(cwd);
+    let (decision, reason) = route_agent(&registry, agent_role, effort);
 
     // Emit smart_route metric (best-effort, never fail)
     let data_pairs = vec![
         ("agent".to_string(), agent_role.clone()),
         ("effort".to_string(), effort.clone()),
-        ("decision".to_string(), decision.to_string()),
+        ("decision".to_string(), decision.clone()),
     ];
     let _ = super::collect_metrics::collect("smart_route", "0", None, &data_pairs, cwd);
 
@@ -57,102 +59,86 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     Ok((format!("{}\n", result), 0))
 }
 
-/// Core routing logic: decide include/skip for a given agent + effort.
-pub fn route_agent(agent_role: &str, effort: &str) -> (&'static str, String) {
-    match agent_role {
-        "scout" => match effort {
-            "turbo" | "fast" => ("skip", format!("effort={}: scout not needed", effort)),
-            _ => ("include", format!("effort={}: scout included", effort)),
-        },
-        "architect" => match effort {
-            "thorough" => ("include", format!("effort={}: architect included", effort)),
-            _ => ("skip", format!("effort={}: architect only for thorough", effort)),
-        },
-        _ => ("include", format!("role={}: always included", agent_role)),
+/// Core routing logic: decide include/skip for a given agent + effort using
+/// the role's routing rules. Unknown roles are always included.
+pub fn route_agent(registry: &RoleRegistry, agent_role: &str, effort: &str) -> (String, String) {
+    match registry.get(agent_role) {
+        Some(role) => {
+            let (decision, reason) = role.route(effort);
+            (decision.to_string(), reason)
+        }
+        None => ("include".to_string(), format!("role={}: always included", agent_role)),
     }
 }
 
-/// Read a boolean flag from config.json. Returns false on any error.
-fn read_bool_flag(config_path: &Path, key: &str) -> bool {
-    let content = match fs::read_to_string(config_path) {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-    let config: serde_json::Value = match serde_json::from_str(&content) {
-        Ok(v) => v,
-        Err(_) => return false,
-    };
-    config.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_scout_skip_turbo() {
-        let (decision, _) = route_agent("scout", "turbo");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "scout", "turbo");
         assert_eq!(decision, "skip");
     }
 
     #[test]
     fn test_scout_skip_fast() {
-        let (decision, _) = route_agent("scout", "fast");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "scout", "fast");
         assert_eq!(decision, "skip");
     }
 
     #[test]
     fn test_scout_include_balanced() {
-        let (decision, _) = route_agent("scout", "balanced");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "scout", "balanced");
         assert_eq!(decision, "include");
     }
 
     #[test]
     fn test_scout_include_thorough() {
-        let (decision, _) = route_agent("scout", "thorough");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "scout", "thorough");
         assert_eq!(decision, "include");
     }
 
     #[test]
     fn test_architect_include_thorough() {
-        let (decision, _) = route_agent("architect", "thorough");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "architect", "thorough");
         assert_eq!(decision, "include");
     }
 
     #[test]
     fn test_architect_skip_turbo() {
-        let (decision, _) = route_agent("architect", "turbo");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "architect", "turbo");
         assert_eq!(decision, "skip");
     }
 
     #[test]
     fn test_architect_skip_fast() {
-        let (decision, _) = route_agent("architect", "fast");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "architect", "fast");
         assert_eq!(decision, "skip");
     }
 
     #[test]
     fn test_architect_skip_balanced() {
-        let (decision, _) = route_agent("architect", "balanced");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "architect", "balanced");
         assert_eq!(decision, "skip");
     }
 
     #[test]
     fn test_dev_always_include() {
-        let (decision, reason) = route_agent("dev", "turbo");
+        let (decision, reason) = route_agent(&RoleRegistry::builtin(), "dev", "turbo");
         assert_eq!(decision, "include");
         assert!(reason.contains("always included"));
     }
 
     #[test]
     fn test_lead_always_include() {
-        let (decision, _) = route_agent("lead", "fast");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "lead", "fast");
         assert_eq!(decision, "include");
     }
 
     #[test]
     fn test_qa_always_include() {
-        let (decision, _) = route_agent("qa", "balanced");
+        let (decision, _) = route_agent(&RoleRegistry::builtin(), "qa", "balanced");
         assert_eq!(decision, "include");
     }
 
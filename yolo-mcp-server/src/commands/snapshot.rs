@@ -0,0 +1,334 @@
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::atomic_io::atomic_write;
+use super::event_bus;
+use super::snapshot_resume::{capture, list_ids};
+
+const USAGE: &str = "Usage: yolo snapshot <list [--phase N] [--role R] | show <id> | diff <from> [<to>|current] | restore <id> [--dry-run]>";
+
+/// `yolo snapshot {list|show|diff|restore}`: browse the snapshots written by
+/// `snapshot-resume save` and restore a specific one.
+pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
+    let start = Instant::now();
+    let action = args.get(2).map(String::as_str).ok_or(USAGE)?;
+    let planning_dir = cwd.join(".yolo-planning");
+    let snapshots_dir = planning_dir.join(".snapshots");
+    let positional: Vec<&str> = args.iter().skip(3).map(String::as_str).filter(|a| !a.starts_with("--")).collect();
+
+    let mut out = match action {
+        "list" => list(&snapshots_dir, option(args, "--phase"), option(args, "--role")),
+        "show" => {
+            let id = positional.first().ok_or("Usage: yolo snapshot show <id>")?;
+            let snap = load(&snapshots_dir, id)?;
+            json!({"ok": true, "snapshot": summary(id, &snap), "content": snap})
+        }
+        "diff" => {
+            let from = positional.first().ok_or("Usage: yolo snapshot diff <from> [<to>|current]")?;
+            let to = positional.get(1).copied().unwrap_or("current");
+            let a = load(&snapshots_dir, from)?;
+            let b = if to == "current" { current(cwd, &planning_dir) } else { load(&snapshots_dir, to)? };
+            json!({"ok": true, "from": from, "to": to, "diff": diff(&a, &b)})
+        }
+        "restore" => {
+            let id = positional.first().ok_or("Usage: yolo snapshot restore <id> [--dry-run]")?;
+            let dry_run = args.iter().any(|a| a == "--dry-run");
+            restore(cwd, &planning_dir, &snapshots_dir, id, dry_run)?
+        }
+        _ => return Err(USAGE.to_string()),
+    };
+
+    if let Some(obj) = out.as_object_mut() {
+        obj.insert("cmd".into(), json!("snapshot"));
+        obj.insert("action".into(), json!(action));
+        obj.insert("elapsed_ms".into(), json!(start.elapsed().as_millis()));
+    }
+    Ok((serde_json::to_string(&out).unwrap() + "\n", 0))
+}
+
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter().position(|a| a == name).and_then(|i| args.get(i + 1)).map(String::as_str)
+}
+
+fn snapshot_path(snapshots_dir: &Path, id: &str) -> PathBuf {
+    snapshots_dir.join(format!("{}.json", id.trim_end_matches(".json")))
+}
+
+fn load(snapshots_dir: &Path, id: &str) -> Result<Value, String> {
+    let path = snapshot_path(snapshots_dir, id);
+    let content = fs::read_to_string(&path).map_err(|_| format!("snapshot '{}' not found in {}", id, snapshots_dir.display()))?;
+    serde_json::from_str(&content).map_err(|e| format!("snapshot '{}' is not valid JSON: {}", id, e))
+}
+
+/// The live execution state, git and leases, shaped like a snapshot.
+fn current(cwd: &Path, planning_dir: &Path) -> Value {
+    let exec_state = fs::read_to_string(planning_dir.join(".execution-state.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or(json!({}));
+    let phase = exec_state.get("phase").map(|p| p.to_string().trim_matches('"').to_string()).unwrap_or_default();
+    let ts = Utc::now().format("%Y%m%dT%H%M%S").to_string();
+    capture(cwd, &ts, &phase, exec_state, "current", "none")
+}
+
+/// One line of `list`: phase, role, git HEAD, timestamp and plan progress.
+fn summary(id: &str, snap: &Value) -> Value {
+    let plans = snap["execution_state"]["plans"].as_array();
+    json!({
+        "id": id,
+        "phase": snap.get("phase"),
+        "role": snap.get("agent_role"),
+        "ts": snap.get("snapshot_ts"),
+        "git_head": snap.get("git_head").cloned()
+            .filter(|h| !h.is_null())
+            .or_else(|| snap["recent_commits"][0].as_str().and_then(|c| c.split_whitespace().next()).map(|h| json!(h))),
+        "trigger": snap.get("compaction_trigger"),
+        "status": snap["execution_state"].get("status"),
+        "plans_complete": plans.map(|p| p.iter().filter(|x| x["status"] == "complete").count()),
+        "plans_total": plans.map(Vec::len),
+    })
+}
+
+fn list(snapshots_dir: &Path, phase: Option<&str>, role: Option<&str>) -> Value {
+    let snapshots: Vec<Value> = list_ids(snapshots_dir)
+        .into_iter()
+        .filter_map(|id| load(snapshots_dir, &id).ok().map(|s| summary(&id, &s)))
+        .filter(|s| phase.is_none_or(|p| s["phase"].as_str() == Some(p)))
+        .filter(|s| role.is_none_or(|r| s["role"].as_str() == Some(r)))
+        .collect();
+    json!({"ok": true, "count": snapshots.len(), "snapshots": snapshots})
+}
+
+/// Items of `state[key]` (plans or tasks) keyed by `id`, with their status.
+fn statuses(state: &Value, key: &str) -> Map<String, Value> {
+    state[key]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let id = item.get("id")?;
+            let id = id.as_str().map(str::to_string).unwrap_or_else(|| id.to_string());
+            Some((id, item.get("status").cloned().unwrap_or(Value::Null)))
+        })
+        .collect()
+}
+
+/// Completed, status-changed, added and removed items between two states.
+fn diff_items(a: &Value, b: &Value, key: &str) -> Value {
+    let (from, to) = (statuses(a, key), statuses(b, key));
+    let mut completed = Vec::new();
+    let mut changed = Vec::new();
+    for (id, status) in &to {
+        match from.get(id) {
+            Some(prev) if prev != status => {
+                if status == "complete" {
+                    completed.push(json!(id));
+                }
+                changed.push(json!({"id": id, "from": prev, "to": status}));
+            }
+            None if status == "complete" => completed.push(json!(id)),
+            _ => {}
+        }
+    }
+    let added: Vec<&String> = to.keys().filter(|k| !from.contains_key(*k)).collect();
+    let removed: Vec<&String> = from.keys().filter(|k| !to.contains_key(*k)).collect();
+    json!({"completed": completed, "changed": changed, "added": added, "removed": removed})
+}
+
+fn strings(v: &Value) -> Vec<String> {
+    v.as_array().into_iter().flatten().filter_map(|s| s.as_str().map(str::to_string)).collect()
+}
+
+fn changed(a: &Value, b: &Value) -> Value {
+    if a == b { Value::Null } else { json!({"from": a, "to": b}) }
+}
+
+/// Structured differences between two snapshots: execution status, plans and
+/// tasks, git HEAD and commits, uncommitted files and held leases.
+fn diff(a: &Value, b: &Value) -> Value {
+    let (ea, eb) = (&a["execution_state"], &b["execution_state"]);
+
+    let commits_a = strings(&a["recent_commits"]);
+    let new_commits: Vec<String> = strings(&b["recent_commits"]).into_iter().filter(|c| !commits_a.contains(c)).collect();
+
+    let (files_a, files_b) = (strings(&a["changed_files"]), strings(&b["changed_files"]));
+    let files_added: Vec<&String> = files_b.iter().filter(|f| !files_a.contains(f)).collect();
+    let files_cleared: Vec<&String> = files_a.iter().filter(|f| !files_b.contains(f)).collect();
+
+    let lease_map = |s: &Value| -> Map<String, Value> {
+        s["leases"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| Some((l["resource"].as_str()?.to_string(), l["owner"].clone())))
+            .collect()
+    };
+    let (la, lb) = (lease_map(a), lease_map(b));
+    let acquired: Vec<Value> = lb.iter().filter(|(r, _)| !la.contains_key(*r)).map(|(r, o)| json!({"resource": r, "owner": o})).collect();
+    let released: Vec<Value> = la.iter().filter(|(r, _)| !lb.contains_key(*r)).map(|(r, o)| json!({"resource": r, "owner": o})).collect();
+    let reassigned: Vec<Value> = lb
+        .iter()
+        .filter_map(|(r, o)| la.get(r).filter(|prev| *prev != o).map(|prev| json!({"resource": r, "from": prev, "to": o})))
+        .collect();
+
+    json!({
+        "phase": changed(&a["phase"], &b["phase"]),
+        "role": changed(&a["agent_role"], &b["agent_role"]),
+        "status": changed(&ea["status"], &eb["status"]),
+        "wave": changed(&ea["wave"], &eb["wave"]),
+        "plans": diff_items(ea, eb, "plans"),
+        "tasks": diff_items(ea, eb, "tasks"),
+        "git": {"head": changed(&a["git_head"], &b["git_head"]), "new_commits": new_commits},
+        "files": {"added": files_added, "cleared": files_cleared},
+        "leases": {"acquired": acquired, "released": released, "reassigned": reassigned},
+    })
+}
+
+/// Write a snapshot's execution state back to `.execution-state.json`. With
+/// `dry_run`, only report what would change.
+fn restore(cwd: &Path, planning_dir: &Path, snapshots_dir: &Path, id: &str, dry_run: bool) -> Result<Value, String> {
+    let snap = load(snapshots_dir, id)?;
+    let changes = diff(&current(cwd, planning_dir), &snap);
+    let exec_state = snap.get("execution_state").cloned().unwrap_or(json!({}));
+
+    if !dry_run {
+        let path = planning_dir.join(".execution-state.json");
+        let content = serde_json::to_string_pretty(&exec_state).unwrap() + "\n";
+        atomic_write(&path, content.as_bytes()).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+        event_bus::publish(planning_dir, &json!({
+            "event": "snapshot_restored",
+            "phase": snap.get("phase"),
+            "data": {"snapshot": id, "role": snap.get("agent_role")}
+        }));
+    }
+    Ok(json!({
+        "ok": true,
+        "id": id,
+        "dry_run": dry_run,
+        "restored": !dry_run,
+        "execution_state": exec_state,
+        "changes": changes,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snapshot(phase: &str, ts: &str, role: &str, plans: Value, files: &[&str], leases: Value) -> Value {
+        json!({
+            "snapshot_ts": ts,
+            "phase": phase,
+            "execution_state": {"phase": phase.parse::<i64>().unwrap(), "status": "running", "plans": plans},
+            "recent_commits": ["abc1234 feat: one"],
+            "git_head": "abc1234def",
+            "changed_files": files,
+            "leases": leases,
+            "agent_role": role,
+            "compaction_trigger": "auto"
+        })
+    }
+
+    fn setup() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let snaps = dir.path().join(".yolo-planning/.snapshots");
+        fs::create_dir_all(&snaps).unwrap();
+        let a = snapshot(
+            "2", "20260101T000001", "dev",
+            json!([{"id": "02-01", "status": "running"}, {"id": "02-02", "status": "pending"}]),
+            &["src/a.rs"],
+            json!([{"resource": "src/a.rs", "owner": "dev-1", "type": "lease"}]),
+        );
+        let mut b = snapshot(
+            "2", "20260101T000002", "lead",
+            json!([{"id": "02-01", "status": "complete"}, {"id": "02-02", "status": "running"}, {"id": "02-03", "status": "pending"}]),
+            &["src/b.rs"],
+            json!([{"resource": "src/b.rs", "owner": "dev-2", "type": "lease"}]),
+        );
+        b["recent_commits"] = json!(["fff0000 feat: two", "abc1234 feat: one"]);
+        b["git_head"] = json!("fff0000aaa");
+        fs::write(snaps.join("2-20260101T000001.json"), a.to_string()).unwrap();
+        fs::write(snaps.join("2-20260101T000002.json"), b.to_string()).unwrap();
+        fs::write(snaps.join("1-20251231T000000.json"), snapshot("1", "20251231T000000", "dev", json!([]), &[], json!([])).to_string()).unwrap();
+        dir
+    }
+
+    fn run(dir: &TempDir, args: &[&str]) -> Value {
+        let mut full = vec!["yolo".to_string(), "snapshot".to_string()];
+        full.extend(args.iter().map(|s| s.to_string()));
+        let (out, code) = execute(&full, dir.path()).unwrap();
+        assert_eq!(code, 0);
+        serde_json::from_str(&out).unwrap()
+    }
+
+    #[test]
+    fn test_list_newest_first_with_filters() {
+        let dir = setup();
+        let out = run(&dir, &["list"]);
+        assert_eq!(out["count"], 3);
+        assert_eq!(out["snapshots"][0]["id"], "2-20260101T000002");
+        assert_eq!(out["snapshots"][0]["role"], "lead");
+        assert_eq!(out["snapshots"][0]["git_head"], "fff0000aaa");
+        assert_eq!(out["snapshots"][0]["plans_complete"], 1);
+        assert_eq!(out["snapshots"][2]["id"], "1-20251231T000000");
+
+        assert_eq!(run(&dir, &["list", "--phase", "2"])["count"], 2);
+        assert_eq!(run(&dir, &["list", "--role", "dev"])["count"], 2);
+    }
+
+    #[test]
+    fn test_diff_reports_plans_files_and_leases() {
+        let dir = setup();
+        let out = run(&dir, &["diff", "2-20260101T000001", "2-20260101T000002"]);
+        let d = &out["diff"];
+        assert_eq!(d["plans"]["completed"], json!(["02-01"]));
+        assert_eq!(d["plans"]["added"], json!(["02-03"]));
+        assert_eq!(d["plans"]["changed"][1], json!({"id": "02-02", "from": "pending", "to": "running"}));
+        assert_eq!(d["git"]["new_commits"], json!(["fff0000 feat: two"]));
+        assert_eq!(d["git"]["head"]["to"], "fff0000aaa");
+        assert_eq!(d["files"]["added"], json!(["src/b.rs"]));
+        assert_eq!(d["files"]["cleared"], json!(["src/a.rs"]));
+        assert_eq!(d["leases"]["acquired"][0]["resource"], "src/b.rs");
+        assert_eq!(d["leases"]["released"][0]["owner"], "dev-1");
+        assert_eq!(d["role"], json!({"from": "dev", "to": "lead"}));
+        assert_eq!(d["status"], Value::Null);
+    }
+
+    #[test]
+    fn test_restore_dry_run_then_apply() {
+        let dir = setup();
+        let state_path = dir.path().join(".yolo-planning/.execution-state.json");
+        fs::write(&state_path, json!({"phase": 2, "status": "failed", "plans": []}).to_string()).unwrap();
+
+        let out = run(&dir, &["restore", "2-20260101T000001", "--dry-run"]);
+        assert_eq!(out["restored"], false);
+        assert_eq!(out["changes"]["status"], json!({"from": "failed", "to": "running"}));
+        assert_eq!(out["changes"]["plans"]["added"], json!(["02-01", "02-02"]));
+        let unchanged: Value = serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+        assert_eq!(unchanged["status"], "failed");
+
+        let out = run(&dir, &["restore", "2-20260101T000001"]);
+        assert_eq!(out["restored"], true);
+        let restored: Value = serde_json::from_str(&fs::read_to_string(&state_path).unwrap()).unwrap();
+        assert_eq!(restored["status"], "running");
+        assert_eq!(restored["plans"][0]["id"], "02-01");
+        let events = event_bus::load_events(&dir.path().join(".yolo-planning"));
+        assert_eq!(events[0]["event"], "snapshot_restored");
+        assert_eq!(events[0]["data"]["snapshot"], "2-20260101T000001");
+    }
+
+    #[test]
+    fn test_show_and_missing_snapshot() {
+        let dir = setup();
+        let out = run(&dir, &["show", "1-20251231T000000"]);
+        assert_eq!(out["snapshot"]["phase"], "1");
+        assert_eq!(out["content"]["compaction_trigger"], "auto");
+
+        let args: Vec<String> = ["yolo", "snapshot", "show", "9-nope"].iter().map(|s| s.to_string()).collect();
+        assert!(execute(&args, dir.path()).unwrap_err().contains("not found"));
+    }
+}
@@ -86,17 +86,7 @@ fn save_snapshot(
         .and_then(|s| serde_json::from_str(&s).ok())
         .unwrap_or(json!({}));
 
-    // Git log (direct git call, not bash)
-    let recent_commits = get_recent_commits(cwd);
-
-    let snapshot = json!({
-        "snapshot_ts": ts,
-        "phase": phase,
-        "execution_state": exec_state,
-        "recent_commits": recent_commits,
-        "agent_role": agent_role,
-        "compaction_trigger": trigger
-    });
+    let snapshot = capture(cwd, &ts, phase, exec_state, &agent_role, trigger);
 
     if fs::write(&snapshot_file, serde_json::to_string_pretty(&snapshot).unwrap_or_default()).is_err() {
         return Ok(("".to_string(), 0));
@@ -160,6 +150,92 @@ fn restore_snapshot(
     }
 }
 
+/// Build a snapshot of `exec_state` plus the git and lease context around it.
+pub fn capture(cwd: &Path, ts: &str, phase: &str, exec_state: Value, agent_role: &str, trigger: &str) -> Value {
+    json!({
+        "snapshot_ts": ts,
+        "phase": phase,
+        "execution_state": exec_state,
+        "recent_commits": get_recent_commits(cwd),
+        "git_head": git_line(cwd, &["rev-parse", "HEAD"]),
+        "changed_files": get_changed_files(cwd),
+        "leases": get_leases(cwd),
+        "agent_role": agent_role,
+        "compaction_trigger": trigger
+    })
+}
+
+/// Snapshot ids (file stems, `<phase>-<ts>`) in `snapshots_dir`, newest first.
+pub fn list_ids(snapshots_dir: &Path) -> Vec<String> {
+    let mut ids: Vec<String> = fs::read_dir(snapshots_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| e.file_name().to_string_lossy().strip_suffix(".json").map(str::to_string))
+        .filter(|id| id.contains('-'))
+        .collect();
+    ids.sort_by(|a, b| {
+        let ts = |id: &str| id.rsplit_once('-').map(|(_, t)| t.to_string()).unwrap_or_default();
+        ts(b).cmp(&ts(a)).then_with(|| b.cmp(a))
+    });
+    ids
+}
+
+fn git_line(cwd: &Path, args: &[&str]) -> Value {
+    match Command::new("git").args(args).current_dir(cwd).output() {
+        Ok(o) if o.status.success() => {
+            let line = String::from_utf8_lossy(&o.stdout).trim().to_string();
+            if line.is_empty() { Value::Null } else { Value::String(line) }
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Paths with uncommitted changes, from `git status --porcelain`.
+fn get_changed_files(cwd: &Path) -> Value {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(cwd)
+        .output();
+    match output {
+        Ok(o) if o.status.success() => {
+            let text = String::from_utf8_lossy(&o.stdout);
+            let files: Vec<Value> = text
+                .lines()
+                .filter(|l| l.len() > 3 && !l[3..].starts_with(".yolo-planning/"))
+                .map(|l| Value::String(l[3..].to_string()))
+                .collect();
+            Value::Array(files)
+        }
+        _ => json!([]),
+    }
+}
+
+/// Held lock-lite and lease locks from `.yolo-planning/.locks`.
+fn get_leases(cwd: &Path) -> Value {
+    let dir = cwd.join(".yolo-planning").join(".locks");
+    let mut leases: Vec<Value> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.ends_with(".lease") || name.ends_with(".lock")
+        })
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|c| serde_json::from_str::<Value>(&c).ok())
+        .map(|l| {
+            json!({
+                "resource": l.get("resource").cloned().unwrap_or(Value::Null),
+                "owner": l.get("owner").cloned().unwrap_or(Value::Null),
+                "type": l.get("type").and_then(|t| t.as_str()).unwrap_or("lock"),
+            })
+        })
+        .collect();
+    leases.sort_by(|a, b| a["resource"].to_string().cmp(&b["resource"].to_string()));
+    Value::Array(leases)
+}
+
 fn get_recent_commits(cwd: &Path) -> Value {
     let output = Command::new("git")
         .args(["log", "--oneline", "-5"])
@@ -389,6 +465,8 @@ mod tests {
 
         // Verify recent commits (git log)
         assert!(snap["recent_commits"].is_array());
+        assert!(snap["changed_files"].is_array());
+        assert!(snap["leases"].is_array());
 
         // Restore snapshot - should find our saved one
         let restore_args: Vec<String> = vec!["restore".into(), "11".into(), "dev".into()];
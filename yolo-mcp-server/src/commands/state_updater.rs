@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 use std::fs;
 use serde_json::{json, Value};
-use super::atomic_io;
+use super::journal::Transaction;
 use super::structured_response::{StructuredResponse, Timer};
 
 pub fn update_state(file_path: &str) -> Result<String, String> {
@@ -28,28 +28,31 @@ pub fn update_state(file_path: &str) -> Result<String, String> {
     let mut changed_files: Vec<String> = Vec::new();
 
     let (plans_before, summaries_before) = count_plans_and_summaries(phase_dir);
+    // STATE.md, ROADMAP.md and .execution-state.json are committed together so a
+    // crash midway never leaves them disagreeing.
+    let mut tx = Transaction::begin(&planning_root);
 
     if is_plan {
         if planning_root.join("STATE.md").exists() {
             changed_files.push("STATE.md".to_string());
         }
-        update_state_md(phase_dir, &planning_root)?;
+        update_state_md(&mut tx, phase_dir, &planning_root)?;
 
         if planning_root.join("ROADMAP.md").exists() {
             changed_files.push("ROADMAP.md".to_string());
         }
-        update_roadmap(phase_dir, &planning_root)?;
+        update_roadmap(&mut tx, phase_dir, &planning_root)?;
 
         let state_md = planning_root.join("STATE.md");
         let mut status_changed_to: Option<String> = None;
         if state_md.exists() {
-            let content = fs::read_to_string(&state_md).unwrap_or_default();
+            let content = tx.read_to_string(&state_md).unwrap_or_default();
             if content.contains("Status: ready") {
-                let updated = content.replace("Status: ready", "Status: active");
-                let _ = atomic_io::atomic_write_with_checksum(&state_md, updated.as_bytes());
+                tx.stage(&state_md, content.replace("Status: ready", "Status: active"));
                 status_changed_to = Some("active".to_string());
             }
         }
+        tx.commit().map_err(|e| format!("Failed to commit state update: {}", e))?;
 
         let (plans_after, summaries_after) = count_plans_and_summaries(phase_dir);
 
@@ -95,20 +98,21 @@ pub fn update_state(file_path: &str) -> Result<String, String> {
     if exec_state_path.exists() {
         changed_files.push(".execution-state.json".to_string());
     }
-    update_execution_state(&planning_root, &final_phase, &final_plan, &final_status, &summary_id);
+    update_execution_state(&mut tx, &planning_root, &final_phase, &final_plan, &final_status, &summary_id);
 
     if planning_root.join("STATE.md").exists() {
         changed_files.push("STATE.md".to_string());
     }
-    update_state_md(phase_dir, &planning_root)?;
+    update_state_md(&mut tx, phase_dir, &planning_root)?;
 
     if planning_root.join("ROADMAP.md").exists() {
         changed_files.push("ROADMAP.md".to_string());
     }
-    update_roadmap(phase_dir, &planning_root)?;
-    update_model_profile(phase_dir, &planning_root)?;
+    update_roadmap(&mut tx, phase_dir, &planning_root)?;
+    update_model_profile(&mut tx, phase_dir, &planning_root)?;
 
-    let advance_info = advance_phase(phase_dir, &planning_root)?;
+    let advance_info = advance_phase(&mut tx, phase_dir, &planning_root)?;
+    tx.commit().map_err(|e| format!("Failed to commit state update: {}", e))?;
 
     let (plans_after, summaries_after) = count_plans_and_summaries(phase_dir);
 
@@ -145,7 +149,7 @@ fn planning_root_from_phase_dir(phase_dir: &Path) -> PathBuf {
     phases_dir.parent().unwrap_or(Path::new(".yolo-planning")).to_path_buf()
 }
 
-fn update_state_md(phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
+fn update_state_md(tx: &mut Transaction, phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
     let state_md = planning_root.join("STATE.md");
     if !state_md.exists() {
         return Ok(());
@@ -154,7 +158,7 @@ fn update_state_md(phase_dir: &Path, planning_root: &Path) -> Result<(), String>
     let (plan_count, summary_count) = count_plans_and_summaries(phase_dir);
     let pct = if plan_count > 0 { (summary_count * 100) / plan_count } else { 0 };
 
-    let content = fs::read_to_string(&state_md).unwrap_or_default();
+    let content = tx.read_to_string(&state_md).unwrap_or_default();
     let mut new_lines = vec![];
 
     for line in content.lines() {
@@ -169,7 +173,7 @@ fn update_state_md(phase_dir: &Path, planning_root: &Path) -> Result<(), String>
 
     let mut new_content = new_lines.join("\n");
     new_content.push('\n');
-    let _ = atomic_io::atomic_write_with_checksum(&state_md, new_content.as_bytes());
+    tx.stage(&state_md, new_content);
 
     Ok(())
 }
@@ -186,7 +190,7 @@ fn slug_to_name(slug: &str) -> String {
     }).collect::<Vec<String>>().join(" ")
 }
 
-fn update_roadmap(phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
+pub fn update_roadmap(tx: &mut Transaction, phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
     let roadmap = planning_root.join("ROADMAP.md");
     if !roadmap.exists() {
         return Ok(());
@@ -211,7 +215,7 @@ fn update_roadmap(phase_dir: &Path, planning_root: &Path) -> Result<(), String>
         ("planned", "-".to_string())
     };
 
-    let content = fs::read_to_string(&roadmap).unwrap_or_default();
+    let content = tx.read_to_string(&roadmap).unwrap_or_default();
     let mut existing_name = String::new();
     let search_prefix = format!("| {} - ", phase_num_str);
     let search_prefix_space = format!("|  {} - ", phase_num_str);
@@ -247,12 +251,12 @@ fn update_roadmap(phase_dir: &Path, planning_root: &Path) -> Result<(), String>
 
     let mut new_content = new_lines.join("\n");
     new_content.push('\n');
-    let _ = atomic_io::atomic_write_with_checksum(&roadmap, new_content.as_bytes());
+    tx.stage(&roadmap, new_content);
 
     Ok(())
 }
 
-fn update_model_profile(_phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
+fn update_model_profile(tx: &mut Transaction, _phase_dir: &Path, planning_root: &Path) -> Result<(), String> {
     let state_md = planning_root.join("STATE.md");
     if !state_md.exists() {
         return Ok(());
@@ -274,7 +278,7 @@ fn update_model_profile(_phase_dir: &Path, planning_root: &Path) -> Result<(), S
         }
     }
 
-    let content = fs::read_to_string(&state_md).unwrap_or_default();
+    let content = tx.read_to_string(&state_md).unwrap_or_default();
     if !content.contains("## Codebase Profile") {
         return Ok(());
     }
@@ -307,12 +311,12 @@ fn update_model_profile(_phase_dir: &Path, planning_root: &Path) -> Result<(), S
 
     let mut new_content = new_lines.join("\n");
     new_content.push('\n');
-    let _ = atomic_io::atomic_write_with_checksum(&state_md, new_content.as_bytes());
+    tx.stage(&state_md, new_content);
 
     Ok(())
 }
 
-fn advance_phase(phase_dir: &Path, planning_root: &Path) -> Result<AdvanceInfo, String> {
+fn advance_phase(tx: &mut Transaction, phase_dir: &Path, planning_root: &Path) -> Result<AdvanceInfo, String> {
     let no_advance = AdvanceInfo { advanced: false, new_phase: None, status_changed_to: None };
     let state_md = planning_root.join("STATE.md");
     if !state_md.exists() {
@@ -354,7 +358,7 @@ fn advance_phase(phase_dir: &Path, planning_root: &Path) -> Result<AdvanceInfo,
         return Ok(no_advance);
     }
 
-    let content = fs::read_to_string(&state_md).unwrap_or_default();
+    let content = tx.read_to_string(&state_md).unwrap_or_default();
     let mut new_lines = vec![];
 
     let mut info = AdvanceInfo { advanced: true, new_phase: None, status_changed_to: None };
@@ -384,7 +388,7 @@ fn advance_phase(phase_dir: &Path, planning_root: &Path) -> Result<AdvanceInfo,
 
     let mut new_content = new_lines.join("\n");
     new_content.push('\n');
-    let _ = atomic_io::atomic_write_with_checksum(&state_md, new_content.as_bytes());
+    tx.stage(&state_md, new_content);
 
     Ok(info)
 }
@@ -423,13 +427,13 @@ fn parse_summary_frontmatter(path: &Path) -> (String, String, String) {
     (phase, plan, status)
 }
 
-fn update_execution_state(planning_root: &Path, phase: &str, plan: &str, status: &str, summary_id: &str) {
+fn update_execution_state(tx: &mut Transaction, planning_root: &Path, phase: &str, plan: &str, status: &str, summary_id: &str) {
     let state_file = planning_root.join(".execution-state.json");
     if !state_file.exists() {
         return;
     }
 
-    if let Ok(content) = fs::read_to_string(&state_file) {
+    if let Ok(content) = tx.read_to_string(&state_file) {
         if let Ok(mut json) = serde_json::from_str::<Value>(&content) {
             // Attempt to update
             if let Some(plans) = json.get_mut("plans").and_then(|p| p.as_array_mut()) {
@@ -451,13 +455,13 @@ fn update_execution_state(planning_root: &Path, phase: &str, plan: &str, status:
             }
 
             if let Ok(updated) = serde_json::to_string_pretty(&json) {
-                let _ = atomic_io::atomic_write_with_checksum(&state_file, updated.as_bytes());
+                tx.stage(&state_file, updated);
             }
         }
     }
 }
 
-fn count_plans_and_summaries(dir: &Path) -> (usize, usize) {
+pub fn count_plans_and_summaries(dir: &Path) -> (usize, usize) {
     let mut plans = 0;
     let mut summaries = 0;
     if let Ok(entries) = fs::read_dir(dir) {
@@ -613,6 +617,10 @@ mod tests {
         assert!(roadmap_content_2.contains("| 1 - Test | 2/2 | complete |"));
         assert!(roadmap_content_2.contains("- [x] Phase 1:"));
 
+        // Each update commits as one journaled transaction and leaves no journal behind.
+        let journal_dir = root.join(".yolo-planning").join(crate::commands::journal::JOURNAL_DIR);
+        assert_eq!(fs::read_dir(&journal_dir).unwrap().count(), 0);
+
         let _ = fs::remove_dir_all(&root);
     }
 
@@ -653,7 +661,9 @@ mod tests {
         let exec_state = root.join(".execution-state.json");
         fs::write(&exec_state, r#"{"phases": {"1": {"1": {"status": "planned"}}}}"#).unwrap();
         
-        update_execution_state(&root, "1", "1", "completed", "1");
+        let mut tx = Transaction::begin(&root);
+        update_execution_state(&mut tx, &root, "1", "1", "completed", "1");
+        tx.commit().unwrap();
         
         let content = fs::read_to_string(&exec_state).unwrap();
         assert!(content.contains(r#""status": "completed""#));
@@ -696,38 +706,42 @@ mod tests {
         let phase_dir = root.join(".yolo-planning/phases/01-test");
         fs::create_dir_all(&phase_dir).unwrap();
 
+        let mut tx = Transaction::begin(&root.join(".yolo-planning"));
+
         // 1. Missing ROADMAP.md
-        let res = update_roadmap(&phase_dir, &root.join(".yolo-planning"));
+        let res = update_roadmap(&mut tx, &phase_dir, &root.join(".yolo-planning"));
         assert!(res.is_ok()); // Should return gracefully
 
         // 2. Missing STATE.md for update_state_md
-        let res2 = update_state_md(&phase_dir, &root.join(".yolo-planning"));
+        let res2 = update_state_md(&mut tx, &phase_dir, &root.join(".yolo-planning"));
         assert!(res2.is_ok());
 
         // 3. Invalid config.json
         fs::write(root.join(".yolo-planning/config.json"), "invalid json").unwrap();
         let state_md = root.join(".yolo-planning/STATE.md");
         fs::write(&state_md, "## Codebase Profile\n- **Test Coverage:**\n").unwrap();
-        update_model_profile(&phase_dir, &root.join(".yolo-planning")).unwrap();
+        update_model_profile(&mut tx, &phase_dir, &root.join(".yolo-planning")).unwrap();
+        tx.commit().unwrap();
+        let mut tx = Transaction::begin(&root.join(".yolo-planning"));
         let content = fs::read_to_string(&state_md).unwrap();
         assert!(content.contains("- **Model Profile:** quality")); // defaults to quality on error
 
         // 4. Update execution state missing file
-        update_execution_state(&root.join(".yolo-planning"), "1", "1", "done", "1");
+        update_execution_state(&mut tx, &root.join(".yolo-planning"), "1", "1", "done", "1");
         
         // 5. update_roadmap empty phase_num_str
         let empty_phase_dir = root.join(".yolo-planning/phases/");
-        update_roadmap(&empty_phase_dir, &root.join(".yolo-planning")).unwrap();
+        update_roadmap(&mut tx, &empty_phase_dir, &root.join(".yolo-planning")).unwrap();
 
         // 6. update_roadmap missing existing_name
         let rm_path = root.join(".yolo-planning/ROADMAP.md");
         fs::write(&rm_path, "no columns here").unwrap();
-        update_roadmap(&phase_dir, &root.join(".yolo-planning")).unwrap();
+        update_roadmap(&mut tx, &phase_dir, &root.join(".yolo-planning")).unwrap();
 
         // 7. update_roadmap with plan count 0
         fs::remove_dir_all(&phase_dir).unwrap();
         fs::create_dir_all(&phase_dir).unwrap(); // empty dir
-        update_roadmap(&phase_dir, &root.join(".yolo-planning")).unwrap();
+        update_roadmap(&mut tx, &phase_dir, &root.join(".yolo-planning")).unwrap();
 
         let _ = fs::remove_dir_all(&root);
     }
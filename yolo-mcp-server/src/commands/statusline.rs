@@ -1,58 +1,72 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
 use serde_json::Value;
 
-// --- ANSI color constants ---
-const C_RESET: &str = "\x1b[0m";
-const C_DIM: &str = "\x1b[2m";
-const C_BOLD: &str = "\x1b[1m";
-const C_CYAN: &str = "\x1b[36m";
-const C_GREEN: &str = "\x1b[32m";
-const C_YELLOW: &str = "\x1b[33m";
-const C_RED: &str = "\x1b[31m";
+use super::cost_forecast;
+use super::layered_config::Config;
+use super::net::{Endpoint, Fetched, Net};
+use super::statusline_layout::{self, CostMode, Layout, Style};
+
+/// What Claude Code sends on stdin.
+struct Session {
+    pct: i64,
+    in_tok: i64,
+    out_tok: i64,
+    cache_w: i64,
+    cache_r: i64,
+    ctx_size: i64,
+    cost: f64,
+    dur_ms: i64,
+    api_ms: i64,
+    added: i64,
+    removed: i64,
+    model: String,
+    cc_version: String,
+}
+
+impl Session {
+    fn parse(input: &Value) -> Self {
+        let int = |ptr: &str| input.pointer(ptr).and_then(|v| v.as_i64()).unwrap_or(0);
+        let text = |ptr: &str, default: &str| input.pointer(ptr).and_then(|v| v.as_str()).unwrap_or(default).to_string();
+        Self {
+            pct: input.pointer("/context_window/used_percentage").and_then(|v| v.as_f64()).unwrap_or(0.0) as i64,
+            in_tok: int("/context_window/current_usage/input_tokens"),
+            out_tok: int("/context_window/current_usage/output_tokens"),
+            cache_w: int("/context_window/current_usage/cache_creation_input_tokens"),
+            cache_r: int("/context_window/current_usage/cache_read_input_tokens"),
+            ctx_size: input.pointer("/context_window/context_window_size").and_then(|v| v.as_i64()).unwrap_or(200_000),
+            cost: input.pointer("/cost/total_cost_usd").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            dur_ms: int("/cost/total_duration_ms"),
+            api_ms: int("/cost/total_api_duration_ms"),
+            added: int("/cost/total_lines_added"),
+            removed: int("/cost/total_lines_removed"),
+            model: text("/model/display_name", "Claude"),
+            cc_version: text("/version", "?"),
+        }
+    }
+}
+
+/// Everything a segment may draw from.
+struct Render<'a> {
+    session: &'a Session,
+    fast: &'a FastCache,
+    slow: &'a SlowCache,
+    config: &'a Config,
+    style: &'a Style,
+    layout: &'a Layout,
+    cache_prefix: &'a str,
+}
 
-/// Main entry point: takes raw stdin JSON from Claude Code, returns 4-line statusline.
+/// Main entry point: takes raw stdin JSON from Claude Code and renders the
+/// configured layout (by default the 4-line statusline).
 pub fn render_statusline(stdin_json: &str) -> Result<String, String> {
     let input: Value = serde_json::from_str(stdin_json).unwrap_or(Value::Object(Default::default()));
+    let session = Session::parse(&input);
 
-    // --- Parse stdin JSON fields ---
-    let pct = input.pointer("/context_window/used_percentage")
-        .and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
-    let _rem = input.pointer("/context_window/remaining_percentage")
-        .and_then(|v| v.as_f64()).unwrap_or(100.0) as i64;
-    let in_tok = input.pointer("/context_window/current_usage/input_tokens")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let out_tok = input.pointer("/context_window/current_usage/output_tokens")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let cache_w = input.pointer("/context_window/current_usage/cache_creation_input_tokens")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let cache_r = input.pointer("/context_window/current_usage/cache_read_input_tokens")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let ctx_size = input.pointer("/context_window/context_window_size")
-        .and_then(|v| v.as_i64()).unwrap_or(200_000);
-
-    let cost = input.pointer("/cost/total_cost_usd")
-        .and_then(|v| v.as_f64()).unwrap_or(0.0);
-    let dur_ms = input.pointer("/cost/total_duration_ms")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let api_ms = input.pointer("/cost/total_api_duration_ms")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let added = input.pointer("/cost/total_lines_added")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-    let removed = input.pointer("/cost/total_lines_removed")
-        .and_then(|v| v.as_i64()).unwrap_or(0);
-
-    let model = input.pointer("/model/display_name")
-        .and_then(|v| v.as_str()).unwrap_or("Claude");
-    let cc_version = input.get("version")
-        .and_then(|v| v.as_str()).unwrap_or("?");
-
-    // --- Token calculations ---
-    let ctx_used = in_tok + cache_w + cache_r;
-    let total_input = in_tok + cache_w + cache_r;
-    let cache_hit_pct = if total_input > 0 { cache_r * 100 / total_input } else { 0 };
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let (config, style, layout) = statusline_layout::load(&cwd);
 
     // --- Cache infrastructure ---
     let cache_prefix = build_cache_prefix();
@@ -63,126 +77,205 @@ pub fn render_statusline(stdin_json: &str) -> Result<String, String> {
     // --- Slow cache (60s TTL): OAuth usage, update check ---
     let slow = read_slow_cache(&cache_prefix);
 
-    // --- L1: [YOLO] Phase/Plans/Effort/Model + git info ---
-    let mut l1 = format!("{}{}{}", C_CYAN, C_BOLD, "[YOLO]");
-    l1.push_str(C_RESET);
-
-    if fast.exec_status == "running" && fast.exec_total > 0 {
-        let exec_pct = fast.exec_done * 100 / fast.exec_total;
-        l1.push_str(&format!(" Build: {} {}/{} plans",
-            progress_bar(exec_pct, 8), fast.exec_done, fast.exec_total));
-        if fast.exec_twaves > 1 {
-            l1.push_str(&format!(" {}|{} Wave {}/{}",
-                C_DIM, C_RESET, fast.exec_wave, fast.exec_twaves));
-        }
-        if !fast.exec_current.is_empty() {
-            l1.push_str(&format!(" {}|{} {}{}{}", C_DIM, C_RESET, C_CYAN, fast.exec_current, C_RESET));
-        }
-    } else if fast.has_planning_dir {
-        if fast.total_phases > 0 {
-            l1.push_str(&format!(" Phase {}/{}", fast.phase, fast.total_phases));
-        } else {
-            l1.push_str(&format!(" Phase {}", fast.phase));
+    let r = Render {
+        session: &session,
+        fast: &fast,
+        slow: &slow,
+        config: &config,
+        style: &style,
+        layout: &layout,
+        cache_prefix: &cache_prefix,
+    };
+    Ok(render_lines(&r))
+}
+
+/// Draws each configured line, dropping empty segments and lines.
+fn render_lines(r: &Render) -> String {
+    let mut out = String::new();
+    for line in &r.layout.lines {
+        let mut rendered = String::new();
+        let mut prev: Option<&str> = None;
+        for name in line {
+            let Some(text) = segment(name, r) else { continue };
+            match prev {
+                // The [YOLO] tag labels the line rather than being one item of it.
+                Some("yolo") => rendered.push(' '),
+                Some(_) => rendered.push_str(&r.style.sep()),
+                None => {}
+            }
+            rendered.push_str(&text);
+            prev = Some(name);
         }
-        if fast.plans_total > 0 {
-            l1.push_str(&format!(" {}|{} Plans: {}/{}",
-                C_DIM, C_RESET, fast.plans_done, fast.plans_total));
+        if !rendered.is_empty() {
+            out.push_str(&rendered);
+            out.push('\n');
         }
-        l1.push_str(&format!(" {}|{} Effort: {} {}|{} Model: {}",
-            C_DIM, C_RESET, fast.effort,
-            C_DIM, C_RESET, fast.model_profile));
-    } else {
-        l1.push_str(&format!(" {}no project{}", C_DIM, C_RESET));
     }
+    out
+}
 
-    // Git info
-    if !fast.branch.is_empty() {
-        l1.push_str(&format!(" {}|{} {}:{}", C_DIM, C_RESET, fast.repo_name, fast.branch));
-        let mut git_ind = String::new();
-        if fast.staged > 0 {
-            git_ind.push_str(&format!("{}+{}{}", C_GREEN, fast.staged, C_RESET));
+/// One segment, or `None` when it has nothing to show. Unknown names that
+/// aren't custom segments render as `?name` so a typo in the layout is visible.
+fn segment(name: &str, r: &Render) -> Option<String> {
+    let (s, f, sl, st) = (r.session, r.fast, r.slow, r.style);
+    let (dim, reset) = (&st.dim, &st.reset);
+    let building = f.exec_status == "running" && f.exec_total > 0;
+    let planning = f.has_planning_dir && !building;
+
+    match name {
+        "yolo" => Some(format!("{}{}[YOLO]{}", st.accent, st.bold, reset)),
+        "phase" if building => {
+            if r.layout.short {
+                return Some(format!("Build {}/{}", f.exec_done, f.exec_total));
+            }
+            let exec_pct = f.exec_done * 100 / f.exec_total;
+            let mut out = format!("Build: {} {}/{} plans", progress_bar(exec_pct, 8, st), f.exec_done, f.exec_total);
+            if f.exec_twaves > 1 {
+                out.push_str(&format!("{}Wave {}/{}", st.sep(), f.exec_wave, f.exec_twaves));
+            }
+            if !f.exec_current.is_empty() {
+                out.push_str(&format!("{}{}{}{}", st.sep(), st.accent, f.exec_current, reset));
+            }
+            Some(out)
         }
-        if fast.modified > 0 {
-            git_ind.push_str(&format!("{}~{}{}", C_YELLOW, fast.modified, C_RESET));
+        "phase" if f.has_planning_dir => {
+            let pos = if f.total_phases > 0 { format!("{}/{}", f.phase, f.total_phases) } else { f.phase.clone() };
+            Some(if r.layout.short { format!("P{}", pos) } else { format!("Phase {}", pos) })
         }
-        if !git_ind.is_empty() {
-            l1.push_str(&format!(" {}", git_ind));
+        "phase" => Some(format!("{}no project{}", dim, reset)),
+        "plans" => (planning && f.plans_total > 0).then(|| format!("Plans: {}/{}", f.plans_done, f.plans_total)),
+        "effort" => planning.then(|| format!("Effort: {}", f.effort)),
+        "profile" => planning.then(|| format!("Model: {}", f.model_profile)),
+        "git" => (!f.branch.is_empty()).then(|| {
+            let mut out = if r.layout.short { f.branch.clone() } else { format!("{}:{}", f.repo_name, f.branch) };
+            let mut git_ind = String::new();
+            if f.staged > 0 {
+                git_ind.push_str(&format!("{}+{}{}", st.ok, f.staged, reset));
+            }
+            if f.modified > 0 {
+                git_ind.push_str(&format!("{}~{}{}", st.warn, f.modified, reset));
+            }
+            if !git_ind.is_empty() {
+                out.push_str(&format!(" {}", git_ind));
+            }
+            out
+        }),
+        "diff" => (s.added > 0 || s.removed > 0).then(|| {
+            format!("{}Diff:{} {}+{}{} {}-{}{}", dim, reset, st.ok, s.added, reset, st.error, s.removed, reset)
+        }),
+        "context" => {
+            let ctx_color = if s.pct >= 90 { &st.error } else if s.pct >= 70 { &st.warn } else { &st.ok };
+            if r.layout.short {
+                return Some(format!("Ctx {}{}%{}", ctx_color, s.pct, reset));
+            }
+            let ctx_used = s.in_tok + s.cache_w + s.cache_r;
+            Some(format!(
+                "Context: {}{}{} {}{}%{} {}/{}",
+                ctx_color,
+                progress_bar(s.pct, 10, st),
+                reset,
+                ctx_color,
+                s.pct,
+                reset,
+                fmt_tok(ctx_used),
+                fmt_tok(s.ctx_size)
+            ))
         }
-    }
-    if added > 0 || removed > 0 {
-        l1.push_str(&format!(" {}Diff:{} {}+{}{} {}-{}{}",
-            C_DIM, C_RESET,
-            C_GREEN, added, C_RESET,
-            C_RED, removed, C_RESET));
-    }
-
-    // --- L2: Context window ---
-    let ctx_color = if pct >= 90 { C_RED } else if pct >= 70 { C_YELLOW } else { C_GREEN };
-    let ctx_bar = progress_bar(pct, 10);
-    let cache_color = if cache_hit_pct >= 70 { C_GREEN } else if cache_hit_pct >= 40 { C_YELLOW } else { C_RED };
-
-    let l2 = format!(
-        "Context: {}{}{} {}{}%{} {}/{} {}|{} Tokens: {} in  {} out {}|{} Prompt Cache: {}{}% hit{} {} write {} read",
-        ctx_color, ctx_bar, C_RESET,
-        ctx_color, pct, C_RESET,
-        fmt_tok(ctx_used), fmt_tok(ctx_size),
-        C_DIM, C_RESET,
-        fmt_tok(in_tok), fmt_tok(out_tok),
-        C_DIM, C_RESET,
-        cache_color, cache_hit_pct, C_RESET,
-        fmt_tok(cache_w), fmt_tok(cache_r),
-    );
-
-    // --- L3: OAuth usage limits ---
-    let l3 = if slow.fetch_ok == "ok" {
-        let five_rem = countdown(slow.five_epoch);
-        let week_rem = countdown(slow.week_epoch);
-
-        let mut line = format!("Session: {} {}%", progress_bar(slow.five_pct, 10), slow.five_pct);
-        if !five_rem.is_empty() {
-            line.push_str(&format!(" {}", five_rem));
+        "tokens" => Some(format!("Tokens: {} in  {} out", fmt_tok(s.in_tok), fmt_tok(s.out_tok))),
+        "cache" => {
+            let total_input = s.in_tok + s.cache_w + s.cache_r;
+            let cache_hit_pct = if total_input > 0 { s.cache_r * 100 / total_input } else { 0 };
+            let cache_color = if cache_hit_pct >= 70 { &st.ok } else if cache_hit_pct >= 40 { &st.warn } else { &st.error };
+            Some(format!(
+                "Prompt Cache: {}{}% hit{} {} write {} read",
+                cache_color,
+                cache_hit_pct,
+                reset,
+                fmt_tok(s.cache_w),
+                fmt_tok(s.cache_r)
+            ))
+        }
+        "limits" if sl.fetch_ok == "ok" && r.layout.short => Some(format!("5h {}% wk {}%", sl.five_pct, sl.week_pct)),
+        "limits" if sl.fetch_ok == "ok" => {
+            let five_rem = countdown(sl.five_epoch);
+            let week_rem = countdown(sl.week_epoch);
+            let mut line = format!("Session: {} {}%", progress_bar(sl.five_pct, 10, st), sl.five_pct);
+            if !five_rem.is_empty() {
+                line.push_str(&format!(" {}", five_rem));
+            }
+            line.push_str(&format!("{}Weekly: {} {}%", st.sep(), progress_bar(sl.week_pct, 10, st), sl.week_pct));
+            if !week_rem.is_empty() {
+                line.push_str(&format!(" {}", week_rem));
+            }
+            Some(line)
         }
-        line.push_str(&format!(" {}|{} Weekly: {} {}%",
-            C_DIM, C_RESET, progress_bar(slow.week_pct, 10), slow.week_pct));
-        if !week_rem.is_empty() {
-            line.push_str(&format!(" {}", week_rem));
+        // Compact panes have no room for "why not".
+        "limits" if r.layout.short => None,
+        "limits" => Some(match sl.fetch_ok.as_str() {
+            "auth" => format!("{}Limits: auth expired (run /login){}", dim, reset),
+            "fail" => format!("{}Limits: fetch failed (retry in 60s){}", dim, reset),
+            "offline" => format!("{}Limits: N/A (network off){}", dim, reset),
+            _ => format!("{}Limits: N/A (using API key){}", dim, reset),
+        }),
+        "model" => Some(format!("Model: {}{}{}", dim, s.model, reset)),
+        "cost" => {
+            // Any OAuth token means a subscription plan; "noauth" means an API key.
+            let subscription = sl.fetch_ok != "noauth";
+            let hidden = match r.layout.cost {
+                CostMode::Show => false,
+                CostMode::Hide => true,
+                CostMode::Auto => subscription,
+            };
+            if hidden {
+                None
+            } else if r.layout.short {
+                Some(fmt_cost(s.cost))
+            } else {
+                Some(format!("Cost: {}", fmt_cost(s.cost)))
+            }
         }
-        line
-    } else if slow.fetch_ok == "auth" {
-        format!("{}Limits: auth expired (run /login){}", C_DIM, C_RESET)
-    } else if slow.fetch_ok == "fail" {
-        format!("{}Limits: fetch failed (retry in 60s){}", C_DIM, C_RESET)
-    } else {
-        format!("{}Limits: N/A (using API key){}", C_DIM, C_RESET)
-    };
-
-    // --- L4: Model / Time / Versions ---
-    let dur_fmt = fmt_dur(dur_ms);
-    let api_dur_fmt = fmt_dur(api_ms);
-    let cost_fmt = fmt_cost(cost);
-    let yolo_ver = read_yolo_version();
-
-    let mut l4 = format!("Model: {}{}{} {}|{} Cost: {} {}|{} Time: {} (API: {})",
-        C_DIM, model, C_RESET,
-        C_DIM, C_RESET,
-        cost_fmt,
-        C_DIM, C_RESET,
-        dur_fmt, api_dur_fmt);
-
-    if let Some(ref remote) = slow.update_avail {
-        l4.push_str(&format!(" {}|{} {}{}YOLO {} -> {}{}",
-            C_DIM, C_RESET,
-            C_YELLOW, C_BOLD, yolo_ver, remote, C_RESET));
-    } else {
-        l4.push_str(&format!(" {}|{} {}YOLO {}{}", C_DIM, C_RESET, C_DIM, yolo_ver, C_RESET));
+        // Written by `report-tokens --forecast` only while a projection is over budget.
+        "budget" => {
+            let alert: Value = fs::read_to_string(Path::new(".yolo-planning").join(cost_forecast::ALERT_FILE))
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())?;
+            let projected = alert["projected_usd"].as_f64()?;
+            let budget = alert["budget_usd"].as_f64()?;
+            if r.layout.short {
+                return Some(format!("{}{}/{}{}", st.error, fmt_cost(projected), fmt_cost(budget), reset));
+            }
+            let scope = match alert["phase"].as_str() {
+                Some(phase) => format!("phase {}", phase),
+                None => "milestone".to_string(),
+            };
+            Some(format!(
+                "{}Budget: {} projected > {} ({}){}",
+                st.error,
+                fmt_cost(projected),
+                fmt_cost(budget),
+                scope,
+                reset
+            ))
+        }
+        "time" => Some(format!("Time: {} (API: {})", fmt_dur(s.dur_ms), fmt_dur(s.api_ms))),
+        "version" => {
+            let yolo_ver = read_yolo_version();
+            Some(match sl.update_avail {
+                Some(ref remote) => format!("{}{}YOLO {} -> {}{}", st.warn, st.bold, yolo_ver, remote, reset),
+                None => format!("{}YOLO {}{}", dim, yolo_ver, reset),
+            })
+        }
+        "cc" => Some(format!("{}CC {}{}", dim, s.cc_version, reset)),
+        custom if r.config.get(&format!("statusline.segments.{}", custom)).is_some() => {
+            statusline_layout::custom_segment(r.config, custom, r.cache_prefix, st)
+        }
+        unknown => Some(format!("{}?{}{}", dim, unknown, reset)),
     }
-    l4.push_str(&format!(" {}|{} {}CC {}{}", C_DIM, C_RESET, C_DIM, cc_version, C_RESET));
-
-    Ok(format!("{}\n{}\n{}\n{}\n", l1, l2, l3, l4))
 }
 
 // === Helper structs ===
 
+#[derive(Default)]
 struct FastCache {
     phase: String,
     total_phases: i64,
@@ -203,6 +296,7 @@ struct FastCache {
     exec_current: String,
 }
 
+#[derive(Default)]
 struct SlowCache {
     five_pct: i64,
     five_epoch: i64,
@@ -294,7 +388,7 @@ fn read_version_from_plugin_cache() -> Option<String> {
 
 // === Cache freshness check ===
 
-fn cache_fresh(path: &str, ttl_secs: u64) -> bool {
+pub(crate) fn cache_fresh(path: &str, ttl_secs: u64) -> bool {
     if let Ok(meta) = fs::metadata(path)
         && let Ok(modified) = meta.modified()
         && let Ok(elapsed) = modified.elapsed()
@@ -536,61 +630,46 @@ fn compute_slow_cache() -> SlowCache {
         update_avail: None,
     };
 
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let net = Net::load(&cwd);
+
     // --- OAuth credential discovery ---
     let oauth_token = get_oauth_token();
 
     if let Some(token) = oauth_token {
-        // Call /api/oauth/usage with curl
-        let result = Command::new("curl")
-            .args([
-                "-s", "--max-time", "3",
-                "-H", &format!("Authorization: Bearer {}", token),
-                "-H", "anthropic-beta: oauth-2025-04-20",
-                "https://api.anthropic.com/api/oauth/usage",
-            ])
-            .output();
-
-        if let Ok(out) = result {
-            if out.status.success() {
-                let body = String::from_utf8_lossy(&out.stdout);
-                if let Ok(usage) = serde_json::from_str::<Value>(&body) {
-                    if usage.get("five_hour").is_some() {
-                        sc.five_pct = usage.pointer("/five_hour/utilization")
-                            .and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
-                        sc.week_pct = usage.pointer("/seven_day/utilization")
-                            .and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
-                        // Parse reset epochs (best effort)
-                        sc.five_epoch = parse_iso_epoch(
-                            usage.pointer("/five_hour/resets_at")
-                                .and_then(|v| v.as_str()).unwrap_or(""));
-                        sc.week_epoch = parse_iso_epoch(
-                            usage.pointer("/seven_day/resets_at")
-                                .and_then(|v| v.as_str()).unwrap_or(""));
-                        sc.fetch_ok = "ok".to_string();
-                    } else {
-                        sc.fetch_ok = "fail".to_string();
-                    }
-                } else {
-                    sc.fetch_ok = "fail".to_string();
+        let auth = format!("Bearer {}", token);
+        let fetched = net.get(
+            Endpoint::OauthUsage,
+            Duration::from_secs(60),
+            &[("Authorization", auth.as_str()), ("anthropic-beta", "oauth-2025-04-20")],
+        );
+        sc.fetch_ok = match fetched {
+            Fetched::Blocked => "offline".to_string(),
+            Fetched::Status(401) => "auth".to_string(),
+            Fetched::Status(_) | Fetched::Failed => "fail".to_string(),
+            Fetched::Network(body) | Fetched::Cached(body) => match serde_json::from_str::<Value>(&body) {
+                Ok(usage) if usage.get("five_hour").is_some() => {
+                    sc.five_pct = usage.pointer("/five_hour/utilization")
+                        .and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
+                    sc.week_pct = usage.pointer("/seven_day/utilization")
+                        .and_then(|v| v.as_f64()).unwrap_or(0.0) as i64;
+                    // Parse reset epochs (best effort)
+                    sc.five_epoch = parse_iso_epoch(
+                        usage.pointer("/five_hour/resets_at")
+                            .and_then(|v| v.as_str()).unwrap_or(""));
+                    sc.week_epoch = parse_iso_epoch(
+                        usage.pointer("/seven_day/resets_at")
+                            .and_then(|v| v.as_str()).unwrap_or(""));
+                    "ok".to_string()
                 }
-            } else {
-                sc.fetch_ok = "fail".to_string();
-            }
-        }
+                _ => "fail".to_string(),
+            },
+        };
     }
 
     // --- Update check ---
-    let remote_ver_result = Command::new("curl")
-        .args([
-            "-sf", "--max-time", "3",
-            "https://raw.githubusercontent.com/slavpetroff/yolo/main/VERSION",
-        ])
-        .output();
-
-    if let Ok(out) = remote_ver_result
-        && out.status.success()
-    {
-        let remote_ver = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if let Some(body) = net.get(Endpoint::YoloVersion, Duration::from_secs(86400), &[]).body() {
+        let remote_ver = body.trim().to_string();
         let local_ver = read_yolo_version();
         if !remote_ver.is_empty() && !local_ver.is_empty()
             && remote_ver != local_ver && remote_ver != "?"
@@ -695,17 +774,19 @@ fn fmt_cost(cost: f64) -> String {
     }
 }
 
-fn progress_bar(pct: i64, width: i64) -> String {
+fn progress_bar(pct: i64, width: i64, style: &Style) -> String {
     let filled = ((pct * width) / 100).max(0).min(width);
     let filled = if pct > 0 && filled == 0 { 1 } else { filled };
     let empty = width - filled;
 
-    let color = if pct >= 80 { C_RED } else if pct >= 50 { C_YELLOW } else { C_GREEN };
+    let color = if pct >= 80 { &style.error } else if pct >= 50 { &style.warn } else { &style.ok };
+    // dark/light shade, or #/- in ASCII mode
+    let (full, light) = if style.ascii { ('#', '-') } else { ('\u{2593}', '\u{2591}') };
 
-    let bar_filled: String = (0..filled).map(|_| '\u{2593}').collect(); // dark shade
-    let bar_empty: String = (0..empty).map(|_| '\u{2591}').collect();   // light shade
+    let bar_filled: String = (0..filled).map(|_| full).collect();
+    let bar_empty: String = (0..empty).map(|_| light).collect();
 
-    format!("{}{}{}{}", color, bar_filled, bar_empty, C_RESET)
+    format!("{}{}{}{}", color, bar_filled, bar_empty, style.reset)
 }
 
 fn countdown(epoch: i64) -> String {
@@ -842,12 +923,63 @@ mod tests {
             }
         }"#;
         let out = render_statusline(input).unwrap();
-        assert!(out.contains("$1.23"), "Should show cost");
+        // The default `auto` cost mode hides cost on subscription (OAuth) sessions.
+        if get_oauth_token().is_none() {
+            assert!(out.contains("$1.23"), "Should show cost");
+        }
         assert!(out.contains("2m 5s"), "Should show 2m 5s duration");
         assert!(out.contains("+150"), "Should show lines added");
         assert!(out.contains("-30"), "Should show lines removed");
     }
 
+    #[test]
+    fn test_render_layouts_ascii_and_cost_visibility() {
+        let session = Session::parse(&serde_json::json!({
+            "context_window": {"used_percentage": 45.0},
+            "cost": {"total_cost_usd": 1.23},
+            "model": {"display_name": "Claude Opus 4"}
+        }));
+        let fast = FastCache {
+            has_planning_dir: true,
+            phase: "2".to_string(),
+            total_phases: 5,
+            branch: "main".to_string(),
+            repo_name: "app".to_string(),
+            staged: 1,
+            ..Default::default()
+        };
+        let mut slow = SlowCache { fetch_ok: "noauth".to_string(), ..Default::default() };
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load(dir.path());
+        let style = Style::plain(true);
+        let render = |layout: &Layout, slow: &SlowCache| {
+            render_lines(&Render {
+                session: &session,
+                fast: &fast,
+                slow,
+                config: &config,
+                style: &style,
+                layout,
+                cache_prefix: "/nonexistent/yolo-test",
+            })
+        };
+
+        let compact = Layout { lines: vec![vec!["yolo".into(), "phase".into(), "context".into(), "limits".into(), "cost".into(), "git".into()]], short: true, cost: CostMode::Auto };
+        assert_eq!(render(&compact, &slow), "[YOLO] P2/5 | Ctx 45% | $1.23 | main +1\n");
+
+        // Subscription (OAuth) sessions hide cost in auto mode; `show` forces it.
+        slow.fetch_ok = "ok".to_string();
+        assert_eq!(render(&compact, &slow), "[YOLO] P2/5 | Ctx 45% | 5h 0% wk 0% | main +1\n");
+        let show = Layout { cost: CostMode::Show, ..compact };
+        assert!(render(&show, &slow).contains("$1.23"));
+
+        let full = Layout { lines: vec![vec!["context".into(), "typo".into()]], short: false, cost: CostMode::Hide };
+        let out = render(&full, &slow);
+        assert!(out.contains("Context: ####------ 45%"), "got: {}", out);
+        assert!(out.ends_with("| ?typo\n"), "got: {}", out);
+        assert!(out.is_ascii());
+    }
+
     #[test]
     fn test_fast_cache_state_parsing() {
         let content = "# State\n\n**Current Phase:** Phase 2\n**Status:** In Progress\n**Progress:** 50%\n";
@@ -881,9 +1013,10 @@ mod tests {
         assert_eq!(fmt_cost(150.0), "$150");
 
         // Progress bar
-        let bar = progress_bar(50, 10);
+        let bar = progress_bar(50, 10, &Style::plain(false));
         assert!(bar.contains('\u{2593}'), "Should contain filled blocks");
         assert!(bar.contains('\u{2591}'), "Should contain empty blocks");
+        assert_eq!(progress_bar(50, 10, &Style::plain(true)), "#####-----");
 
         // Extract number
         assert_eq!(extract_first_number("Phase 2"), Some(2));
@@ -0,0 +1,292 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use super::layered_config::{Config, Layer};
+use super::utils::run_with_timeout;
+
+/// The four-line statusline YOLO has always drawn.
+const FULL: &[&[&str]] = &[
+    &["yolo", "phase", "plans", "effort", "profile", "git", "diff"],
+    &["context", "tokens", "cache"],
+    &["limits"],
+    &["model", "cost", "budget", "time", "version", "cc"],
+];
+
+/// One line for narrow tmux panes; segments render their short forms.
+const COMPACT: &[&[&str]] = &[&["yolo", "phase", "context", "limits", "cost", "budget", "git"]];
+
+pub const BUILTIN_SEGMENTS: &[&str] = &[
+    "yolo", "phase", "plans", "effort", "profile", "git", "diff", "context", "tokens", "cache", "limits", "model",
+    "cost", "budget", "time", "version", "cc",
+];
+
+const CUSTOM_TIMEOUT: Duration = Duration::from_secs(1);
+const CUSTOM_TTL_SECS: u64 = 30;
+
+/// Colours and glyphs. Every colour is an SGR escape, or empty when colour is off.
+pub struct Style {
+    pub accent: String,
+    pub ok: String,
+    pub warn: String,
+    pub error: String,
+    pub dim: String,
+    pub bold: String,
+    pub reset: String,
+    pub ascii: bool,
+}
+
+impl Style {
+    /// `visual_format` picks the mode: `unicode` (colour), `plain` (no colour)
+    /// or `ascii` (no colour, ASCII bars). `NO_COLOR` also turns colour off.
+    /// `statusline.theme` overrides individual roles of the default palette.
+    pub fn from_config(config: &Config) -> Self {
+        let format = config.get("visual_format").and_then(Value::as_str).unwrap_or("unicode");
+        let colour = format == "unicode" && std::env::var_os("NO_COLOR").is_none();
+        let ascii = format == "ascii";
+        if !colour {
+            return Self::plain(ascii);
+        }
+        let theme = config.get("statusline.theme");
+        let role = |name: &str, default: &str| {
+            let code = theme.and_then(|t| t.get(name)).and_then(Value::as_str).map(sgr).unwrap_or_else(|| default.to_string());
+            if code.is_empty() { String::new() } else { format!("\x1b[{}m", code) }
+        };
+        Self {
+            accent: role("accent", "36"),
+            ok: role("ok", "32"),
+            warn: role("warn", "33"),
+            error: role("error", "31"),
+            dim: role("dim", "2"),
+            bold: role("bold", "1"),
+            reset: "\x1b[0m".to_string(),
+            ascii,
+        }
+    }
+
+    pub fn plain(ascii: bool) -> Self {
+        Self {
+            accent: String::new(),
+            ok: String::new(),
+            warn: String::new(),
+            error: String::new(),
+            dim: String::new(),
+            bold: String::new(),
+            reset: String::new(),
+            ascii,
+        }
+    }
+
+    pub fn colour(&self) -> bool {
+        !self.reset.is_empty()
+    }
+
+    /// The separator between segments.
+    pub fn sep(&self) -> String {
+        format!(" {}|{} ", self.dim, self.reset)
+    }
+}
+
+/// A colour name or a raw SGR parameter string (`"38;5;208"`). `none` disables the role.
+fn sgr(name: &str) -> String {
+    let code = match name {
+        "none" => "",
+        "bold" => "1",
+        "dim" => "2",
+        "black" => "30",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        "gray" | "grey" => "90",
+        "bright_red" => "91",
+        "bright_green" => "92",
+        "bright_yellow" => "93",
+        "bright_blue" => "94",
+        "bright_magenta" => "95",
+        "bright_cyan" => "96",
+        raw if raw.chars().all(|c| c.is_ascii_digit() || c == ';') => raw,
+        _ => "",
+    };
+    code.to_string()
+}
+
+/// When the cost segment is shown. `auto` hides it on subscription plans,
+/// where the per-session dollar figure is notional.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CostMode {
+    Auto,
+    Show,
+    Hide,
+}
+
+pub struct Layout {
+    pub lines: Vec<Vec<String>>,
+    /// Segments render their short forms (compact layout).
+    pub short: bool,
+    pub cost: CostMode,
+}
+
+impl Layout {
+    /// `statusline.layout` picks a preset (`full`, `compact`); `statusline.lines`
+    /// replaces its segment lists while keeping its short/long rendering.
+    pub fn from_config(config: &Config) -> Self {
+        let short = config.get("statusline.layout").and_then(Value::as_str) == Some("compact");
+        let preset = if short { COMPACT } else { FULL };
+        let lines = match config.get("statusline.lines").and_then(Value::as_array) {
+            Some(lines) => lines
+                .iter()
+                .filter_map(Value::as_array)
+                .map(|line| line.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                .collect(),
+            None => preset.iter().map(|line| line.iter().map(|s| s.to_string()).collect()).collect(),
+        };
+        let cost = match config.get("statusline.cost").and_then(Value::as_str) {
+            Some("show") => CostMode::Show,
+            Some("hide") => CostMode::Hide,
+            _ => CostMode::Auto,
+        };
+        Self { lines, short, cost }
+    }
+}
+
+/// A user-defined segment: the first line of a shell command's output,
+/// cached for `ttl_secs` so the statusline never waits on it twice in a row.
+/// The command is only taken from the user and env layers; a project config
+/// comes with the repository and must not run shell commands on render.
+pub fn custom_segment(config: &Config, name: &str, cache_prefix: &str, style: &Style) -> Option<String> {
+    let key = format!("statusline.segments.{}", name);
+    let command = config
+        .get_from(&format!("{}.command", key), &[Layer::User, Layer::Env])
+        .and_then(Value::as_str)?;
+    let ttl = config.get(&format!("{}.ttl_secs", key)).and_then(Value::as_u64).unwrap_or(CUSTOM_TTL_SECS);
+    let cache_file = format!("{}-seg-{}", cache_prefix, name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+
+    let text = if super::statusline::cache_fresh(&cache_file, ttl) {
+        fs::read_to_string(&cache_file).unwrap_or_default()
+    } else {
+        // A failing or slow command caches an empty result, so it is retried
+        // after the TTL rather than on every render.
+        let text = run_with_timeout(Command::new("sh").arg("-c").arg(command), CUSTOM_TIMEOUT)
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).lines().next().unwrap_or("").trim().to_string())
+            .unwrap_or_default();
+        let _ = fs::write(&cache_file, &text);
+        text
+    };
+    let text = if style.colour() { text } else { strip_ansi(&text) };
+    (!text.is_empty()).then_some(text)
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Config for the statusline of the project at `cwd`.
+pub fn load(cwd: &Path) -> (Config, Style, Layout) {
+    let config = Config::load(cwd);
+    let style = Style::from_config(&config);
+    let layout = Layout::from_config(&config);
+    (config, style, layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config(project: Value) -> Config {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        fs::write(dir.path().join(".yolo-planning/config.json"), project.to_string()).unwrap();
+        Config::load(dir.path())
+    }
+
+    #[test]
+    fn test_layout_presets_and_overrides() {
+        let full = Layout::from_config(&config(json!({})));
+        assert_eq!(full.lines.len(), 4);
+        assert!(!full.short);
+        assert_eq!(full.cost, CostMode::Auto);
+
+        let compact = Layout::from_config(&config(json!({"statusline": {"layout": "compact", "cost": "hide"}})));
+        assert_eq!(compact.lines.len(), 1);
+        assert!(compact.short);
+        assert_eq!(compact.cost, CostMode::Hide);
+
+        let custom = Layout::from_config(&config(json!({"statusline": {"lines": [["yolo", "k8s"], ["context"]]}})));
+        assert_eq!(custom.lines, vec![vec!["yolo", "k8s"], vec!["context"]]);
+    }
+
+    #[test]
+    fn test_style_modes_and_theme() {
+        let ascii = Style::from_config(&config(json!({"visual_format": "ascii"})));
+        assert!(ascii.ascii && !ascii.colour());
+        let plain = Style::from_config(&config(json!({"visual_format": "plain"})));
+        assert!(!plain.ascii && !plain.colour());
+
+        if std::env::var_os("NO_COLOR").is_none() {
+            let themed = Style::from_config(&config(json!({"statusline": {"theme": {"accent": "magenta", "ok": "38;5;112", "dim": "none"}}})));
+            assert_eq!(themed.accent, "\x1b[35m");
+            assert_eq!(themed.ok, "\x1b[38;5;112m");
+            assert_eq!(themed.dim, "");
+            assert_eq!(themed.error, "\x1b[31m");
+        }
+    }
+
+    /// Config with `segments` set in the user layer.
+    fn user_segments(segments: Value) -> Config {
+        let user = json!({"statusline": {"segments": segments}});
+        config(json!({})).with_layer_values(Layer::User, user.as_object().unwrap().clone())
+    }
+
+    #[test]
+    fn test_custom_segment_runs_caches_and_strips_colour() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("sl").to_string_lossy().to_string();
+        let cfg = user_segments(json!({
+            "ctx": {"command": "printf '\\033[32mprod\\033[0m\\nignored'", "ttl_secs": 60},
+            "bad": {"command": "exit 3"}
+        }));
+        let plain = Style::plain(false);
+        assert_eq!(custom_segment(&cfg, "ctx", &prefix, &plain).as_deref(), Some("prod"));
+
+        // Served from the cache while fresh, even if the command would now differ.
+        fs::write(format!("{}-seg-ctx", prefix), "cached").unwrap();
+        assert_eq!(custom_segment(&cfg, "ctx", &prefix, &plain).as_deref(), Some("cached"));
+
+        assert_eq!(custom_segment(&cfg, "bad", &prefix, &plain), None);
+        assert_eq!(custom_segment(&cfg, "missing", &prefix, &plain), None);
+    }
+
+    #[test]
+    fn test_custom_segment_ignores_project_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("sl").to_string_lossy().to_string();
+        let marker = dir.path().join("ran");
+        let cfg = config(json!({"statusline": {"segments": {
+            "evil": {"command": format!("touch {} && echo pwned", marker.display())}
+        }}}));
+        assert_eq!(custom_segment(&cfg, "evil", &prefix, &Style::plain(false)), None);
+        assert!(!marker.exists());
+    }
+}
@@ -0,0 +1,277 @@
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Languages with symbol extraction. Detection is by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Rust,
+    Python,
+    JavaScript,
+    Go,
+    Java,
+    Kotlin,
+    Shell,
+    Ruby,
+}
+
+impl Language {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "rs" => Some(Language::Rust),
+            "py" => Some(Language::Python),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Some(Language::JavaScript),
+            "go" => Some(Language::Go),
+            "java" | "cs" => Some(Language::Java),
+            "kt" | "kts" => Some(Language::Kotlin),
+            "sh" | "bash" | "zsh" | "bats" => Some(Language::Shell),
+            "rb" => Some(Language::Ruby),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Python => "python",
+            Language::JavaScript => "javascript",
+            Language::Go => "go",
+            Language::Java => "java",
+            Language::Kotlin => "kotlin",
+            Language::Shell => "shell",
+            Language::Ruby => "ruby",
+        }
+    }
+}
+
+/// A named definition and the 1-based line it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: &'static str,
+    pub line: usize,
+}
+
+struct Rule {
+    lang: Language,
+    kind: &'static str,
+    re: Regex,
+}
+
+/// Line-anchored definition patterns; the last capture group is the name.
+fn rules() -> &'static [Rule] {
+    static RULES: OnceLock<Vec<Rule>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let table: &[(Language, &str, &str)] = &[
+            (Language::Rust, "fn", r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*fn\s+([A-Za-z_]\w*)"#),
+            (Language::Rust, "type", r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:struct|enum|trait|union|type)\s+([A-Za-z_]\w*)"),
+            (Language::Rust, "mod", r"^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+([A-Za-z_]\w*)"),
+            (Language::Rust, "impl", r"^\s*(?:unsafe\s+)?impl(?:<[^>]*>)?\s+(?:[\w:]+(?:<[^>]*>)?\s+for\s+)?(?:[\w]+::)*([A-Za-z_]\w*)"),
+            (Language::Rust, "const", r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const|static)\s+(?:mut\s+)?([A-Za-z_]\w*)\s*:"),
+            (Language::Rust, "macro", r"^\s*macro_rules!\s*([A-Za-z_]\w*)"),
+            (Language::Python, "def", r"^\s*(?:async\s+)?def\s+([A-Za-z_]\w*)"),
+            (Language::Python, "class", r"^\s*class\s+([A-Za-z_]\w*)"),
+            (Language::JavaScript, "function", r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\*?\s+([A-Za-z_$][\w$]*)"),
+            (Language::JavaScript, "class", r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?(?:class|interface|enum|type)\s+([A-Za-z_$][\w$]*)"),
+            (Language::JavaScript, "function", r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]+)?=>|[A-Za-z_$][\w$]*\s*=>)"),
+            (Language::JavaScript, "method", r"^\s+(?:(?:public|private|protected|static|async|get|set|readonly|override)\s+)*([A-Za-z_$][\w$]*)\s*\([^)]*\)\s*(?::\s*[^{]+)?\{\s*$"),
+            (Language::Go, "func", r"^func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)"),
+            (Language::Go, "type", r"^type\s+([A-Za-z_]\w*)"),
+            (Language::Java, "class", r"^\s*(?:(?:public|private|protected|internal|static|final|abstract|sealed|partial)\s+)*(?:class|interface|enum|record|struct)\s+([A-Za-z_]\w*)"),
+            (Language::Java, "method", r"^\s*(?:(?:public|private|protected|internal|static|final|abstract|synchronized|override|async|virtual)\s+)+[\w<>\[\],.?\s]+?\s+([A-Za-z_]\w*)\s*\("),
+            (Language::Kotlin, "fun", r"^\s*(?:(?:public|private|protected|internal|override|open|suspend|inline|operator)\s+)*fun\s+(?:<[^>]*>\s*)?(?:[\w.]+\.)?([A-Za-z_]\w*)\s*\("),
+            (Language::Kotlin, "class", r"^\s*(?:(?:public|private|protected|internal|data|sealed|open|abstract|enum)\s+)*(?:class|interface|object)\s+([A-Za-z_]\w*)"),
+            (Language::Shell, "function", r"^\s*(?:function\s+([A-Za-z_][\w:-]*)|([A-Za-z_][\w:-]*)\s*\(\s*\))"),
+            (Language::Ruby, "def", r"^\s*def\s+(?:self\.)?([A-Za-z_]\w*[?!=]?)"),
+            (Language::Ruby, "class", r"^\s*(?:class|module)\s+([A-Z]\w*)"),
+        ];
+        table
+            .iter()
+            .map(|(lang, kind, pattern)| Rule { lang: *lang, kind, re: Regex::new(pattern).unwrap() })
+            .collect()
+    })
+}
+
+/// Control-flow keywords the loose method patterns would otherwise pick up.
+const NOT_SYMBOLS: &[&str] = &["if", "for", "while", "switch", "catch", "return", "function", "else", "new"];
+
+/// Definitions in a file, in line order.
+pub fn extract(lang: Language, content: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        for rule in rules().iter().filter(|r| r.lang == lang) {
+            let Some(caps) = rule.re.captures(line) else { continue };
+            let Some(name) = caps.iter().skip(1).flatten().last() else { continue };
+            if NOT_SYMBOLS.contains(&name.as_str()) {
+                continue;
+            }
+            symbols.push(Symbol { name: name.as_str().to_string(), kind: rule.kind, line: i + 1 });
+            break;
+        }
+    }
+    symbols
+}
+
+/// The nearest definition starting at or above `line`.
+pub fn enclosing(symbols: &[Symbol], line: usize) -> Option<&Symbol> {
+    symbols.iter().rev().find(|s| s.line <= line)
+}
+
+/// Symbols a changed line range belongs to: the enclosing definition of its
+/// first line plus every definition that starts inside it.
+pub fn touched_by(symbols: &[Symbol], start: usize, len: usize) -> Vec<&Symbol> {
+    let end = start + len.saturating_sub(1);
+    let mut hit: Vec<&Symbol> = enclosing(symbols, start).into_iter().collect();
+    for s in symbols.iter().filter(|s| s.line > start && s.line <= end) {
+        hit.push(s);
+    }
+    hit
+}
+
+fn identifier_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[A-Za-z_$][\w$]*(?:(?:::|\.|/|-)[A-Za-z_$][\w$]*)*(?:\(\))?").unwrap())
+}
+
+/// Code-like tokens in free text: anything in backticks, plus snake_case,
+/// camelCase, `a::b`, `a.b()`, `name()` and path-like words. Plain English
+/// words are not identifiers.
+pub fn identifiers(text: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    let mut push = |s: &str| {
+        let s = s.trim_end_matches("()").trim_matches(|c: char| c == '.' || c == '-');
+        if !s.is_empty() && !out.iter().any(|o| o == s) {
+            out.push(s.to_string());
+        }
+    };
+
+    for (i, part) in text.split('`').enumerate() {
+        if i % 2 == 1 {
+            for m in identifier_re().find_iter(part) {
+                push(m.as_str());
+            }
+            continue;
+        }
+        for m in identifier_re().find_iter(part) {
+            let tok = m.as_str();
+            let camel = tok.as_bytes().windows(2).any(|w| w[0].is_ascii_lowercase() && w[1].is_ascii_uppercase());
+            if tok.contains('_') || tok.contains("::") || tok.ends_with("()") || camel
+                || (tok.contains('/') && !tok.contains(' '))
+                || (tok.contains('.')
+                    && tok.split('.').next().is_some_and(|stem| stem.len() >= 2)
+                    && tok.rsplit('.').next().is_some_and(|ext| ext.len() <= 4))
+            {
+                push(tok);
+            }
+        }
+    }
+    out
+}
+
+/// The last path segment of an identifier: `a::b::c` -> `c`, `obj.method` -> `method`.
+pub fn leaf(identifier: &str) -> &str {
+    identifier.rsplit(['.', ':', '/']).next().unwrap_or(identifier)
+}
+
+/// Lowercased words of a symbol name: `fetchUserById` and `fetch_user_by_id`
+/// both give `["fetch", "user", "by", "id"]`.
+pub fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(lang: Language, src: &str) -> Vec<(String, usize)> {
+        extract(lang, src).into_iter().map(|s| (s.name, s.line)).collect()
+    }
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(Language::from_path(Path::new("src/main.rs")), Some(Language::Rust));
+        assert_eq!(Language::from_path(Path::new("web/app.tsx")), Some(Language::JavaScript));
+        assert_eq!(Language::from_path(Path::new("tests/x.bats")), Some(Language::Shell));
+        assert_eq!(Language::from_path(Path::new("README.md")), None);
+    }
+
+    #[test]
+    fn test_rust_symbols() {
+        let src = "use x;\npub struct Client;\n\nimpl Client {\n    pub async fn fetch_user(&self) {}\n}\n\nimpl Display for Client {}\nconst MAX: u32 = 3;\n";
+        assert_eq!(
+            names(Language::Rust, src),
+            vec![
+                ("Client".into(), 2),
+                ("Client".into(), 4),
+                ("fetch_user".into(), 5),
+                ("Client".into(), 8),
+                ("MAX".into(), 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_js_go_symbols() {
+        assert_eq!(
+            names(Language::Python, "class Api:\n    async def get(self):\n        pass\n"),
+            vec![("Api".into(), 1), ("get".into(), 2)]
+        );
+        assert_eq!(
+            names(Language::JavaScript, "export const load = async (id) => {\n  if (x) {\n  }\n}\nclass A {\n  save(x) {\n  }\n}\n"),
+            vec![("load".into(), 1), ("A".into(), 5), ("save".into(), 6)]
+        );
+        assert_eq!(
+            names(Language::Go, "func (c *Client) Fetch() error {\n}\ntype Client struct {}\n"),
+            vec![("Fetch".into(), 1), ("Client".into(), 3)]
+        );
+        assert_eq!(
+            names(Language::Shell, "setup() {\n  :\n}\nfunction teardown {\n}\n"),
+            vec![("setup".into(), 1), ("teardown".into(), 4)]
+        );
+    }
+
+    #[test]
+    fn test_touched_by() {
+        let symbols = extract(Language::Rust, "fn a() {\n    1;\n}\n\nfn b() {}\nfn c() {}\n");
+        let hit: Vec<&str> = touched_by(&symbols, 2, 4).iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(hit, vec!["a", "b"]);
+        assert!(touched_by(&[], 1, 1).is_empty());
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(identifiers("add retry to fetch_user"), vec!["fetch_user"]);
+        assert_eq!(identifiers("`Client::connect` handles timeouts"), vec!["Client::connect"]);
+        assert_eq!(identifiers("loadConfig() reads src/config.rs"), vec!["loadConfig", "src/config.rs"]);
+        assert!(identifiers("Feature works end-to-end, e.g. offline").is_empty());
+    }
+
+    #[test]
+    fn test_leaf_and_words() {
+        assert_eq!(leaf("a::b::fetch"), "fetch");
+        assert_eq!(leaf("obj.save"), "save");
+        assert_eq!(name_words("fetchUserById"), vec!["fetch", "user", "by", "id"]);
+        assert_eq!(name_words("fetch_user"), vec!["fetch", "user"]);
+    }
+}
@@ -3,6 +3,8 @@ use sha2::{Sha256, Digest};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+use super::role_registry::RoleRegistry;
+
 /// Returns the cache directory path: /tmp/yolo-tier-cache-{uid}/
 fn cache_dir() -> PathBuf {
     let uid = unsafe { libc::getuid() };
@@ -65,12 +67,9 @@ fn write_cache(cache_path: &Path, content: &str, source_mtime: u64) {
 }
 
 /// Maps a role name to its role family for tier 2 content selection.
-pub fn role_family(role: &str) -> &'static str {
-    match role {
-        "architect" | "lead" | "researcher" | "reviewer" => "planning",
-        "dev" | "qa" | "debugger" => "execution",
-        _ => "default",
-    }
+/// Roles missing from the registry use the `default` family.
+pub fn role_family<'r>(registry: &'r RoleRegistry, role: &str) -> &'r str {
+    registry.get(role).map(|r| r.family.as_str()).unwrap_or("default")
 }
 
 /// Returns the deterministic list of tier 1 file basenames (shared base).
@@ -416,7 +415,8 @@ pub fn build_tiered_context(
     phases_dir: Option<&Path>,
     plan_path: Option<&Path>,
 ) -> TieredContext {
-    let family = role_family(role);
+    let registry = RoleRegistry::load(planning_dir.parent().unwrap_or(Path::new(".")));
+    let family = role_family(&registry, role);
     let tier1 = build_tier1(planning_dir);
     let tier2 = build_tier2(planning_dir, family);
     let tier3 = build_tier3_volatile(phase, phases_dir, plan_path);
@@ -467,15 +467,15 @@ mod tests {
 
     #[test]
     fn test_role_family_known_roles() {
-        assert_eq!(role_family("architect"), "planning");
-        assert_eq!(role_family("lead"), "planning");
-        assert_eq!(role_family("dev"), "execution");
-        assert_eq!(role_family("qa"), "execution");
-        assert_eq!(role_family("debugger"), "execution");
-        assert_eq!(role_family("senior"), "default");
-        assert_eq!(role_family("security"), "default");
-        assert_eq!(role_family("unknown"), "default");
-        assert_eq!(role_family(""), "default");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "architect"), "planning");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "lead"), "planning");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "dev"), "execution");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "qa"), "execution");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "debugger"), "execution");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "senior"), "default");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "security"), "default");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "unknown"), "default");
+        assert_eq!(role_family(&RoleRegistry::builtin(), ""), "default");
     }
 
     #[test]
@@ -519,13 +519,13 @@ mod tests {
         let planning = tmp.path().join(".yolo-planning");
 
         // dev and qa are both "execution" family
-        let t2_dev = build_tier2(&planning, role_family("dev"));
-        let t2_qa = build_tier2(&planning, role_family("qa"));
+        let t2_dev = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "dev"));
+        let t2_qa = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "qa"));
         assert_eq!(t2_dev, t2_qa);
 
         // lead and architect are both "planning" family
-        let t2_lead = build_tier2(&planning, role_family("lead"));
-        let t2_arch = build_tier2(&planning, role_family("architect"));
+        let t2_lead = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "lead"));
+        let t2_arch = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "architect"));
         assert_eq!(t2_lead, t2_arch);
     }
 
@@ -534,8 +534,8 @@ mod tests {
         let tmp = setup_planning_dir();
         let planning = tmp.path().join(".yolo-planning");
 
-        let t2_dev = build_tier2(&planning, role_family("dev"));
-        let t2_lead = build_tier2(&planning, role_family("lead"));
+        let t2_dev = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "dev"));
+        let t2_lead = build_tier2(&planning, role_family(&RoleRegistry::builtin(), "lead"));
 
         // Different families must produce different tier 2 content
         assert_ne!(t2_dev, t2_lead);
@@ -987,12 +987,12 @@ mod tests {
 
     #[test]
     fn test_researcher_is_planning_family() {
-        assert_eq!(role_family("researcher"), "planning");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "researcher"), "planning");
     }
 
     #[test]
     fn test_reviewer_is_planning_family() {
-        assert_eq!(role_family("reviewer"), "planning");
+        assert_eq!(role_family(&RoleRegistry::builtin(), "reviewer"), "planning");
     }
 
     #[test]
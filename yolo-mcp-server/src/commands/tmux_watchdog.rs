@@ -1,8 +1,8 @@
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 use std::process::Command;
-use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::event_bus;
 
 /// Spawn the tmux watchdog as a background thread.
 /// The watchdog polls for detached tmux clients and terminates orphaned agents.
@@ -72,9 +72,7 @@ pub fn get_tmux_session() -> Option<String> {
 }
 
 fn run_watchdog_loop(planning_dir: &Path, session: &str) {
-    let log_path = planning_dir.join(".watchdog.log");
-
-    log_msg(&log_path, &format!("Watchdog started for session: {session} (PID={})", std::process::id()));
+    log_msg(planning_dir, &format!("Watchdog started for session: {session} (PID={})", std::process::id()));
 
     let mut consecutive_empty = 0u32;
 
@@ -87,7 +85,7 @@ fn run_watchdog_loop(planning_dir: &Path, session: &str) {
             .unwrap_or(false);
 
         if !has_session {
-            log_msg(&log_path, &format!("Session {session} no longer exists, exiting"));
+            log_msg(planning_dir, &format!("Session {session} no longer exists, exiting"));
             break;
         }
 
@@ -104,17 +102,17 @@ fn run_watchdog_loop(planning_dir: &Path, session: &str) {
 
         if client_count == 0 {
             consecutive_empty += 1;
-            log_msg(&log_path, &format!("No clients attached (consecutive: {consecutive_empty})"));
+            log_msg(planning_dir, &format!("No clients attached (consecutive: {consecutive_empty})"));
 
             if consecutive_empty >= 2 {
-                log_msg(&log_path, "Session detached (2 consecutive polls), cleaning up agents");
-                cleanup_agents(planning_dir, &log_path);
-                log_msg(&log_path, "Watchdog exiting");
+                log_msg(planning_dir, "Session detached (2 consecutive polls), cleaning up agents");
+                cleanup_agents(planning_dir);
+                log_msg(planning_dir, "Watchdog exiting");
                 break;
             }
         } else {
             if consecutive_empty > 0 {
-                log_msg(&log_path, "Client attached, resetting empty counter");
+                log_msg(planning_dir, "Client attached, resetting empty counter");
             }
             consecutive_empty = 0;
         }
@@ -126,18 +124,18 @@ fn run_watchdog_loop(planning_dir: &Path, session: &str) {
     let _ = fs::remove_file(planning_dir.join(".watchdog-pid"));
 }
 
-fn cleanup_agents(planning_dir: &Path, log_path: &Path) {
+fn cleanup_agents(planning_dir: &Path) {
     let pid_file = planning_dir.join(".agent-pids");
 
     if !pid_file.exists() {
-        log_msg(log_path, "No active agent PIDs to terminate");
+        log_msg(planning_dir, "No active agent PIDs to terminate");
         return;
     }
 
     let content = match fs::read_to_string(&pid_file) {
         Ok(c) => c,
         Err(_) => {
-            log_msg(log_path, "Failed to read .agent-pids");
+            log_msg(planning_dir, "Failed to read .agent-pids");
             return;
         }
     };
@@ -148,14 +146,14 @@ fn cleanup_agents(planning_dir: &Path, log_path: &Path) {
         .collect();
 
     if pids.is_empty() {
-        log_msg(log_path, "No active agent PIDs to terminate");
+        log_msg(planning_dir, "No active agent PIDs to terminate");
         return;
     }
 
     // SIGTERM pass
     for pid in &pids {
         if signal_alive(*pid) {
-            log_msg(log_path, &format!("Sending SIGTERM to agent PID {pid}"));
+            log_msg(planning_dir, &format!("Sending SIGTERM to agent PID {pid}"));
             let _ = unsafe { libc::kill(*pid as i32, libc::SIGTERM) };
         }
     }
@@ -166,15 +164,15 @@ fn cleanup_agents(planning_dir: &Path, log_path: &Path) {
     // SIGKILL fallback
     for pid in &pids {
         if signal_alive(*pid) {
-            log_msg(log_path, &format!("Agent PID {pid} survived SIGTERM, sending SIGKILL"));
+            log_msg(planning_dir, &format!("Agent PID {pid} survived SIGTERM, sending SIGKILL"));
             let _ = unsafe { libc::kill(*pid as i32, libc::SIGKILL) };
         }
     }
 
     // Clean up PID file
     let _ = fs::remove_file(&pid_file);
-    log_msg(log_path, "Removed .agent-pids file");
-    log_msg(log_path, "Agent cleanup complete");
+    log_msg(planning_dir, "Removed .agent-pids file");
+    log_msg(planning_dir, "Agent cleanup complete");
 }
 
 /// Check if a process is still alive via kill(pid, 0).
@@ -182,18 +180,9 @@ fn signal_alive(pid: u32) -> bool {
     unsafe { libc::kill(pid as i32, 0) == 0 }
 }
 
-fn log_msg(log_path: &Path, msg: &str) {
-    let ts = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(log_path)
-    {
-        let _ = writeln!(f, "[{ts}] {msg}");
-    }
+/// Watchdog progress goes to the event bus as `hook_message` events.
+fn log_msg(planning_dir: &Path, msg: &str) {
+    event_bus::publish_message(planning_dir, "tmux_watchdog", msg);
 }
 
 #[cfg(test)]
@@ -216,9 +205,8 @@ mod tests {
     #[test]
     fn test_cleanup_agents_no_pid_file() {
         let dir = tempdir().unwrap();
-        let log = dir.path().join("watchdog.log");
-        cleanup_agents(dir.path(), &log);
-        let content = fs::read_to_string(&log).unwrap();
+        cleanup_agents(dir.path());
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("No active agent PIDs to terminate"));
     }
 
@@ -226,9 +214,8 @@ mod tests {
     fn test_cleanup_agents_empty_pid_file() {
         let dir = tempdir().unwrap();
         fs::write(dir.path().join(".agent-pids"), "").unwrap();
-        let log = dir.path().join("watchdog.log");
-        cleanup_agents(dir.path(), &log);
-        let content = fs::read_to_string(&log).unwrap();
+        cleanup_agents(dir.path());
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("No active agent PIDs to terminate"));
     }
 
@@ -237,11 +224,10 @@ mod tests {
         let dir = tempdir().unwrap();
         // Use PIDs that definitely don't exist
         fs::write(dir.path().join(".agent-pids"), "4000001\n4000002\n").unwrap();
-        let log = dir.path().join("watchdog.log");
-        cleanup_agents(dir.path(), &log);
+        cleanup_agents(dir.path());
         // Should have cleaned up the file
         assert!(!dir.path().join(".agent-pids").exists());
-        let content = fs::read_to_string(&log).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("Agent cleanup complete"));
     }
 
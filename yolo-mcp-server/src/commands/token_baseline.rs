@@ -3,6 +3,8 @@ use std::fs;
 use chrono::Utc;
 use serde_json::{Value, json, Map};
 
+use super::event_bus;
+
 fn load_jsonl(path: &Path) -> Vec<Value> {
     let mut res = Vec::new();
     if let Ok(content) = fs::read_to_string(path) {
@@ -18,10 +20,9 @@ fn load_jsonl(path: &Path) -> Vec<Value> {
 fn build_measurement(cwd: &Path, phase_filter: Option<&str>) -> Value {
     let planning_dir = cwd.join(".yolo-planning");
     let metrics_file = planning_dir.join(".metrics").join("run-metrics.jsonl");
-    let events_file = planning_dir.join(".events").join("event-log.jsonl");
     let budgets_path = cwd.join("config").join("token-budgets.json");
 
-    let events: Vec<Value> = load_jsonl(&events_file);
+    let events: Vec<Value> = event_bus::load_events(&planning_dir);
     let metrics: Vec<Value> = load_jsonl(&metrics_file);
 
     let ts = Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
@@ -310,7 +311,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
 
     let planning_dir = cwd.join(".yolo-planning");
     let metrics_file = planning_dir.join(".metrics").join("run-metrics.jsonl");
-    let events_file = planning_dir.join(".events").join("event-log.jsonl");
+    let events_file = event_bus::log_path(&planning_dir);
 
     if !metrics_file.exists() && !events_file.exists() {
         return Ok(("No event data found. Enable v3_event_log=true and v3_metrics=true in config.\n".to_string(), 0));
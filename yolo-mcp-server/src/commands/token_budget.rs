@@ -2,6 +2,7 @@ use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
 
+use super::feature_flags::{self, FeatureFlag};
 use super::{log_event, collect_metrics};
 
 /// Default budgets per role (chars) when no config/token-budgets.json exists.
@@ -23,18 +24,6 @@ fn load_budgets(cwd: &Path) -> Value {
     })
 }
 
-/// Check if v2_token_budgets is enabled in config.json.
-fn is_enabled(cwd: &Path) -> bool {
-    let config_path = cwd.join(".yolo-planning").join("config.json");
-    if config_path.exists()
-        && let Ok(content) = fs::read_to_string(&config_path)
-        && let Ok(config) = serde_json::from_str::<Value>(&content)
-    {
-        return config.get("v2_token_budgets").and_then(|v| v.as_bool()).unwrap_or(false);
-    }
-    false
-}
-
 /// Get the budget for a role. Checks per-task budget from contract metadata first,
 /// then falls back to per-role budget from token-budgets.json, then DEFAULT_BUDGET.
 fn resolve_budget(role: &str, budgets_config: &Value, contract: Option<&Value>) -> u64 {
@@ -122,7 +111,7 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         return Err("Usage: yolo token-budget <role> [file] [--contract=<path>]".to_string());
     }
 
-    if !is_enabled(cwd) {
+    if !feature_flags::is_enabled(FeatureFlag::V2TokenBudgets, cwd) {
         return Ok((json!({"result": "skip", "reason": "v2_token_budgets=false"}).to_string(), 0));
     }
 
@@ -5,6 +5,10 @@ use std::process::Command;
 
 use serde_json::{json, Value};
 
+use super::cost_forecast::{self, Forecast};
+use super::cost_history::{self, Attribution, Usage};
+use super::event_bus;
+
 // ANSI color constants (matching statusline.rs)
 const C_RESET: &str = "\x1b[0m";
 const C_DIM: &str = "\x1b[2m";
@@ -33,18 +37,26 @@ impl AgentTokenStats {
 struct Flags {
     phase_filter: Option<String>,
     json_output: bool,
+    forecast: bool,
+    cost: bool,
 }
 
 fn parse_flags(args: &[String]) -> Flags {
     let mut flags = Flags {
         phase_filter: None,
         json_output: false,
+        forecast: false,
+        cost: false,
     };
     for arg in args {
         if let Some(rest) = arg.strip_prefix("--phase=") {
             flags.phase_filter = Some(rest.to_string());
         } else if arg == "--json" {
             flags.json_output = true;
+        } else if arg == "--forecast" {
+            flags.forecast = true;
+        } else if arg == "--cost" {
+            flags.cost = true;
         }
     }
     flags
@@ -515,12 +527,216 @@ fn build_json_output(
     serde_json::to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
 }
 
+fn fmt_usd(v: f64) -> String {
+    format!("${:.2}", v)
+}
+
+fn render_forecast(forecast: &Forecast) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "\n{}{}[YOLO]{} Cost Forecast{}\n",
+        C_CYAN,
+        C_BOLD,
+        C_RESET,
+        forecast.milestone.as_ref().map(|m| format!(" -- {}", m)).unwrap_or_default()
+    ));
+    out.push_str(&format!(
+        "{}================================================================{}\n",
+        C_DIM, C_RESET
+    ));
+    out.push_str(&format!(
+        "{}Based on {} plan(s) of history, priced for the {} profile (pricing table {}){}\n\n",
+        C_DIM,
+        forecast.history_plans,
+        forecast.profile,
+        forecast.pricing_updated.as_deref().unwrap_or("undated"),
+        C_RESET
+    ));
+
+    out.push_str(&format!(
+        "{}{:<8} {:>9} {:>10} {:>10} {:>10} {:>11} {:>10}{}\n",
+        C_BOLD, "Phase", "Remaining", "Spent", "Tokens", "Forecast", "Projected", "Budget", C_RESET
+    ));
+    out.push_str(&format!(
+        "{}----------------------------------------------------------------{}\n",
+        C_DIM, C_RESET
+    ));
+    for p in &forecast.phases {
+        let projected = p.spent.usd + p.remaining.usd;
+        let over = p.remaining_plans > 0 && p.budget_usd.is_some_and(|b| projected > b);
+        let (color, flag) = if over { (C_RED, " OVER") } else { ("", "") };
+        out.push_str(&format!(
+            "{:<8} {:>9} {:>10} {:>10} {:>10} {}{:>11}{} {:>10}{}{}{}\n",
+            p.phase,
+            p.remaining_plans,
+            fmt_usd(p.spent.usd),
+            fmt_tok(p.remaining.tokens()),
+            fmt_usd(p.remaining.usd),
+            color,
+            fmt_usd(projected),
+            C_RESET,
+            p.budget_usd.map(fmt_usd).unwrap_or_else(|| "-".to_string()),
+            C_RED,
+            flag,
+            C_RESET
+        ));
+    }
+    out.push_str(&format!(
+        "{}----------------------------------------------------------------{}\n",
+        C_DIM, C_RESET
+    ));
+    out.push_str(&format!(
+        "{}Milestone: {} spent + {} forecast = {} projected{}{}\n",
+        C_BOLD,
+        fmt_usd(forecast.spent_usd()),
+        fmt_usd(forecast.remaining_usd()),
+        fmt_usd(forecast.projected_usd()),
+        forecast.milestone_budget_usd.map(|b| format!(" (budget {})", fmt_usd(b))).unwrap_or_default(),
+        C_RESET
+    ));
+
+    if !forecast.by_role.is_empty() {
+        out.push_str(&format!("\n{}Remaining spend by role{}\n", C_BOLD, C_RESET));
+        for (role, usage) in &forecast.by_role {
+            out.push_str(&format!(
+                "  {:<12} {:>10} {:>10}\n",
+                role,
+                fmt_tok(usage.tokens()),
+                fmt_usd(usage.usd)
+            ));
+        }
+    }
+
+    let alerts = forecast.alerts();
+    out.push('\n');
+    if alerts.is_empty() {
+        out.push_str(&format!("{}Projected spend is within budget.{}\n", C_GREEN, C_RESET));
+    }
+    for alert in &alerts {
+        let what = match &alert.phase {
+            Some(phase) => format!("Phase {}", phase),
+            None => "Milestone".to_string(),
+        };
+        out.push_str(&format!(
+            "{}{} projected at {}, over its {} budget{}\n",
+            C_YELLOW,
+            what,
+            fmt_usd(alert.projected_usd),
+            fmt_usd(alert.budget_usd),
+            C_RESET
+        ));
+    }
+    out.push_str(&format!(
+        "{}================================================================{}\n",
+        C_DIM, C_RESET
+    ));
+    out
+}
+
+fn render_cost_table(out: &mut String, title: &str, label: &str, rows: &std::collections::BTreeMap<String, Usage>, total: f64) {
+    out.push_str(&format!("{}{}{}{}\n", C_CYAN, C_BOLD, title, C_RESET));
+    out.push_str(&format!(
+        "{}{:<20} {:>10} {:>10} {:>7}{}\n",
+        C_BOLD, label, "Tokens", "Cost", "Share", C_RESET
+    ));
+    let mut sorted: Vec<_> = rows.iter().collect();
+    sorted.sort_by(|a, b| b.1.usd.partial_cmp(&a.1.usd).unwrap_or(std::cmp::Ordering::Equal));
+    for (name, usage) in sorted {
+        let share = if total > 0.0 { usage.usd / total * 100.0 } else { 0.0 };
+        out.push_str(&format!(
+            "{:<20} {:>10} {:>10} {:>6.1}%\n",
+            name,
+            fmt_tok(usage.tokens()),
+            fmt_usd(usage.usd),
+            share
+        ));
+    }
+    out.push('\n');
+}
+
+fn render_attribution(a: &Attribution) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("\n{}{}[YOLO]{} Cost Attribution\n", C_CYAN, C_BOLD, C_RESET));
+    out.push_str(&format!(
+        "{}================================================================{}\n",
+        C_DIM, C_RESET
+    ));
+    out.push_str(&format!(
+        "Total: {}{}{} over {} tokens {}(pricing table {}){}\n",
+        C_BOLD,
+        fmt_usd(a.total.usd),
+        C_RESET,
+        fmt_tok(a.total.tokens()),
+        C_DIM,
+        a.pricing_updated.as_deref().unwrap_or("undated"),
+        C_RESET
+    ));
+    if a.unpriced_tokens > 0 {
+        out.push_str(&format!(
+            "{}{} tokens are on models missing from the pricing table (add them with yolo pricing set){}\n",
+            C_YELLOW,
+            fmt_tok(a.unpriced_tokens),
+            C_RESET
+        ));
+    }
+    out.push('\n');
+
+    let total = a.total.usd;
+    render_cost_table(&mut out, "By role", "Role", &a.by_role, total);
+    render_cost_table(&mut out, "By phase", "Phase", &a.by_phase, total);
+    render_cost_table(&mut out, "By plan", "Plan", &a.by_plan, total);
+    render_cost_table(&mut out, "By model", "Model", &a.by_model, total);
+    render_cost_table(&mut out, "By model profile", "Profile", &a.by_profile, total);
+
+    out.push_str(&format!("{}{}Profile comparison{}\n", C_CYAN, C_BOLD, C_RESET));
+    out.push_str(&format!("{}The same history with every role on each profile's model{}\n", C_DIM, C_RESET));
+    let active = a.profile_totals.get(&a.active_profile).copied();
+    for (profile, usd) in &a.profile_totals {
+        let marker = if *profile == a.active_profile { " (active)".to_string() } else { String::new() };
+        let delta = match active {
+            Some(base) if base > 0.0 && *profile != a.active_profile => {
+                let pct = (usd - base) / base * 100.0;
+                let color = if pct <= 0.0 { C_GREEN } else { C_RED };
+                format!("  {}{:+.1}%{}", color, pct, C_RESET)
+            }
+            _ => String::new(),
+        };
+        out.push_str(&format!("  {:<12} {:>10}{}{}\n", profile, fmt_usd(*usd), delta, marker));
+    }
+    out.push_str(&format!(
+        "{}================================================================{}\n",
+        C_DIM, C_RESET
+    ));
+    out
+}
+
 pub fn execute(args: &[String], cwd: &Path, _db_path: &Path) -> Result<(String, i32), String> {
     let flags = parse_flags(args);
 
+    if flags.cost {
+        let attribution = cost_history::attribute(cwd);
+        let output = if flags.json_output {
+            serde_json::to_string_pretty(&attribution.to_json()).unwrap_or_else(|_| "{}".to_string())
+        } else {
+            render_attribution(&attribution)
+        };
+        return Ok((output, 0));
+    }
+
+    if flags.forecast {
+        let forecast = cost_forecast::run(cwd);
+        let output = if flags.json_output {
+            serde_json::to_string_pretty(&forecast.to_json()).unwrap_or_else(|_| "{}".to_string())
+        } else {
+            render_forecast(&forecast)
+        };
+        return Ok((output, 0));
+    }
+
     let planning_dir = cwd.join(".yolo-planning");
     let metrics_file = planning_dir.join(".metrics").join("run-metrics.jsonl");
-    let events_file = planning_dir.join(".events").join("event-log.jsonl");
+    let events_file = event_bus::log_path(&planning_dir);
 
     if !metrics_file.exists() && !events_file.exists() {
         let msg = format!(
@@ -532,7 +748,7 @@ pub fn execute(args: &[String], cwd: &Path, _db_path: &Path) -> Result<(String,
     }
 
     let metrics = load_jsonl(&metrics_file);
-    let events = load_jsonl(&events_file);
+    let events = event_bus::load_events(&planning_dir);
 
     let stats = build_agent_stats(&metrics, flags.phase_filter.as_deref());
 
@@ -635,6 +851,58 @@ mod tests {
         assert!(output.contains("ROI Metrics"), "Missing ROI section");
     }
 
+    #[test]
+    fn test_forecast_flag_renders_projection() {
+        let dir = setup_test_dir();
+        let db_path = dir.path().join("test.db");
+        let phase = dir.path().join(".yolo-planning/phases/01-core");
+        fs::create_dir_all(&phase).unwrap();
+        fs::write(phase.join("01-PLAN.md"), "---\nphase: 1\nplan: 1\n---\n").unwrap();
+        fs::write(phase.join("01-01-SUMMARY.md"), "done").unwrap();
+        fs::write(phase.join("02-PLAN.md"), "---\nphase: 1\nplan: 2\n---\n").unwrap();
+        write_test_metrics(
+            dir.path(),
+            r#"{"event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000,"output_tokens":0}}"#,
+        );
+
+        let args: Vec<String> = vec!["yolo".into(), "report-tokens".into(), "--forecast".into()];
+        let (output, code) = execute(&args, dir.path(), &db_path).unwrap();
+        assert_eq!(code, 0);
+        assert!(output.contains("Cost Forecast"));
+        // dev on opus under the default quality profile: 1M input is $5.
+        assert!(output.contains("$5.00 spent + $5.00 forecast = $10.00 projected"), "got: {}", output);
+
+        let args: Vec<String> = vec!["yolo".into(), "report-tokens".into(), "--forecast".into(), "--json".into()];
+        let (output, _) = execute(&args, dir.path(), &db_path).unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["phases"][0]["remaining_plans"], 1);
+        assert_eq!(parsed["milestone_total"]["projected_usd"], 10.0);
+    }
+
+    #[test]
+    fn test_cost_flag_attributes_and_compares_profiles() {
+        let dir = setup_test_dir();
+        let db_path = dir.path().join("test.db");
+        write_test_metrics(
+            dir.path(),
+            r#"{"event":"agent_token_usage","phase":1,"plan":1,"data":{"role":"dev","input_tokens":1000000,"output_tokens":0}}"#,
+        );
+
+        let args: Vec<String> = vec!["yolo".into(), "report-tokens".into(), "--cost".into()];
+        let (output, code) = execute(&args, dir.path(), &db_path).unwrap();
+        assert_eq!(code, 0);
+        for section in ["By role", "By phase", "By plan", "By model", "By model profile", "Profile comparison"] {
+            assert!(output.contains(section), "missing {}", section);
+        }
+        assert!(output.contains("claude-opus-4-6"));
+
+        let args: Vec<String> = vec!["yolo".into(), "report-tokens".into(), "--cost".into(), "--json".into()];
+        let (output, _) = execute(&args, dir.path(), &db_path).unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["by_role"]["dev"]["usd"], 5.0);
+        assert_eq!(parsed["profile_comparison"]["totals_usd"]["balanced"], 3.0);
+    }
+
     #[test]
     fn test_cache_hit_rate_calculation() {
         // 3000 read / (3000 read + 500 write + 500 input) = 3000/4000 = 75%
@@ -1,10 +1,21 @@
+use super::domain_types::ResourceId;
 use super::feature_flags::{self, FeatureFlag};
+use super::journal::Transaction;
+use super::layered_config::Config;
+use super::lease_lock;
 use super::log_event;
+use super::must_have_evidence::{Evidence, Verifier};
+use super::utils;
 use chrono::Utc;
 use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
 use std::process::Command;
+use std::time::Duration;
+
+/// TTL of the lease held while updating `.execution-state.json`; long enough
+/// for one read/stage/commit, short enough that a crashed holder soon lapses.
+const STATE_LEASE_TTL_SECS: u64 = 30;
 
 /// Parse key=value pairs from evidence args.
 fn parse_evidence_args(args: &[String]) -> (Vec<String>, Vec<(String, String)>) {
@@ -24,9 +35,83 @@ fn parse_evidence_args(args: &[String]) -> (Vec<String>, Vec<(String, String)>)
     (evidence_parts, kv_pairs)
 }
 
+/// Per-command timeout for verification checks and evidence commands,
+/// from `command_timeout_ms` in the layered config.
+fn command_timeout(cwd: &Path) -> Duration {
+    Duration::from_millis(Config::load(cwd).settings.command_timeout_ms)
+}
+
+/// Group `evidence.N=kind:value` pairs by 1-based must-have index.
+/// Malformed specs become per-must-have reasons; bad indices become errors.
+fn collect_evidence(
+    kv_pairs: &[(String, String)],
+    must_have_count: usize,
+    errors: &mut Vec<String>,
+) -> (Vec<Vec<Evidence>>, Vec<Vec<String>>) {
+    let mut evidence = vec![Vec::new(); must_have_count];
+    let mut parse_errors = vec![Vec::new(); must_have_count];
+    for (key, value) in kv_pairs {
+        let Some(idx) = key.strip_prefix("evidence.") else { continue };
+        match idx.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= must_have_count => match Evidence::parse(value) {
+                Ok(ev) => evidence[n - 1].push(ev),
+                Err(e) => parse_errors[n - 1].push(e),
+            },
+            _ => errors.push(format!(
+                "{} does not name a must_have (contract has {})",
+                key, must_have_count
+            )),
+        }
+    }
+    (evidence, parse_errors)
+}
+
+/// Mark `task_id` complete in `.execution-state.json` through a journaled
+/// transaction, adding the task entry if the state does not track it yet.
+/// Parallel Dev agents confirm tasks concurrently, so the read/stage/commit
+/// runs under a lease on the state file.
+fn record_confirmed(task_id: &str, plan: &str, cwd: &Path) -> Result<(), String> {
+    let planning_dir = cwd.join(".yolo-planning");
+    let state_file = planning_dir.join(".execution-state.json");
+    if !state_file.exists() {
+        return Ok(());
+    }
+    let owner = format!("two-phase-complete:{}:{}", task_id, std::process::id());
+    lease_lock::with_lease(
+        &ResourceId::new(".execution-state.json"),
+        &owner,
+        STATE_LEASE_TTL_SECS,
+        command_timeout(cwd),
+        cwd,
+        || write_confirmed(task_id, plan, &planning_dir, &state_file),
+    )?
+}
+
+fn write_confirmed(task_id: &str, plan: &str, planning_dir: &Path, state_file: &Path) -> Result<(), String> {
+    let mut tx = Transaction::begin(planning_dir);
+    let content = tx.read_to_string(state_file).map_err(|e| format!("Failed to read execution state: {}", e))?;
+    let Ok(mut state) = serde_json::from_str::<Value>(&content) else {
+        return Ok(());
+    };
+    let Some(obj) = state.as_object_mut() else {
+        return Ok(());
+    };
+    let tasks = obj.entry("tasks").or_insert_with(|| json!([]));
+    let Some(tasks) = tasks.as_array_mut() else {
+        return Ok(());
+    };
+    match tasks.iter_mut().find(|t| t["id"].as_str() == Some(task_id)) {
+        Some(task) => task["status"] = json!("complete"),
+        None => tasks.push(json!({"id": task_id, "plan": plan, "status": "complete"})),
+    }
+    tx.stage(state_file, serde_json::to_string_pretty(&state).unwrap_or_default());
+    tx.commit().map(|_| ()).map_err(|e| format!("Failed to commit execution state: {}", e))
+}
+
 /// Run the two-phase completion protocol.
 /// Phase 1: Emit candidate event
-/// Phase 2: Validate must_haves + files against contract
+/// Phase 2: Verify must_haves have machine-checkable evidence, check files
+///          against allowed_paths, run verification_checks (with timeout)
 /// Phase 3: Emit confirmed/rejected event
 pub fn complete(
     task_id: &str,
@@ -47,7 +132,8 @@ pub fn complete(
         .collect();
 
     // Validate evidence
-    if evidence.is_empty() && evidence_parts.is_empty() {
+    let has_structured = kv_pairs.iter().any(|(k, _)| k.starts_with("evidence."));
+    if evidence.is_empty() && !has_structured {
         let result = json!({
             "task_id": task_id,
             "result": "rejected",
@@ -90,19 +176,45 @@ pub fn complete(
     // Phase 2: Validate
     let mut errors = Vec::new();
 
-    // must_haves are documented requirements — the agent self-reports evidence.
-    // We verify evidence is non-empty (done above). The must_haves check is soft.
-    let _must_haves: Vec<&str> = contract.get("must_haves")
+    let timeout = command_timeout(cwd);
+
+    let must_haves: Vec<&str> = contract.get("must_haves")
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
         .unwrap_or_default();
 
-    // Check files_modified against allowed_paths
     let allowed_paths: Vec<&str> = contract.get("allowed_paths")
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
         .unwrap_or_default();
 
+    // Each must_have needs machine-checkable evidence passed as evidence.N pairs.
+    let (evidence_by_must_have, parse_errors) = collect_evidence(&kv_pairs, must_haves.len(), &mut errors);
+    let scope: Vec<String> = allowed_paths.iter().map(|p| p.to_string())
+        .chain(files_modified.iter().cloned())
+        .collect();
+    let verifier = Verifier::new(cwd, scope, timeout);
+    let mut must_have_results = Vec::new();
+    let mut must_haves_verified = 0;
+
+    for (i, must_have) in must_haves.iter().enumerate() {
+        let mut verdict = verifier.verify(must_have, &evidence_by_must_have[i]);
+        verdict.reasons.splice(0..0, parse_errors[i].iter().cloned());
+        if verdict.verified {
+            must_haves_verified += 1;
+        } else {
+            errors.push(format!("must_have {} ({:?}) unverified: {}", i + 1, must_have, verdict.reasons.join("; ")));
+        }
+        must_have_results.push(json!({
+            "must_have": verdict.must_have,
+            "status": if verdict.verified { "verified" } else { "unverified" },
+            "evidence": verdict.evidence,
+            "reasons": verdict.reasons,
+        }));
+    }
+
+    // Check files_modified against allowed_paths
+
     if !allowed_paths.is_empty() {
         for file in &files_modified {
             let is_allowed = allowed_paths.iter().any(|allowed| {
@@ -124,20 +236,21 @@ pub fn complete(
     let mut checks_failed = 0;
 
     for check in &verification_checks {
-        let output = Command::new("sh")
-            .arg("-c")
-            .arg(check)
-            .current_dir(cwd)
-            .output();
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(check).current_dir(cwd);
 
-        match output {
+        match utils::run_with_timeout(&mut cmd, timeout) {
             Ok(o) if o.status.success() => {
                 checks_passed += 1;
             }
-            _ => {
+            Ok(_) => {
                 checks_failed += 1;
                 errors.push(format!("verification check failed: {}", check));
             }
+            Err(e) => {
+                checks_failed += 1;
+                errors.push(format!("verification check {}: {}", e, check));
+            }
         }
     }
 
@@ -145,10 +258,12 @@ pub fn complete(
     let ts = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
     if errors.is_empty() {
+        record_confirmed(task_id, plan, cwd)?;
         let confirmed_data = vec![
             ("task_id".to_string(), task_id.to_string()),
             ("evidence".to_string(), evidence),
             ("checks_passed".to_string(), checks_passed.to_string()),
+            ("must_haves_verified".to_string(), must_haves_verified.to_string()),
         ];
         let _ = log_event::log("task_completed_confirmed", phase, Some(plan), &confirmed_data, cwd);
 
@@ -157,6 +272,7 @@ pub fn complete(
             "result": "confirmed",
             "checks_passed": checks_passed,
             "checks_failed": checks_failed,
+            "must_haves": must_have_results,
             "ts": ts,
         });
         Ok((result.to_string(), 0))
@@ -174,6 +290,7 @@ pub fn complete(
             "errors": errors,
             "checks_passed": checks_passed,
             "checks_failed": checks_failed,
+            "must_haves": must_have_results,
             "ts": ts,
         });
         Ok((result.to_string(), 2))
@@ -181,6 +298,9 @@ pub fn complete(
 }
 
 /// CLI entry point: `yolo two-phase-complete <task_id> <phase> <plan> <contract_path> [evidence...]`
+///
+/// Evidence for the Nth must_have is passed as `evidence.N=<kind>:<value>`, where
+/// kind is `test`, `commit`, `file`, `symbol` (`path#name` or `name`) or `cmd`.
 pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     // args[0] = "yolo", args[1] = "two-phase-complete"
     // args[2] = task_id, args[3] = phase, args[4] = plan, args[5] = contract_path
@@ -283,7 +403,7 @@ mod tests {
     fn test_confirmed_when_checks_pass() {
         let dir = setup_test_env(true);
         let contract_path = create_passing_contract(&dir);
-        let evidence = vec!["all tests pass".to_string()];
+        let evidence = vec!["all tests pass".to_string(), "evidence.1=cmd:true".to_string()];
         let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
         assert_eq!(code, 0);
         let result: Value = serde_json::from_str(&out).unwrap();
@@ -307,7 +427,7 @@ mod tests {
     fn test_emits_candidate_and_confirmed_events() {
         let dir = setup_test_env(true);
         let contract_path = create_passing_contract(&dir);
-        let evidence = vec!["evidence".to_string()];
+        let evidence = vec!["evidence".to_string(), "evidence.1=cmd:true".to_string()];
         complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
 
         let events_file = dir.path().join(".yolo-planning/.events/event-log.jsonl");
@@ -316,6 +436,40 @@ mod tests {
         assert!(content.contains("task_completed_confirmed"));
     }
 
+    #[test]
+    fn test_confirmed_marks_task_complete_in_execution_state() {
+        let dir = setup_test_env(true);
+        let state_file = dir.path().join(".yolo-planning/.execution-state.json");
+        fs::write(&state_file, r#"{"phase": 1, "tasks": [{"id": "1-1-T1", "status": "running"}]}"#).unwrap();
+        let contract_path = create_passing_contract(&dir);
+        let evidence = vec!["evidence".to_string(), "evidence.1=cmd:true".to_string()];
+        complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+        complete("1-1-T2", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+
+        let state: Value = serde_json::from_str(&fs::read_to_string(&state_file).unwrap()).unwrap();
+        assert_eq!(state["tasks"][0]["status"], "complete");
+        assert_eq!(state["tasks"][1], json!({"id": "1-1-T2", "plan": "1", "status": "complete"}));
+        assert!(!dir.path().join(".yolo-planning/.journal").read_dir().unwrap().any(|_| true));
+    }
+
+    #[test]
+    fn test_concurrent_confirmations_keep_every_task() {
+        let dir = setup_test_env(true);
+        let state_file = dir.path().join(".yolo-planning/.execution-state.json");
+        fs::write(&state_file, r#"{"phase": 1, "tasks": []}"#).unwrap();
+
+        std::thread::scope(|s| {
+            for i in 1..=8 {
+                let cwd = dir.path();
+                s.spawn(move || record_confirmed(&format!("1-1-T{}", i), "1", cwd).unwrap());
+            }
+        });
+
+        let state: Value = serde_json::from_str(&fs::read_to_string(&state_file).unwrap()).unwrap();
+        assert_eq!(state["tasks"].as_array().unwrap().len(), 8);
+        assert!(!dir.path().join(".yolo-planning/.locks/.execution-state.json.lease").exists());
+    }
+
     #[test]
     fn test_emits_rejection_event() {
         let dir = setup_test_env(true);
@@ -368,7 +522,11 @@ mod tests {
     fn test_files_within_allowed_paths_confirmed() {
         let dir = setup_test_env(true);
         let contract_path = create_passing_contract(&dir);
-        let evidence = vec!["files_modified=src/a.js".to_string(), "feature works".to_string()];
+        let evidence = vec![
+            "files_modified=src/a.js".to_string(),
+            "feature works".to_string(),
+            "evidence.1=cmd:true".to_string(),
+        ];
         let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
         assert_eq!(code, 0);
         let result: Value = serde_json::from_str(&out).unwrap();
@@ -381,4 +539,69 @@ mod tests {
         let args = vec!["yolo".into(), "two-phase-complete".into()];
         assert!(execute(&args, dir.path()).is_err());
     }
+
+    fn write_contract(dir: &TempDir, must_haves: &[&str], checks: &[&str]) -> String {
+        let contract = json!({
+            "plan_id": "1-1",
+            "allowed_paths": ["src"],
+            "must_haves": must_haves,
+            "verification_checks": checks,
+        });
+        let path = dir.path().join(".yolo-planning/.contracts/1-1.json");
+        fs::write(&path, contract.to_string()).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_unevidenced_must_have_rejected() {
+        let dir = setup_test_env(true);
+        let contract_path = write_contract(&dir, &["Feature works"], &[]);
+        let evidence = vec!["it works, trust me".to_string()];
+        let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+        assert_eq!(code, 2);
+        let result: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(result["must_haves"][0]["status"], "unverified");
+        assert!(result["errors"][0].as_str().unwrap().starts_with("must_have 1 (\"Feature works\") unverified"));
+    }
+
+    #[test]
+    fn test_explicit_evidence_per_must_have() {
+        let dir = setup_test_env(true);
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub fn retry() {}\n").unwrap();
+        let contract_path = write_contract(&dir, &["Retries on failure", "Config is valid"], &[]);
+        let evidence = vec![
+            "evidence.1=symbol:src/lib.rs#retry".to_string(),
+            "evidence.2=cmd:test -f src/lib.rs".to_string(),
+        ];
+        let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+        assert_eq!(code, 0, "{}", out);
+        let result: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(result["must_haves"][1]["evidence"], "command `test -f src/lib.rs` succeeded");
+
+        let evidence = vec![
+            "evidence.1=symbol:src/lib.rs#backoff".to_string(),
+            "evidence.2=vibes:good".to_string(),
+            "evidence.3=file:src/lib.rs".to_string(),
+        ];
+        let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+        assert_eq!(code, 2);
+        let result: Value = serde_json::from_str(&out).unwrap();
+        let errors: Vec<&str> = result["errors"].as_array().unwrap().iter().map(|e| e.as_str().unwrap()).collect();
+        assert!(errors.iter().any(|e| e.contains("evidence.3 does not name a must_have")));
+        assert!(errors.iter().any(|e| e.contains("symbol backoff not defined in src/lib.rs")));
+        assert!(errors.iter().any(|e| e.contains("unknown evidence kind 'vibes'")));
+    }
+
+    #[test]
+    fn test_verification_check_times_out() {
+        let dir = setup_test_env(true);
+        let config = json!({"v2_two_phase_completion": true, "command_timeout_ms": 200});
+        fs::write(dir.path().join(".yolo-planning/config.json"), config.to_string()).unwrap();
+        let contract_path = write_contract(&dir, &[], &["sleep 5"]);
+        let evidence = vec!["done".to_string()];
+        let (out, code) = complete("1-1-T1", "1", "1", &contract_path, &evidence, dir.path()).unwrap();
+        assert_eq!(code, 2);
+        assert!(out.contains("timed out after 200ms"), "{}", out);
+    }
 }
@@ -1,5 +1,10 @@
 use std::fs;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 use serde::Deserialize;
 
 /// Typed config struct for `.yolo-planning/config.json`.
@@ -23,6 +28,10 @@ pub struct YoloConfig {
     pub review_max_cycles: u32,
     pub qa_max_cycles: u32,
     pub compaction_threshold: u64,
+    pub command_timeout_ms: u64,
+    pub task_lease_ttl_secs: u64,
+    pub max_token_budget: u64,
+    pub task_timeout_seconds: u64,
 }
 
 impl Default for YoloConfig {
@@ -43,6 +52,10 @@ impl Default for YoloConfig {
             review_max_cycles: 3,
             qa_max_cycles: 3,
             compaction_threshold: 130000,
+            command_timeout_ms: 30000,
+            task_lease_ttl_secs: 300,
+            max_token_budget: 50000,
+            task_timeout_seconds: 600,
         }
     }
 }
@@ -55,25 +68,18 @@ pub fn load_config(config_path: &Path) -> YoloConfig {
         .unwrap_or_default()
 }
 
-/// Extract raw frontmatter text between `---` delimiters.
-/// Returns None if content doesn't start with `---` or has no closing delimiter.
-pub fn extract_frontmatter(content: &str) -> Option<String> {
-    let mut lines = content.lines();
-    if lines.next()? != "---" {
-        return None;
-    }
-    let mut fm_lines = Vec::new();
-    for line in lines {
-        if line == "---" {
-            return if fm_lines.is_empty() {
-                None
-            } else {
-                Some(fm_lines.join("\n"))
-            };
+/// Plugin root: `CLAUDE_PLUGIN_ROOT`, else the nearest ancestor of `cwd` with
+/// `config/defaults.json`.
+pub fn plugin_root(cwd: &Path) -> Option<PathBuf> {
+    if let Ok(root) = std::env::var("CLAUDE_PLUGIN_ROOT") {
+        let p = PathBuf::from(root);
+        if p.is_dir() {
+            return Some(p);
         }
-        fm_lines.push(line);
     }
-    None // no closing ---
+    cwd.ancestors()
+        .find(|d| d.join("config").join("defaults.json").exists())
+        .map(Path::to_path_buf)
 }
 
 /// Split content into (frontmatter_text, body_text).
@@ -122,3 +128,50 @@ pub fn sorted_phase_dirs(phases_dir: &Path) -> Vec<(String, PathBuf)> {
     dirs.sort_by(|a, b| a.0.cmp(&b.0));
     dirs
 }
+
+/// Run a command, killing its whole process group if it outlives `timeout`.
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<Output, String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .map_err(|e| format!("failed to spawn: {}", e))?;
+
+    // Drain pipes on threads so a chatty child cannot block on a full buffer.
+    let mut stdout = child.stdout.take().unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let out_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+    let err_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stderr.read_to_end(&mut buf);
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                return Ok(Output {
+                    status,
+                    stdout: out_reader.join().unwrap_or_default(),
+                    stderr: err_reader.join().unwrap_or_default(),
+                });
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                unsafe {
+                    libc::kill(-(child.id() as i32), libc::SIGKILL);
+                }
+                let _ = child.wait();
+                return Err(format!("timed out after {}ms", timeout.as_millis()));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("wait failed: {}", e)),
+        }
+    }
+}
@@ -2,23 +2,33 @@ use serde_json::json;
 use std::fs;
 use std::path::Path;
 
+use super::file_overlap::{self, FileOverlap};
+use super::frontmatter::{self, CrossPhaseDep};
+use super::plan_graph::PlanGraph;
+
 /// Validates a plan file's depends_on references and cross-phase dependencies.
 ///
-/// Usage: yolo validate-plan <plan_path> <phase_dir>
+/// Usage: yolo validate-plan <plan_path> <phase_dir> [--suggest]
 ///
 /// Checks:
 /// 1. Plan file exists and has valid YAML frontmatter
 /// 2. depends_on references resolve to existing plan files in phase_dir
 /// 3. cross_phase_deps references resolve to completed SUMMARY.md files
+/// 4. files_modified (globs included) doesn't overlap another plan in the same wave
+///
+/// `--suggest` adds `suggestions`: wave reassignments that remove every overlap
+/// in the phase while keeping dependencies ordered.
 ///
 /// Exit codes: 0=valid, 1=invalid, 2=partial
 pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
-    if args.len() < 4 {
-        return Err("Usage: yolo validate-plan <plan_path> <phase_dir>".to_string());
+    let suggest = args.iter().any(|a| a == "--suggest");
+    let positional: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+    if positional.len() < 4 {
+        return Err("Usage: yolo validate-plan <plan_path> <phase_dir> [--suggest]".to_string());
     }
 
-    let plan_path = Path::new(&args[2]);
-    let phase_dir = Path::new(&args[3]);
+    let plan_path = Path::new(positional[2]);
+    let phase_dir = Path::new(positional[3]);
 
     // Check plan file exists
     if !plan_path.exists() {
@@ -33,10 +43,10 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     let content = fs::read_to_string(plan_path)
         .map_err(|e| format!("Failed to read plan file: {}", e))?;
 
-    // Extract frontmatter
-    let frontmatter = match extract_frontmatter(&content) {
-        Some(fm) => fm,
-        None => {
+    // Parse frontmatter
+    let plan = match frontmatter::parse(&content) {
+        Ok(Some(fm)) => fm.plan(),
+        Ok(None) => {
             let resp = json!({
                 "ok": false,
                 "cmd": "validate-plan",
@@ -44,6 +54,16 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
             });
             return Ok((resp.to_string(), 1));
         }
+        Err(e) => {
+            let resp = json!({
+                "ok": false,
+                "cmd": "validate-plan",
+                "errors": [format!("Invalid YAML frontmatter at {}", e)],
+                "line": e.line,
+                "column": e.column
+            });
+            return Ok((resp.to_string(), 1));
+        }
     };
 
     let mut errors: Vec<String> = Vec::new();
@@ -51,8 +71,8 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     let mut cross_phase_checked = 0u32;
 
     // Validate depends_on
-    let depends_on = parse_depends_on(&frontmatter);
-    for dep_id in &depends_on {
+    let depends_on = &plan.depends_on;
+    for dep_id in depends_on {
         depends_on_checked += 1;
         if !plan_file_exists(phase_dir, dep_id) {
             errors.push(format!("depends_on: plan {} not found in phase dir", dep_id));
@@ -60,8 +80,8 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Validate cross_phase_deps
-    let cross_phase_deps = parse_cross_phase_deps(&frontmatter);
-    for dep in &cross_phase_deps {
+    let cross_phase_deps = &plan.cross_phase_deps;
+    for dep in cross_phase_deps {
         cross_phase_checked += 1;
         match validate_cross_phase_dep(phase_dir, dep) {
             Ok(()) => {}
@@ -69,186 +89,80 @@ pub fn execute(args: &[String], _cwd: &Path) -> Result<(String, i32), String> {
         }
     }
 
-    if errors.is_empty() {
-        let resp = json!({
-            "ok": true,
-            "cmd": "validate-plan",
-            "depends_on": {
-                "valid": true,
-                "checked": depends_on_checked
-            },
-            "cross_phase": {
-                "valid": true,
-                "checked": cross_phase_checked
-            }
-        });
-        Ok((resp.to_string(), 0))
-    } else {
-        let all_deps_failed = errors.len() as u32 == depends_on_checked + cross_phase_checked;
-        let exit_code = if all_deps_failed { 1 } else { 2 };
-        let resp = json!({
-            "ok": false,
-            "cmd": "validate-plan",
-            "errors": errors,
-            "depends_on": {
-                "valid": depends_on.is_empty() || !errors.iter().any(|e| e.starts_with("depends_on:")),
-                "checked": depends_on_checked
-            },
-            "cross_phase": {
-                "valid": cross_phase_deps.is_empty() || !errors.iter().any(|e| e.starts_with("cross_phase:")),
-                "checked": cross_phase_checked
-            }
-        });
-        Ok((resp.to_string(), exit_code))
-    }
-}
-
-/// Extract frontmatter content between first `---` and second `---`.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-    let after_first = &content[3..];
-    let rest = after_first.trim_start_matches(|c: char| c != '\n');
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(rest[..end_idx].to_string())
-    } else if rest.ends_with("---") {
-        let trimmed = rest.trim_end_matches("---");
-        Some(trimmed.to_string())
-    } else {
-        None
-    }
-}
-
-/// Parse depends_on field from frontmatter.
-/// Supports both inline array `depends_on: ["01", "02"]` and YAML list format.
-fn parse_depends_on(frontmatter: &str) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut in_depends_on = false;
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if let Some(rest) = trimmed.strip_prefix("depends_on:") {
-            let value = rest.trim();
-
-            // Check for inline array: depends_on: ["01", "02"]
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !cleaned.is_empty() {
-                        result.push(cleaned.to_string());
-                    }
-                }
-                return result;
-            }
-
-            // Check for empty inline: depends_on: []
-            if value == "[]" {
-                return result;
-            }
-
-            // Start of YAML list
-            in_depends_on = true;
-            continue;
+    // Validate files_modified against same-wave plans
+    let graph = PlanGraph::load(phase_dir);
+    let analysis = graph.analyze();
+    let waves = file_overlap::effective_waves(&graph, &analysis);
+    let this = file_overlap::node_for_path(&graph, plan_path);
+    let (overlaps, overlap_checked): (Vec<FileOverlap>, u32) = match this {
+        Some(n) => {
+            let id = &graph.nodes[n].id;
+            let peers = (0..graph.nodes.len())
+                .filter(|&m| m != n && graph.nodes[m].phase == graph.nodes[n].phase && waves[m] == waves[n])
+                .count() as u32;
+            let overlaps: Vec<FileOverlap> = file_overlap::find_overlaps(&graph, &waves)
+                .into_iter()
+                .filter(|o| o.involves(id))
+                .collect();
+            (overlaps, peers)
         }
-
-        if in_depends_on {
-            if let Some(rest) = trimmed.strip_prefix("- ") {
-                let item = rest.trim().trim_matches('"').trim_matches('\'');
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() {
-                // Hit a new field, stop parsing depends_on
-                break;
-            }
+        None => (Vec::new(), 0),
+    };
+    // Only identical paths are certain conflicts; glob matches are reported as warnings
+    let mut warnings: Vec<String> = Vec::new();
+    let mut overlap_failed = 0u32;
+    for overlap in &overlaps {
+        if overlap.exact {
+            overlap_failed += 1;
+            errors.push(format!("file_overlap: {}", overlap.describe()));
+        } else {
+            warnings.push(format!("file_overlap: {} (glob match)", overlap.describe()));
         }
     }
 
-    result
-}
-
-/// Parse cross_phase_deps field from frontmatter.
-/// Each entry is expected to be in format: "phase:NN:plan:MM" or "NN-MM".
-fn parse_cross_phase_deps(frontmatter: &str) -> Vec<CrossPhaseDep> {
-    let mut result = Vec::new();
-    let mut in_cross_phase = false;
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if let Some(rest) = trimmed.strip_prefix("cross_phase_deps:") {
-            let value = rest.trim();
-
-            // Check for inline array
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if let Some(dep) = CrossPhaseDep::parse(cleaned) {
-                        result.push(dep);
-                    }
-                }
-                return result;
-            }
-
-            if value == "[]" {
-                return result;
-            }
-
-            in_cross_phase = true;
-            continue;
-        }
-
-        if in_cross_phase {
-            if let Some(rest) = trimmed.strip_prefix("- ") {
-                let item = rest.trim().trim_matches('"').trim_matches('\'');
-                if let Some(dep) = CrossPhaseDep::parse(item) {
-                    result.push(dep);
-                }
-            } else if !trimmed.is_empty() {
-                break;
-            }
+    let mut resp = json!({
+        "ok": errors.is_empty(),
+        "cmd": "validate-plan",
+        "depends_on": {
+            "valid": !errors.iter().any(|e| e.starts_with("depends_on:")),
+            "checked": depends_on_checked
+        },
+        "cross_phase": {
+            "valid": !errors.iter().any(|e| e.starts_with("cross_phase:")),
+            "checked": cross_phase_checked
+        },
+        "file_overlap": {
+            "valid": overlap_failed == 0,
+            "checked": overlap_checked,
+            "overlaps": overlaps.iter().map(|o| o.to_json()).collect::<Vec<_>>()
         }
+    });
+    if !errors.is_empty() {
+        resp["errors"] = json!(errors);
+    }
+    if !warnings.is_empty() {
+        resp["warnings"] = json!(warnings);
     }
 
-    result
-}
-
-#[derive(Debug)]
-struct CrossPhaseDep {
-    phase: String,
-    plan: String,
-}
-
-impl CrossPhaseDep {
-    /// Parse a cross-phase dep reference like "phase:03:plan:01" or "03-01".
-    fn parse(s: &str) -> Option<Self> {
-        // Try "phase:NN:plan:MM" format
-        if s.starts_with("phase:") {
-            let parts: Vec<&str> = s.split(':').collect();
-            if parts.len() >= 4 && parts[2] == "plan" {
-                return Some(CrossPhaseDep {
-                    phase: parts[1].to_string(),
-                    plan: parts[3].to_string(),
-                });
-            }
-        }
-
-        // Try "NN-MM" format
-        let parts: Vec<&str> = s.split('-').collect();
-        if parts.len() == 2 {
-            return Some(CrossPhaseDep {
-                phase: parts[0].to_string(),
-                plan: parts[1].to_string(),
-            });
-        }
-
-        None
+    if suggest {
+        resp["suggestions"] = match file_overlap::suggest_waves(&graph, &analysis) {
+            Some(moves) => json!(moves.iter().map(|m| m.to_json()).collect::<Vec<_>>()),
+            None => serde_json::Value::Null,
+        };
     }
+
+    // Each dependency and each same-wave peer is one check; exit 1 when all of them failed
+    let dep_failed = errors.iter().filter(|e| !e.starts_with("file_overlap:")).count() as u32;
+    let failed = dep_failed + overlap_failed;
+    let checked = depends_on_checked + cross_phase_checked + overlap_checked;
+    let exit_code = if failed == 0 {
+        0
+    } else if failed == checked {
+        1
+    } else {
+        2
+    };
+    Ok((resp.to_string(), exit_code))
 }
 
 /// Check if a plan file exists in the phase directory.
@@ -308,9 +222,8 @@ fn validate_cross_phase_dep(phase_dir: &Path, dep: &CrossPhaseDep) -> Result<(),
                             dep.phase, dep.plan, e
                         )
                     })?;
-                    let fm = extract_frontmatter(&content);
-                    if let Some(fm) = fm {
-                        let status = frontmatter_field_value(&fm, "status");
+                    if let Ok(Some(fm)) = frontmatter::parse(&content) {
+                        let status = fm.summary().status;
                         if status.as_deref() != Some("complete") {
                             return Err(format!(
                                 "cross_phase: Phase {} Plan {} status is '{}', expected 'complete'",
@@ -383,66 +296,37 @@ fn find_summary_file(phase_dir: &Path, plan_id: &str) -> Option<std::path::PathB
     None
 }
 
-/// Extract the value of a frontmatter field.
-fn frontmatter_field_value(frontmatter: &str, field_name: &str) -> Option<String> {
-    let prefix = format!("{}:", field_name);
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim().trim_matches('"').trim();
-            return Some(value.to_string());
-        }
-    }
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::tempdir;
 
-    #[test]
-    fn test_extract_frontmatter() {
-        let content = "---\nphase: \"01\"\nplan: \"01\"\n---\n## Body";
-        let fm = extract_frontmatter(content);
-        assert!(fm.is_some());
-        let fm = fm.unwrap();
-        assert!(fm.contains("phase:"));
-        assert!(fm.contains("plan:"));
-    }
-
-    #[test]
-    fn test_extract_frontmatter_missing() {
-        let content = "No frontmatter here";
-        assert!(extract_frontmatter(content).is_none());
+    fn plan_fm(fm: &str) -> frontmatter::PlanFrontmatter {
+        frontmatter::parse(&format!("---\n{}\n---\n", fm)).unwrap().unwrap().plan()
     }
 
     #[test]
     fn test_parse_depends_on_inline() {
-        let fm = "depends_on: [\"01\", \"02\"]\ntitle: test";
-        let deps = parse_depends_on(fm);
+        let deps = plan_fm("depends_on: [\"01\", \"02\"]\ntitle: test").depends_on;
         assert_eq!(deps, vec!["01", "02"]);
     }
 
     #[test]
     fn test_parse_depends_on_yaml_list() {
-        let fm = "depends_on:\n  - \"01\"\n  - \"02\"\ntitle: test";
-        let deps = parse_depends_on(fm);
+        let deps = plan_fm("depends_on:\n  - \"01\"\n  - \"02\"\ntitle: test").depends_on;
         assert_eq!(deps, vec!["01", "02"]);
     }
 
     #[test]
     fn test_parse_depends_on_empty() {
-        let fm = "depends_on: []\ntitle: test";
-        let deps = parse_depends_on(fm);
+        let deps = plan_fm("depends_on: []\ntitle: test").depends_on;
         assert!(deps.is_empty());
     }
 
     #[test]
     fn test_parse_cross_phase_deps() {
-        let fm = "cross_phase_deps:\n  - \"phase:03:plan:01\"\ntitle: test";
-        let deps = parse_cross_phase_deps(fm);
+        let deps = plan_fm("cross_phase_deps:\n  - \"phase:03:plan:01\"\ntitle: test").cross_phase_deps;
         assert_eq!(deps.len(), 1);
         assert_eq!(deps[0].phase, "03");
         assert_eq!(deps[0].plan, "01");
@@ -450,8 +334,7 @@ mod tests {
 
     #[test]
     fn test_parse_cross_phase_deps_dash_format() {
-        let fm = "cross_phase_deps: [\"03-01\", \"04-02\"]";
-        let deps = parse_cross_phase_deps(fm);
+        let deps = plan_fm("cross_phase_deps: [\"03-01\", \"04-02\"]").cross_phase_deps;
         assert_eq!(deps.len(), 2);
         assert_eq!(deps[0].phase, "03");
         assert_eq!(deps[0].plan, "01");
@@ -460,17 +343,22 @@ mod tests {
     }
 
     #[test]
-    fn test_cross_phase_dep_parse() {
-        let dep = CrossPhaseDep::parse("phase:03:plan:01");
-        assert!(dep.is_some());
-        let dep = dep.unwrap();
-        assert_eq!(dep.phase, "03");
-        assert_eq!(dep.plan, "01");
-
-        let dep2 = CrossPhaseDep::parse("03-01");
-        assert!(dep2.is_some());
+    fn test_validate_plan_invalid_yaml_reports_position() {
+        let dir = tempdir().unwrap();
+        let plan_path = dir.path().join("01-PLAN.md");
+        fs::write(&plan_path, "---\nphase: 1\ndepends_on: [01\n---\n").unwrap();
 
-        assert!(CrossPhaseDep::parse("invalid").is_none());
+        let args = vec![
+            "yolo".to_string(),
+            "validate-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            dir.path().to_string_lossy().to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 1);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(parsed["errors"][0].as_str().unwrap().starts_with("Invalid YAML frontmatter at line"));
+        assert!(parsed["line"].as_u64().unwrap() >= 3);
     }
 
     #[test]
@@ -627,6 +515,147 @@ mod tests {
         assert_eq!(parsed["ok"], false);
     }
 
+    #[test]
+    fn test_validate_plan_same_wave_file_overlap() {
+        let dir = tempdir().unwrap();
+        let phase_dir = dir.path().join("phases").join("09-validation");
+        fs::create_dir_all(&phase_dir).unwrap();
+
+        let plan_path = phase_dir.join("09-01-PLAN.md");
+        fs::write(
+            &plan_path,
+            "---\nphase: \"09\"\nplan: \"01\"\nwave: 1\ndepends_on: []\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            phase_dir.join("09-02-PLAN.md"),
+            "---\nphase: \"09\"\nplan: \"02\"\nwave: 1\ndepends_on: []\nfiles_modified:\n  - \"src/lib.rs\"\n---\n",
+        )
+        .unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "validate-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            phase_dir.to_string_lossy().to_string(),
+            "--suggest".to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 1);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["file_overlap"]["valid"], false);
+        assert_eq!(parsed["file_overlap"]["checked"], 1);
+        assert_eq!(parsed["file_overlap"]["overlaps"][0]["plans"], serde_json::json!(["09-01", "09-02"]));
+        assert!(parsed["errors"][0].as_str().unwrap().starts_with("file_overlap:"));
+        assert_eq!(
+            parsed["suggestions"],
+            serde_json::json!([{"plan": "09-02", "from": 1, "to": 2}])
+        );
+    }
+
+    #[test]
+    fn test_validate_plan_glob_overlap_is_warning() {
+        let dir = tempdir().unwrap();
+        let phase_dir = dir.path().join("phases").join("09-validation");
+        fs::create_dir_all(&phase_dir).unwrap();
+
+        let plan_path = phase_dir.join("09-01-PLAN.md");
+        fs::write(
+            &plan_path,
+            "---\nphase: \"09\"\nplan: \"01\"\nwave: 1\ndepends_on: []\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            phase_dir.join("09-02-PLAN.md"),
+            "---\nphase: \"09\"\nplan: \"02\"\nwave: 1\ndepends_on: []\nfiles_modified:\n  - \"src/*.rs\"\n---\n",
+        )
+        .unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "validate-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            phase_dir.to_string_lossy().to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 0);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["ok"], true);
+        assert!(parsed.get("errors").is_none());
+        assert_eq!(parsed["file_overlap"]["valid"], true);
+        assert_eq!(parsed["file_overlap"]["overlaps"][0]["exact"], false);
+        assert!(parsed["warnings"][0].as_str().unwrap().starts_with("file_overlap:"));
+    }
+
+    #[test]
+    fn test_validate_plan_exit_code_counts_checks() {
+        let dir = tempdir().unwrap();
+        let phase_dir = dir.path().join("phases").join("09-validation");
+        fs::create_dir_all(&phase_dir).unwrap();
+
+        // One missing dependency plus one exact overlap with a peer that is not a dependency
+        let plan_path = phase_dir.join("09-01-PLAN.md");
+        fs::write(
+            &plan_path,
+            "---\nphase: \"09\"\nplan: \"01\"\nwave: 1\ndepends_on: [\"09-09\"]\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            phase_dir.join("09-02-PLAN.md"),
+            "---\nphase: \"09\"\nplan: \"02\"\nwave: 1\ndepends_on: []\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            phase_dir.join("09-03-PLAN.md"),
+            "---\nphase: \"09\"\nplan: \"03\"\nwave: 1\ndepends_on: []\nfiles_modified: [src/main.rs]\n---\n",
+        )
+        .unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "validate-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            phase_dir.to_string_lossy().to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["errors"].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["file_overlap"]["checked"], 2);
+        // 2 of 3 checks failed: partial failure
+        assert_eq!(code, 2);
+    }
+
+    #[test]
+    fn test_validate_plan_different_waves_no_overlap() {
+        let dir = tempdir().unwrap();
+        let phase_dir = dir.path().join("phases").join("09-validation");
+        fs::create_dir_all(&phase_dir).unwrap();
+
+        let plan_path = phase_dir.join("09-01-PLAN.md");
+        fs::write(
+            &plan_path,
+            "---\nphase: \"09\"\nplan: \"01\"\nwave: 1\ndepends_on: []\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+        fs::write(
+            phase_dir.join("09-02-PLAN.md"),
+            "---\nphase: \"09\"\nplan: \"02\"\nwave: 2\ndepends_on: [\"01\"]\nfiles_modified: [src/lib.rs]\n---\n",
+        )
+        .unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "validate-plan".to_string(),
+            plan_path.to_string_lossy().to_string(),
+            phase_dir.to_string_lossy().to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 0, "{}", output);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["file_overlap"]["valid"], true);
+        assert!(parsed.get("suggestions").is_none());
+    }
+
     #[test]
     fn test_plan_file_exists() {
         let dir = tempdir().unwrap();
@@ -635,12 +664,4 @@ mod tests {
         assert!(plan_file_exists(dir.path(), "01"));
         assert!(!plan_file_exists(dir.path(), "03"));
     }
-
-    #[test]
-    fn test_frontmatter_field_value() {
-        let fm = "status: complete\nphase: \"01\"";
-        assert_eq!(frontmatter_field_value(fm, "status"), Some("complete".to_string()));
-        assert_eq!(frontmatter_field_value(fm, "phase"), Some("01".to_string()));
-        assert_eq!(frontmatter_field_value(fm, "missing"), None);
-    }
 }
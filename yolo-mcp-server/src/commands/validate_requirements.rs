@@ -3,6 +3,8 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use super::frontmatter;
+
 /// Validates that plan requirements (must_haves) are evidenced in deliverables.
 ///
 /// Usage: yolo validate-requirements <plan_path> <phase_dir>
@@ -40,10 +42,9 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let plan_content = fs::read_to_string(plan_path)
         .map_err(|e| format!("Failed to read plan: {}", e))?;
 
-    let frontmatter = extract_frontmatter(&plan_content);
-    let must_haves = match &frontmatter {
-        Some(fm) => parse_list_field(fm, "must_haves"),
-        None => Vec::new(),
+    let must_haves = match frontmatter::parse(&plan_content) {
+        Ok(Some(fm)) => fm.plan().must_haves,
+        _ => Vec::new(),
     };
 
     if must_haves.is_empty() {
@@ -126,73 +127,6 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     Ok((resp.to_string(), if ok { 0 } else { 1 }))
 }
 
-/// Extract frontmatter content between first `---` and second `---`.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-    let after_first = &content[3..];
-    let rest = after_first.trim_start_matches(|c: char| c != '\n');
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(rest[..end_idx].to_string())
-    } else if rest.ends_with("---") {
-        let trimmed = rest.trim_end_matches("---");
-        Some(trimmed.to_string())
-    } else {
-        None
-    }
-}
-
-/// Parse a YAML list field from frontmatter.
-fn parse_list_field(frontmatter: &str, field_name: &str) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut in_field = false;
-    let prefix = format!("{}:", field_name);
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim();
-
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                if inner.trim().is_empty() {
-                    return result;
-                }
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !cleaned.is_empty() {
-                        result.push(cleaned.to_string());
-                    }
-                }
-                return result;
-            }
-
-            if value == "[]" {
-                return result;
-            }
-
-            in_field = true;
-            continue;
-        }
-
-        if in_field {
-            if let Some(rest) = trimmed.strip_prefix("- ") {
-                let item = rest.trim().trim_matches('"').trim_matches('\'');
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() {
-                break;
-            }
-        }
-    }
-
-    result
-}
-
 /// Collect content from all SUMMARY.md files in the phase directory.
 fn collect_summaries(phase_dir: &Path) -> String {
     let mut content = String::new();
@@ -1,4 +1,4 @@
-use crate::commands::utils::extract_frontmatter;
+use crate::commands::frontmatter;
 use regex::Regex;
 use serde_json::json;
 use std::fs;
@@ -57,8 +57,11 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     let plan_content = fs::read_to_string(plan_path)
         .map_err(|e| format!("Failed to read PLAN: {}", e))?;
 
-    // Extract frontmatter from SUMMARY
-    let frontmatter = extract_frontmatter(&summary_content);
+    // Parse frontmatter from SUMMARY
+    let (summary_fm, fm_error) = match frontmatter::parse(&summary_content) {
+        Ok(fm) => (fm, None),
+        Err(e) => (None, Some(e)),
+    };
 
     // Check 1: Required frontmatter fields
     let required_fields = [
@@ -70,15 +73,19 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
         "tasks_total",
         "commit_hashes",
     ];
-    match &frontmatter {
+    match &summary_fm {
         None => {
-            checks.push(json!({"name": "frontmatter_fields", "status": "fail", "detail": "No YAML frontmatter found in SUMMARY", "fixable_by": "dev"}));
+            let detail = match &fm_error {
+                Some(e) => format!("Invalid YAML frontmatter in SUMMARY: {}", e),
+                None => "No YAML frontmatter found in SUMMARY".to_string(),
+            };
+            checks.push(json!({"name": "frontmatter_fields", "status": "fail", "detail": detail, "fixable_by": "dev"}));
             all_pass = false;
         }
         Some(fm) => {
             let missing: Vec<&str> = required_fields
                 .iter()
-                .filter(|f| !has_field(fm, f))
+                .filter(|f| !fm.has(f))
                 .copied()
                 .collect();
             if missing.is_empty() {
@@ -94,8 +101,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
 
     // Check 2: Plan task count matches tasks_total
     let plan_task_count = count_plan_tasks(&plan_content);
-    if let Some(ref fm) = frontmatter {
-        let tasks_total = field_value(fm, "tasks_total")
+    if let Some(ref fm) = summary_fm {
+        let tasks_total = fm.scalar("tasks_total")
             .and_then(|v| v.parse::<u32>().ok())
             .unwrap_or(0);
         if plan_task_count == tasks_total {
@@ -112,9 +119,9 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Check 3: tasks_completed == plan task count when status=complete
-    if let Some(ref fm) = frontmatter {
-        let status = field_value(fm, "status").unwrap_or_default();
-        let tasks_completed = field_value(fm, "tasks_completed")
+    if let Some(ref fm) = summary_fm {
+        let status = fm.scalar("status").unwrap_or_default();
+        let tasks_completed = fm.scalar("tasks_completed")
             .and_then(|v| v.parse::<u32>().ok())
             .unwrap_or(0);
         if status == "complete" {
@@ -135,8 +142,8 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     }
 
     // Check 4: commit_hashes non-empty, valid format, and exist in git repo
-    if let Some(ref fm) = frontmatter {
-        let hashes = parse_list_field(fm, "commit_hashes");
+    if let Some(ref fm) = summary_fm {
+        let hashes = fm.list("commit_hashes");
         let hex_re = hex_hash_re();
         if hashes.is_empty() {
             checks.push(
@@ -218,27 +225,6 @@ pub fn execute(args: &[String], cwd: &Path) -> Result<(String, i32), String> {
     Ok((resp.to_string(), if all_pass { 0 } else { 1 }))
 }
 
-/// Check if a frontmatter field exists.
-fn has_field(frontmatter: &str, field_name: &str) -> bool {
-    let prefix = format!("{}:", field_name);
-    frontmatter
-        .lines()
-        .any(|line| line.trim().starts_with(&prefix))
-}
-
-/// Extract the value of a frontmatter field.
-fn field_value(frontmatter: &str, field_name: &str) -> Option<String> {
-    let prefix = format!("{}:", field_name);
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim().trim_matches('"').trim();
-            return Some(value.to_string());
-        }
-    }
-    None
-}
-
 fn hex_hash_re() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| Regex::new(r"^[0-9a-fA-F]{7,}$").unwrap())
@@ -254,56 +240,6 @@ fn count_plan_tasks(content: &str) -> u32 {
     task_header_re().find_iter(content).count() as u32
 }
 
-/// Parse a YAML list field from frontmatter (supports inline array and block list).
-fn parse_list_field(frontmatter: &str, field_name: &str) -> Vec<String> {
-    let mut result = Vec::new();
-    let mut in_field = false;
-    let prefix = format!("{}:", field_name);
-
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim();
-
-            // Inline array: ["a", "b"]
-            if value.starts_with('[') {
-                let inner = value.trim_start_matches('[').trim_end_matches(']');
-                if inner.trim().is_empty() {
-                    return result;
-                }
-                for item in inner.split(',') {
-                    let cleaned = item.trim().trim_matches('"').trim_matches('\'').trim();
-                    if !cleaned.is_empty() {
-                        result.push(cleaned.to_string());
-                    }
-                }
-                return result;
-            }
-
-            if value == "[]" {
-                return result;
-            }
-
-            in_field = true;
-            continue;
-        }
-
-        if in_field {
-            if let Some(rest) = trimmed.strip_prefix("- ") {
-                let item = rest.trim().trim_matches('"').trim_matches('\'');
-                if !item.is_empty() {
-                    result.push(item.to_string());
-                }
-            } else if !trimmed.is_empty() {
-                break;
-            }
-        }
-    }
-
-    result
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -630,4 +566,26 @@ must_haves:
             }
         }
     }
+
+    #[test]
+    fn test_invalid_frontmatter_reports_position() {
+        let dir = tempdir().unwrap();
+        let summary_path = dir.path().join("SUMMARY.md");
+        let plan_path = dir.path().join("PLAN.md");
+        fs::write(&summary_path, "---\nphase: [04\n---\n## What Was Built\n## Files Modified\n").unwrap();
+        fs::write(&plan_path, "### Task 1: Only task\n").unwrap();
+
+        let args = vec![
+            "yolo".to_string(),
+            "verify-plan-completion".to_string(),
+            summary_path.to_string_lossy().to_string(),
+            plan_path.to_string_lossy().to_string(),
+        ];
+        let (output, code) = execute(&args, dir.path()).unwrap();
+        assert_eq!(code, 1);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let check = parsed["checks"].as_array().unwrap().iter().find(|c| c["name"] == "frontmatter_fields").unwrap();
+        assert_eq!(check["status"], "fail");
+        assert!(check["detail"].as_str().unwrap().starts_with("Invalid YAML frontmatter in SUMMARY: line "));
+    }
 }
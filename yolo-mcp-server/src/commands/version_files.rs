@@ -0,0 +1,448 @@
+use globset::Glob;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::layered_config::Config;
+
+/// Capture for `__version__ = "1.2.3"` in Python modules.
+const PYTHON_VERSION_PATTERN: &str = r#"__version__\s*=\s*["']([^"']+)["']"#;
+
+/// One `release.version_files` entry in the project config.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase", deny_unknown_fields)]
+pub enum VersionFileSpec {
+    /// The whole (trimmed) file is the version, e.g. `VERSION`.
+    Text { path: String },
+    /// String at a JSON pointer (default `/version`).
+    Json {
+        path: String,
+        #[serde(default = "default_pointer")]
+        pointer: String,
+    },
+    /// String at a dotted TOML key path, e.g. `project.version`.
+    Toml { path: String, key: String },
+    /// First capture group of `pattern` in a plain-text file.
+    Regex { path: String, pattern: String },
+    /// `__version__ = "..."` in a Python module.
+    Python { path: String },
+    /// Cargo manifest: its own version plus every workspace member that does not
+    /// inherit `version.workspace = true`.
+    Cargo { path: String },
+    /// package.json: its own version plus the package.json of every workspace member.
+    Npm { path: String },
+}
+
+fn default_pointer() -> String {
+    "/version".to_string()
+}
+
+/// How to find the version string inside a file.
+#[derive(Debug, Clone)]
+pub enum Locator {
+    Text,
+    Json(String),
+    Toml(Vec<String>),
+    Regex(Regex),
+}
+
+/// A concrete file and locator, after workspace expansion.
+#[derive(Debug, Clone)]
+pub struct VersionFile {
+    /// Path relative to the project root.
+    pub path: String,
+    pub locator: Locator,
+}
+
+/// The version files configured under `release.version_files`, expanded, and
+/// whether they came from the config (false: YOLO's own release layout).
+pub fn load(cwd: &Path) -> Result<(Vec<VersionFile>, bool), String> {
+    let config = Config::load(cwd);
+    let Some(raw) = config.get("release.version_files") else {
+        return Ok((builtin(), false));
+    };
+    let specs: Vec<VersionFileSpec> =
+        serde_json::from_value(raw.clone()).map_err(|e| format!("Invalid release.version_files: {}", e))?;
+    if specs.is_empty() {
+        return Err("release.version_files is empty".to_string());
+    }
+    let mut files = Vec::new();
+    for spec in &specs {
+        files.extend(expand(spec, cwd)?);
+    }
+    Ok((files, true))
+}
+
+/// The files YOLO versions in its own repository.
+fn builtin() -> Vec<VersionFile> {
+    vec![
+        VersionFile { path: "VERSION".into(), locator: Locator::Text },
+        VersionFile { path: ".claude-plugin/plugin.json".into(), locator: Locator::Json("/version".into()) },
+        VersionFile { path: ".claude-plugin/marketplace.json".into(), locator: Locator::Json("/plugins/0/version".into()) },
+        VersionFile { path: "yolo-mcp-server/Cargo.toml".into(), locator: toml_key("package.version") },
+    ]
+}
+
+fn toml_key(key: &str) -> Locator {
+    Locator::Toml(key.split('.').map(str::to_string).collect())
+}
+
+fn expand(spec: &VersionFileSpec, cwd: &Path) -> Result<Vec<VersionFile>, String> {
+    let one = |path: &str, locator: Locator| Ok(vec![VersionFile { path: path.to_string(), locator }]);
+    match spec {
+        VersionFileSpec::Text { path } => one(path, Locator::Text),
+        VersionFileSpec::Json { path, pointer } => one(path, Locator::Json(pointer.clone())),
+        VersionFileSpec::Toml { path, key } => one(path, toml_key(key)),
+        VersionFileSpec::Regex { path, pattern } => {
+            let re = Regex::new(pattern).map_err(|e| format!("Invalid pattern for {}: {}", path, e))?;
+            if re.captures_len() < 2 {
+                return Err(format!("Pattern for {} needs a capture group around the version", path));
+            }
+            one(path, Locator::Regex(re))
+        }
+        VersionFileSpec::Python { path } => one(path, Locator::Regex(Regex::new(PYTHON_VERSION_PATTERN).unwrap())),
+        VersionFileSpec::Cargo { path } => expand_cargo(path, cwd),
+        VersionFileSpec::Npm { path } => expand_npm(path, cwd),
+    }
+}
+
+fn expand_cargo(path: &str, cwd: &Path) -> Result<Vec<VersionFile>, String> {
+    let doc = parse_toml(&cwd.join(path))?;
+    let mut files = Vec::new();
+    if doc.get("workspace").and_then(|w| w.get("package")).and_then(|p| p.get("version")).and_then(|v| v.as_str()).is_some() {
+        files.push(VersionFile { path: path.to_string(), locator: toml_key("workspace.package.version") });
+    }
+    if doc.get("package").and_then(|p| p.get("version")).and_then(|v| v.as_str()).is_some() {
+        files.push(VersionFile { path: path.to_string(), locator: toml_key("package.version") });
+    }
+    let members: Vec<String> = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let root = parent_of(path);
+    for member in members {
+        for dir in expand_pattern(cwd, &root, &member) {
+            let manifest = dir.join("Cargo.toml");
+            let Ok(member_doc) = parse_toml(&cwd.join(&manifest)) else { continue };
+            // Members with `version.workspace = true` follow the workspace version.
+            if member_doc.get("package").and_then(|p| p.get("version")).and_then(|v| v.as_str()).is_some() {
+                files.push(VersionFile { path: to_rel(&manifest), locator: toml_key("package.version") });
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(format!("{}: no package.version or workspace.package.version to manage", path));
+    }
+    Ok(files)
+}
+
+fn expand_npm(path: &str, cwd: &Path) -> Result<Vec<VersionFile>, String> {
+    let root_json = parse_json(&cwd.join(path))?;
+    let mut files = Vec::new();
+    if root_json.get("version").and_then(|v| v.as_str()).is_some() {
+        files.push(VersionFile { path: path.to_string(), locator: Locator::Json("/version".into()) });
+    }
+    // `workspaces` is an array of globs, or `{ "packages": [...] }` (yarn).
+    let patterns = root_json
+        .get("workspaces")
+        .map(|w| w.get("packages").unwrap_or(w))
+        .and_then(|w| w.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str().map(str::to_string)).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let root = parent_of(path);
+    for pattern in patterns {
+        for dir in expand_pattern(cwd, &root, &pattern) {
+            let manifest = dir.join("package.json");
+            if parse_json(&cwd.join(&manifest)).is_ok_and(|j| j.get("version").and_then(|v| v.as_str()).is_some()) {
+                files.push(VersionFile { path: to_rel(&manifest), locator: Locator::Json("/version".into()) });
+            }
+        }
+    }
+    if files.is_empty() {
+        return Err(format!("{}: no version field in the package or its workspaces", path));
+    }
+    Ok(files)
+}
+
+fn parent_of(path: &str) -> PathBuf {
+    Path::new(path).parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+fn to_rel(path: &Path) -> String {
+    path.to_string_lossy().trim_start_matches("./").to_string()
+}
+
+/// Directories (relative to `cwd`) matching a workspace member pattern such as
+/// `crates/*` or `packages/**`, resolved from `root`. Sorted for stable output.
+fn expand_pattern(cwd: &Path, root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for component in pattern.trim_end_matches('/').split('/') {
+        if component.is_empty() || component == "." {
+            continue;
+        }
+        if component == "**" {
+            let mut all = Vec::new();
+            let mut stack = dirs.clone();
+            while let Some(dir) = stack.pop() {
+                all.push(dir.clone());
+                stack.extend(subdirs(cwd, &dir));
+            }
+            dirs = all;
+        } else if component.contains(['*', '?', '[']) {
+            let Ok(glob) = Glob::new(component) else { return Vec::new() };
+            let matcher = glob.compile_matcher();
+            dirs = dirs
+                .iter()
+                .flat_map(|d| subdirs(cwd, d))
+                .filter(|d| d.file_name().is_some_and(|n| matcher.is_match(n)))
+                .collect();
+        } else {
+            dirs = dirs.iter().map(|d| d.join(component)).filter(|d| cwd.join(d).is_dir()).collect();
+        }
+    }
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+fn subdirs(cwd: &Path, dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(cwd.join(dir))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir() && !e.file_name().to_string_lossy().starts_with('.'))
+        .filter(|e| e.file_name() != "node_modules" && e.file_name() != "target")
+        .map(|e| dir.join(e.file_name()))
+        .collect()
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    if !path.exists() {
+        return Err(format!("{} not found", path.display()));
+    }
+    fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+fn parse_json(path: &Path) -> Result<Value, String> {
+    serde_json::from_str(&read(path)?).map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))
+}
+
+fn parse_toml(path: &Path) -> Result<toml_edit::DocumentMut, String> {
+    read(path)?
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| format!("Invalid TOML in {}: {}", path.display(), e))
+}
+
+impl Locator {
+    /// Short human form shown next to the path in verify output.
+    pub fn describe(&self) -> String {
+        match self {
+            Locator::Text => "file".to_string(),
+            Locator::Json(pointer) => pointer.clone(),
+            Locator::Toml(keys) => keys.join("."),
+            Locator::Regex(re) => re.as_str().to_string(),
+        }
+    }
+}
+
+impl VersionFile {
+    pub fn exists(&self, cwd: &Path) -> bool {
+        cwd.join(&self.path).exists()
+    }
+
+    pub fn read(&self, cwd: &Path) -> Result<String, String> {
+        let path = cwd.join(&self.path);
+        let not_found = |what: &str| format!("Version field not found at {} in {}", what, path.display());
+        match &self.locator {
+            Locator::Text => Ok(read(&path)?.trim().to_string()),
+            Locator::Json(pointer) => parse_json(&path)?
+                .pointer(pointer)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| not_found(pointer)),
+            Locator::Toml(keys) => {
+                let doc = parse_toml(&path)?;
+                let mut item = doc.as_item();
+                for key in keys {
+                    item = item.get(key).ok_or_else(|| not_found(&keys.join(".")))?;
+                }
+                item.as_str().map(str::to_string).ok_or_else(|| not_found(&keys.join(".")))
+            }
+            Locator::Regex(re) => re
+                .captures(&read(&path)?)
+                .and_then(|c| c.get(1))
+                .map(|m| m.as_str().to_string())
+                .ok_or_else(|| not_found(re.as_str())),
+        }
+    }
+
+    /// Replace the version in place, leaving the rest of the file untouched.
+    pub fn write(&self, cwd: &Path, version: &str) -> Result<(), String> {
+        let path = cwd.join(&self.path);
+        let updated = match &self.locator {
+            Locator::Text => format!("{}\n", version),
+            Locator::Json(pointer) => write_json(&path, pointer, version)?,
+            Locator::Toml(keys) => {
+                self.read(cwd)?;
+                let mut doc = parse_toml(&path)?;
+                let (last, parents) = keys.split_last().ok_or("empty TOML key")?;
+                let mut item = doc.as_item_mut();
+                for key in parents {
+                    item = &mut item[key.as_str()];
+                }
+                item[last.as_str()] = toml_edit::value(version);
+                doc.to_string()
+            }
+            Locator::Regex(re) => {
+                let content = read(&path)?;
+                let range = re
+                    .captures(&content)
+                    .and_then(|c| c.get(1))
+                    .map(|m| m.range())
+                    .ok_or_else(|| format!("Version field not found at {} in {}", re.as_str(), path.display()))?;
+                format!("{}{}{}", &content[..range.start], version, &content[range.end..])
+            }
+        };
+        fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// New content of a JSON file with the string at `pointer` set to `version`.
+/// Edits the literal in place when it is unambiguous so key order and
+/// formatting survive; otherwise re-serializes the document.
+fn write_json(path: &Path, pointer: &str, version: &str) -> Result<String, String> {
+    let content = read(path)?;
+    let mut val: Value = serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+    let target = val
+        .pointer_mut(pointer)
+        .ok_or_else(|| format!("Version field not found at {} in {}", pointer, path.display()))?;
+    let old = target.as_str().unwrap_or_default().to_string();
+    *target = Value::String(version.to_string());
+
+    let key = pointer.rsplit('/').next().unwrap_or_default();
+    let literal = Regex::new(&format!(r#""{}"\s*:\s*"{}""#, regex::escape(key), regex::escape(&old))).unwrap();
+    if literal.find_iter(&content).count() == 1 {
+        let m = literal.find(&content).unwrap();
+        // The quoted value ends the match; swap just that part.
+        let value_start = m.end() - old.len() - 2;
+        return Ok(format!("{}\"{}\"{}", &content[..value_start], version, &content[m.end()..]));
+    }
+    let output = serde_json::to_string_pretty(&val).map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+    Ok(format!("{}\n", output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn project(config: Value) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        fs::write(dir.path().join(".yolo-planning/config.json"), config.to_string()).unwrap();
+        dir
+    }
+
+    fn write(dir: &TempDir, rel: &str, content: &str) {
+        let path = dir.path().join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_builtin_when_unconfigured() {
+        let dir = project(json!({}));
+        let (files, configured) = load(dir.path()).unwrap();
+        assert!(!configured);
+        assert_eq!(files[0].path, "VERSION");
+        assert_eq!(files.len(), 4);
+    }
+
+    #[test]
+    fn test_json_write_preserves_key_order() {
+        let dir = project(json!({}));
+        write(&dir, "package.json", "{\n  \"name\": \"app\",\n  \"version\": \"1.0.0\",\n  \"dependencies\": {}\n}\n");
+        let file = VersionFile { path: "package.json".into(), locator: Locator::Json("/version".into()) };
+        file.write(dir.path(), "1.1.0").unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("package.json")).unwrap(),
+            "{\n  \"name\": \"app\",\n  \"version\": \"1.1.0\",\n  \"dependencies\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_json_write_nested_pointer() {
+        let dir = project(json!({}));
+        write(&dir, "m.json", r#"{"plugins": [{"version": "1.0.0"}], "version": "1.0.0"}"#);
+        let file = VersionFile { path: "m.json".into(), locator: Locator::Json("/plugins/0/version".into()) };
+        file.write(dir.path(), "3.0.0").unwrap();
+        let content: Value = serde_json::from_str(&fs::read_to_string(dir.path().join("m.json")).unwrap()).unwrap();
+        assert_eq!(content["plugins"][0]["version"], "3.0.0");
+        assert_eq!(content["version"], "1.0.0");
+    }
+
+    #[test]
+    fn test_toml_key_path_and_python_and_regex() {
+        let dir = project(json!({"release": {"version_files": [
+            {"format": "toml", "path": "pyproject.toml", "key": "tool.poetry.version"},
+            {"format": "python", "path": "src/app/__init__.py"},
+            {"format": "regex", "path": "README.md", "pattern": "Current release: v(\\S+)"},
+        ]}}));
+        write(&dir, "pyproject.toml", "[tool.poetry]\nname = \"app\" # keep\nversion = \"0.4.0\"\n");
+        write(&dir, "src/app/__init__.py", "\"\"\"App.\"\"\"\n__version__ = '0.4.0'\n");
+        write(&dir, "README.md", "# App\nCurrent release: v0.4.0\n");
+
+        let (files, configured) = load(dir.path()).unwrap();
+        assert!(configured);
+        for f in &files {
+            assert_eq!(f.read(dir.path()).unwrap(), "0.4.0");
+            f.write(dir.path(), "0.5.0").unwrap();
+            assert_eq!(f.read(dir.path()).unwrap(), "0.5.0");
+        }
+        assert!(fs::read_to_string(dir.path().join("pyproject.toml")).unwrap().contains("name = \"app\" # keep"));
+        assert_eq!(fs::read_to_string(dir.path().join("src/app/__init__.py")).unwrap(), "\"\"\"App.\"\"\"\n__version__ = '0.5.0'\n");
+    }
+
+    #[test]
+    fn test_cargo_workspace_skips_inheriting_members() {
+        let dir = project(json!({"release": {"version_files": [{"format": "cargo", "path": "Cargo.toml"}]}}));
+        write(&dir, "Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"2.0.0\"\n");
+        write(&dir, "crates/core/Cargo.toml", "[package]\nname = \"core\"\nversion.workspace = true\n");
+        write(&dir, "crates/cli/Cargo.toml", "[package]\nname = \"cli\"\nversion = \"2.0.0\"\n");
+
+        let (files, _) = load(dir.path()).unwrap();
+        let found: Vec<(String, String)> = files.iter().map(|f| (f.path.clone(), f.locator.describe())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Cargo.toml".to_string(), "workspace.package.version".to_string()),
+                ("crates/cli/Cargo.toml".to_string(), "package.version".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_workspaces_expand_members() {
+        let dir = project(json!({"release": {"version_files": [{"format": "npm", "path": "package.json"}]}}));
+        write(&dir, "package.json", r#"{"name": "root", "version": "1.0.0", "workspaces": {"packages": ["packages/*"]}}"#);
+        write(&dir, "packages/a/package.json", r#"{"name": "a", "version": "1.0.0"}"#);
+        write(&dir, "packages/b/package.json", r#"{"name": "b", "private": true}"#);
+
+        let (files, _) = load(dir.path()).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["package.json", "packages/a/package.json"]);
+    }
+
+    #[test]
+    fn test_invalid_config_is_an_error() {
+        let dir = project(json!({"release": {"version_files": [{"format": "yaml", "path": "x"}]}}));
+        assert!(load(dir.path()).unwrap_err().contains("Invalid release.version_files"));
+        let dir = project(json!({"release": {"version_files": [{"format": "regex", "path": "x", "pattern": "v\\d+"}]}}));
+        assert!(load(dir.path()).unwrap_err().contains("capture group"));
+    }
+}
@@ -178,6 +178,7 @@ fn map_tmux_pane(planning_dir: &Path, agent_pid: u32) {
         };
     }
 
+    // A PID -> pane lookup table pruned by agent-stop, so it stays out of the event bus.
     if let Some(pane_id) = found_pane {
         let pane_map = planning_dir.join(".agent-panes");
         if let Ok(mut f) = fs::OpenOptions::new()
@@ -0,0 +1,162 @@
+use regex::Regex;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::types::{HookInput, HookOutput};
+use crate::commands::event_bus;
+use crate::commands::layered_config::Config;
+
+/// Default blocklist: one case-insensitive regex per line, `#` comments.
+const BUILTIN_PATTERNS: &str = include_str!("../../../config/destructive-commands.txt");
+
+/// Project-specific patterns that supplement the defaults.
+const LOCAL_PATTERNS_FILE: &str = "destructive-commands.local.txt";
+
+/// Blocked commands are logged truncated to this many characters.
+const PREVIEW_CHARS: usize = 40;
+
+/// PreToolUse handler: block Bash commands matching the destructive-command blocklist.
+///
+/// Skipped when `YOLO_ALLOW_DESTRUCTIVE=1` or `bash_guard` is false in the layered config.
+/// Every block is published to the event bus as `bash_guard_block`, which the
+/// `forbidden_commands` hard gate reads back.
+pub fn handle(input: &HookInput, planning_dir: Option<&Path>) -> Result<HookOutput, String> {
+    if input.data.get("tool_name").and_then(|v| v.as_str()) != Some("Bash") {
+        return Ok(HookOutput::empty());
+    }
+    if std::env::var("YOLO_ALLOW_DESTRUCTIVE").is_ok_and(|v| v == "1") {
+        return Ok(HookOutput::empty());
+    }
+    if let Some(cwd) = planning_dir.and_then(|pd| pd.parent())
+        && Config::load(cwd).get("bash_guard").and_then(|v| v.as_bool()) == Some(false)
+    {
+        return Ok(HookOutput::empty());
+    }
+
+    let Some(command) = input.data.pointer("/tool_input/command").and_then(|v| v.as_str()) else {
+        return Ok(HookOutput::empty());
+    };
+
+    let local = planning_dir
+        .and_then(|pd| fs::read_to_string(pd.join(LOCAL_PATTERNS_FILE)).ok())
+        .map(|content| compile(&content))
+        .unwrap_or_default();
+    let Some(pattern) = builtin().iter().chain(local.iter()).find(|re| re.is_match(command)) else {
+        return Ok(HookOutput::empty());
+    };
+
+    if let Some(pd) = planning_dir {
+        let agent = fs::read_to_string(pd.join(".active-agent")).unwrap_or_default();
+        event_bus::publish(pd, &json!({
+            "event": "bash_guard_block",
+            "data": {
+                "command_preview": command.chars().take(PREVIEW_CHARS).collect::<String>(),
+                "pattern": pattern_source(pattern),
+                "agent": agent.trim(),
+            },
+        }));
+    }
+    Ok(deny(&format!(
+        "Blocked: destructive command detected (matches `{}`). Set YOLO_ALLOW_DESTRUCTIVE=1 or bash_guard=false to allow it.",
+        pattern_source(pattern)
+    )))
+}
+
+fn builtin() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| compile(BUILTIN_PATTERNS))
+}
+
+/// Compile a blocklist, skipping comments, blank lines and invalid regexes.
+fn compile(content: &str) -> Vec<Regex> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| Regex::new(&format!("(?i){}", l)).ok())
+        .collect()
+}
+
+/// The blocklist line a compiled pattern came from.
+fn pattern_source(re: &Regex) -> &str {
+    re.as_str().trim_start_matches("(?i)")
+}
+
+/// Build a deny HookOutput with permissionDecision JSON.
+fn deny(message: &str) -> HookOutput {
+    eprintln!("{}", message);
+    let json = json!({
+        "permissionDecision": "deny",
+        "message": message
+    });
+    HookOutput::block(json.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    fn bash(command: &str) -> HookInput {
+        HookInput { data: json!({"tool_name": "Bash", "tool_input": {"command": command}}) }
+    }
+
+    fn setup_test_env() -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let planning_dir = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning_dir).unwrap();
+        (dir, planning_dir)
+    }
+
+    #[test]
+    fn test_blocks_destructive_command_and_publishes_event() {
+        let (_dir, planning_dir) = setup_test_env();
+        fs::write(planning_dir.join(".active-agent"), "dev\n").unwrap();
+
+        let result = handle(&bash("php artisan migrate:fresh --seed"), Some(&planning_dir)).unwrap();
+        assert_eq!(result.exit_code, 2);
+        assert!(result.stdout.contains("deny"));
+
+        let events = event_bus::load_events(&planning_dir);
+        let block: &Value = events.iter().find(|e| e["event"] == "bash_guard_block").unwrap();
+        assert_eq!(block["data"]["command_preview"], "php artisan migrate:fresh --seed");
+        assert_eq!(block["data"]["pattern"], r"artisan\s+migrate:(fresh|reset|refresh)");
+        assert_eq!(block["data"]["agent"], "dev");
+    }
+
+    #[test]
+    fn test_allows_safe_commands_and_other_tools() {
+        let (_dir, planning_dir) = setup_test_env();
+        assert_eq!(handle(&bash("php artisan migrate"), Some(&planning_dir)).unwrap().exit_code, 0);
+        let read = HookInput { data: json!({"tool_name": "Read", "tool_input": {"file_path": "a.rs"}}) };
+        assert_eq!(handle(&read, Some(&planning_dir)).unwrap().exit_code, 0);
+        assert!(event_bus::load_events(&planning_dir).is_empty());
+    }
+
+    #[test]
+    fn test_local_patterns_supplement_defaults() {
+        let (_dir, planning_dir) = setup_test_env();
+        fs::write(planning_dir.join(LOCAL_PATTERNS_FILE), "# ours\nscripts/nuke-dev-data\\.sh\n").unwrap();
+        assert_eq!(handle(&bash("./scripts/nuke-dev-data.sh"), Some(&planning_dir)).unwrap().exit_code, 2);
+        assert_eq!(handle(&bash("rails db:drop"), Some(&planning_dir)).unwrap().exit_code, 2);
+    }
+
+    #[test]
+    fn test_disabled_by_config() {
+        let (_dir, planning_dir) = setup_test_env();
+        fs::write(planning_dir.join("config.json"), r#"{"bash_guard": false}"#).unwrap();
+        assert_eq!(handle(&bash("rails db:drop"), Some(&planning_dir)).unwrap().exit_code, 0);
+    }
+
+    #[test]
+    fn test_preview_is_truncated() {
+        let (_dir, planning_dir) = setup_test_env();
+        let command = format!("redis-cli FLUSHALL && echo {}", "x".repeat(80));
+        handle(&bash(&command), Some(&planning_dir)).unwrap();
+        let events = event_bus::load_events(&planning_dir);
+        assert_eq!(events[0]["data"]["command_preview"].as_str().unwrap().chars().count(), PREVIEW_CHARS);
+    }
+}
@@ -1,10 +1,10 @@
 use chrono::Utc;
 use serde_json::{json, Value};
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 
 use super::utils;
+use crate::commands::role_registry::RoleRegistry;
 
 /// PreCompact handler: inject agent-specific summarization priorities.
 ///
@@ -26,7 +26,8 @@ pub fn handle_pre_compact(input: &Value) -> (Value, i32) {
         .unwrap_or("auto");
 
     let role = utils::normalize_agent_role(agent_name);
-    let priorities = role_priorities(&role);
+    let registry = RoleRegistry::load(Path::new("."));
+    let priorities = role_priorities(&registry, &role);
 
     let manual_suffix = if matcher == "manual" {
         " User requested compaction."
@@ -53,17 +54,15 @@ pub fn handle_pre_compact(input: &Value) -> (Value, i32) {
     (output, 0)
 }
 
-/// Map normalized agent role to compaction priority strings.
-fn role_priorities(role: &str) -> &'static str {
-    match role {
-        "scout" => "Preserve research findings, URLs, confidence assessments",
-        "dev" => "Preserve commit hashes, file paths modified, deviation decisions, current task number. After compaction, if .yolo-planning/codebase/META.md exists, re-read CONVENTIONS.md, PATTERNS.md, STRUCTURE.md, and DEPENDENCIES.md (whichever exist) from .yolo-planning/codebase/",
-        "qa" => "Preserve pass/fail status, gap descriptions, verification results. After compaction, if .yolo-planning/codebase/META.md exists, re-read TESTING.md, CONCERNS.md, and ARCHITECTURE.md (whichever exist) from .yolo-planning/codebase/",
-        "lead" => "Preserve phase status, plan structure, coordination decisions. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md, CONCERNS.md, and STRUCTURE.md (whichever exist) from .yolo-planning/codebase/",
-        "architect" => "Preserve requirement IDs, phase structure, success criteria, key decisions. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md and STACK.md (whichever exist) from .yolo-planning/codebase/",
-        "debugger" => "Preserve reproduction steps, hypotheses, evidence gathered, diagnosis. After compaction, if .yolo-planning/codebase/META.md exists, re-read ARCHITECTURE.md, CONCERNS.md, PATTERNS.md, and DEPENDENCIES.md (whichever exist) from .yolo-planning/codebase/",
-        _ => "Preserve active command being executed, user's original request, current phase/plan context, file modification paths, any pending user decisions. Discard: tool output details, reference file contents (re-read from disk), previous command results",
-    }
+/// Priorities for roles without their own in the role registry.
+const DEFAULT_PRIORITIES: &str = "Preserve active command being executed, user's original request, current phase/plan context, file modification paths, any pending user decisions. Discard: tool output details, reference file contents (re-read from disk), previous command results";
+
+/// Map normalized agent role to its compaction priority string.
+fn role_priorities(registry: &RoleRegistry, role: &str) -> String {
+    registry
+        .get(role)
+        .and_then(|r| r.compaction.clone())
+        .unwrap_or_else(|| DEFAULT_PRIORITIES.to_string())
 }
 
 /// Write `.compaction-marker` with Unix timestamp into `.yolo-planning/`.
@@ -139,20 +138,10 @@ fn save_snapshot_for_phase(
         let _ = fs::write(&snap_path, json_str);
     }
 
-    // Log snapshot
-    let log_path = planning.join(".hook-errors.log");
-    let ts_iso = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-    let msg = format!(
-        "[{}] Snapshot saved: phase={} agent={}\n",
-        ts_iso, phase, agent_name
+    utils::log_hook_message(
+        planning,
+        &format!("Snapshot saved: phase={} agent={}", phase, agent_name),
     );
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)
-    {
-        let _ = f.write_all(msg.as_bytes());
-    }
 }
 
 #[cfg(test)]
@@ -161,45 +150,45 @@ mod tests {
 
     #[test]
     fn test_role_priorities_dev() {
-        let p = role_priorities("dev");
+        let p = role_priorities(&RoleRegistry::builtin(), "dev");
         assert!(p.contains("commit hashes"));
         assert!(p.contains("CONVENTIONS.md"));
     }
 
     #[test]
     fn test_role_priorities_scout() {
-        let p = role_priorities("scout");
+        let p = role_priorities(&RoleRegistry::builtin(), "scout");
         assert!(p.contains("research findings"));
     }
 
     #[test]
     fn test_role_priorities_qa() {
-        let p = role_priorities("qa");
+        let p = role_priorities(&RoleRegistry::builtin(), "qa");
         assert!(p.contains("pass/fail"));
         assert!(p.contains("TESTING.md"));
     }
 
     #[test]
     fn test_role_priorities_lead() {
-        let p = role_priorities("lead");
+        let p = role_priorities(&RoleRegistry::builtin(), "lead");
         assert!(p.contains("phase status"));
     }
 
     #[test]
     fn test_role_priorities_architect() {
-        let p = role_priorities("architect");
+        let p = role_priorities(&RoleRegistry::builtin(), "architect");
         assert!(p.contains("requirement IDs"));
     }
 
     #[test]
     fn test_role_priorities_debugger() {
-        let p = role_priorities("debugger");
+        let p = role_priorities(&RoleRegistry::builtin(), "debugger");
         assert!(p.contains("reproduction steps"));
     }
 
     #[test]
     fn test_role_priorities_default() {
-        let p = role_priorities("unknown");
+        let p = role_priorities(&RoleRegistry::builtin(), "unknown");
         assert!(p.contains("original request"));
     }
 
@@ -3,6 +3,7 @@ use serde_json::Value;
 use super::agent_health;
 use super::agent_start;
 use super::agent_stop;
+use super::bash_guard;
 use super::blocker_notify;
 use super::compaction_instructions;
 use super::notification_log;
@@ -21,7 +22,7 @@ use super::utils;
 ///
 /// - Parses `stdin_json` into a `HookInput`
 /// - Routes to the handler for `event`
-/// - On handler error: logs to `.hook-errors.log`, returns exit 0 (graceful degradation)
+/// - On handler error: publishes `hook_error` to the event bus, returns exit 0 (graceful degradation)
 /// - On handler returning exit 2: passes through (intentional block)
 /// - Never panics
 pub fn dispatch(event: &HookEvent, stdin_json: &str) -> (String, i32) {
@@ -71,6 +72,10 @@ fn route_event(event: &HookEvent, input: &HookInput) -> Result<HookOutput, Strin
             if sf_result.exit_code == 2 {
                 return Ok(sf_result);
             }
+            let guard_result = bash_guard::handle(input, planning_dir.as_deref())?;
+            if guard_result.exit_code == 2 {
+                return Ok(guard_result);
+            }
             Ok(HookOutput::empty())
         }
         HookEvent::PostToolUse => handle_post_tool_use(input),
@@ -186,6 +191,7 @@ fn handle_session_start(input: &HookInput) -> Result<HookOutput, String> {
 /// Gated behind `v4_session_cache_warm` feature flag.
 /// Always succeeds (errors are silently swallowed).
 fn warm_session_cache() {
+    use crate::commands::feature_flags::{self, FeatureFlag};
     use crate::commands::tier_context;
 
     let cwd = match std::env::current_dir() {
@@ -199,14 +205,8 @@ fn warm_session_cache() {
     };
 
     // Check feature flag
-    let config_path = planning_dir.join("config.json");
-    let enabled = std::fs::read_to_string(&config_path)
-        .ok()
-        .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
-        .and_then(|v| v.get("v4_session_cache_warm")?.as_bool())
-        .unwrap_or(false);
-
-    if !enabled {
+    let project_root = planning_dir.parent().unwrap_or(&planning_dir);
+    if !feature_flags::is_enabled(FeatureFlag::V4SessionCacheWarm, project_root) {
         return;
     }
 
@@ -386,6 +386,14 @@ mod tests {
         assert_eq!(output.exit_code, 0);
     }
 
+    #[test]
+    fn test_dispatch_pre_tool_use_blocks_destructive_bash() {
+        let stdin = r#"{"tool_name":"Bash","tool_input":{"command":"rails db:drop"}}"#;
+        let (output, code) = dispatch(&HookEvent::PreToolUse, stdin);
+        assert_eq!(code, 2, "PreToolUse should block destructive Bash commands");
+        assert!(output.contains("deny"));
+    }
+
     #[test]
     fn test_post_tool_use_exits_0_with_normal_input() {
         let stdin = r#"{"tool_name":"Bash","tool_input":{"command":"ls"}}"#;
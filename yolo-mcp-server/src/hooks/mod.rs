@@ -2,7 +2,7 @@
 //!
 //! Supported hook events and their handlers:
 //!   - **SessionStart**: `post_compact` (compact mode), `map_staleness` (non-compact)
-//!   - **PreToolUse**: `security_filter` (blocks sensitive file access)
+//!   - **PreToolUse**: `security_filter` (blocks sensitive file access), `bash_guard` (blocks destructive commands)
 //!   - **PostToolUse**: `validate_summary`, `skill_hook_dispatch`
 //!   - **PreCompact**: `compaction_instructions`
 //!   - **SubagentStart**: `agent_start`, `agent_health`
@@ -37,6 +37,7 @@ pub mod validate_schema;
 
 // Security hooks (dev-05)
 pub mod security_filter;
+pub mod bash_guard;
 pub mod prompt_preflight;
 
 // Skill/blocker hook modules (dev-07)
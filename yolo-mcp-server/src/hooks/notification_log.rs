@@ -1,13 +1,13 @@
 use chrono::Utc;
 use serde_json::{json, Value};
-use std::fs;
-use std::io::Write;
 use std::path::Path;
 
-/// Notification handler: log notification metadata to `.notification-log.jsonl`.
+use crate::commands::event_bus;
+
+/// Notification handler: publish notification metadata to the event bus.
 ///
 /// - Extracts notification_type, message, title from input
-/// - Appends JSON line to `.yolo-planning/.notification-log.jsonl`
+/// - Publishes a `notification` event (canonical store `.events/event-log.jsonl`)
 /// - Always exit 0
 pub fn handle_notification(input: &Value) -> (Value, i32) {
     let planning = Path::new(".yolo-planning");
@@ -17,7 +17,13 @@ pub fn handle_notification(input: &Value) -> (Value, i32) {
         return (Value::Null, 0);
     }
 
-    let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    event_bus::publish(planning, &notification_event(input));
+
+    (Value::Null, 0)
+}
+
+/// Build the `notification` event from hook input.
+fn notification_event(input: &Value) -> Value {
     let ntype = input
         .get("notification_type")
         .and_then(|v| v.as_str())
@@ -28,28 +34,15 @@ pub fn handle_notification(input: &Value) -> (Value, i32) {
         .unwrap_or("");
     let title = input.get("title").and_then(|v| v.as_str()).unwrap_or("");
 
-    let entry = json!({
-        "timestamp": timestamp,
-        "type": ntype,
-        "title": title,
-        "message": message,
-    });
-
-    let log_path = planning.join(".notification-log.jsonl");
-    let line = match serde_json::to_string(&entry) {
-        Ok(s) => s,
-        Err(_) => return (Value::Null, 0),
-    };
-
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)
-    {
-        let _ = writeln!(f, "{}", line);
-    }
-
-    (Value::Null, 0)
+    json!({
+        "ts": Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        "event": "notification",
+        "data": {
+            "type": ntype,
+            "title": title,
+            "message": message,
+        },
+    })
 }
 
 #[cfg(test)]
@@ -69,32 +62,22 @@ mod tests {
     fn test_handle_notification_with_dir() {
         let dir = tempfile::tempdir().unwrap();
         let planning = dir.path().join(".yolo-planning");
-        fs::create_dir_all(&planning).unwrap();
-
-        // We test the JSON construction directly since handle_notification uses
-        // a hardcoded path. The real integration test verifies end-to-end.
-        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-        let entry = json!({
-            "timestamp": timestamp,
-            "type": "warning",
+        std::fs::create_dir_all(&planning).unwrap();
+
+        // handle_notification uses a hardcoded path, so publish the built event directly.
+        let input = json!({
+            "notification_type": "warning",
             "title": "Test Title",
             "message": "Test message body",
         });
-
-        let log_path = planning.join(".notification-log.jsonl");
-        let line = serde_json::to_string(&entry).unwrap();
-        let mut f = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&log_path)
-            .unwrap();
-        writeln!(f, "{}", line).unwrap();
-
-        let content = fs::read_to_string(&log_path).unwrap();
-        let parsed: Value = serde_json::from_str(content.trim()).unwrap();
-        assert_eq!(parsed["type"], "warning");
-        assert_eq!(parsed["title"], "Test Title");
-        assert_eq!(parsed["message"], "Test message body");
+        event_bus::publish(&planning, &notification_event(&input));
+
+        let events = event_bus::load_events(&planning);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "notification");
+        assert_eq!(events[0]["data"]["type"], "warning");
+        assert_eq!(events[0]["data"]["title"], "Test Title");
+        assert_eq!(events[0]["data"]["message"], "Test message body");
     }
 
     #[test]
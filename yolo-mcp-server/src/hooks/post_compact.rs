@@ -3,6 +3,7 @@ use std::fs;
 use std::path::Path;
 
 use super::utils;
+use crate::commands::role_registry::RoleRegistry;
 
 /// SessionStart(compact) handler: remind agent to re-read key files after compaction.
 ///
@@ -19,8 +20,9 @@ pub fn handle_post_compact(input: &Value) -> (Value, i32) {
     cleanup_stale_files(planning);
 
     // Detect role from input context
-    let role = detect_role_from_input(input);
-    let files = role_reread_files(&role);
+    let registry = RoleRegistry::load(Path::new("."));
+    let role = detect_role_from_input(&registry, input);
+    let files = role_reread_files(&registry, &role);
 
     // Attempt snapshot restore
     let snapshot_context = restore_snapshot(planning, &role);
@@ -53,22 +55,16 @@ fn cleanup_stale_files(planning: &Path) {
     let _ = fs::remove_file(planning.join(".compaction-marker"));
 }
 
-/// Detect agent role by scanning the input JSON for known patterns.
-fn detect_role_from_input(input: &Value) -> String {
+/// Detect agent role by scanning the input JSON for `yolo-<role>` mentions.
+/// Longer role names are tried first so `yolo-dev` does not shadow `yolo-devops`.
+fn detect_role_from_input(registry: &RoleRegistry, input: &Value) -> String {
     let input_str = input.to_string().to_lowercase();
 
-    let patterns = [
-        ("yolo-lead", "lead"),
-        ("yolo-dev", "dev"),
-        ("yolo-qa", "qa"),
-        ("yolo-scout", "scout"),
-        ("yolo-debugger", "debugger"),
-        ("yolo-architect", "architect"),
-        ("yolo-docs", "docs"),
-    ];
-
-    for (pattern, role) in &patterns {
-        if input_str.contains(pattern) {
+    let mut names = registry.names();
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+
+    for role in names {
+        if input_str.contains(&format!("yolo-{}", role)) {
             return role.to_string();
         }
     }
@@ -77,18 +73,11 @@ fn detect_role_from_input(input: &Value) -> String {
 }
 
 /// Map role to suggested re-read files after compaction.
-fn role_reread_files(role: &str) -> &'static str {
-    match role {
-        "lead" => "STATE.md, ROADMAP.md, config.json, and current phase plans",
-        "dev" => "your assigned plan file, SUMMARY.md template, and relevant source files",
-        "qa" => "SUMMARY.md files under review, verification criteria, and gap reports",
-        "scout" => "research notes, REQUIREMENTS.md, and any scout-specific findings",
-        "debugger" => "reproduction steps, hypothesis log, and related source files",
-        "architect" => {
-            "REQUIREMENTS.md, ROADMAP.md, phase structure, and architecture decisions"
-        }
-        _ => "STATE.md, your assigned task context, and any in-progress files",
-    }
+fn role_reread_files(registry: &RoleRegistry, role: &str) -> String {
+    registry
+        .get(role)
+        .and_then(|r| r.reread.clone())
+        .unwrap_or_else(|| "STATE.md, your assigned task context, and any in-progress files".to_string())
 }
 
 /// Attempt to restore the most recent snapshot for the current phase/role.
@@ -238,7 +227,7 @@ fn find_task_context(
     plan_id: &str,
     snapshot: &Value,
 ) -> (String, String, String) {
-    let event_log = planning.join(".events").join("event-log.jsonl");
+    let event_log = crate::commands::event_bus::log_path(planning);
     if !event_log.is_file() {
         return (String::new(), String::new(), String::new());
     }
@@ -337,38 +326,38 @@ mod tests {
     #[test]
     fn test_detect_role_lead() {
         let input = json!({"context": "yolo-lead session"});
-        assert_eq!(detect_role_from_input(&input), "lead");
+        assert_eq!(detect_role_from_input(&RoleRegistry::builtin(), &input), "lead");
     }
 
     #[test]
     fn test_detect_role_dev() {
         let input = json!({"agent": "yolo-dev-1"});
-        assert_eq!(detect_role_from_input(&input), "dev");
+        assert_eq!(detect_role_from_input(&RoleRegistry::builtin(), &input), "dev");
     }
 
     #[test]
     fn test_detect_role_unknown() {
         let input = json!({"context": "some random input"});
-        assert_eq!(detect_role_from_input(&input), "unknown");
+        assert_eq!(detect_role_from_input(&RoleRegistry::builtin(), &input), "unknown");
     }
 
     #[test]
     fn test_role_reread_files_lead() {
-        let files = role_reread_files("lead");
+        let files = role_reread_files(&RoleRegistry::builtin(), "lead");
         assert!(files.contains("STATE.md"));
         assert!(files.contains("ROADMAP.md"));
     }
 
     #[test]
     fn test_role_reread_files_dev() {
-        let files = role_reread_files("dev");
+        let files = role_reread_files(&RoleRegistry::builtin(), "dev");
         assert!(files.contains("plan file"));
         assert!(files.contains("source files"));
     }
 
     #[test]
     fn test_role_reread_files_default() {
-        let files = role_reread_files("unknown");
+        let files = role_reread_files(&RoleRegistry::builtin(), "unknown");
         assert!(files.contains("STATE.md"));
     }
 
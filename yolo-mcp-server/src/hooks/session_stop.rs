@@ -1,15 +1,16 @@
 use chrono::Utc;
 use serde_json::{json, Value};
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 use std::process::Command;
 
+use crate::commands::event_bus;
+
 /// Stop handler: log session metrics and clean up transient files.
 ///
 /// - Extracts cost, duration, tokens, model from input
 /// - Gets current branch via `git rev-parse`
-/// - Appends metrics to `.session-log.jsonl`
+/// - Publishes a `session_stop` event with the metrics to the event bus
 /// - Persists cost summary from `.cost-ledger.json` if present
 /// - Cleans up transient agent markers
 /// - Always exit 0
@@ -34,19 +35,18 @@ pub fn handle_stop(input: &Value) -> (Value, i32) {
     let branch = get_git_branch();
     let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
-    // Append session metrics to log
-    let session_entry = json!({
-        "timestamp": timestamp,
-        "duration_ms": duration,
-        "cost_usd": cost,
-        "tokens_in": tokens_in,
-        "tokens_out": tokens_out,
-        "model": model,
-        "branch": branch,
-    });
-
-    let log_path = planning.join(".session-log.jsonl");
-    append_jsonl(&log_path, &session_entry);
+    event_bus::publish(planning, &json!({
+        "ts": timestamp,
+        "event": "session_stop",
+        "data": {
+            "duration_ms": duration,
+            "cost_usd": cost,
+            "tokens_in": tokens_in,
+            "tokens_out": tokens_out,
+            "model": model,
+            "branch": branch,
+        },
+    }));
 
     // Persist cost summary from agent-attributed ledger
     persist_cost_summary(planning, &timestamp);
@@ -88,22 +88,6 @@ fn get_git_branch() -> String {
         .unwrap_or_else(|| "unknown".to_string())
 }
 
-/// Append a JSON value as a single line to a JSONL file.
-fn append_jsonl(path: &Path, entry: &Value) {
-    let line = match serde_json::to_string(entry) {
-        Ok(s) => s,
-        Err(_) => return,
-    };
-
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(path)
-    {
-        let _ = writeln!(f, "{}", line);
-    }
-}
-
 /// Persist cost summary from `.cost-ledger.json` if valid JSON.
 fn persist_cost_summary(planning: &Path, timestamp: &str) {
     let ledger_path = planning.join(".cost-ledger.json");
@@ -127,14 +111,11 @@ fn persist_cost_summary(planning: &Path, timestamp: &str) {
         }
     };
 
-    let summary_entry = json!({
-        "timestamp": timestamp,
-        "type": "cost_summary",
-        "costs": costs,
-    });
-
-    let log_path = planning.join(".session-log.jsonl");
-    append_jsonl(&log_path, &summary_entry);
+    event_bus::publish(planning, &json!({
+        "ts": timestamp,
+        "event": "cost_summary",
+        "data": {"costs": costs},
+    }));
 
     let _ = fs::remove_file(&ledger_path);
 }
@@ -192,28 +173,6 @@ mod tests {
         assert!(!branch.is_empty());
     }
 
-    #[test]
-    fn test_append_jsonl() {
-        let dir = tempfile::tempdir().unwrap();
-        let log_path = dir.path().join("test.jsonl");
-
-        let entry1 = json!({"key": "value1"});
-        let entry2 = json!({"key": "value2"});
-
-        append_jsonl(&log_path, &entry1);
-        append_jsonl(&log_path, &entry2);
-
-        let content = fs::read_to_string(&log_path).unwrap();
-        let lines: Vec<&str> = content.lines().collect();
-        assert_eq!(lines.len(), 2);
-
-        let parsed1: Value = serde_json::from_str(lines[0]).unwrap();
-        assert_eq!(parsed1["key"], "value1");
-
-        let parsed2: Value = serde_json::from_str(lines[1]).unwrap();
-        assert_eq!(parsed2["key"], "value2");
-    }
-
     #[test]
     fn test_cleanup_transient() {
         let dir = tempfile::tempdir().unwrap();
@@ -253,12 +212,12 @@ mod tests {
         // Ledger should be deleted
         assert!(!planning.join(".cost-ledger.json").exists());
 
-        // Session log should have the cost summary
-        let log_content = fs::read_to_string(planning.join(".session-log.jsonl")).unwrap();
-        let entry: Value = serde_json::from_str(log_content.trim()).unwrap();
-        assert_eq!(entry["type"], "cost_summary");
-        assert_eq!(entry["timestamp"], timestamp);
-        assert!((entry["costs"]["yolo-dev"].as_f64().unwrap() - 0.25).abs() < 0.001);
+        // Event log should have the cost summary
+        let events = event_bus::load_events(planning);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "cost_summary");
+        assert_eq!(events[0]["ts"], timestamp);
+        assert!((events[0]["data"]["costs"]["yolo-dev"].as_f64().unwrap() - 0.25).abs() < 0.001);
     }
 
     #[test]
@@ -269,7 +228,7 @@ mod tests {
         // No ledger file — should be a no-op
         persist_cost_summary(planning, "2026-02-20T10:00:00Z");
 
-        assert!(!planning.join(".session-log.jsonl").exists());
+        assert!(!event_bus::log_path(planning).exists());
     }
 
     #[test]
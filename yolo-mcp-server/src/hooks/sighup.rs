@@ -113,6 +113,7 @@ fn find_planning_dir() -> Option<std::path::PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::event_bus;
     use tempfile::TempDir;
 
     #[test]
@@ -121,9 +122,7 @@ mod tests {
         // Should not panic when .agent-pids doesn't exist
         handle_sighup_cleanup(dir.path());
 
-        let log = dir.path().join(".hook-errors.log");
-        assert!(log.exists());
-        let content = fs::read_to_string(&log).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("no .agent-pids file found"));
     }
 
@@ -134,8 +133,7 @@ mod tests {
 
         handle_sighup_cleanup(dir.path());
 
-        let log = dir.path().join(".hook-errors.log");
-        let content = fs::read_to_string(&log).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("no valid PIDs found"));
     }
 
@@ -146,8 +144,7 @@ mod tests {
 
         handle_sighup_cleanup(dir.path());
 
-        let log = dir.path().join(".hook-errors.log");
-        let content = fs::read_to_string(&log).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("no valid PIDs found"));
     }
 
@@ -159,8 +156,7 @@ mod tests {
 
         handle_sighup_cleanup(dir.path());
 
-        let log = dir.path().join(".hook-errors.log");
-        let content = fs::read_to_string(&log).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("sending SIGTERM to 2 PIDs"));
     }
 
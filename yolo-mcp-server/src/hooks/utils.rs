@@ -1,9 +1,10 @@
 use std::env;
 use std::fs;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use serde_json::json;
+
+use crate::commands::event_bus;
 
 /// Resolve the Claude config directory.
 /// Uses `CLAUDE_CONFIG_DIR` env var if set, otherwise `$HOME/.claude`.
@@ -44,54 +45,18 @@ pub fn resolve_plugin_cache() -> Option<PathBuf> {
     versions.last().cloned()
 }
 
-/// Log a hook error to `.yolo-planning/.hook-errors.log`.
-/// Trims to 50 entries (keeps last 30) to prevent unbounded growth.
+/// Publish a `hook_error` event to the event bus.
+/// Growth is bounded by the bus's size-based rotation.
 pub fn log_hook_error(planning_dir: &Path, script_name: &str, exit_code: i32) {
-    let log_path = planning_dir.join(".hook-errors.log");
-    let ts = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-    let entry = format!("[{}] {} exit={}\n", ts, script_name, exit_code);
-
-    // Append entry
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)
-    {
-        let _ = f.write_all(entry.as_bytes());
-    }
-
-    // Trim if over 50 lines
-    trim_log_file(&log_path, 50, 30);
+    event_bus::publish(planning_dir, &json!({
+        "event": "hook_error",
+        "data": {"script": script_name, "exit_code": exit_code},
+    }));
 }
 
-/// Log a freeform message to the hook error log.
+/// Publish a freeform hook diagnostic message to the event bus.
 pub fn log_hook_message(planning_dir: &Path, message: &str) {
-    let log_path = planning_dir.join(".hook-errors.log");
-    let ts = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
-    let entry = format!("[{}] {}\n", ts, message);
-
-    if let Ok(mut f) = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)
-    {
-        let _ = f.write_all(entry.as_bytes());
-    }
-
-    trim_log_file(&log_path, 50, 30);
-}
-
-/// Trim a log file: if it exceeds `max_lines`, keep only the last `keep_lines`.
-fn trim_log_file(path: &Path, max_lines: usize, keep_lines: usize) {
-    if let Ok(content) = fs::read_to_string(path) {
-        let lines: Vec<&str> = content.lines().collect();
-        if lines.len() > max_lines {
-            let trimmed: Vec<&str> = lines[lines.len() - keep_lines..].to_vec();
-            let mut output = trimmed.join("\n");
-            output.push('\n');
-            let _ = fs::write(path, output);
-        }
-    }
+    event_bus::publish_message(planning_dir, "hook", message);
 }
 
 /// Walk up from `start` to find a directory containing `.yolo-planning/`.
@@ -246,32 +211,35 @@ mod tests {
     }
 
     #[test]
-    fn test_log_hook_error_creates_file() {
+    fn test_log_hook_error_publishes_event() {
         let dir = TempDir::new().unwrap();
         log_hook_error(dir.path(), "test-hook.sh", 1);
 
-        let log_path = dir.path().join(".hook-errors.log");
-        assert!(log_path.exists());
-
-        let content = fs::read_to_string(&log_path).unwrap();
-        assert!(content.contains("test-hook.sh exit=1"));
+        let events = event_bus::load_events(dir.path());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "hook_error");
+        assert_eq!(events[0]["data"]["script"], "test-hook.sh");
+        assert_eq!(events[0]["data"]["exit_code"], 1);
     }
 
     #[test]
-    fn test_log_hook_error_trims_under_50() {
+    fn test_log_hook_error_bounded_by_rotation() {
         let dir = TempDir::new().unwrap();
+        let planning_dir = dir.path().join(".yolo-planning");
+        fs::create_dir_all(&planning_dir).unwrap();
+        fs::write(
+            planning_dir.join("config.json"),
+            r#"{"event_bus": {"max_bytes": 1024, "keep": 1}}"#,
+        )
+        .unwrap();
 
-        // Write 55 entries — trim fires when crossing 50, keeping 30.
-        // Subsequent appends bring it to 34.
         for i in 0..55 {
-            log_hook_error(dir.path(), &format!("hook-{}.sh", i), 1);
+            log_hook_error(&planning_dir, &format!("hook-{}.sh", i), 1);
         }
 
-        let log_path = dir.path().join(".hook-errors.log");
-        let content = fs::read_to_string(&log_path).unwrap();
-        let lines: Vec<&str> = content.lines().collect();
-        // After trim at 51 (keeps 30), then 4 more appends = 34
-        assert!(lines.len() <= 50, "Log should never exceed 50 lines, got {}", lines.len());
+        let live = event_bus::log_path(&planning_dir);
+        assert!(fs::metadata(&live).unwrap().len() <= 1024);
+        let content = event_bus::read_all(&planning_dir);
         // Should contain the latest entries
         assert!(content.contains("hook-54.sh"));
         // Should NOT contain the earliest entries
@@ -283,8 +251,7 @@ mod tests {
         let dir = TempDir::new().unwrap();
         log_hook_message(dir.path(), "SIGHUP received, cleaning up");
 
-        let log_path = dir.path().join(".hook-errors.log");
-        let content = fs::read_to_string(&log_path).unwrap();
+        let content = event_bus::read_all(dir.path());
         assert!(content.contains("SIGHUP received, cleaning up"));
     }
 
@@ -1,4 +1,4 @@
-use crate::commands::utils::extract_frontmatter;
+use crate::commands::frontmatter;
 use serde_json::{json, Value};
 use std::fs;
 
@@ -73,57 +73,38 @@ fn extract_file_path(input: &Value) -> String {
 
 /// Check the description field in frontmatter content.
 pub fn check_frontmatter_description(content: &str) -> FrontmatterResult {
-    let frontmatter = match extract_frontmatter(content) {
-        Some(fm) => fm,
-        None => return FrontmatterResult::Skip,
+    let fm = match frontmatter::parse(content) {
+        Ok(Some(fm)) => fm,
+        _ => return FrontmatterResult::Skip,
     };
-
-    // Find description: line
-    let mut found_desc = false;
-    let mut desc_value = String::new();
-    let mut has_continuation = false;
-
-    for line in frontmatter.lines() {
-        if found_desc {
-            // Check for indented continuation lines
-            if line.starts_with(' ') || line.starts_with('\t') {
-                has_continuation = true;
-            } else {
-                break;
-            }
-        } else if line.starts_with("description:") {
-            found_desc = true;
-            desc_value = line
-                .strip_prefix("description:")
-                .unwrap_or("")
-                .trim()
-                .to_string();
-        }
-    }
-
-    if !found_desc {
+    let Some(desc) = fm.get("description") else {
         return FrontmatterResult::Skip;
-    }
+    };
 
-    // Check for block scalar indicators (| or >)
-    if desc_value.starts_with('|') || desc_value.starts_with('>') {
+    // Parsing folds block scalars and continuation lines into one value, so
+    // look at how the field is written in the raw block.
+    let block = &content[..content.len() - fm.body.len()];
+    if spans_lines(block, "description") {
         return FrontmatterResult::MultiLine;
     }
 
-    // Empty description value
-    if desc_value.is_empty() {
-        if has_continuation {
-            return FrontmatterResult::MultiLine;
-        }
-        return FrontmatterResult::Empty;
-    }
-
-    // Non-empty but has continuation lines
-    if has_continuation {
-        return FrontmatterResult::MultiLine;
+    match frontmatter::scalar_to_string(desc) {
+        Some(v) if !v.trim().is_empty() => FrontmatterResult::Ok,
+        _ => FrontmatterResult::Empty,
     }
+}
 
-    FrontmatterResult::Ok
+/// True when `key` is a block scalar (`|`/`>`) or has indented continuation lines.
+fn spans_lines(block: &str, key: &str) -> bool {
+    let prefix = format!("{}:", key);
+    let mut lines = block.lines().skip_while(|l| !l.starts_with(&prefix));
+    let Some(first) = lines.next() else {
+        return false;
+    };
+    let value = first[prefix.len()..].trim();
+    value.starts_with('|')
+        || value.starts_with('>')
+        || lines.next().is_some_and(|l| l.starts_with(' ') || l.starts_with('\t'))
 }
 
 #[cfg(test)]
@@ -210,19 +191,6 @@ mod tests {
         assert_eq!(output, Value::Null);
     }
 
-    #[test]
-    fn test_extract_frontmatter_basic() {
-        let content = "---\nfoo: bar\nbaz: qux\n---\nbody";
-        let fm = extract_frontmatter(content).unwrap();
-        assert_eq!(fm, "foo: bar\nbaz: qux");
-    }
-
-    #[test]
-    fn test_extract_frontmatter_none() {
-        let content = "no frontmatter here";
-        assert!(extract_frontmatter(content).is_none());
-    }
-
     #[test]
     fn test_validate_frontmatter_with_real_file() {
         let dir = tempfile::tempdir().unwrap();
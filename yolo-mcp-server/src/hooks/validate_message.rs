@@ -1,4 +1,5 @@
 use crate::commands::feature_flags::{self, FeatureFlag};
+use crate::commands::role_registry::RoleRegistry;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
@@ -22,7 +23,12 @@ const SCHEMAS_PATH: &str = "config/schemas/message-schemas.json";
 /// Exit 0 when valid (or flag off), exit 2 when invalid.
 pub fn validate_message(msg_json: &str) -> (Value, i32) {
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    let v2_typed = feature_flags::is_enabled(FeatureFlag::V2TypedProtocol, &cwd);
+    validate_message_in(msg_json, &cwd)
+}
+
+/// `validate_message` with the project root (config and role registry) given explicitly.
+fn validate_message_in(msg_json: &str, cwd: &Path) -> (Value, i32) {
+    let v2_typed = feature_flags::is_enabled(FeatureFlag::V2TypedProtocol, cwd);
     if !v2_typed {
         return (
             json!({"valid": true, "errors": [], "reason": "v2_typed_protocol=false"}),
@@ -89,7 +95,9 @@ pub fn validate_message(msg_json: &str) -> (Value, i32) {
         }
     }
 
-    // 4. Role authorization
+    // 4. Role authorization. Roles the schemas file does not know fall back
+    // to the role registry's can_send/can_receive.
+    let registry = RoleRegistry::load(cwd);
     let author_role = msg
         .get("author_role")
         .and_then(|v| v.as_str())
@@ -97,6 +105,7 @@ pub fn validate_message(msg_json: &str) -> (Value, i32) {
     if !author_role.is_empty() && !msg_type.is_empty() && type_exists
         && let Some(schema) = schemas.schemas.get(msg_type)
         && !schema.allowed_roles.contains(&author_role.to_string())
+        && !registry_can_send(&registry, &schemas, author_role, msg_type)
     {
         errors.push(format!(
             "role {} not authorized for {}",
@@ -109,9 +118,14 @@ pub fn validate_message(msg_json: &str) -> (Value, i32) {
         .get("target_role")
         .and_then(|v| v.as_str())
         .unwrap_or("");
+    let can_receive = if let Some(role_info) = schemas.role_hierarchy.get(target_role) {
+        Some(&role_info.can_receive)
+    } else {
+        registry.get(target_role).map(|r| &r.can_receive).filter(|c| !c.is_empty())
+    };
     if !target_role.is_empty() && !msg_type.is_empty()
-        && let Some(role_info) = schemas.role_hierarchy.get(target_role)
-        && !role_info.can_receive.contains(&msg_type.to_string())
+        && let Some(can_receive) = can_receive
+        && !can_receive.contains(&msg_type.to_string())
     {
         errors.push(format!(
             "target role {} cannot receive {}",
@@ -160,6 +174,13 @@ pub fn validate_message_hook(input: &Value) -> (Value, i32) {
     validate_message(&msg_str)
 }
 
+/// Whether a role missing from the schemas' role_hierarchy lists `msg_type`
+/// in its registry `can_send`.
+fn registry_can_send(registry: &RoleRegistry, schemas: &MessageSchemas, role: &str, msg_type: &str) -> bool {
+    !schemas.role_hierarchy.contains_key(role)
+        && registry.get(role).is_some_and(|r| r.can_send.iter().any(|t| t == msg_type))
+}
+
 #[derive(Debug, Deserialize)]
 struct MessageSchemas {
     envelope_fields: Vec<String>,
@@ -318,16 +339,22 @@ mod tests {
 
     #[test]
     fn test_validate_empty_message() {
-        // When flag is off (no config.json), returns valid/0 — flag guard short-circuits
-        let (result, code) = validate_message("");
+        // When flag is off, returns valid/0 — flag guard short-circuits
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        std::fs::write(dir.path().join(".yolo-planning/config.json"), r#"{"v2_typed_protocol": false}"#).unwrap();
+        let (result, code) = validate_message_in("", dir.path());
         assert_eq!(code, 0);
         assert_eq!(result["valid"], true);
     }
 
     #[test]
     fn test_validate_invalid_json() {
-        // When flag is off (no config.json), returns valid/0 — flag guard short-circuits
-        let (result, code) = validate_message("not json {{{");
+        // When flag is off, returns valid/0 — flag guard short-circuits
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".yolo-planning")).unwrap();
+        std::fs::write(dir.path().join(".yolo-planning/config.json"), r#"{"v2_typed_protocol": false}"#).unwrap();
+        let (result, code) = validate_message_in("not json {{{", dir.path());
         assert_eq!(code, 0);
         assert_eq!(result["valid"], true);
     }
@@ -350,8 +377,9 @@ mod tests {
     #[test]
     fn test_validate_flag_off_returns_valid() {
         // Without config.json, flag is off => valid
+        let dir = tempfile::tempdir().unwrap();
         let msg = serde_json::to_string(&make_valid_message()).unwrap();
-        let (result, code) = validate_message(&msg);
+        let (result, code) = validate_message_in(&msg, dir.path());
         assert_eq!(code, 0);
         assert_eq!(result["valid"], true);
     }
@@ -441,6 +469,24 @@ mod tests {
         assert_eq!(result["valid"], true);
     }
 
+    #[test]
+    fn test_registry_can_send_for_unlisted_roles() {
+        let mut role_hierarchy = HashMap::new();
+        role_hierarchy.insert("dev".to_string(), RoleInfo { can_send: vec![], can_receive: vec![] });
+        let schemas = MessageSchemas { envelope_fields: vec![], schemas: HashMap::new(), role_hierarchy };
+        let mut registry = RoleRegistry::builtin();
+        registry.merge_config(&json!({
+            "security-auditor": {"can_send": ["qa_verdict"]},
+            "dev": {"can_send": ["qa_verdict"]},
+        }));
+
+        assert!(registry_can_send(&registry, &schemas, "security-auditor", "qa_verdict"));
+        assert!(!registry_can_send(&registry, &schemas, "security-auditor", "plan_contract"));
+        // The schemas file stays authoritative for roles it lists.
+        assert!(!registry_can_send(&registry, &schemas, "dev", "qa_verdict"));
+        assert!(!registry_can_send(&registry, &schemas, "stranger", "qa_verdict"));
+    }
+
     #[test]
     fn test_schema_deserialization() {
         let schema_json = r#"{
@@ -1,6 +1,8 @@
 use serde_json::{json, Value};
 use std::fs;
 
+use crate::commands::frontmatter;
+
 const PLANNING_DIR: &str = ".yolo-planning";
 
 /// Validates YAML frontmatter or JSON fields for a given schema type.
@@ -30,9 +32,10 @@ pub fn validate_schema(schema_type: &str, file_path: &str) -> (String, i32) {
         Err(_) => return ("valid".to_string(), 0),
     };
 
-    let frontmatter = match extract_frontmatter(&content) {
-        Some(fm) => fm,
-        None => return ("invalid: no frontmatter".to_string(), 0),
+    let frontmatter = match frontmatter::parse(&content) {
+        Ok(Some(fm)) => fm,
+        Ok(None) => return ("invalid: no frontmatter".to_string(), 0),
+        Err(e) => return (format!("invalid: {}", e), 0),
     };
 
     let required = match schema_type {
@@ -50,7 +53,7 @@ pub fn validate_schema(schema_type: &str, file_path: &str) -> (String, i32) {
 
     let missing: Vec<&str> = required
         .iter()
-        .filter(|field| !frontmatter_has_field(&frontmatter, field))
+        .filter(|field| !frontmatter.has(field))
         .copied()
         .collect();
 
@@ -117,35 +120,6 @@ fn validate_contract_schema(file_path: &str) -> (String, i32) {
     ("valid".to_string(), 0)
 }
 
-/// Extract frontmatter block between first and second `---` lines.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    let mut lines = content.lines();
-
-    if lines.next() != Some("---") {
-        return None;
-    }
-
-    let mut fm_lines = Vec::new();
-    for line in lines {
-        if line == "---" {
-            break;
-        }
-        fm_lines.push(line);
-    }
-
-    if fm_lines.is_empty() {
-        return None;
-    }
-
-    Some(fm_lines.join("\n"))
-}
-
-/// Check if a frontmatter block contains a top-level field (line starts with `field:`).
-fn frontmatter_has_field(frontmatter: &str, field: &str) -> bool {
-    let prefix = format!("{}:", field);
-    frontmatter.lines().any(|line| line.starts_with(&prefix))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,11 +127,11 @@ mod tests {
     #[test]
     fn test_plan_schema_valid() {
         let content = "---\nphase: 1\nplan: 01\ntitle: Test\nwave: 1\ndepends_on: []\nmust_haves:\n  - item\n---\n";
-        let fm = extract_frontmatter(content).unwrap();
+        let fm = frontmatter::parse(content).unwrap().unwrap();
         let required = vec!["phase", "plan", "title", "wave", "depends_on", "must_haves"];
         let missing: Vec<&str> = required
             .iter()
-            .filter(|f| !frontmatter_has_field(&fm, f))
+            .filter(|f| !fm.has(f))
             .copied()
             .collect();
         assert!(missing.is_empty());
@@ -166,15 +140,15 @@ mod tests {
     #[test]
     fn test_plan_schema_missing_wave() {
         let content = "---\nphase: 1\nplan: 01\ntitle: Test\ndepends_on: []\nmust_haves:\n  - item\n---\n";
-        let fm = extract_frontmatter(content).unwrap();
-        assert!(!frontmatter_has_field(&fm, "wave"));
+        let fm = frontmatter::parse(content).unwrap().unwrap();
+        assert!(!fm.has("wave"));
     }
 
     #[test]
     fn test_summary_schema_valid() {
         let content =
             "---\nphase: 1\nplan: 01\ntitle: Test\nstatus: done\ntasks_completed: 5\ntasks_total: 5\n---\n";
-        let fm = extract_frontmatter(content).unwrap();
+        let fm = frontmatter::parse(content).unwrap().unwrap();
         let required = vec![
             "phase",
             "plan",
@@ -185,7 +159,7 @@ mod tests {
         ];
         let missing: Vec<&str> = required
             .iter()
-            .filter(|f| !frontmatter_has_field(&fm, f))
+            .filter(|f| !fm.has(f))
             .copied()
             .collect();
         assert!(missing.is_empty());
@@ -194,9 +168,9 @@ mod tests {
     #[test]
     fn test_summary_schema_missing_fields() {
         let content = "---\nphase: 1\ntitle: Test\n---\n";
-        let fm = extract_frontmatter(content).unwrap();
-        assert!(!frontmatter_has_field(&fm, "plan"));
-        assert!(!frontmatter_has_field(&fm, "status"));
+        let fm = frontmatter::parse(content).unwrap().unwrap();
+        assert!(!fm.has("plan"));
+        assert!(!fm.has("status"));
     }
 
     #[test]
@@ -237,31 +211,24 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_frontmatter() {
-        let content = "---\nfoo: bar\nbaz: 1\n---\nbody";
-        let fm = extract_frontmatter(content).unwrap();
-        assert_eq!(fm, "foo: bar\nbaz: 1");
-    }
-
-    #[test]
-    fn test_extract_frontmatter_no_opening() {
-        let content = "no frontmatter";
-        assert!(extract_frontmatter(content).is_none());
+    fn test_block_and_inline_lists_both_count_as_present() {
+        let content = "---\ndepends_on: [\"01\"]\nmust_haves:\n  - a\n---\nbody";
+        let fm = frontmatter::parse(content).unwrap().unwrap();
+        assert!(fm.has("depends_on"));
+        assert!(fm.has("must_haves"));
+        assert!(!fm.has("wave"));
     }
 
     #[test]
-    fn test_extract_frontmatter_empty() {
-        let content = "---\n---\nbody";
-        assert!(extract_frontmatter(content).is_none());
+    fn test_no_frontmatter() {
+        assert!(frontmatter::parse("no frontmatter").unwrap().is_none());
     }
 
     #[test]
-    fn test_frontmatter_has_field() {
-        let fm = "phase: 1\nplan: 01\ntitle: Test";
-        assert!(frontmatter_has_field(fm, "phase"));
-        assert!(frontmatter_has_field(fm, "plan"));
-        assert!(frontmatter_has_field(fm, "title"));
-        assert!(!frontmatter_has_field(fm, "wave"));
+    fn test_nested_key_is_not_top_level() {
+        let content = "---\nmust_haves:\n  wave: 1\n---\n";
+        let fm = frontmatter::parse(content).unwrap().unwrap();
+        assert!(!fm.has("wave"));
     }
 
     #[test]
@@ -323,11 +290,11 @@ mod tests {
         // Note: validate_schema reads config from relative PLANNING_DIR,
         // so the flag won't be found in the test env. Test core logic directly.
         let content = std::fs::read_to_string(&plan_path).unwrap();
-        let fm = extract_frontmatter(&content).unwrap();
+        let fm = frontmatter::parse(&content).unwrap().unwrap();
         let required = vec!["phase", "plan", "title", "wave", "depends_on", "must_haves"];
         let missing: Vec<&str> = required
             .iter()
-            .filter(|f| !frontmatter_has_field(&fm, f))
+            .filter(|f| !fm.has(f))
             .copied()
             .collect();
         assert!(missing.is_empty());
@@ -344,7 +311,7 @@ mod tests {
         .unwrap();
 
         let content = std::fs::read_to_string(&summary_path).unwrap();
-        let fm = extract_frontmatter(&content).unwrap();
+        let fm = frontmatter::parse(&content).unwrap().unwrap();
         let required = vec![
             "phase",
             "plan",
@@ -355,7 +322,7 @@ mod tests {
         ];
         let missing: Vec<&str> = required
             .iter()
-            .filter(|f| !frontmatter_has_field(&fm, f))
+            .filter(|f| !fm.has(f))
             .copied()
             .collect();
         assert!(missing.is_empty());
@@ -1,6 +1,8 @@
 use serde_json::{json, Value};
 use std::fs;
 
+use crate::commands::frontmatter::{self, FrontmatterErrorKind};
+
 /// PostToolUse handler that validates SUMMARY.md structure in .yolo-planning/.
 /// Non-blocking: always returns exit code 0.
 ///
@@ -57,12 +59,13 @@ fn is_summary_in_planning(path: &str) -> bool {
 fn check_summary_structure(content: &str) -> Vec<String> {
     let mut missing = Vec::new();
 
-    if !content.starts_with("---") {
-        missing.push("Missing YAML frontmatter.".to_string());
-    } else {
-        // Extract and validate frontmatter fields
-        let frontmatter = extract_frontmatter(content);
-        if let Some(fm) = frontmatter {
+    match frontmatter::parse(content) {
+        Ok(None) => missing.push("Missing YAML frontmatter.".to_string()),
+        Err(e) if e.kind == FrontmatterErrorKind::Unterminated => {
+            missing.push("YAML frontmatter not properly closed (missing second '---').".to_string());
+        }
+        Err(e) => missing.push(format!("Invalid YAML frontmatter at {}.", e)),
+        Ok(Some(fm)) => {
             let required_fields = [
                 "phase",
                 "plan",
@@ -72,12 +75,12 @@ fn check_summary_structure(content: &str) -> Vec<String> {
                 "commit_hashes",
             ];
             for field in &required_fields {
-                if !frontmatter_has_field(&fm, field) {
+                if !fm.has(field) {
                     missing.push(format!("Missing frontmatter field '{}'.", field));
                 }
             }
             // Validate status value if present
-            if let Some(status_val) = frontmatter_field_value(&fm, "status") {
+            if let Some(status_val) = fm.summary().status {
                 let valid_statuses = ["complete", "partial", "failed"];
                 if !valid_statuses.contains(&status_val.as_str()) {
                     missing.push(format!(
@@ -86,8 +89,6 @@ fn check_summary_structure(content: &str) -> Vec<String> {
                     ));
                 }
             }
-        } else {
-            missing.push("YAML frontmatter not properly closed (missing second '---').".to_string());
         }
     }
 
@@ -106,50 +107,6 @@ fn check_summary_structure(content: &str) -> Vec<String> {
     missing
 }
 
-/// Extract the frontmatter content between the first `---` and second `---`.
-/// Returns None if frontmatter is not properly delimited.
-fn extract_frontmatter(content: &str) -> Option<String> {
-    if !content.starts_with("---") {
-        return None;
-    }
-    // Find the second `---` after the first line
-    let after_first = &content[3..];
-    // Skip any characters on the first --- line (e.g., newline)
-    let rest = after_first.trim_start_matches(|c: char| c != '\n');
-    let rest = rest.strip_prefix('\n').unwrap_or(rest);
-    if let Some(end_idx) = rest.find("\n---") {
-        Some(rest[..end_idx].to_string())
-    } else if rest.ends_with("---") {
-        // Handle case where --- is at the very end without trailing newline
-        let trimmed = rest.trim_end_matches("---");
-        Some(trimmed.to_string())
-    } else {
-        None
-    }
-}
-
-/// Check if a frontmatter string contains a given field (line starting with `field_name:`).
-fn frontmatter_has_field(frontmatter: &str, field_name: &str) -> bool {
-    let prefix = format!("{}:", field_name);
-    frontmatter.lines().any(|line| {
-        let trimmed = line.trim();
-        trimmed.starts_with(&prefix)
-    })
-}
-
-/// Extract the value of a frontmatter field. Returns None if field not found.
-fn frontmatter_field_value(frontmatter: &str, field_name: &str) -> Option<String> {
-    let prefix = format!("{}:", field_name);
-    for line in frontmatter.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with(&prefix) {
-            let value = trimmed[prefix.len()..].trim().trim_matches('"').trim();
-            return Some(value.to_string());
-        }
-    }
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
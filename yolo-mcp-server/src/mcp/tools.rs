@@ -6,6 +6,7 @@ use std::sync::{Arc, Mutex};
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
 
+use crate::commands::layered_config::Config;
 use crate::commands::tier_context;
 
 const EXECUTION_STATE_PATH: &str = ".yolo-planning/.execution-state.json";
@@ -83,16 +84,9 @@ impl ToolState {
 /// Default command timeout in milliseconds (30 seconds).
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
 
-/// Read `command_timeout_ms` from `.yolo-planning/config.json`, falling back to DEFAULT_TIMEOUT_MS.
+/// Read `command_timeout_ms` from the layered config, falling back to DEFAULT_TIMEOUT_MS.
 fn read_timeout_config() -> u64 {
-    let config_path = Path::new(".yolo-planning/config.json");
-    if let Ok(data) = std::fs::read_to_string(config_path)
-        && let Ok(v) = serde_json::from_str::<Value>(&data)
-        && let Some(ms) = v.get("command_timeout_ms").and_then(|v| v.as_u64())
-    {
-        return ms;
-    }
-    DEFAULT_TIMEOUT_MS
+    Config::load(Path::new(".")).u64_or("command_timeout_ms", DEFAULT_TIMEOUT_MS)
 }
 
 /// Spawn a command with a timeout. On timeout, kill the child and return an error.
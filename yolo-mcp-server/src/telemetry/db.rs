@@ -50,6 +50,27 @@ impl TelemetryDb {
             )",
             [],
         )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS events (
+                id INTEGER PRIMARY KEY,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                timestamp TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Store a raw event-bus line (see `commands::event_bus`).
+    pub fn record_event(&self, event_type: &str, payload: &str) -> Result<()> {
+        let ts = Utc::now().to_rfc3339();
+        let conn = self.conn.lock()
+            .map_err(|e| rusqlite::Error::InvalidParameterName(format!("Mutex poisoned: {}", e)))?;
+        conn.execute(
+            "INSERT INTO events (event_type, payload, timestamp) VALUES (?1, ?2, ?3)",
+            (event_type, payload, &ts),
+        )?;
         Ok(())
     }
 